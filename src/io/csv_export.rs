@@ -1,8 +1,8 @@
-use crate::model::Task;
-use std::path::Path;
+use crate::model::{Project, Task};
+use std::path::{Path, PathBuf};
 
 /// Map progress float back to a human-readable status string.
-fn progress_to_status(progress: f32) -> &'static str {
+pub(super) fn progress_to_status(progress: f32) -> &'static str {
     if progress >= 1.0 {
         "Finished"
     } else if progress >= 0.5 {
@@ -16,10 +16,57 @@ fn progress_to_status(progress: f32) -> &'static str {
 
 /// Export tasks to a semicolon-delimited CSV file.
 ///
-/// Columns: Task Label ; Start Date ; End Date ; Status ; Priority ; Description ; Parent
-/// Dates are formatted as DD/MM/YYYY.
+/// Columns: Task Label ; Start Date ; End Date ; Status ; Priority ; Description ; Parent ; Cost ;
+/// Resource ; Allocation
+/// Dates are formatted as DD/MM/YYYY; cost is in the project currency. Several
+/// assignees are listed comma-separated, with their allocations in the same order.
+/// Tentative tasks are skipped unless the project includes them.
 /// Returns the number of tasks written.
-pub fn export_csv(tasks: &[Task], path: &Path) -> Result<usize, String> {
+pub fn export_csv(project: &Project, path: &Path) -> Result<usize, String> {
+    write_tasks(project, path, |_| true)
+}
+
+/// Export one CSV per resource into `dir`, each holding only the tasks
+/// assigned to them, in the same columns as [`export_csv`]. Files are named
+/// `<project> - <resource>.csv`; resources without tasks get none.
+/// Returns the files written with their task counts.
+pub fn export_csv_per_assignee(project: &Project, dir: &Path) -> Result<Vec<(PathBuf, usize)>, String> {
+    let mut written = Vec::new();
+    for resource in &project.resources {
+        let assigned = |task: &Task| task.assignments.iter().any(|a| a.resource_id == resource.id);
+        if !project.tasks.iter().any(|t| project.is_counted(t) && assigned(t)) {
+            continue;
+        }
+        let path = dir.join(format!("{} - {}.csv", file_safe(&project.name), file_safe(&resource.name)));
+        let count = write_tasks(project, &path, assigned)?;
+        written.push((path, count));
+    }
+    Ok(written)
+}
+
+/// A name for a Resource cell: the separators the importer splits on, and
+/// the backslash itself, are backslashed.
+fn escape_list_item(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, ',' | '|' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// `name` with characters that are not allowed in file names replaced.
+fn file_safe(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| if c.is_control() || "/\\:*?\"<>|".contains(c) { '_' } else { c })
+        .collect()
+}
+
+fn write_tasks(project: &Project, path: &Path, keep: impl Fn(&Task) -> bool) -> Result<usize, String> {
+    let tasks = &project.tasks;
     let mut wtr = csv::WriterBuilder::new()
         .delimiter(b';')
         .has_headers(false)
@@ -27,15 +74,38 @@ pub fn export_csv(tasks: &[Task], path: &Path) -> Result<usize, String> {
         .map_err(|e| format!("Failed to create CSV file: {}", e))?;
 
     // Write header
-    wtr.write_record(["Task Label", "Start Date", "End Date", "Status", "Priority", "Description", "Parent"])
+    wtr.write_record([
+        "Task Label",
+        "Start Date",
+        "End Date",
+        "Status",
+        "Priority",
+        "Description",
+        "Parent",
+        "Cost",
+        "Resource",
+        "Allocation",
+    ])
         .map_err(|e| format!("Failed to write header: {}", e))?;
 
     // Write each task
-    for task in tasks {
+    let mut written = 0;
+    for task in tasks.iter().filter(|t| project.is_counted(t) && keep(t)) {
         let parent_name = task.parent_id
             .and_then(|pid| tasks.iter().find(|t| t.id == pid))
             .map(|t| t.name.as_str())
             .unwrap_or("");
+        let assigned: Vec<(&str, f32)> = task
+            .assignments
+            .iter()
+            .filter_map(|a| project.resources.iter().find(|r| r.id == a.resource_id).map(|r| (r.name.as_str(), a.units)))
+            .collect();
+        let names = assigned.iter().map(|(name, _)| escape_list_item(name)).collect::<Vec<_>>().join(", ");
+        let allocations = assigned
+            .iter()
+            .map(|(_, units)| format!("{:.0}%", units * 100.0))
+            .collect::<Vec<_>>()
+            .join(", ");
         wtr.write_record([
             &task.name,
             &task.start.format("%d/%m/%Y").to_string(),
@@ -44,10 +114,14 @@ pub fn export_csv(tasks: &[Task], path: &Path) -> Result<usize, String> {
             task.priority.label(),
             &task.description,
             parent_name,
+            &format!("{:.2}", project.task_cost(task)),
+            &names,
+            &allocations,
         ])
         .map_err(|e| format!("Failed to write task '{}': {}", task.name, e))?;
+        written += 1;
     }
 
     wtr.flush().map_err(|e| format!("Failed to flush CSV: {}", e))?;
-    Ok(tasks.len())
+    Ok(written)
 }
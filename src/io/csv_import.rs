@@ -1,33 +1,177 @@
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
 
 use chrono::NaiveDate;
 
-use crate::model::Task;
 use crate::model::task::TaskPriority;
+use crate::model::{Assignment, Resource, Task};
 use crate::ui::theme;
 
-/// Map a status string to a progress value (0.0 – 1.0).
-fn status_to_progress(status: &str) -> f32 {
-    match status.trim().to_lowercase().as_str() {
-        "finished" | "done" | "complete" | "completed" => 1.0,
-        "in progress" | "in-progress" | "active" | "started" => 0.5,
-        "released" | "planned" => 0.25,
-        "firm planned" | "firm-planned" | "not started" | "not-started" | "new" => 0.0,
-        _ => 0.0,
+/// One status value and the progress (0.0 – 1.0) it imports as.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StatusRule {
+    pub status: String,
+    pub progress: f32,
+}
+
+/// How the Status column maps to progress. Organisations name their
+/// statuses differently, so the list is a setting rather than fixed.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct StatusMapping {
+    pub rules: Vec<StatusRule>,
+}
+
+impl Default for StatusMapping {
+    fn default() -> Self {
+        let rules = [
+            ("finished", 1.0),
+            ("done", 1.0),
+            ("complete", 1.0),
+            ("completed", 1.0),
+            ("in progress", 0.5),
+            ("in-progress", 0.5),
+            ("active", 0.5),
+            ("started", 0.5),
+            ("released", 0.25),
+            ("planned", 0.25),
+            ("firm planned", 0.0),
+            ("firm-planned", 0.0),
+            ("not started", 0.0),
+            ("not-started", 0.0),
+            ("new", 0.0),
+        ];
+        Self {
+            rules: rules
+                .iter()
+                .map(|(status, progress)| StatusRule { status: status.to_string(), progress: *progress })
+                .collect(),
+        }
+    }
+}
+
+impl StatusMapping {
+    /// Progress for a status, ignoring case and surrounding spaces; `None`
+    /// if the status is not listed.
+    pub fn progress(&self, status: &str) -> Option<f32> {
+        let status = status.trim();
+        self.rules
+            .iter()
+            .find(|rule| rule.status.trim().eq_ignore_ascii_case(status))
+            .map(|rule| rule.progress.clamp(0.0, 1.0))
     }
 }
 
-/// Try parsing a date string with several common formats.
-fn parse_date(s: &str) -> Option<NaiveDate> {
+/// Which comes first in dates like 01/02/2025.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    DayFirst,
+    MonthFirst,
+}
+
+impl DateOrder {
+    pub fn label(self) -> &'static str {
+        match self {
+            DateOrder::DayFirst => "DD/MM/YYYY",
+            DateOrder::MonthFirst => "MM/DD/YYYY",
+        }
+    }
+}
+
+/// How a date cell can be read.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum DateReading {
+    /// Only one way, e.g. ISO dates or 05/05/2025.
+    Plain(NaiveDate),
+    /// Only with this order, e.g. 25/01/2025 — evidence for the column.
+    Only(NaiveDate, DateOrder),
+    /// Differently with each order, e.g. 01/02/2025.
+    Either { day_first: NaiveDate, month_first: NaiveDate },
+}
+
+impl DateReading {
+    pub(super) fn date(self, order: DateOrder) -> NaiveDate {
+        match (self, order) {
+            (DateReading::Plain(d) | DateReading::Only(d, _), _) => d,
+            (DateReading::Either { day_first, .. }, DateOrder::DayFirst) => day_first,
+            (DateReading::Either { month_first, .. }, DateOrder::MonthFirst) => month_first,
+        }
+    }
+}
+
+/// Smallest number read as an Excel serial date: 18 May 1927. Smaller
+/// numbers are far more likely counts or ids than dates, and this also keeps
+/// clear of serials up to 60, which Excel's fictitious 29 February 1900
+/// puts a day off.
+const MIN_EXCEL_SERIAL: f64 = 10_000.0;
+
+/// Date of an Excel serial number (days since 1899-12-30, any time of day
+/// in the fraction), as date columns copied out of Excel often arrive.
+fn excel_serial_date(s: &str) -> Option<NaiveDate> {
+    let serial: f64 = s.trim().parse().ok()?;
+    // 2958465 is 9999-12-31, the last date Excel shows.
+    if !(MIN_EXCEL_SERIAL..2_958_466.0).contains(&serial) {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(1899, 12, 30)?.checked_add_signed(chrono::Duration::days(serial.floor() as i64))
+}
+
+/// Try reading a date string with several common formats.
+pub(super) fn read_date(s: &str) -> Option<DateReading> {
     let s = s.trim();
-    for fmt in &["%Y-%m-%d", "%d/%m/%Y", "%m/%d/%Y", "%d-%m-%Y", "%d.%m.%Y", "%Y/%m/%d", "%m-%d-%Y"] {
+    for fmt in ["%Y-%m-%d", "%Y/%m/%d"] {
         if let Ok(d) = NaiveDate::parse_from_str(s, fmt) {
-            return Some(d);
+            return Some(DateReading::Plain(d));
+        }
+    }
+    if let Some(d) = excel_serial_date(s) {
+        return Some(DateReading::Plain(d));
+    }
+    // Dotted dates are only written day first.
+    let try_fmt = |fmt: &str| NaiveDate::parse_from_str(s, fmt).ok();
+    let day_first = try_fmt("%d/%m/%Y").or_else(|| try_fmt("%d-%m-%Y")).or_else(|| try_fmt("%d.%m.%Y"));
+    let month_first = try_fmt("%m/%d/%Y").or_else(|| try_fmt("%m-%d-%Y"));
+    match (day_first, month_first) {
+        (Some(d), Some(m)) if d == m => Some(DateReading::Plain(d)),
+        (Some(day_first), Some(month_first)) => Some(DateReading::Either { day_first, month_first }),
+        (Some(d), None) => Some(DateReading::Only(d, DateOrder::DayFirst)),
+        (None, Some(m)) => Some(DateReading::Only(m, DateOrder::MonthFirst)),
+        (None, None) => None,
+    }
+}
+
+/// Date cells counted by what they say about the file's date order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DateEvidence {
+    /// Cells only valid day first (e.g. 25/01/2025).
+    pub day_first: usize,
+    /// Cells only valid month first (e.g. 01/25/2025).
+    pub month_first: usize,
+    /// Cells valid both ways with different dates (e.g. 01/02/2025).
+    pub ambiguous: usize,
+}
+
+impl DateEvidence {
+    pub(super) fn count(&mut self, reading: Option<DateReading>) {
+        match reading {
+            Some(DateReading::Only(_, DateOrder::DayFirst)) => self.day_first += 1,
+            Some(DateReading::Only(_, DateOrder::MonthFirst)) => self.month_first += 1,
+            Some(DateReading::Either { .. }) => self.ambiguous += 1,
+            _ => {}
         }
     }
-    None
+
+    /// The order most unambiguous cells use; day first on a tie.
+    pub fn inferred(&self) -> DateOrder {
+        if self.month_first > self.day_first { DateOrder::MonthFirst } else { DateOrder::DayFirst }
+    }
 }
 
+const END_BEFORE_START: &str = "End before start; end moved to the start date";
+
 /// Detect delimiter by checking the first line for common separators.
 fn detect_delimiter(first_line: &str) -> u8 {
     let semicolons = first_line.matches(';').count();
@@ -51,7 +195,8 @@ fn normalize_header(h: &str) -> String {
 }
 
 /// Map a normalized header to our column index:
-///   0 = name, 1 = start, 2 = end, 3 = status, 4 = priority, 5 = description
+///   0 = name, 1 = start, 2 = end, 3 = status, 4 = priority, 5 = description,
+///   6 = resources, 7 = allocation
 fn header_to_col(normalized: &str) -> Option<usize> {
     match normalized {
         "name" | "task" | "tasklabel" | "taskname" | "label" | "title"
@@ -67,28 +212,256 @@ fn header_to_col(normalized: &str) -> Option<usize> {
 
         "description" | "notes" | "note" | "details" | "comment" | "comments" => Some(5),
 
+        "resource" | "resources" | "assignee" | "assignees" | "assignedto" | "owner" => Some(6),
+
+        "allocation" | "allocations" | "units" | "allocation%" | "load" => Some(7),
+
         _ => None,
     }
 }
 
-/// Import tasks from a CSV file.
+/// Name of the field a column index from [`header_to_col`] maps to.
+fn col_label(col: usize) -> &'static str {
+    match col {
+        0 => "Name",
+        1 => "Start",
+        2 => "End",
+        3 => "Status",
+        4 => "Priority",
+        5 => "Description",
+        6 => "Resource",
+        _ => "Allocation",
+    }
+}
+
+/// A parsed CSV data row as shown in the import preview.
+pub struct PreviewRow {
+    /// Line number in the file (the header is line 1).
+    pub line: usize,
+    /// The task built from the row, or `None` if the row can't be imported.
+    pub task: Option<Task>,
+    /// Problems found in the row; for rows without a task, the reason it
+    /// can't be imported.
+    pub warnings: Vec<String>,
+    /// Whether the row will be imported.
+    pub include: bool,
+    /// Start and end readings, kept when either can be read both ways so
+    /// the task can be re-dated if the date order changes.
+    ambiguous: Option<(DateReading, DateReading)>,
+}
+
+impl PreviewRow {
+    fn failed(line: usize, warnings: Vec<String>) -> Self {
+        Self { line, task: None, warnings, include: false, ambiguous: None }
+    }
+}
+
+/// Result of parsing a CSV file for the import preview.
+pub struct CsvPreview {
+    /// Each header with the field it was matched to (`None` = ignored).
+    pub columns: Vec<(String, Option<&'static str>)>,
+    pub rows: Vec<PreviewRow>,
+    pub dates: DateEvidence,
+    /// Order used for ambiguous dates; starts as [`DateEvidence::inferred`].
+    pub date_order: DateOrder,
+    /// Whether the user confirmed `date_order`. Only asked for when some
+    /// dates are ambiguous.
+    pub date_order_confirmed: bool,
+    /// One resource per name in the Resource column; the tasks' assignments
+    /// point at these until [`CsvPreview::staff`] matches them to a project.
+    pub resources: Vec<Resource>,
+}
+
+impl CsvPreview {
+    /// Re-date the tasks of rows with ambiguous dates using `order`.
+    pub fn set_date_order(&mut self, order: DateOrder) {
+        self.date_order = order;
+        for row in &mut self.rows {
+            let (Some(task), Some((start, end))) = (row.task.as_mut(), row.ambiguous) else { continue };
+            let (start, end) = (start.date(order), end.date(order));
+            task.start = start;
+            task.end = end.max(start);
+            row.warnings.retain(|w| w != END_BEFORE_START);
+            if end < start {
+                row.warnings.push(END_BEFORE_START.to_string());
+            }
+        }
+    }
+
+    /// The tasks of all included rows.
+    pub fn included_tasks(&self) -> Vec<Task> {
+        self.rows
+            .iter()
+            .filter(|r| r.include)
+            .filter_map(|r| r.task.clone())
+            .collect()
+    }
+
+    /// The tasks of all included rows, assigned to `project`'s resources of
+    /// the same names; resources it does not have yet are added to it.
+    pub fn staff(&self, project: &mut crate::model::Project) -> Vec<Task> {
+        let mut tasks = self.included_tasks();
+        project.adopt_assignments(&self.resources, &mut tasks);
+        tasks
+    }
+}
+
+/// Split a Resource or Allocation cell holding several values ("Ann, Bo"
+/// or "Ann | Bo"). A backslash keeps the character after it, so
+/// "Smith\\, John" is one name; see `csv_export::escape_list_item`.
+fn split_list(cell: &str) -> Vec<String> {
+    let mut items = vec![String::new()];
+    let mut chars = cell.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ',' | '|' => items.push(String::new()),
+            '\\' => items.last_mut().into_iter().for_each(|item| item.push(chars.next().unwrap_or('\\'))),
+            _ => items.last_mut().into_iter().for_each(|item| item.push(c)),
+        }
+    }
+    items.into_iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
+
+/// Read an allocation as units: "50%", "50" and "0.5" are all half time.
+fn read_units(s: &str) -> Option<f32> {
+    let (number, percent) = match s.strip_suffix('%') {
+        Some(n) => (n.trim(), true),
+        None => (s, false),
+    };
+    let value: f32 = number.replace(',', ".").parse().ok()?;
+    let units = if percent || value > 2.0 { value / 100.0 } else { value };
+    (units > 0.0).then_some(units)
+}
+
+/// Assignments for a row's Resource and Allocation cells, adding resources
+/// not seen before to `resources`. A single allocation applies to every
+/// resource listed.
+fn read_assignments(
+    names: &str,
+    allocation: Option<&str>,
+    resources: &mut Vec<Resource>,
+    warnings: &mut Vec<String>,
+) -> Vec<Assignment> {
+    let units: Vec<Option<f32>> = split_list(allocation.unwrap_or("")).iter().map(|s| read_units(s)).collect();
+    if units.iter().any(|u| u.is_none()) {
+        warnings.push(format!("Unrecognised allocation '{}', assigned full time", allocation.unwrap_or("")));
+    }
+    split_list(names)
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let resource_id = match resources.iter().find(|r| r.name.eq_ignore_ascii_case(&name)) {
+                Some(resource) => resource.id,
+                None => {
+                    let resource = Resource::new(name);
+                    let id = resource.id;
+                    resources.push(resource);
+                    id
+                }
+            };
+            let units = match units.len() {
+                1 => units[0],
+                _ => units.get(i).copied().flatten(),
+            };
+            Assignment { resource_id, units: units.unwrap_or(1.0) }
+        })
+        .collect()
+}
+
+/// Parse a CSV file into a preview of the tasks it would import.
 ///
 /// Auto-detects delimiter (comma, semicolon, tab).
 /// Matches column headers flexibly (e.g. "Task Label", "Start Date", etc.).
-/// Returns `(tasks, skipped_count)` on success.
-pub fn import_csv(path: &PathBuf) -> Result<(Vec<Task>, usize), String> {
-    // Read the whole file to detect delimiter from the first line
-    let content = std::fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read file: {}", e))?;
+/// Rows are included by default unless they can't be imported.
+pub fn preview_csv(path: &Path, statuses: &StatusMapping) -> Result<CsvPreview, String> {
+    read_csv(path, statuses, |_, _| true)
+}
+
+/// A CSV preview being read on a background thread, so that very large
+/// files neither freeze the window nor have to fit in memory twice.
+pub struct CsvImportJob {
+    pub path: PathBuf,
+    /// Size of the file in bytes.
+    pub total: u64,
+    read: Arc<AtomicU64>,
+    rows: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    result: Receiver<Result<CsvPreview, String>>,
+}
 
-    let first_line = content.lines().next().unwrap_or("");
-    let delimiter = detect_delimiter(first_line);
+impl Drop for CsvImportJob {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+impl CsvImportJob {
+    pub fn start(path: PathBuf, statuses: StatusMapping) -> Self {
+        let total = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let read = Arc::new(AtomicU64::new(0));
+        let rows = Arc::new(AtomicUsize::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, result) = mpsc::channel();
+        let (progress, count, stop, file) = (read.clone(), rows.clone(), cancel.clone(), path.clone());
+        std::thread::spawn(move || {
+            let preview = read_csv(&file, &statuses, |bytes, n| {
+                progress.store(bytes, Ordering::Relaxed);
+                count.store(n, Ordering::Relaxed);
+                !stop.load(Ordering::Relaxed)
+            });
+            let _ = tx.send(preview);
+        });
+        Self { path, total, read, rows, cancel, result }
+    }
+
+    /// Share of the file read so far, 0.0 – 1.0.
+    pub fn fraction(&self) -> f32 {
+        match self.total {
+            0 => 0.0,
+            total => (self.read.load(Ordering::Relaxed) as f64 / total as f64).min(1.0) as f32,
+        }
+    }
+
+    pub fn rows_read(&self) -> usize {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    /// The preview once the file has been read.
+    pub fn poll(&self) -> Option<Result<CsvPreview, String>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err("The import stopped unexpectedly".to_string())),
+        }
+    }
+}
+
+/// Stream the records of a CSV file into a preview, reading statuses with
+/// `statuses`. `on_record` gets the bytes read and the rows parsed after
+/// each record, and stops the read by returning `false`.
+fn read_csv(
+    path: &Path,
+    statuses: &StatusMapping,
+    mut on_record: impl FnMut(u64, usize) -> bool,
+) -> Result<CsvPreview, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let mut input = BufReader::new(file);
+
+    // Detect the delimiter from the first line, then start over.
+    let mut first_line = String::new();
+    input
+        .read_line(&mut first_line)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let delimiter = detect_delimiter(&first_line);
+    input
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| format!("Failed to read file: {}", e))?;
 
     let mut reader = csv::ReaderBuilder::new()
         .delimiter(delimiter)
         .flexible(true)
         .trim(csv::Trim::All)
-        .from_reader(content.as_bytes());
+        .from_reader(input);
 
     // Parse headers and map them to column indices
     let headers = reader
@@ -115,19 +488,32 @@ pub fn import_csv(path: &PathBuf) -> Result<(Vec<Task>, usize), String> {
         ));
     }
 
-    let colors = theme::task_palette();
-    let mut tasks = Vec::new();
-    let mut skipped = 0usize;
+    let columns = headers
+        .iter()
+        .zip(&col_map)
+        .map(|(h, c)| (h.to_string(), c.map(col_label)))
+        .collect();
 
-    for (i, result) in reader.records().enumerate() {
-        let record = match result {
-            Ok(r) => r,
+    let colors = theme::task_palette();
+    let mut rows = Vec::new();
+    let mut dates = DateEvidence::default();
+    let mut resources = Vec::new();
+    let mut record = csv::StringRecord::new();
+
+    loop {
+        if !on_record(reader.position().byte(), rows.len()) {
+            return Err("CSV import cancelled".to_string());
+        }
+        let line = rows.len() + 2;
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) if e.is_io_error() => return Err(format!("Failed to read file: {}", e)),
             Err(e) => {
-                eprintln!("Skipping CSV row {}: {}", i + 2, e);
-                skipped += 1;
+                rows.push(PreviewRow::failed(line, vec![e.to_string()]));
                 continue;
             }
-        };
+        }
 
         // Extract fields by mapped column positions
         let mut name_val = None;
@@ -136,6 +522,8 @@ pub fn import_csv(path: &PathBuf) -> Result<(Vec<Task>, usize), String> {
         let mut status_val = None;
         let mut priority_val = None;
         let mut description_val = None;
+        let mut resource_val = None;
+        let mut allocation_val = None;
 
         for (col_idx, field) in record.iter().enumerate() {
             if col_idx < col_map.len() {
@@ -146,49 +534,61 @@ pub fn import_csv(path: &PathBuf) -> Result<(Vec<Task>, usize), String> {
                     Some(3) => status_val = Some(field.trim().to_string()),
                     Some(4) => priority_val = Some(field.trim().to_string()),
                     Some(5) => description_val = Some(field.trim().to_string()),
+                    Some(6) => resource_val = Some(field.trim().to_string()),
+                    Some(7) => allocation_val = Some(field.trim().to_string()),
                     _ => {}
                 }
             }
         }
 
-        let name = match name_val {
-            Some(n) if !n.is_empty() => n,
-            _ => { skipped += 1; continue; }
-        };
-
-        let start = match start_val.as_deref().and_then(parse_date) {
-            Some(d) => d,
-            None => {
-                eprintln!("Skipping row {}: invalid start date '{}'", i + 2, start_val.as_deref().unwrap_or(""));
-                skipped += 1;
-                continue;
-            }
+        let mut warnings = Vec::new();
+        let name = name_val.filter(|n| !n.is_empty());
+        let start_reading = start_val.as_deref().and_then(read_date);
+        let end_reading = end_val.as_deref().and_then(read_date);
+        dates.count(start_reading);
+        dates.count(end_reading);
+        // Ambiguous dates are read day first for now; see set_date_order.
+        let start = start_reading.map(|r| r.date(DateOrder::DayFirst));
+        let end = end_reading.map(|r| r.date(DateOrder::DayFirst));
+        if name.is_none() {
+            warnings.push("Missing task name".to_string());
+        }
+        if start.is_none() {
+            warnings.push(format!("Invalid start date '{}'", start_val.as_deref().unwrap_or("")));
+        }
+        if end.is_none() {
+            warnings.push(format!("Invalid end date '{}'", end_val.as_deref().unwrap_or("")));
+        }
+        let (Some(name), Some(start), Some(end)) = (name, start, end) else {
+            rows.push(PreviewRow::failed(line, warnings));
+            continue;
         };
+        if end < start {
+            warnings.push(END_BEFORE_START.to_string());
+        }
 
-        let end = match end_val.as_deref().and_then(parse_date) {
-            Some(d) => d,
-            None => {
-                eprintln!("Skipping row {}: invalid end date '{}'", i + 2, end_val.as_deref().unwrap_or(""));
-                skipped += 1;
-                continue;
-            }
+        let progress = match status_val.as_deref().filter(|s| !s.is_empty()) {
+            Some(s) => statuses.progress(s).unwrap_or_else(|| {
+                warnings.push(format!("Unrecognised status '{}', treated as not started", s));
+                0.0
+            }),
+            None => 0.0,
         };
 
-        let progress = status_val
-            .as_deref()
-            .map(status_to_progress)
-            .unwrap_or(0.0);
-
-        let priority = priority_val
-            .as_deref()
-            .map(|s| match s.trim().to_lowercase().as_str() {
+        let priority = match priority_val.as_deref().filter(|s| !s.is_empty()) {
+            Some(s) => match s.trim().to_lowercase().as_str() {
                 "critical" => TaskPriority::Critical,
                 "high" => TaskPriority::High,
                 "medium" | "med" | "normal" => TaskPriority::Medium,
                 "low" => TaskPriority::Low,
-                _ => TaskPriority::None,
-            })
-            .unwrap_or(TaskPriority::None);
+                "none" => TaskPriority::None,
+                _ => {
+                    warnings.push(format!("Unrecognised priority '{}'", s));
+                    TaskPriority::None
+                }
+            },
+            None => TaskPriority::None,
+        };
 
         let description = description_val.unwrap_or_default();
 
@@ -196,19 +596,70 @@ pub fn import_csv(path: &PathBuf) -> Result<(Vec<Task>, usize), String> {
         task.progress = progress;
         task.priority = priority;
         task.description = description;
-        task.color = colors[tasks.len() % colors.len()];
-        tasks.push(task);
+        if let Some(names) = resource_val.as_deref() {
+            task.assignments = read_assignments(names, allocation_val.as_deref(), &mut resources, &mut warnings);
+        }
+        task.color = colors[rows.len() % colors.len()];
+        let ambiguous = match (start_reading, end_reading) {
+            (Some(s), Some(e)) if [s, e].iter().any(|r| matches!(r, DateReading::Either { .. })) => Some((s, e)),
+            _ => None,
+        };
+        rows.push(PreviewRow { line, task: Some(task), warnings, include: true, ambiguous });
     }
 
-    if tasks.is_empty() && skipped > 0 {
-        return Err(format!(
-            "No valid tasks found in CSV ({} rows skipped)",
-            skipped
-        ));
-    }
-    if tasks.is_empty() {
+    if rows.is_empty() {
         return Err("CSV file is empty or has no data rows".to_string());
     }
 
-    Ok((tasks, skipped))
+    let mut preview = CsvPreview {
+        columns,
+        rows,
+        dates,
+        date_order: DateOrder::DayFirst,
+        date_order_confirmed: dates.ambiguous == 0,
+        resources,
+    };
+    preview.set_date_order(dates.inferred());
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_only_plausible_excel_serials() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(excel_serial_date("45000"), Some(date(2023, 3, 15)));
+        assert_eq!(excel_serial_date("45000.75"), Some(date(2023, 3, 15)));
+        // Small numbers are not dates, including those around Excel's
+        // non-existent 29 February 1900.
+        assert_eq!(excel_serial_date("5"), None);
+        assert_eq!(excel_serial_date("60"), None);
+        assert_eq!(excel_serial_date("61"), None);
+        assert_eq!(excel_serial_date("9999"), None);
+        assert!(read_date("5").is_none());
+    }
+
+    #[test]
+    fn resource_names_with_commas_survive_a_round_trip() {
+        let day = NaiveDate::from_ymd_opt(2024, 4, 2).unwrap();
+        let mut project = crate::model::Project::new("Staffing");
+        let smith = Resource::new("Smith, John");
+        let ops = Resource::new("Ops | Night");
+        let mut task = Task::new("Deploy", day, day + chrono::Duration::days(2));
+        task.assignments = vec![Assignment { resource_id: smith.id, units: 0.5 }, Assignment::new(ops.id)];
+        project.resources = vec![smith, ops];
+        project.tasks.push(task);
+
+        let path = std::env::temp_dir().join(format!("round-trip-{}.csv", uuid::Uuid::new_v4()));
+        crate::io::csv_export::export_csv(&project, &path).unwrap();
+        let preview = preview_csv(&path, &StatusMapping::default());
+        let _ = std::fs::remove_file(&path);
+        let preview = preview.unwrap();
+        let names: Vec<&str> = preview.resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["Smith, John", "Ops | Night"]);
+        let units: Vec<f32> = preview.included_tasks()[0].assignments.iter().map(|a| a.units).collect();
+        assert_eq!(units, [0.5, 1.0]);
+    }
 }
@@ -0,0 +1,159 @@
+//! `ganttapp://open?file=…&task=…` deep links, also accepted on the command
+//! line as `rust-gantt-app [--readonly] <file> [--task <id or name>]`.
+
+use std::path::PathBuf;
+use uuid::Uuid;
+
+pub const SCHEME: &str = "ganttapp";
+
+/// What a deep link asks the app to open.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeepLink {
+    pub file: Option<PathBuf>,
+    /// Task id, or a task name when no id matches.
+    pub task: Option<String>,
+    /// Open the file read-only (`--readonly` on the command line).
+    pub read_only: bool,
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| s.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(b), _) => {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+            (None, b'+') => out.push(b' '),
+            (None, b) => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Parse a `ganttapp://open?file=…&task=…` URL.
+pub fn parse_url(url: &str) -> Option<DeepLink> {
+    let rest = url.strip_prefix(SCHEME)?.strip_prefix("://")?;
+    let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+    if action.trim_end_matches('/') != "open" {
+        return None;
+    }
+    let mut link = DeepLink::default();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = percent_decode(value);
+        match key {
+            "file" if !value.is_empty() => link.file = Some(PathBuf::from(value)),
+            "task" if !value.is_empty() => link.task = Some(value),
+            _ => {}
+        }
+    }
+    Some(link)
+}
+
+/// Read a deep link from the command line arguments (without the program
+/// name): either a `ganttapp://` URL or a file path with an optional
+/// `--task <id or name>` and `--readonly`.
+pub fn from_args(args: &[String]) -> Option<DeepLink> {
+    let first = args.first()?;
+    if first.starts_with(SCHEME) {
+        return parse_url(first);
+    }
+    let mut link = DeepLink::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--task" {
+            link.task = iter.next().cloned();
+        } else if arg == "--readonly" {
+            link.read_only = true;
+        } else if link.file.is_none() {
+            link.file = Some(PathBuf::from(arg));
+        }
+    }
+    (link.file.is_some() || link.task.is_some()).then_some(link)
+}
+
+/// Link that opens `file` at `task`.
+pub fn task_url(file: &std::path::Path, task: Uuid) -> String {
+    format!(
+        "{}://open?file={}&task={}",
+        SCHEME,
+        percent_encode(&file.to_string_lossy()),
+        task
+    )
+}
+
+/// Register this executable as the handler for `ganttapp://` links for the
+/// current user. Returns a description of what was done.
+pub fn register_url_scheme() -> Result<String, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Cannot find the executable: {}", e))?;
+    register_for(&exe)
+}
+
+#[cfg(target_os = "windows")]
+fn register_for(exe: &std::path::Path) -> Result<String, String> {
+    let key = format!(r"HKCU\Software\Classes\{}", SCHEME);
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let entries: [(String, Vec<&str>); 3] = [
+        (key.clone(), vec!["/ve", "/d", "URL:Gantt App link"]),
+        (key.clone(), vec!["/v", "URL Protocol", "/d", ""]),
+        (format!(r"{}\shell\open\command", key), vec!["/ve", "/d", command.as_str()]),
+    ];
+    for (path, args) in &entries {
+        let status = std::process::Command::new("reg")
+            .arg("add")
+            .arg(path)
+            .args(args)
+            .arg("/f")
+            .status()
+            .map_err(|e| format!("Failed to run reg: {}", e))?;
+        if !status.success() {
+            return Err(format!("reg add {} failed", path));
+        }
+    }
+    Ok("Registered ganttapp:// links in the user registry".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn register_for(exe: &std::path::Path) -> Result<String, String> {
+    let dirs = directories::BaseDirs::new().ok_or("Cannot find the home directory")?;
+    let apps = dirs.data_dir().join("applications");
+    std::fs::create_dir_all(&apps).map_err(|e| format!("Failed to create {}: {}", apps.display(), e))?;
+    let desktop = "rust-gantt-app-url.desktop";
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=Rust Gantt App\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/{};\n",
+        exe.display(),
+        SCHEME
+    );
+    std::fs::write(apps.join(desktop), entry).map_err(|e| format!("Failed to write desktop entry: {}", e))?;
+    let status = std::process::Command::new("xdg-mime")
+        .args(["default", desktop, &format!("x-scheme-handler/{}", SCHEME)])
+        .status()
+        .map_err(|e| format!("Failed to run xdg-mime: {}", e))?;
+    if !status.success() {
+        return Err("xdg-mime could not set the link handler".to_string());
+    }
+    Ok(format!("Registered ganttapp:// links ({})", desktop))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn register_for(_exe: &std::path::Path) -> Result<String, String> {
+    Err("On this platform ganttapp:// links are registered by the app bundle (CFBundleURLTypes)".to_string())
+}
@@ -0,0 +1,199 @@
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::model::task::{Dependency, DependencyKind, NOTE_KEY};
+use crate::model::Project;
+
+/// Export dependencies to a semicolon-delimited CSV file.
+///
+/// Columns: From ID ; From ; To ID ; To ; Type ; Lag ; Note
+/// Type is FS/SS/FF/SF; lag is in days. Returns the number of links written.
+pub fn export_dependencies_csv(project: &Project, path: &Path) -> Result<usize, String> {
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(b';')
+        .from_path(path)
+        .map_err(|e| format!("Failed to create CSV file: {}", e))?;
+
+    wtr.write_record(["From ID", "From", "To ID", "To", "Type", "Lag", "Note"])
+        .map_err(|e| format!("Failed to write header: {}", e))?;
+
+    let name_of = |id: Uuid| {
+        project
+            .tasks
+            .iter()
+            .find(|t| t.id == id)
+            .map(|t| t.name.as_str())
+            .unwrap_or("")
+    };
+    let mut written = 0;
+    for dep in &project.dependencies {
+        wtr.write_record([
+            &dep.from_task.to_string(),
+            name_of(dep.from_task),
+            &dep.to_task.to_string(),
+            name_of(dep.to_task),
+            dep.kind.short_label(),
+            &dep.lag_days.to_string(),
+            dep.note().unwrap_or(""),
+        ])
+        .map_err(|e| format!("Failed to write dependency: {}", e))?;
+        written += 1;
+    }
+
+    wtr.flush().map_err(|e| format!("Failed to flush CSV: {}", e))?;
+    Ok(written)
+}
+
+/// Column roles recognised in a dependency CSV.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Column {
+    FromId,
+    From,
+    ToId,
+    To,
+    Kind,
+    Lag,
+    Note,
+}
+
+fn header_to_column(header: &str) -> Option<Column> {
+    let normalized = header.trim().to_lowercase().replace([' ', '-', '_'], "");
+    match normalized.as_str() {
+        "fromid" | "predecessorid" | "sourceid" => Some(Column::FromId),
+        "from" | "predecessor" | "source" | "fromtask" => Some(Column::From),
+        "toid" | "successorid" | "targetid" => Some(Column::ToId),
+        "to" | "successor" | "target" | "totask" => Some(Column::To),
+        "type" | "kind" | "linktype" | "relation" => Some(Column::Kind),
+        "lag" | "lagdays" | "delay" => Some(Column::Lag),
+        "note" | "notes" | "comment" => Some(Column::Note),
+        _ => None,
+    }
+}
+
+fn parse_kind(s: &str) -> Option<DependencyKind> {
+    match s.trim().to_lowercase().replace([' ', '-', '_'], "").as_str() {
+        "" | "fs" | "finishtostart" => Some(DependencyKind::FinishToStart),
+        "ss" | "starttostart" => Some(DependencyKind::StartToStart),
+        "ff" | "finishtofinish" => Some(DependencyKind::FinishToFinish),
+        "sf" | "starttofinish" => Some(DependencyKind::StartToFinish),
+        _ => None,
+    }
+}
+
+/// Parse a lag such as `2`, `2d`, `-1 days`; empty means no lag.
+fn parse_lag(s: &str) -> Option<i64> {
+    let digits = s.trim().trim_end_matches(|c: char| c.is_alphabetic() || c.is_whitespace());
+    if digits.is_empty() {
+        return Some(0);
+    }
+    digits.trim_start_matches('+').parse().ok()
+}
+
+/// Find a task by id, or by exact (case-insensitive) name when the name is
+/// unique in the project.
+fn resolve(project: &Project, id: Option<&str>, name: Option<&str>) -> Option<Uuid> {
+    if let Some(id) = id.and_then(|s| Uuid::parse_str(s.trim()).ok()) {
+        if project.tasks.iter().any(|t| t.id == id) {
+            return Some(id);
+        }
+    }
+    let name = name?.trim().to_lowercase();
+    let mut matches = project.tasks.iter().filter(|t| t.name.to_lowercase() == name);
+    let first = matches.next()?;
+    if matches.next().is_some() {
+        return None;
+    }
+    Some(first.id)
+}
+
+/// Read dependencies from a CSV file, resolving the tasks they reference in
+/// `project` by id or name. Links to unknown or ambiguous tasks, and links
+/// rejected by [`Project::check_link`] (self-links, duplicates, cycles) given
+/// the rows accepted before them, are skipped.
+/// Returns `(dependencies, skipped_count)`.
+pub fn import_dependencies_csv(project: &Project, path: &Path) -> Result<(Vec<Dependency>, usize), String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let first_line = content.lines().next().unwrap_or("");
+    let delimiter = if first_line.matches(';').count() >= first_line.matches(',').count() { b';' } else { b',' };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV headers: {}", e))?
+        .clone();
+    let columns: Vec<Option<Column>> = headers.iter().map(header_to_column).collect();
+    let has = |c: Column| columns.contains(&Some(c));
+    if !(has(Column::From) || has(Column::FromId)) || !(has(Column::To) || has(Column::ToId)) {
+        return Err(format!(
+            "CSV is missing required columns. Found headers: {:?}. Need columns for: from task, to task.",
+            headers.iter().collect::<Vec<_>>()
+        ));
+    }
+
+    // Accepted rows go into a scratch copy so later rows are checked against them.
+    let mut scratch = project.clone();
+    let links_before = scratch.dependencies.len();
+    let mut skipped = 0usize;
+    for (i, result) in reader.records().enumerate() {
+        let Ok(record) = result else {
+            skipped += 1;
+            continue;
+        };
+        let field = |c: Column| {
+            columns
+                .iter()
+                .position(|col| *col == Some(c))
+                .and_then(|idx| record.get(idx))
+                .filter(|s| !s.is_empty())
+        };
+        let from = resolve(project, field(Column::FromId), field(Column::From));
+        let to = resolve(project, field(Column::ToId), field(Column::To));
+        let kind = parse_kind(field(Column::Kind).unwrap_or(""));
+        let lag = parse_lag(field(Column::Lag).unwrap_or(""));
+        let (Some(from), Some(to), Some(kind), Some(lag)) = (from, to, kind, lag) else {
+            tracing::warn!("Skipping dependency row {}: unknown task, type or lag", i + 2);
+            skipped += 1;
+            continue;
+        };
+        if scratch.check_link(from, to).is_err() {
+            skipped += 1;
+            continue;
+        }
+        let mut dep = Dependency::new(from, to, kind);
+        dep.lag_days = lag;
+        if let Some(note) = field(Column::Note) {
+            dep.metadata.insert(NOTE_KEY.to_string(), note.to_string());
+        }
+        scratch.dependencies.push(dep);
+    }
+    Ok((scratch.dependencies.split_off(links_before), skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Task;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn rows_that_close_a_cycle_with_earlier_rows_are_skipped() {
+        let day = NaiveDate::from_ymd_opt(2024, 4, 2).unwrap();
+        let mut project = Project::new("Links");
+        project.tasks.push(Task::new("A", day, day + chrono::Duration::days(2)));
+        project.tasks.push(Task::new("B", day, day + chrono::Duration::days(2)));
+
+        let path = std::env::temp_dir().join(format!("links-{}.csv", Uuid::new_v4()));
+        std::fs::write(&path, "From;To;Type\nA;B;FS\nB;A;FS\nA;B;SS\n").unwrap();
+        let imported = import_dependencies_csv(&project, &path);
+        let _ = std::fs::remove_file(&path);
+        let (deps, skipped) = imported.unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!((deps[0].from_task, deps[0].to_task), (project.tasks[0].id, project.tasks[1].id));
+        assert_eq!(skipped, 2);
+    }
+}
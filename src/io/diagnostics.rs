@@ -0,0 +1,160 @@
+//! Log file and the "Export Diagnostics" bundle attached to bug reports.
+//!
+//! Events are written with `tracing` to `gantt.log` in the user's data
+//! directory; records from eframe and the renderer arrive through the `log`
+//! bridge. A panic hook logs the panic and its backtrace before the default
+//! hook runs, so a crash leaves its cause in the file.
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::model::Project;
+
+/// Size at which the log is rotated to `gantt.log.1` on startup.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Directory holding the log files.
+pub fn log_dir() -> PathBuf {
+    directories::ProjectDirs::from("", "", "RustGanttApp")
+        .map(|dirs| dirs.data_local_dir().join("logs"))
+        .unwrap_or_else(|| PathBuf::from("logs"))
+}
+
+pub fn log_path() -> PathBuf {
+    log_dir().join("gantt.log")
+}
+
+fn open_log() -> Result<File, String> {
+    let dir = log_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = log_path();
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > MAX_LOG_BYTES) {
+        let _ = std::fs::rename(&path, path.with_extension("log.1"));
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
+
+/// Start logging to the log file and install the panic hook. Logging is
+/// best effort: when the file cannot be opened the app runs without it.
+pub fn init_logging() -> Result<(), String> {
+    // Unbuffered: each event reaches the file before the next one starts.
+    let file = open_log()?;
+    tracing_subscriber::fmt()
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .with_max_level(tracing::Level::INFO)
+        .try_init()
+        .map_err(|e| e.to_string())?;
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        tracing::error!(target: "panic", "{}\n{}", info, backtrace);
+        default_hook(info);
+    }));
+    tracing::info!("Rust Gantt App {} starting on {}", env!("CARGO_PKG_VERSION"), std::env::consts::OS);
+    Ok(())
+}
+
+/// Shape of the project without names, notes or dates: counts and sizes
+/// that help reproduce rendering and scheduling issues.
+pub fn project_summary(project: &Project) -> String {
+    let tasks = &project.tasks;
+    let parents = tasks.iter().filter(|t| t.has_children(tasks)).count();
+    let count = |f: &dyn Fn(&crate::model::Task) -> bool| tasks.iter().filter(|t| f(t)).count();
+    let depth_of = |task: &crate::model::Task| {
+        let mut depth = 0;
+        let mut parent = task.parent_id;
+        while let Some(id) = parent.filter(|_| depth < tasks.len()) {
+            depth += 1;
+            parent = tasks.iter().find(|t| t.id == id).and_then(|t| t.parent_id);
+        }
+        depth
+    };
+    let mut kinds: BTreeMap<&str, usize> = BTreeMap::new();
+    for dep in &project.dependencies {
+        *kinds.entry(dep.kind.short_label()).or_default() += 1;
+    }
+    let span = tasks.iter().map(|t| t.start).min().zip(tasks.iter().map(|t| t.end).max());
+    let issues = crate::model::validation::check_schedule(project, true);
+    let calendar = &project.calendar;
+
+    let mut out = String::new();
+    let mut line = |label: &str, value: String| out.push_str(&format!("{}: {}\n", label, value));
+    line("Format version", project.version.to_string());
+    line("Tasks", tasks.len().to_string());
+    line("Parents", parents.to_string());
+    line("Milestones", count(&|t| t.is_milestone).to_string());
+    line("Auto-dated", count(&|t| t.auto_date).to_string());
+    line("Effort-driven", count(&|t| t.work_days.is_some()).to_string());
+    line("External", count(&|t| t.external).to_string());
+    line("Tentative", count(&|t| t.tentative).to_string());
+    line("Collapsed", count(&|t| t.collapsed).to_string());
+    line("Max depth", tasks.iter().map(depth_of).max().unwrap_or(0).to_string());
+    line(
+        "Span (days)",
+        span.map_or("-".to_string(), |(start, end)| (end - start).num_days().to_string()),
+    );
+    line(
+        "Dependencies",
+        format!(
+            "{} ({})",
+            project.dependencies.len(),
+            kinds.iter().map(|(k, n)| format!("{} {}", n, k)).collect::<Vec<_>>().join(", ")
+        ),
+    );
+    line("Resources", project.resources.len().to_string());
+    line("Assignments", tasks.iter().map(|t| t.assignments.len()).sum::<usize>().to_string());
+    line("Baseline", project.baseline.is_some().to_string());
+    line("Saved views", project.views.len().to_string());
+    line("Markers / bands", format!("{} / {}", project.markers.len(), project.bands.len()));
+    line("Notes", project.annotations.len().to_string());
+    line("Trash entries", project.trash.len().to_string());
+    line("Audit entries", project.audit.len().to_string());
+    line("Schedule issues", issues.len().to_string());
+    line(
+        "Calendar",
+        format!(
+            "{}, weeks start {}, {} h/day, fiscal year from month {}",
+            calendar.week_numbering.label(),
+            calendar.week_start_day.label(),
+            calendar.hours_per_day,
+            calendar.fiscal_year_start_month
+        ),
+    );
+    out
+}
+
+/// Write a zip with the log files, the app settings and an anonymized
+/// project summary. Returns the number of files packed.
+pub fn export_diagnostics(path: &Path, settings_json: String, project: &Project) -> Result<usize, String> {
+    let mut files = vec![
+        (
+            "about.txt".to_string(),
+            format!(
+                "Rust Gantt App {}\nOS: {} ({})\nExported: {}\n",
+                env!("CARGO_PKG_VERSION"),
+                std::env::consts::OS,
+                std::env::consts::ARCH,
+                chrono::Utc::now().to_rfc3339()
+            ),
+        ),
+        ("settings.json".to_string(), settings_json),
+        ("project-summary.txt".to_string(), project_summary(project)),
+    ];
+    let log = log_path();
+    for (name, file) in [("gantt.log.1", log.with_extension("log.1")), ("gantt.log", log)] {
+        if let Ok(text) = std::fs::read(&file) {
+            files.push((name.to_string(), String::from_utf8_lossy(&text).into_owned()));
+        }
+    }
+    std::fs::write(path, crate::io::pptx::write_zip(&files))
+        .map_err(|e| format!("Failed to write diagnostics: {}", e))?;
+    Ok(files.len())
+}
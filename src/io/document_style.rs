@@ -0,0 +1,52 @@
+//! The "document" rendering profile used by every export: white paper,
+//! near-black text and print-safe bar colors, whatever theme the app is
+//! showing on screen.
+
+use egui::Color32;
+
+use crate::ui::theme;
+
+pub struct DocumentStyle {
+    pub background: Color32,
+    pub text: Color32,
+    /// Secondary text: dates, ranges, captions.
+    pub muted: Color32,
+    /// Table rules and timeline grid lines.
+    pub rule: Color32,
+    /// Alternate row shading.
+    pub stripe: Color32,
+    /// Unfilled part of a task bar.
+    pub track: Color32,
+    /// Date marker lines and their labels.
+    pub marker: Color32,
+    pub today: Color32,
+}
+
+pub const DOCUMENT: DocumentStyle = DocumentStyle {
+    background: Color32::WHITE,
+    text: Color32::from_rgb(0x1F, 0x29, 0x37),
+    muted: Color32::from_rgb(0x6B, 0x72, 0x80),
+    rule: Color32::from_rgb(0xD1, 0xD5, 0xDB),
+    stripe: Color32::from_rgb(0xF3, 0xF4, 0xF6),
+    track: Color32::from_rgb(0xD1, 0xD5, 0xDB),
+    marker: Color32::from_rgb(0xB7, 0x79, 0x1F),
+    today: Color32::from_rgb(0xDC, 0x26, 0x26),
+};
+
+impl DocumentStyle {
+    /// A task color darkened just enough to stay visible on paper, so pale
+    /// bars chosen for a dark screen theme do not vanish when printed.
+    pub fn bar(&self, color: Color32) -> Color32 {
+        theme::readable_on(Color32::from_rgb(color.r(), color.g(), color.b()), self.background)
+    }
+}
+
+/// `#rrggbb`, for HTML and CSS.
+pub fn css(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+/// `RRGGBB`, for Office XML.
+pub fn ooxml(color: Color32) -> String {
+    format!("{:02X}{:02X}{:02X}", color.r(), color.g(), color.b())
+}
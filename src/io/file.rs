@@ -1,14 +1,102 @@
 use crate::model::Project;
-use std::path::PathBuf;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Identifies the on-disk state of a project file at the time it was loaded or
+/// last saved, so external modifications can be detected before overwriting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFingerprint {
+    pub modified: Option<SystemTime>,
+    pub len: u64,
+    pub hash: u64,
+}
+
+/// Compute the fingerprint of a file, or `None` if it cannot be read.
+pub fn file_fingerprint(path: &Path) -> Option<FileFingerprint> {
+    let bytes = std::fs::read(path).ok()?;
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    Some(FileFingerprint {
+        modified,
+        len: bytes.len() as u64,
+        hash: hasher.finish(),
+    })
+}
 
 /// Save a project to a JSON file.
+///
+/// The JSON is written to a temporary sibling file first and then renamed over
+/// the target, so a crash mid-save never leaves a truncated project behind.
 pub fn save_project(project: &Project, path: &PathBuf) -> Result<(), String> {
     let json = serde_json::to_string_pretty(project).map_err(|e| e.to_string())?;
-    std::fs::write(path, json).map_err(|e| e.to_string())
+    write_replacing(path, &json)
+}
+
+/// Save a project like [`save_project`], but first check that the JSON reads
+/// back as the same project, and keep up to `keep` earlier versions as
+/// `<file>.bak1` (newest) … `<file>.bakN`.
+pub fn save_project_with_backups(project: &Project, path: &PathBuf, keep: usize) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(project).map_err(|e| e.to_string())?;
+    let reread: Project = serde_json::from_str(&json).map_err(|e| format!("Saved data does not read back: {}", e))?;
+    // Compared as values: map fields (e.g. baseline dates) have no fixed order.
+    let as_value = |text: &str| serde_json::from_str::<serde_json::Value>(text).map_err(|e| e.to_string());
+    let again = serde_json::to_string(&reread).map_err(|e| e.to_string())?;
+    if as_value(&again)? != as_value(&json)? {
+        return Err("Saved data does not read back as the same project; the file was not replaced".to_string());
+    }
+    if keep > 0 && path.exists() {
+        rotate_backups(path, keep)?;
+    }
+    write_replacing(path, &json)
+}
+
+/// `<file>.bak<n>` next to `path`.
+pub fn backup_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_else(|| "project".into());
+    name.push(format!(".bak{}", n));
+    path.with_file_name(name)
+}
+
+/// Shift `.bak1…` up by one, dropping the oldest, and copy the current file
+/// to `.bak1`.
+fn rotate_backups(path: &Path, keep: usize) -> Result<(), String> {
+    let _ = std::fs::remove_file(backup_path(path, keep));
+    for n in (1..keep).rev() {
+        let from = backup_path(path, n);
+        if from.exists() {
+            std::fs::rename(&from, backup_path(path, n + 1)).map_err(|e| format!("Failed to rotate backups: {}", e))?;
+        }
+    }
+    std::fs::copy(path, backup_path(path, 1)).map_err(|e| format!("Failed to write backup: {}", e))?;
+    Ok(())
+}
+
+fn write_replacing(path: &PathBuf, json: &str) -> Result<(), String> {
+    let mut tmp_name = path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_else(|| "project".into());
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    std::fs::write(&tmp_path, json).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        e.to_string()
+    })
 }
 
-/// Load a project from a JSON file.
+/// Load a project from a JSON file. Tasks are put back under their parents
+/// if the file lists them out of order, e.g. after a hand edit or a merge.
 pub fn load_project(path: &PathBuf) -> Result<Project, String> {
     let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
-    serde_json::from_str(&json).map_err(|e| e.to_string())
+    let mut project: Project = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    if project.sort_tasks_grouped() {
+        tracing::info!("Reordered the tasks of {} to follow their parents", path.display());
+    }
+    Ok(project)
 }
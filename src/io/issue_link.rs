@@ -0,0 +1,186 @@
+//! Pull the status of a task's linked GitHub issue/PR or Jira ticket.
+//!
+//! Requests go through the system `curl` so the app needs no HTTP stack of its
+//! own. Credentials are read from the environment: `GITHUB_TOKEN` for GitHub,
+//! and `JIRA_USER` + `JIRA_TOKEN` for Jira (basic auth with an API token),
+//! sent only to the Jira at `JIRA_BASE_URL`. They reach curl as a config on
+//! its standard input, so they never show up in the process list.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// A parsed external link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueRef {
+    GitHub { owner: String, repo: String, number: u64, pull: bool },
+    Jira { base: String, key: String },
+}
+
+impl IssueRef {
+    /// Recognise `https://github.com/<owner>/<repo>/issues/<n>` (or `/pull/<n>`)
+    /// and `https://<host>/browse/<KEY-123>`.
+    pub fn parse(url: &str) -> Option<IssueRef> {
+        let url = url.trim().trim_end_matches('/');
+        let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"))?;
+        let parts: Vec<&str> = rest.split('/').collect();
+        match parts.as_slice() {
+            ["github.com", owner, repo, kind @ ("issues" | "pull"), number, ..] => Some(IssueRef::GitHub {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                number: number.parse().ok()?,
+                pull: *kind == "pull",
+            }),
+            [host, path @ ..] => {
+                let browse = path.iter().position(|p| *p == "browse")?;
+                let key = path.get(browse + 1)?;
+                if !key.contains('-') {
+                    return None;
+                }
+                let prefix = path[..browse].join("/");
+                let base = if prefix.is_empty() {
+                    format!("https://{}", host)
+                } else {
+                    format!("https://{}/{}", host, prefix)
+                };
+                Some(IssueRef::Jira { base, key: key.to_string() })
+            }
+            _ => None,
+        }
+    }
+
+    fn api_url(&self) -> String {
+        match self {
+            IssueRef::GitHub { owner, repo, number, pull: false } => {
+                format!("https://api.github.com/repos/{}/{}/issues/{}", owner, repo, number)
+            }
+            IssueRef::GitHub { owner, repo, number, pull: true } => {
+                format!("https://api.github.com/repos/{}/{}/pulls/{}", owner, repo, number)
+            }
+            IssueRef::Jira { base, key } => format!("{}/rest/api/2/issue/{}?fields=status", base, key),
+        }
+    }
+}
+
+/// Status reported by the tracker, and the progress it implies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkStatus {
+    /// Tracker status as shown to the user, e.g. "In Review" or "closed".
+    pub status: String,
+    /// `Some(1.0)` when done, `Some(0.0)` when not started, `None` when the
+    /// item is in progress and the task's own progress should be kept.
+    pub progress: Option<f32>,
+}
+
+/// Whether `base`, a Jira parsed from a link, is the one at `configured`
+/// (`JIRA_BASE_URL`), so it may be sent the Jira credentials.
+fn is_configured_jira(base: &str, configured: Option<&str>) -> bool {
+    configured.is_some_and(|c| c.trim().trim_end_matches('/').eq_ignore_ascii_case(base))
+}
+
+/// `value` as a double-quoted curl config string.
+fn config_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn curl(issue: &IssueRef) -> Result<serde_json::Value, String> {
+    let mut cmd = Command::new("curl");
+    cmd.args(["-sS", "-f", "-L", "--max-time", "15", "-K", "-"]);
+    let mut config = String::new();
+    match issue {
+        IssueRef::GitHub { .. } => {
+            cmd.args(["-H", "Accept: application/vnd.github+json"]);
+            if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+                config += &format!("header = {}\n", config_quote(&format!("Authorization: Bearer {}", token)));
+            }
+        }
+        IssueRef::Jira { base, .. } => {
+            cmd.args(["-H", "Accept: application/json"]);
+            let credentials = (std::env::var("JIRA_USER"), std::env::var("JIRA_TOKEN"));
+            if let (Ok(user), Ok(token)) = credentials {
+                if is_configured_jira(base, std::env::var("JIRA_BASE_URL").ok().as_deref()) {
+                    config += &format!("user = {}\n", config_quote(&format!("{}:{}", user, token)));
+                }
+            }
+        }
+    }
+    let mut child = cmd
+        .arg(issue.api_url())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(config.as_bytes()).map_err(|e| format!("Failed to run curl: {}", e))?;
+    }
+    let output = child.wait_with_output().map_err(|e| format!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Request failed: {}", stderr.trim()));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("Unexpected response: {}", e))
+}
+
+/// Fetch the current status of the item behind `url`.
+pub fn fetch_status(url: &str) -> Result<LinkStatus, String> {
+    let issue = IssueRef::parse(url).ok_or_else(|| format!("Not a GitHub or Jira link: {}", url))?;
+    let json = curl(&issue)?;
+    match issue {
+        IssueRef::GitHub { pull, .. } => {
+            let state = json["state"].as_str().ok_or("Response has no state")?;
+            let merged = pull && json["merged"].as_bool().unwrap_or(false);
+            let status = if merged { "merged".to_string() } else { state.to_string() };
+            let progress = if state == "closed" { Some(1.0) } else { None };
+            Ok(LinkStatus { status, progress })
+        }
+        IssueRef::Jira { .. } => {
+            let status = &json["fields"]["status"];
+            let name = status["name"].as_str().ok_or("Response has no status")?;
+            let progress = match status["statusCategory"]["key"].as_str() {
+                Some("done") => Some(1.0),
+                Some("new") => Some(0.0),
+                _ => None,
+            };
+            Ok(LinkStatus { status: name.to_string(), progress })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_github_and_jira_links() {
+        assert_eq!(
+            IssueRef::parse("https://github.com/acme/app/issues/42/"),
+            Some(IssueRef::GitHub { owner: "acme".into(), repo: "app".into(), number: 42, pull: false })
+        );
+        assert_eq!(
+            IssueRef::parse("https://github.com/acme/app/pull/7/files"),
+            Some(IssueRef::GitHub { owner: "acme".into(), repo: "app".into(), number: 7, pull: true })
+        );
+        assert_eq!(
+            IssueRef::parse("http://acme.atlassian.net/jira/browse/OPS-12"),
+            Some(IssueRef::Jira { base: "https://acme.atlassian.net/jira".into(), key: "OPS-12".into() })
+        );
+        assert_eq!(IssueRef::parse("https://example.com/browse/readme"), None);
+        assert_eq!(IssueRef::parse("https://github.com/acme/app"), None);
+        assert_eq!(IssueRef::parse("ftp://github.com/acme/app/issues/1"), None);
+    }
+
+    #[test]
+    fn jira_credentials_go_only_to_the_configured_host() {
+        let Some(IssueRef::Jira { base, .. }) = IssueRef::parse("https://evil.example/browse/OPS-1") else {
+            panic!("not parsed as Jira");
+        };
+        assert!(!is_configured_jira(&base, Some("https://acme.atlassian.net")));
+        assert!(!is_configured_jira(&base, None));
+        assert!(is_configured_jira("https://acme.atlassian.net", Some("https://ACME.atlassian.net/ ")));
+    }
+
+    #[test]
+    fn config_values_are_quoted() {
+        assert_eq!(config_quote(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
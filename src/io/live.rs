@@ -0,0 +1,328 @@
+//! Experimental live co-editing. One instance hosts the open plan over a
+//! websocket and others who know its session token join it. Edits are broadcast as the
+//! [`ProjectCommand`]s that made them, and the host relays each client's
+//! edits to the others. A command that would create different ids on each
+//! copy, and edits made outside commands (dragging a bar, undo), go out as
+//! the changes they made (see `model::journal`), so ids match everywhere.
+
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tungstenite::client::IntoClientRequest;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::{HeaderValue, StatusCode};
+use tungstenite::{Message, WebSocket};
+
+use crate::model::journal::Snapshot;
+use crate::model::{Project, ProjectCommand};
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Interface hosting listens on unless another is chosen: this machine only.
+pub const DEFAULT_INTERFACE: &str = "127.0.0.1";
+
+/// Handshake header carrying the session token.
+const TOKEN_HEADER: &str = "X-Live-Token";
+
+/// Seconds between checks for local edits to broadcast.
+pub const SEND_INTERVAL: f64 = 0.2;
+
+/// How long joining waits for the host before giving up; joining runs on the
+/// UI thread.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LiveMessage {
+    /// Sent by the host to a client that joins: the plan as it is now.
+    Welcome(Box<Project>),
+    /// An edit someone made.
+    Edit { user: String, command: ProjectCommand },
+}
+
+pub enum LiveEvent {
+    /// A client connected to the host and needs a [`LiveMessage::Welcome`].
+    Joined(usize),
+    Left,
+    Received(LiveMessage),
+    /// The session ended, with the reason.
+    Closed(String),
+}
+
+/// Outboxes of the connected peers; a client's only peer is the host.
+type Peers = Arc<Mutex<Vec<(usize, Sender<String>)>>>;
+
+pub struct LiveSession {
+    pub hosting: bool,
+    /// Interface and port hosted on, or the host joined.
+    pub address: String,
+    /// Secret a client must present to join; empty when joined.
+    pub token: String,
+    /// The plan as last shared; edits since are broadcast.
+    pub base: Snapshot,
+    pub last_sent: f64,
+    /// Whether edits are shared yet: a client waits for the host's plan.
+    pub ready: bool,
+    peers: Peers,
+    events: Receiver<LiveEvent>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for LiveSession {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+impl LiveSession {
+    /// Listen for clients on `interface` and `port`. Clients must present
+    /// the session's [`token`](Self::token) to join.
+    pub fn host(interface: &str, port: u16, project: &Project) -> Result<Self, String> {
+        let interface = interface.trim();
+        let listener = TcpListener::bind((interface, port))
+            .map_err(|e| format!("Cannot host on {}:{}: {}", interface, port, e))?;
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+        let (events_tx, events) = mpsc::channel();
+        let peers: Peers = Arc::default();
+        let stop = Arc::new(AtomicBool::new(false));
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let session = Self {
+            hosting: true,
+            address: format!("{}:{}", interface, port),
+            token: token.clone(),
+            base: Snapshot::of(project),
+            last_sent: 0.0,
+            ready: true,
+            peers: peers.clone(),
+            events,
+            stop: stop.clone(),
+        };
+        std::thread::spawn(move || {
+            let mut next_id = 0;
+            while !stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, from)) => {
+                        next_id += 1;
+                        tracing::info!("Live session: client {} connected from {}", next_id, from);
+                        spawn_host_peer(next_id, stream, &token, peers.clone(), events_tx.clone(), stop.clone());
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(Duration::from_millis(100));
+                    }
+                    Err(e) => {
+                        let _ = events_tx.send(LiveEvent::Closed(format!("Hosting stopped: {}", e)));
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(session)
+    }
+
+    /// Connect to a host at `address` (`host:port`, port defaulting to
+    /// [`DEFAULT_PORT`]) with the host's session `token`. The plan arrives as
+    /// a [`LiveMessage::Welcome`].
+    pub fn join(address: &str, token: &str, project: &Project) -> Result<Self, String> {
+        let address = address.trim();
+        let address = if address.contains(':') { address.to_string() } else { format!("{}:{}", address, DEFAULT_PORT) };
+        let target = address
+            .to_socket_addrs()
+            .map_err(|e| format!("Cannot reach {}: {}", address, e))?
+            .next()
+            .ok_or_else(|| format!("Cannot reach {}: no such host", address))?;
+        let stream =
+            TcpStream::connect_timeout(&target, CONNECT_TIMEOUT).map_err(|e| format!("Cannot reach {}: {}", address, e))?;
+        // A host that accepts but never answers the handshake must not hang the app.
+        stream.set_read_timeout(Some(CONNECT_TIMEOUT)).map_err(|e| e.to_string())?;
+        stream.set_write_timeout(Some(CONNECT_TIMEOUT)).map_err(|e| e.to_string())?;
+        let mut request = format!("ws://{}/", address)
+            .into_client_request()
+            .map_err(|e| format!("Cannot reach {}: {}", address, e))?;
+        let token = HeaderValue::from_str(token.trim()).map_err(|_| "The session token is not valid".to_string())?;
+        request.headers_mut().insert(TOKEN_HEADER, token);
+        let (socket, _) = tungstenite::client(request, stream).map_err(|e| match e {
+            tungstenite::HandshakeError::Failure(tungstenite::Error::Http(response))
+                if response.status() == StatusCode::UNAUTHORIZED =>
+            {
+                format!("{} refused the session token", address)
+            }
+            e => format!("Handshake with {} failed: {}", address, e),
+        })?;
+        let (events_tx, events) = mpsc::channel();
+        let (outbox, outgoing) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let session = Self {
+            hosting: false,
+            address: address.clone(),
+            token: String::new(),
+            base: Snapshot::of(project),
+            last_sent: 0.0,
+            ready: false,
+            peers: Arc::new(Mutex::new(vec![(0, outbox)])),
+            events,
+            stop: stop.clone(),
+        };
+        std::thread::spawn(move || {
+            let received = events_tx.clone();
+            let result = pump(socket, outgoing, &stop, |text| {
+                if let Ok(message) = serde_json::from_str(&text) {
+                    let _ = received.send(LiveEvent::Received(message));
+                }
+            });
+            let reason = match result {
+                Ok(()) => format!("Disconnected from {}", address),
+                Err(e) => format!("Lost connection to {}: {}", address, e),
+            };
+            let _ = events_tx.send(LiveEvent::Closed(reason));
+        });
+        Ok(session)
+    }
+
+    pub fn peer_count(&self) -> usize {
+        self.peers.lock().map(|p| p.len()).unwrap_or(0)
+    }
+
+    /// Send to every peer.
+    pub fn broadcast(&self, message: &LiveMessage) {
+        let Ok(text) = serde_json::to_string(message) else { return };
+        if let Ok(peers) = self.peers.lock() {
+            for (_, outbox) in peers.iter() {
+                let _ = outbox.send(text.clone());
+            }
+        }
+    }
+
+    pub fn send_to(&self, peer: usize, message: &LiveMessage) {
+        let Ok(text) = serde_json::to_string(message) else { return };
+        if let Ok(peers) = self.peers.lock() {
+            if let Some((_, outbox)) = peers.iter().find(|(id, _)| *id == peer) {
+                let _ = outbox.send(text);
+            }
+        }
+    }
+
+    /// Events since the last call.
+    pub fn poll(&self) -> Vec<LiveEvent> {
+        self.events.try_iter().collect()
+    }
+}
+
+/// Serve one client that presents `token`: hand its edits to the app and
+/// relay them to the other clients.
+// The refusal's type is tungstenite's handshake callback signature.
+#[allow(clippy::result_large_err)]
+fn spawn_host_peer(
+    id: usize,
+    stream: TcpStream,
+    token: &str,
+    peers: Peers,
+    events: Sender<LiveEvent>,
+    stop: Arc<AtomicBool>,
+) {
+    let token = token.to_string();
+    std::thread::spawn(move || {
+        let _ = stream.set_nonblocking(false);
+        let check_token = |request: &Request, response: Response| {
+            let presented = request.headers().get(TOKEN_HEADER).and_then(|v| v.to_str().ok());
+            if presented == Some(token.as_str()) {
+                Ok(response)
+            } else {
+                let mut refusal = ErrorResponse::new(Some("Wrong session token".to_string()));
+                *refusal.status_mut() = StatusCode::UNAUTHORIZED;
+                Err(refusal)
+            }
+        };
+        let socket = match tungstenite::accept_hdr(stream, check_token) {
+            Ok(socket) => socket,
+            Err(e) => {
+                tracing::warn!("Live session: client {} refused: {}", id, e);
+                return;
+            }
+        };
+        let (outbox, outgoing) = mpsc::channel();
+        if let Ok(mut list) = peers.lock() {
+            list.push((id, outbox));
+        }
+        let _ = events.send(LiveEvent::Joined(id));
+        let received = events.clone();
+        let relay = peers.clone();
+        let result = pump(socket, outgoing, &stop, |text| {
+            let Ok(message) = serde_json::from_str::<LiveMessage>(&text) else { return };
+            // Only the host hands out the plan; a client's Welcome is dropped.
+            if matches!(message, LiveMessage::Welcome(_)) {
+                return;
+            }
+            if let Ok(list) = relay.lock() {
+                for (_, outbox) in list.iter().filter(|(peer, _)| *peer != id) {
+                    let _ = outbox.send(text.clone());
+                }
+            }
+            let _ = received.send(LiveEvent::Received(message));
+        });
+        if let Err(e) = result {
+            tracing::warn!("Live session: client {} dropped: {}", id, e);
+        }
+        if let Ok(mut list) = peers.lock() {
+            list.retain(|(peer, _)| *peer != id);
+        }
+        let _ = events.send(LiveEvent::Left);
+    });
+}
+
+/// Read and write one websocket until it closes, `outgoing` is dropped or
+/// `stop` is set. Reads time out briefly so queued messages go out promptly.
+fn pump(
+    mut socket: WebSocket<TcpStream>,
+    outgoing: Receiver<String>,
+    stop: &AtomicBool,
+    mut on_text: impl FnMut(String),
+) -> Result<(), String> {
+    socket
+        .get_ref()
+        .set_read_timeout(Some(Duration::from_millis(50)))
+        .map_err(|e| e.to_string())?;
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            let _ = socket.close(None);
+            let _ = socket.flush();
+            return Ok(());
+        }
+        match socket.read() {
+            Ok(Message::Text(text)) => on_text(text),
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {}
+            Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Err(e) => return Err(e.to_string()),
+        }
+        loop {
+            match outgoing.try_recv() {
+                Ok(text) => socket.send(Message::Text(text)).map_err(|e| e.to_string())?,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    let _ = socket.close(None);
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joining_needs_the_session_token() {
+        let project = Project::default();
+        let host = LiveSession::host(DEFAULT_INTERFACE, 47878, &project).unwrap();
+        let address = "127.0.0.1:47878";
+        let refused = LiveSession::join(address, "not-the-token", &project).err().unwrap();
+        assert!(refused.contains("refused the session token"), "{}", refused);
+        assert!(LiveSession::join(address, &host.token, &project).is_ok());
+    }
+}
@@ -1,5 +1,21 @@
 pub mod csv_export;
 pub mod csv_import;
+pub mod deep_link;
+pub mod diagnostics;
+pub mod document_style;
+pub mod dependency_csv;
 pub mod file;
+pub mod issue_link;
+pub mod live;
+pub mod paste_list;
+pub mod pptx;
+pub mod print_html;
+pub mod project_template;
+pub mod report_template;
+pub mod status_report;
+pub mod sync;
+pub mod table_text;
+pub mod view_state;
+pub mod watch_folder;
 
-pub use file::{load_project, save_project};
+pub use file::{file_fingerprint, load_project, save_project, save_project_with_backups, FileFingerprint};
@@ -0,0 +1,97 @@
+//! Two-column lists pasted into the Markers and Resources dialogs: holidays
+//! as date and name, resources as name and role. Columns are split at a
+//! tab (as copied from a spreadsheet), a semicolon or a comma; holiday lines
+//! may also separate the date from the name with a space.
+
+use chrono::NaiveDate;
+
+use super::csv_import::{read_date, DateEvidence};
+
+/// Rows read from a pasted list, and how many non-empty lines could not be
+/// read (a header row counts as one).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Pasted<T> {
+    pub rows: Vec<T>,
+    pub skipped: usize,
+}
+
+fn split_columns(line: &str) -> (&str, &str) {
+    ['\t', ';', ',']
+        .into_iter()
+        .find_map(|sep| line.split_once(sep))
+        .map_or((line, ""), |(a, b)| (a.trim(), b.trim()))
+}
+
+fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().map(str::trim).filter(|l| !l.is_empty())
+}
+
+/// Holidays as (date, name). Dates valid both day and month first are read
+/// the way the list's unambiguous dates are written.
+pub fn read_holidays(text: &str) -> Pasted<(NaiveDate, String)> {
+    let mut evidence = DateEvidence::default();
+    let mut read = Vec::new();
+    let mut skipped = 0;
+    for line in lines(text) {
+        let (date, name) = split_columns(line);
+        let reading = read_date(date).map(|d| (d, name)).or_else(|| {
+            let (date, name) = line.split_once(char::is_whitespace)?;
+            read_date(date).map(|d| (d, name.trim()))
+        });
+        match reading {
+            Some((date, name)) => {
+                evidence.count(Some(date));
+                read.push((date, name.to_string()));
+            }
+            None => skipped += 1,
+        }
+    }
+    let order = evidence.inferred();
+    let rows = read
+        .into_iter()
+        .map(|(date, name)| (date.date(order), if name.is_empty() { "Holiday".to_string() } else { name }))
+        .collect();
+    Pasted { rows, skipped }
+}
+
+/// Resources as (name, role); the role may be empty. A first line reading
+/// "Name" is taken for a header.
+pub fn read_resources(text: &str) -> Pasted<(String, String)> {
+    let mut pasted = Pasted::default();
+    for (i, line) in lines(text).enumerate() {
+        let (name, role) = split_columns(line);
+        if name.is_empty() || (i == 0 && name.eq_ignore_ascii_case("name")) {
+            pasted.skipped += 1;
+            continue;
+        }
+        pasted.rows.push((name.to_string(), role.to_string()));
+    }
+    pasted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_both_lists_past_headers_and_ambiguous_dates() {
+        let holidays = read_holidays("Date\tName\n01/05/2025\tLabour Day\n25/12/2025;Christmas\n2025-12-26 Boxing Day\n");
+        assert_eq!(holidays.skipped, 1);
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        assert_eq!(
+            holidays.rows,
+            vec![
+                // Day first, as 25/12/2025 shows the list is written.
+                (date(5, 1), "Labour Day".to_string()),
+                (date(12, 25), "Christmas".to_string()),
+                (date(12, 26), "Boxing Day".to_string()),
+            ]
+        );
+        let resources = read_resources("Name\tRole\nAlice\tDesigner\n\nBob\n");
+        assert_eq!(resources.skipped, 1);
+        assert_eq!(
+            resources.rows,
+            vec![("Alice".to_string(), "Designer".to_string()), ("Bob".to_string(), String::new())]
+        );
+    }
+}
@@ -0,0 +1,497 @@
+//! PowerPoint export: a `.pptx` with the schedule drawn as native slide
+//! shapes (bars, milestones, month grid, notes) and a slide summarising the
+//! milestones. The package is written as an uncompressed zip, so no
+//! compression library is needed.
+
+use super::document_style::{ooxml, DOCUMENT};
+use crate::model::view::in_range;
+use crate::model::{Project, Task};
+use chrono::{Datelike, NaiveDate};
+use std::path::Path;
+
+/// Slide size (16:9) in EMU; 914400 EMU = 1 inch.
+const SLIDE_W: i64 = 12_192_000;
+const SLIDE_H: i64 = 6_858_000;
+const MARGIN: i64 = 457_200;
+/// Width of the task-name column on the chart slide.
+const NAME_COL: i64 = 2_743_200;
+const TITLE_H: i64 = 685_800;
+const HEADER_H: i64 = 274_320;
+/// Rows drawn on the chart slide; longer projects are cut off with a note.
+const MAX_ROWS: usize = 24;
+
+const NS: &str = "xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\" \
+                  xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\" \
+                  xmlns:p=\"http://schemas.openxmlformats.org/presentationml/2006/main\"";
+const XML_DECL: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn hex(color: egui::Color32) -> String {
+    ooxml(DOCUMENT.bar(color))
+}
+
+// ── Zip (stored) ────────────────────────────────────────────────
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Pack `files` into a zip archive without compression.
+pub fn write_zip(files: &[(String, String)]) -> Vec<u8> {
+    // DOS date 1980-01-01, time 00:00.
+    const DOS_DATE: u16 = (1 << 5) | 1;
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for (name, content) in files {
+        let data = content.as_bytes();
+        let crc = crc32(data);
+        let offset = out.len() as u32;
+        let mut header = Vec::new();
+        header.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        header.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        header.extend_from_slice(&0u16.to_le_bytes()); // flags
+        header.extend_from_slice(&0u16.to_le_bytes()); // method: stored
+        header.extend_from_slice(&0u16.to_le_bytes()); // time
+        header.extend_from_slice(&DOS_DATE.to_le_bytes());
+        header.extend_from_slice(&crc.to_le_bytes());
+        header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        out.extend_from_slice(&header);
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central.extend_from_slice(&header[4..]); // shared fields up to extra length
+        central.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        central.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        central.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        central.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+    let central_offset = out.len() as u32;
+    out.extend_from_slice(&central);
+    out.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out
+}
+
+// ── Slide shapes ────────────────────────────────────────────────
+
+/// Builds the `<p:spTree>` of one slide, numbering shapes as it goes.
+struct Shapes {
+    xml: String,
+    next_id: u32,
+}
+
+impl Shapes {
+    fn new() -> Self {
+        Self { xml: String::new(), next_id: 2 }
+    }
+
+    fn id(&mut self) -> u32 {
+        self.next_id += 1;
+        self.next_id - 1
+    }
+
+    /// A filled preset shape (`rect`, `diamond`, …) without text.
+    fn shape(&mut self, geom: &str, x: i64, y: i64, w: i64, h: i64, fill: &str) {
+        let id = self.id();
+        self.xml.push_str(&format!(
+            "<p:sp><p:nvSpPr><p:cNvPr id=\"{id}\" name=\"Shape {id}\"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>\
+             <p:spPr><a:xfrm><a:off x=\"{x}\" y=\"{y}\"/><a:ext cx=\"{w}\" cy=\"{h}\"/></a:xfrm>\
+             <a:prstGeom prst=\"{geom}\"><a:avLst/></a:prstGeom><a:solidFill><a:srgbClr val=\"{fill}\"/></a:solidFill>\
+             <a:ln><a:noFill/></a:ln></p:spPr></p:sp>",
+            w = w.max(1),
+            h = h.max(1),
+        ));
+    }
+
+    /// A frameless text box with a single run.
+    #[allow(clippy::too_many_arguments)]
+    fn text(&mut self, text: &str, x: i64, y: i64, w: i64, h: i64, size: u32, color: &str, bold: bool) {
+        let id = self.id();
+        self.xml.push_str(&format!(
+            "<p:sp><p:nvSpPr><p:cNvPr id=\"{id}\" name=\"Text {id}\"/><p:cNvSpPr txBox=\"1\"/><p:nvPr/></p:nvSpPr>\
+             <p:spPr><a:xfrm><a:off x=\"{x}\" y=\"{y}\"/><a:ext cx=\"{w}\" cy=\"{h}\"/></a:xfrm>\
+             <a:prstGeom prst=\"rect\"><a:avLst/></a:prstGeom><a:noFill/></p:spPr>\
+             <p:txBody><a:bodyPr wrap=\"none\" lIns=\"0\" tIns=\"0\" rIns=\"0\" bIns=\"0\" anchor=\"ctr\"/><a:lstStyle/>\
+             <a:p><a:r><a:rPr lang=\"en-US\" sz=\"{size}\" b=\"{b}\" dirty=\"0\"><a:solidFill><a:srgbClr val=\"{color}\"/></a:solidFill></a:rPr>\
+             <a:t>{text}</a:t></a:r></a:p></p:txBody></p:sp>",
+            b = bold as u8,
+            size = size * 100,
+            text = escape_xml(text),
+        ));
+    }
+
+    /// A straight line from (`x1`, `y1`) to (`x2`, `y2`) ending in an arrowhead.
+    fn arrow(&mut self, x1: i64, y1: i64, x2: i64, y2: i64, color: &str) {
+        let id = self.id();
+        let flip = match (x2 < x1, y2 < y1) {
+            (true, true) => " flipH=\"1\" flipV=\"1\"",
+            (true, false) => " flipH=\"1\"",
+            (false, true) => " flipV=\"1\"",
+            (false, false) => "",
+        };
+        self.xml.push_str(&format!(
+            "<p:cxnSp><p:nvCxnSpPr><p:cNvPr id=\"{id}\" name=\"Arrow {id}\"/><p:cNvCxnSpPr/><p:nvPr/></p:nvCxnSpPr>\
+             <p:spPr><a:xfrm{flip}><a:off x=\"{x}\" y=\"{y}\"/><a:ext cx=\"{w}\" cy=\"{h}\"/></a:xfrm>\
+             <a:prstGeom prst=\"line\"><a:avLst/></a:prstGeom><a:ln w=\"12700\"><a:solidFill><a:srgbClr val=\"{color}\"/></a:solidFill>\
+             <a:tailEnd type=\"triangle\"/></a:ln></p:spPr></p:cxnSp>",
+            x = x1.min(x2),
+            y = y1.min(y2),
+            w = (x2 - x1).abs(),
+            h = (y2 - y1).abs(),
+        ));
+    }
+
+    /// A table with a shaded header row.
+    fn table(&mut self, x: i64, y: i64, widths: &[i64], rows: &[Vec<String>]) {
+        let id = self.id();
+        let row_h = 320_040;
+        let grid: String = widths.iter().map(|w| format!("<a:gridCol w=\"{}\"/>", w)).collect();
+        let mut body = String::new();
+        for (r, row) in rows.iter().enumerate() {
+            body.push_str(&format!("<a:tr h=\"{}\">", row_h));
+            for cell in row {
+                let fill = if r == 0 {
+                    format!("<a:solidFill><a:srgbClr val=\"{}\"/></a:solidFill>", ooxml(DOCUMENT.text))
+                } else {
+                    String::new()
+                };
+                let color = ooxml(if r == 0 { DOCUMENT.background } else { DOCUMENT.text });
+                body.push_str(&format!(
+                    "<a:tc><a:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang=\"en-US\" sz=\"1200\" b=\"{}\" dirty=\"0\">\
+                     <a:solidFill><a:srgbClr val=\"{}\"/></a:solidFill></a:rPr><a:t>{}</a:t></a:r></a:p></a:txBody>\
+                     <a:tcPr>{}</a:tcPr></a:tc>",
+                    (r == 0) as u8,
+                    color,
+                    escape_xml(cell),
+                    fill
+                ));
+            }
+            body.push_str("</a:tr>");
+        }
+        self.xml.push_str(&format!(
+            "<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id=\"{id}\" name=\"Table {id}\"/>\
+             <p:cNvGraphicFramePr><a:graphicFrameLocks noGrp=\"1\"/></p:cNvGraphicFramePr><p:nvPr/></p:nvGraphicFramePr>\
+             <p:xfrm><a:off x=\"{x}\" y=\"{y}\"/><a:ext cx=\"{w}\" cy=\"{h}\"/></p:xfrm>\
+             <a:graphic><a:graphicData uri=\"http://schemas.openxmlformats.org/drawingml/2006/table\">\
+             <a:tbl><a:tblPr firstRow=\"1\" bandRow=\"1\"/><a:tblGrid>{grid}</a:tblGrid>{body}</a:tbl>\
+             </a:graphicData></a:graphic></p:graphicFrame>",
+            w = widths.iter().sum::<i64>(),
+            h = row_h * rows.len() as i64,
+        ));
+    }
+
+    fn into_slide(self) -> String {
+        format!(
+            "{XML_DECL}<p:sld {NS}><p:cSld><p:spTree><p:nvGrpSpPr><p:cNvPr id=\"1\" name=\"\"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>\
+             <p:grpSpPr><a:xfrm><a:off x=\"0\" y=\"0\"/><a:ext cx=\"0\" cy=\"0\"/><a:chOff x=\"0\" y=\"0\"/><a:chExt cx=\"0\" cy=\"0\"/></a:xfrm></p:grpSpPr>\
+             {}</p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sld>",
+            self.xml
+        )
+    }
+}
+
+fn depth(project: &Project, task: &Task) -> i64 {
+    let mut depth = 0;
+    let mut parent = task.parent_id;
+    while let Some(pid) = parent {
+        depth += 1;
+        parent = project.tasks.iter().find(|t| t.id == pid).and_then(|t| t.parent_id);
+    }
+    depth
+}
+
+/// The chart slide: task names, a month grid and one bar or diamond per
+/// task. Returns the slide XML and the number of tasks drawn.
+fn chart_slide(project: &Project, today: NaiveDate, range: Option<(NaiveDate, NaiveDate)>) -> (String, usize) {
+    let mut shapes = Shapes::new();
+    shapes.text(&project.name, MARGIN, MARGIN / 2, SLIDE_W - 2 * MARGIN, TITLE_H - MARGIN / 2, 24, &ooxml(DOCUMENT.text), true);
+
+    let shown: Vec<&Task> = project.tasks.iter().filter(|t| range.is_none_or(|r| in_range(t, r))).collect();
+    let (Some(first), Some(last)) = (
+        shown.iter().map(|t| t.start).min(),
+        shown.iter().map(|t| t.end).max(),
+    ) else {
+        return (shapes.into_slide(), 0);
+    };
+    let (first, last) = range.unwrap_or((first, last));
+    let chart_x = MARGIN + NAME_COL;
+    let chart_w = SLIDE_W - MARGIN - chart_x;
+    let top = TITLE_H + MARGIN / 2;
+    let span = (last - first).num_days().max(1);
+    let x = |date: NaiveDate| chart_x + (date - first).num_days().clamp(0, span) * chart_w / span;
+
+    let rows: Vec<&Task> = shown.iter().copied().take(MAX_ROWS).collect();
+    let body_top = top + HEADER_H;
+    let row_h = ((SLIDE_H - MARGIN - body_top) / MAX_ROWS as i64).min(320_040);
+    let body_h = row_h * rows.len() as i64;
+
+    // Month grid, labelling every n-th month so the header stays readable.
+    let months = (last.year() - first.year()) * 12 + last.month() as i32 - first.month() as i32 + 1;
+    let every = (months as f32 / 12.0).ceil().max(1.0) as i32;
+    let mut month = NaiveDate::from_ymd_opt(first.year(), first.month(), 1).unwrap_or(first);
+    let mut index = 0;
+    while month <= last {
+        if month >= first {
+            shapes.shape("rect", x(month), top, 6_350, HEADER_H + body_h, &ooxml(DOCUMENT.rule));
+        }
+        if index % every == 0 {
+            let label_x = x(month.max(first)) + 45_720;
+            shapes.text(&month.format("%b %Y").to_string(), label_x, top, 914_400, HEADER_H, 9, &ooxml(DOCUMENT.muted), false);
+        }
+        index += 1;
+        month = if month.month() == 12 {
+            NaiveDate::from_ymd_opt(month.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(month.year(), month.month() + 1, 1)
+        }
+        .unwrap_or(last + chrono::Duration::days(1));
+    }
+
+    for (i, task) in rows.iter().enumerate() {
+        let y = body_top + i as i64 * row_h;
+        if i % 2 == 0 {
+            shapes.shape("rect", MARGIN, y, SLIDE_W - 2 * MARGIN, row_h, &ooxml(DOCUMENT.stripe));
+        }
+        let summary = task.has_children(&project.tasks);
+        let indent = depth(project, task) * 137_160;
+        shapes.text(&task.name, MARGIN + indent + 45_720, y, NAME_COL - indent - 91_440, row_h, 10, &ooxml(DOCUMENT.text), summary);
+        let color = hex(task.color);
+        if task.is_milestone {
+            let size = row_h * 2 / 3;
+            shapes.shape("diamond", x(task.start) - size / 2, y + (row_h - size) / 2, size, size, &color);
+        } else if summary {
+            shapes.shape("rect", x(task.start), y + row_h * 2 / 5, x(task.end) - x(task.start), row_h / 5, &color);
+        } else {
+            let (bx, bw) = (x(task.start), (x(task.end) - x(task.start)).max(18_288));
+            let (by, bh) = (y + row_h / 5, row_h * 3 / 5);
+            shapes.shape("rect", bx, by, bw, bh, &ooxml(DOCUMENT.track));
+            let done = (bw as f32 * task.progress.clamp(0.0, 1.0)) as i64;
+            if done > 0 {
+                shapes.shape("rect", bx, by, done, bh, &color);
+            }
+        }
+    }
+    if today >= first && today <= last {
+        shapes.shape("rect", x(today), top, 12_700, HEADER_H + body_h, &ooxml(DOCUMENT.today));
+    }
+    // Notes sit on their task's row, or in the header when free-floating.
+    let row_mid = |task: Option<uuid::Uuid>| match task {
+        Some(id) => rows.iter().position(|t| t.id == id).map(|i| body_top + i as i64 * row_h + row_h / 2),
+        None => Some(top + HEADER_H / 2),
+    };
+    let clamp = |date: NaiveDate| x(date.clamp(first, last));
+    for note in &project.annotations {
+        let Some(y) = row_mid(note.task) else { continue };
+        let width = (note.text.chars().count() as i64 * 64_008).max(182_880);
+        if let (Some(to), Some(to_y)) = (note.arrow_to, row_mid(note.arrow_task.or(note.task))) {
+            let to_x = clamp(to);
+            let from_x = if to_x < clamp(note.date) { clamp(note.date) } else { clamp(note.date) + width };
+            shapes.arrow(from_x, y, to_x, to_y, &ooxml(DOCUMENT.marker));
+        }
+        shapes.text(&note.text, clamp(note.date), y - row_h / 2, width, row_h, 9, &ooxml(DOCUMENT.marker), true);
+    }
+    let hidden = shown.len().saturating_sub(rows.len());
+    if hidden > 0 {
+        shapes.text(
+            &format!("+{} more task(s) not shown", hidden),
+            MARGIN,
+            body_top + body_h + 45_720,
+            NAME_COL,
+            HEADER_H,
+            9,
+            &ooxml(DOCUMENT.muted),
+            false,
+        );
+    }
+    (shapes.into_slide(), rows.len())
+}
+
+/// The milestone slide: one row per milestone with its date and status.
+fn milestone_slide(project: &Project, today: NaiveDate, range: Option<(NaiveDate, NaiveDate)>) -> String {
+    let mut shapes = Shapes::new();
+    shapes.text("Milestones", MARGIN, MARGIN / 2, SLIDE_W - 2 * MARGIN, TITLE_H - MARGIN / 2, 24, &ooxml(DOCUMENT.text), true);
+    let mut milestones: Vec<&Task> = project
+        .tasks
+        .iter()
+        .filter(|t| t.is_milestone && range.is_none_or(|r| in_range(t, r)))
+        .collect();
+    milestones.sort_by_key(|t| t.start);
+    if milestones.is_empty() {
+        let none = if range.is_some() { "No milestones in this range." } else { "This project has no milestones." };
+        shapes.text(none, MARGIN, TITLE_H + MARGIN / 2, 4_572_000, HEADER_H, 14, &ooxml(DOCUMENT.muted), false);
+        return shapes.into_slide();
+    }
+    let mut rows = vec![vec!["Milestone".to_string(), "Date".to_string(), "Status".to_string()]];
+    for m in milestones.iter().take(15) {
+        let status = if m.is_achieved() {
+            "Achieved"
+        } else if m.start < today {
+            "Missed"
+        } else {
+            "Pending"
+        };
+        rows.push(vec![m.name.clone(), m.start.format("%a %d %b %Y").to_string(), status.to_string()]);
+    }
+    let table_w = SLIDE_W - 2 * MARGIN;
+    shapes.table(MARGIN, TITLE_H + MARGIN / 2, &[table_w / 2, table_w * 3 / 10, table_w / 5], &rows);
+    shapes.into_slide()
+}
+
+// ── Package parts ───────────────────────────────────────────────
+
+fn theme_xml() -> String {
+    let solid = "<a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill>";
+    let accents = ["2563EB", "16A34A", "D97706", "DC2626", "7C3AED", "0891B2"];
+    let accent_xml: String = accents
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!("<a:accent{0}><a:srgbClr val=\"{1}\"/></a:accent{0}>", i + 1, c))
+        .collect();
+    let font = "<a:latin typeface=\"Calibri\"/><a:ea typeface=\"\"/><a:cs typeface=\"\"/>";
+    format!(
+        "{XML_DECL}<a:theme xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\" name=\"Gantt\"><a:themeElements>\
+         <a:clrScheme name=\"Gantt\"><a:dk1><a:srgbClr val=\"000000\"/></a:dk1><a:lt1><a:srgbClr val=\"FFFFFF\"/></a:lt1>\
+         <a:dk2><a:srgbClr val=\"1F2937\"/></a:dk2><a:lt2><a:srgbClr val=\"E5E7EB\"/></a:lt2>{accent_xml}\
+         <a:hlink><a:srgbClr val=\"2563EB\"/></a:hlink><a:folHlink><a:srgbClr val=\"7C3AED\"/></a:folHlink></a:clrScheme>\
+         <a:fontScheme name=\"Gantt\"><a:majorFont>{font}</a:majorFont><a:minorFont>{font}</a:minorFont></a:fontScheme>\
+         <a:fmtScheme name=\"Gantt\"><a:fillStyleLst>{solid}{solid}{solid}</a:fillStyleLst>\
+         <a:lnStyleLst><a:ln w=\"6350\">{solid}</a:ln><a:ln w=\"12700\">{solid}</a:ln><a:ln w=\"19050\">{solid}</a:ln></a:lnStyleLst>\
+         <a:effectStyleLst><a:effectStyle><a:effectLst/></a:effectStyle><a:effectStyle><a:effectLst/></a:effectStyle>\
+         <a:effectStyle><a:effectLst/></a:effectStyle></a:effectStyleLst>\
+         <a:bgFillStyleLst>{solid}{solid}{solid}</a:bgFillStyleLst></a:fmtScheme></a:themeElements></a:theme>"
+    )
+}
+
+fn empty_tree() -> &'static str {
+    "<p:cSld><p:spTree><p:nvGrpSpPr><p:cNvPr id=\"1\" name=\"\"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/></p:spTree></p:cSld>"
+}
+
+fn rels(targets: &[(&str, String)]) -> String {
+    let items: String = targets
+        .iter()
+        .enumerate()
+        .map(|(i, (kind, target))| {
+            format!(
+                "<Relationship Id=\"rId{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}\" Target=\"{}\"/>",
+                i + 1,
+                kind,
+                target
+            )
+        })
+        .collect();
+    format!("{XML_DECL}<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">{items}</Relationships>")
+}
+
+/// All parts of the package for the given slide XMLs.
+fn package(slides: Vec<String>) -> Vec<(String, String)> {
+    let ct = "application/vnd.openxmlformats-officedocument";
+    let mut overrides = format!(
+        "<Override PartName=\"/ppt/presentation.xml\" ContentType=\"{ct}.presentationml.presentation.main+xml\"/>\
+         <Override PartName=\"/ppt/slideMasters/slideMaster1.xml\" ContentType=\"{ct}.presentationml.slideMaster+xml\"/>\
+         <Override PartName=\"/ppt/slideLayouts/slideLayout1.xml\" ContentType=\"{ct}.presentationml.slideLayout+xml\"/>\
+         <Override PartName=\"/ppt/theme/theme1.xml\" ContentType=\"{ct}.theme+xml\"/>"
+    );
+    for i in 1..=slides.len() {
+        overrides.push_str(&format!(
+            "<Override PartName=\"/ppt/slides/slide{i}.xml\" ContentType=\"{ct}.presentationml.slide+xml\"/>"
+        ));
+    }
+    let content_types = format!(
+        "{XML_DECL}<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+         <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+         <Default Extension=\"xml\" ContentType=\"application/xml\"/>{overrides}</Types>"
+    );
+
+    let slide_ids: String = (0..slides.len())
+        .map(|i| format!("<p:sldId id=\"{}\" r:id=\"rId{}\"/>", 256 + i, i + 2))
+        .collect();
+    let presentation = format!(
+        "{XML_DECL}<p:presentation {NS}><p:sldMasterIdLst><p:sldMasterId id=\"2147483648\" r:id=\"rId1\"/></p:sldMasterIdLst>\
+         <p:sldIdLst>{slide_ids}</p:sldIdLst><p:sldSz cx=\"{SLIDE_W}\" cy=\"{SLIDE_H}\"/><p:notesSz cx=\"6858000\" cy=\"9144000\"/></p:presentation>"
+    );
+    let mut presentation_rels = vec![("slideMaster", "slideMasters/slideMaster1.xml".to_string())];
+    for i in 1..=slides.len() {
+        presentation_rels.push(("slide", format!("slides/slide{}.xml", i)));
+    }
+    presentation_rels.push(("theme", "theme/theme1.xml".to_string()));
+
+    let master = format!(
+        "{XML_DECL}<p:sldMaster {NS}>{}<p:clrMap bg1=\"lt1\" tx1=\"dk1\" bg2=\"lt2\" tx2=\"dk2\" accent1=\"accent1\" accent2=\"accent2\" \
+         accent3=\"accent3\" accent4=\"accent4\" accent5=\"accent5\" accent6=\"accent6\" hlink=\"hlink\" folHlink=\"folHlink\"/>\
+         <p:sldLayoutIdLst><p:sldLayoutId id=\"2147483649\" r:id=\"rId1\"/></p:sldLayoutIdLst></p:sldMaster>",
+        empty_tree()
+    );
+    let layout = format!(
+        "{XML_DECL}<p:sldLayout {NS} type=\"blank\" preserve=\"1\">{}<p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sldLayout>",
+        empty_tree().replace("<p:cSld>", "<p:cSld name=\"Blank\">")
+    );
+
+    let mut files = vec![
+        ("[Content_Types].xml".to_string(), content_types),
+        (
+            "_rels/.rels".to_string(),
+            rels(&[("officeDocument", "ppt/presentation.xml".to_string())]),
+        ),
+        ("ppt/presentation.xml".to_string(), presentation),
+        ("ppt/_rels/presentation.xml.rels".to_string(), rels(&presentation_rels)),
+        ("ppt/slideMasters/slideMaster1.xml".to_string(), master),
+        (
+            "ppt/slideMasters/_rels/slideMaster1.xml.rels".to_string(),
+            rels(&[
+                ("slideLayout", "../slideLayouts/slideLayout1.xml".to_string()),
+                ("theme", "../theme/theme1.xml".to_string()),
+            ]),
+        ),
+        ("ppt/slideLayouts/slideLayout1.xml".to_string(), layout),
+        (
+            "ppt/slideLayouts/_rels/slideLayout1.xml.rels".to_string(),
+            rels(&[("slideMaster", "../slideMasters/slideMaster1.xml".to_string())]),
+        ),
+        ("ppt/theme/theme1.xml".to_string(), theme_xml()),
+    ];
+    for (i, slide) in slides.into_iter().enumerate() {
+        files.push((format!("ppt/slides/slide{}.xml", i + 1), slide));
+        files.push((
+            format!("ppt/slides/_rels/slide{}.xml.rels", i + 1),
+            rels(&[("slideLayout", "../slideLayouts/slideLayout1.xml".to_string())]),
+        ));
+    }
+    files
+}
+
+/// Write the project as a two-slide PowerPoint deck: the schedule chart and
+/// a milestone summary table, cropped to `range` if given. Returns the
+/// number of tasks on the chart.
+pub fn export_pptx(project: &Project, range: Option<(NaiveDate, NaiveDate)>, path: &Path) -> Result<usize, String> {
+    let today = chrono::Local::now().date_naive();
+    let (chart, drawn) = chart_slide(project, today, range);
+    let files = package(vec![chart, milestone_slide(project, today, range)]);
+    std::fs::write(path, write_zip(&files)).map_err(|e| format!("Failed to write presentation: {}", e))?;
+    Ok(drawn)
+}
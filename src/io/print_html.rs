@@ -0,0 +1,233 @@
+//! Printable schedule: an HTML page with the Gantt bars drawn as inline SVG,
+//! meant to be printed (or saved as PDF) from a browser.
+
+use super::document_style::{css, DOCUMENT};
+use super::status_report::escape_html;
+use crate::model::view::in_range;
+use crate::model::{Project, Task};
+use chrono::NaiveDate;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Width of the bar area in SVG user units; it is stretched to the page width.
+const BAR_AREA: f32 = 1000.0;
+
+/// One printed page: a title and the tasks on it, in outline order.
+struct Page<'a> {
+    title: String,
+    tasks: Vec<&'a Task>,
+}
+
+fn hex(color: egui::Color32) -> String {
+    css(DOCUMENT.bar(color))
+}
+
+fn depth(project: &Project, task: &Task) -> usize {
+    let mut depth = 0;
+    let mut parent = task.parent_id;
+    while let Some(pid) = parent {
+        depth += 1;
+        parent = project.tasks.iter().find(|t| t.id == pid).and_then(|t| t.parent_id);
+    }
+    depth
+}
+
+/// Split the project into pages: one per top-level phase when
+/// `page_per_phase`, with loose top-level tasks gathered on a last page;
+/// otherwise a single page with everything. With a `range`, only tasks
+/// within it are kept and pages left empty are dropped.
+fn paginate(project: &Project, page_per_phase: bool, range: Option<(NaiveDate, NaiveDate)>) -> Vec<Page<'_>> {
+    let mut pages = split_pages(project, page_per_phase);
+    if let Some(range) = range {
+        for page in &mut pages {
+            page.tasks.retain(|t| in_range(t, range));
+        }
+        pages.retain(|page| !page.tasks.is_empty());
+    }
+    pages
+}
+
+fn split_pages(project: &Project, page_per_phase: bool) -> Vec<Page<'_>> {
+    let in_order = |ids: &[Uuid]| -> Vec<&Task> { project.tasks.iter().filter(|t| ids.contains(&t.id)).collect() };
+    if !page_per_phase {
+        return vec![Page { title: project.name.clone(), tasks: project.tasks.iter().collect() }];
+    }
+    let mut pages = Vec::new();
+    let mut loose = Vec::new();
+    for top in project.tasks.iter().filter(|t| t.parent_id.is_none()) {
+        if top.has_children(&project.tasks) {
+            pages.push(Page { title: top.name.clone(), tasks: in_order(&project.subtree_ids(top.id)) });
+        } else {
+            loose.push(top);
+        }
+    }
+    if !loose.is_empty() {
+        let title = if pages.is_empty() { project.name.clone() } else { "Other tasks".to_string() };
+        pages.push(Page { title, tasks: loose });
+    }
+    pages
+}
+
+fn render_page(project: &Project, page: &Page, range: Option<(NaiveDate, NaiveDate)>, out: &mut String) {
+    let (Some(first), Some(last)) = (
+        page.tasks.iter().map(|t| t.start).min(),
+        page.tasks.iter().map(|t| t.end).max(),
+    ) else {
+        return;
+    };
+    // Each page gets its own range so its bars fill the page width, unless
+    // the export is cropped to a range; bars are then cut at its edges.
+    let (first, last) = range.unwrap_or((first, last));
+    let span = (last - first).num_days().max(1) as f32;
+    let x = |date: NaiveDate| ((date - first).num_days() as f32 / span * BAR_AREA).clamp(0.0, BAR_AREA);
+
+    let markers: Vec<_> = project.markers.iter().filter(|m| m.date >= first && m.date <= last).collect();
+    let marker_lines: String = markers
+        .iter()
+        .map(|m| {
+            format!(
+                "<line x1=\"{0:.1}\" y1=\"0\" x2=\"{0:.1}\" y2=\"14\" class=\"marker\"/>",
+                x(m.date)
+            )
+        })
+        .collect();
+
+    out.push_str(&format!(
+        "<section class=\"page\">\n<h2>{}</h2>\n<p class=\"range\">{} → {}</p>\n",
+        escape_html(&page.title),
+        first.format("%d/%m/%Y"),
+        last.format("%d/%m/%Y")
+    ));
+    if !markers.is_empty() {
+        let list: Vec<String> = markers
+            .iter()
+            .map(|m| format!("{} ({})", escape_html(&m.name), m.date.format("%d/%m/%Y")))
+            .collect();
+        out.push_str(&format!("<p class=\"markers\">Markers: {}</p>\n", list.join(" · ")));
+    }
+    let notes: Vec<String> = project
+        .annotations
+        .iter()
+        .filter(|n| n.date >= first && n.date <= last)
+        .filter(|n| n.task.is_none_or(|id| page.tasks.iter().any(|t| t.id == id)))
+        .map(|n| {
+            let on = n.task.and_then(|id| page.tasks.iter().find(|t| t.id == id));
+            format!(
+                "{} ({}{}{})",
+                escape_html(&n.text),
+                n.date.format("%d/%m/%Y"),
+                on.map(|t| format!(", {}", escape_html(&t.name))).unwrap_or_default(),
+                n.arrow_to.map(|d| format!(" → {}", d.format("%d/%m/%Y"))).unwrap_or_default()
+            )
+        })
+        .collect();
+    if !notes.is_empty() {
+        out.push_str(&format!("<p class=\"markers\">Notes: {}</p>\n", notes.join(" · ")));
+    }
+    out.push_str("<table>\n");
+    for task in &page.tasks {
+        let summary = task.has_children(&project.tasks);
+        let bar = if task.is_milestone {
+            let cx = x(task.start);
+            // Pending milestones are hollow, achieved ones filled.
+            let fill = if task.is_achieved() { hex(task.color) } else { css(DOCUMENT.background) };
+            format!(
+                "<polygon points=\"{:.1},1 {:.1},7 {:.1},13 {:.1},7\" fill=\"{}\" stroke=\"{}\" \
+                 vector-effect=\"non-scaling-stroke\"/>",
+                cx,
+                cx + 6.0,
+                cx,
+                cx - 6.0,
+                fill,
+                hex(task.color)
+            )
+        } else {
+            let x0 = x(task.start);
+            let width = (x(task.end) - x0).max(2.0);
+            let (y, height) = if summary { (4.0, 6.0) } else { (2.0, 10.0) };
+            format!(
+                "<rect x=\"{:.1}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\" opacity=\"0.45\"/>\
+                 <rect x=\"{:.1}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\"/>",
+                x0,
+                y,
+                width,
+                height,
+                hex(task.color),
+                x0,
+                y,
+                width * task.progress.clamp(0.0, 1.0),
+                height,
+                hex(task.color)
+            )
+        };
+        out.push_str(&format!(
+            "<tr><td class=\"name{}\" style=\"padding-left:{}em\">{}</td><td class=\"dates\">{} – {}</td>\
+             <td class=\"bar\"><svg viewBox=\"0 0 {} 14\" preserveAspectRatio=\"none\">{}{}</svg></td></tr>\n",
+            if summary { " summary" } else { "" },
+            depth(project, task) as f32 * 1.2,
+            escape_html(&task.name),
+            task.start.format("%d/%m"),
+            task.end.format("%d/%m"),
+            BAR_AREA,
+            marker_lines,
+            bar
+        ));
+    }
+    out.push_str("</table>\n</section>\n");
+}
+
+/// Build the printable schedule for `project`, cropped to `range` if given.
+pub fn build_print_html(project: &Project, page_per_phase: bool, range: Option<(NaiveDate, NaiveDate)>) -> String {
+    let title = escape_html(&project.name);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0} — Schedule</title>\n\
+         <style>@page{{size:landscape;margin:12mm;}}\
+         body{{font-family:sans-serif;font-size:10pt;margin:0;background:{bg};color:{text};}}\
+         h1{{font-size:14pt;}}h2{{font-size:12pt;margin:0;}}.range{{color:{muted};margin:2px 0 8px;}}\
+         .page+.page{{break-before:page;}}table{{width:100%;border-collapse:collapse;table-layout:fixed;}}\
+         td{{padding:1px 4px;border-bottom:1px solid {stripe};white-space:nowrap;overflow:hidden;text-overflow:ellipsis;}}\
+         td.name{{width:28%;}}td.dates{{width:10%;color:{muted};}}.summary{{font-weight:bold;}}\
+         svg{{display:block;width:100%;height:14px;}}.markers{{color:{marker};margin:0 0 8px;}}\
+         .marker{{stroke:{marker};stroke-width:1;vector-effect:non-scaling-stroke;}}</style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        title,
+        bg = css(DOCUMENT.background),
+        text = css(DOCUMENT.text),
+        muted = css(DOCUMENT.muted),
+        stripe = css(DOCUMENT.stripe),
+        marker = css(DOCUMENT.marker),
+    );
+    for page in paginate(project, page_per_phase, range) {
+        render_page(project, &page, range, &mut out);
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Write the printable schedule to `path`. Returns the number of pages.
+pub fn export_print_html(
+    project: &Project,
+    page_per_phase: bool,
+    range: Option<(NaiveDate, NaiveDate)>,
+    path: &Path,
+) -> Result<usize, String> {
+    let pages = paginate(project, page_per_phase, range).len();
+    std::fs::write(path, build_print_html(project, page_per_phase, range))
+        .map_err(|e| format!("Failed to write schedule: {}", e))?;
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn task_names_are_escaped() {
+        let mut project = Project::new("Q&A");
+        let day = NaiveDate::from_ymd_opt(2024, 5, 6).unwrap();
+        project.tasks.push(Task::new("<script>alert(\"x\")</script>", day, day + chrono::Duration::days(3)));
+        let html = build_print_html(&project, false, None);
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt;"));
+        assert!(html.contains("Q&amp;A"));
+    }
+}
@@ -0,0 +1,46 @@
+//! Project templates: plans saved with File → Save As Template into the
+//! templates folder, as `<name>.gantt.json` beside the report templates.
+
+use std::path::PathBuf;
+
+use super::report_template::templates_dir;
+use crate::model::Project;
+
+const EXTENSION: &str = ".gantt.json";
+
+/// Project templates in the templates folder, as (name, path), by name.
+pub fn list_templates() -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(templates_dir()) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<(String, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.strip_suffix(EXTENSION)?.to_string();
+            Some((name, path))
+        })
+        .collect();
+    templates.sort_by_key(|(name, _)| name.to_lowercase());
+    templates
+}
+
+/// Save `project` as a template named `name`, made with
+/// [`Project::to_template`]. Returns the file written.
+pub fn save_template(project: &Project, name: &str) -> Result<PathBuf, String> {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_control() || r#"\/:*?"<>|"#.contains(c) { '-' } else { c })
+        .collect();
+    if name.is_empty() {
+        return Err("Give the template a name".to_string());
+    }
+    let dir = templates_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(format!("{}{}", name, EXTENSION));
+    let mut template = project.to_template();
+    template.name = name;
+    super::save_project(&template, &path)?;
+    Ok(path)
+}
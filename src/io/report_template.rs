@@ -0,0 +1,182 @@
+//! Report formats of the user's own: Handlebars templates in the
+//! `templates` folder next to the settings, offered in the Status Report
+//! dialog beside the built-in Markdown and HTML.
+//!
+//! A template is named `<name>.<extension>.hbs`, e.g. `weekly.md.hbs` or
+//! `tasks.csv.hbs`; reports from it are saved with that extension. Only
+//! `.html` templates escape the values they insert.
+
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use handlebars::{handlebars_helper, no_escape, Handlebars};
+use serde_json::{json, Value};
+
+use super::csv_export::progress_to_status;
+use super::status_report::{build_sections, overall_progress};
+use crate::model::Project;
+
+/// Written to the folder the first time it is opened, to copy from.
+const EXAMPLE: &str = "\
+# {{project.name}} — {{status_date}}
+
+Overall progress: {{overall_progress}}%
+
+{{#each sections}}
+## {{title}}
+{{#each items}}
+- {{this}}
+{{else}}
+_{{../empty}}_
+{{/each}}
+
+{{/each}}
+## All tasks
+
+| Task | Start | End | Status | Resources |
+|------|-------|-----|--------|-----------|
+{{#each tasks}}
+| {{name}} | {{start}} | {{end}} | {{status}} | {{resources}} |
+{{/each}}
+";
+
+/// A template found in the templates folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportTemplate {
+    pub name: String,
+    /// Extension of the reports it makes, without the dot.
+    pub extension: String,
+    pub path: PathBuf,
+}
+
+/// Folder holding the user's templates.
+pub fn templates_dir() -> PathBuf {
+    directories::ProjectDirs::from("", "", "RustGanttApp")
+        .map(|dirs| dirs.config_dir().join("templates"))
+        .unwrap_or_else(|| PathBuf::from("templates"))
+}
+
+/// Create the templates folder, with an example in it, if there is none.
+pub fn ensure_templates_dir() -> Result<PathBuf, String> {
+    let dir = templates_dir();
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        std::fs::write(dir.join("example.md.hbs"), EXAMPLE)
+            .map_err(|e| format!("Failed to write the example template: {}", e))?;
+    }
+    Ok(dir)
+}
+
+/// Templates in the templates folder, by name.
+pub fn list_templates() -> Vec<ReportTemplate> {
+    let Ok(entries) = std::fs::read_dir(templates_dir()) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<ReportTemplate> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| parse_name(&e.path()))
+        .collect();
+    templates.sort_by_key(|t| t.name.to_lowercase());
+    templates
+}
+
+fn parse_name(path: &Path) -> Option<ReportTemplate> {
+    let file = path.file_name()?.to_str()?.strip_suffix(".hbs")?;
+    let (name, extension) = file.rsplit_once('.')?;
+    (!name.is_empty() && !extension.is_empty()).then(|| ReportTemplate {
+        name: name.to_string(),
+        extension: extension.to_lowercase(),
+        path: path.to_path_buf(),
+    })
+}
+
+/// What templates can refer to: the project, the status report's sections
+/// and every counted task.
+fn report_data(project: &Project, status_date: NaiveDate) -> Value {
+    let sections: Vec<Value> = build_sections(project, status_date)
+        .into_iter()
+        .map(|s| json!({ "title": s.title, "empty": s.empty, "items": s.items }))
+        .collect();
+    let tasks: Vec<Value> = project
+        .tasks
+        .iter()
+        .filter(|t| project.is_counted(t))
+        .map(|t| {
+            let resources: Vec<&str> = t
+                .assignments
+                .iter()
+                .filter_map(|a| project.resources.iter().find(|r| r.id == a.resource_id))
+                .map(|r| r.name.as_str())
+                .collect();
+            json!({
+                "name": t.name,
+                "start": t.start.format("%d/%m/%Y").to_string(),
+                "end": t.end.format("%d/%m/%Y").to_string(),
+                "progress": (t.progress * 100.0).round(),
+                "status": progress_to_status(t.progress),
+                "priority": t.priority.label(),
+                "description": t.description,
+                "parent": t.parent_id.and_then(|p| project.tasks.iter().find(|x| x.id == p)).map(|p| p.name.as_str()),
+                "milestone": t.is_milestone,
+                "resources": resources.join(", "),
+                "cost": format!("{:.2}", project.task_cost(t)),
+            })
+        })
+        .collect();
+    json!({
+        "project": { "name": project.name, "description": project.description },
+        "status_date": status_date.format("%d/%m/%Y").to_string(),
+        "overall_progress": (overall_progress(project) * 100.0).round(),
+        "sections": sections,
+        "tasks": tasks,
+    })
+}
+
+handlebars_helper!(csv_field: |text: str| {
+    if text.contains([';', ',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+});
+
+/// Render `source` for `project` as of `status_date`. `extension` decides
+/// whether inserted values are HTML-escaped. Templates may quote a value
+/// for CSV with `{{csv name}}`.
+pub fn render(source: &str, extension: &str, project: &Project, status_date: NaiveDate) -> Result<String, String> {
+    let mut registry = Handlebars::new();
+    if !matches!(extension, "html" | "htm") {
+        registry.register_escape_fn(no_escape);
+    }
+    registry.register_helper("csv", Box::new(csv_field));
+    registry
+        .render_template(source, &report_data(project, status_date))
+        .map_err(|e| format!("Template error: {}", e))
+}
+
+/// Render a template file for `project` as of `status_date`.
+pub fn render_template(template: &ReportTemplate, project: &Project, status_date: NaiveDate) -> Result<String, String> {
+    let source = std::fs::read_to_string(&template.path)
+        .map_err(|e| format!("Failed to read {}: {}", template.path.display(), e))?;
+    render(&source, &template.extension, project, status_date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Task;
+
+    #[test]
+    fn renders_tasks_and_quotes_csv_fields() {
+        let mut project = Project::new("Launch");
+        let date = |day| NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+        project.tasks.push(Task::new("Design; review", date(3), date(7)));
+        let source = "{{project.name}}\n{{#each tasks}}{{csv name}};{{start}};{{status}}\n{{/each}}";
+        let out = render(source, "csv", &project, date(10)).unwrap();
+        assert_eq!(out, "Launch\n\"Design; review\";03/06/2024;Not Started\n");
+        assert_eq!(
+            parse_name(Path::new("/t/weekly.md.hbs")).map(|t| (t.name, t.extension)),
+            Some(("weekly".to_string(), "md".to_string()))
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use super::document_style::{css, DOCUMENT};
+use crate::model::task::TaskPriority;
+use crate::model::{Project, Task};
+use chrono::NaiveDate;
+
+/// Milestones due within this many days after the status date are "upcoming".
+const UPCOMING_DAYS: i64 = 30;
+/// High-priority tasks due within this many days are checked for risk.
+const RISK_HORIZON_DAYS: i64 = 7;
+
+/// Output format of a status report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "Markdown",
+            ReportFormat::Html => "HTML",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+/// One report section: a heading and its bullet lines.
+pub(super) struct Section {
+    pub title: &'static str,
+    pub empty: &'static str,
+    pub items: Vec<String>,
+}
+
+/// Task name as shown in the report; external tasks are flagged.
+fn display_name(task: &Task) -> String {
+    if task.external {
+        format!("{} [external]", task.name)
+    } else {
+        task.name.clone()
+    }
+}
+
+fn leaf_tasks(project: &Project) -> impl Iterator<Item = &Task> {
+    project
+        .tasks
+        .iter()
+        .filter(|t| !t.has_children(&project.tasks) && project.is_counted(t))
+}
+
+pub(super) fn build_sections(project: &Project, status_date: NaiveDate) -> Vec<Section> {
+    let week_start = status_date - chrono::Duration::days(6);
+
+    let completed = leaf_tasks(project)
+        .filter(|t| t.progress >= 1.0 && t.end >= week_start && t.end <= status_date)
+        .map(|t| format!("{} (finished {})", display_name(t), t.end.format("%d/%m/%Y")))
+        .collect();
+
+    let slipped = leaf_tasks(project)
+        .filter_map(|t| {
+            let slip = project.baseline.as_ref().and_then(|b| b.finish_slip_days(t)).unwrap_or(0);
+            let overdue = t.end < status_date && t.progress < 1.0;
+            let rebaselined = project
+                .baseline
+                .as_ref()
+                .and_then(|b| b.interim(t.id))
+                .map(|r| if r.label.is_empty() { " (re-baselined)".to_string() } else { format!(" (re-baselined: {})", r.label) })
+                .unwrap_or_default();
+            if slip > 0 {
+                Some(format!(
+                    "{} — {} day(s) behind baseline{}, due {}",
+                    display_name(t),
+                    slip,
+                    rebaselined,
+                    t.end.format("%d/%m/%Y")
+                ))
+            } else if overdue {
+                Some(format!(
+                    "{} — overdue since {} ({:.0}% done)",
+                    display_name(t),
+                    t.end.format("%d/%m/%Y"),
+                    t.progress * 100.0
+                ))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let horizon = status_date + chrono::Duration::days(UPCOMING_DAYS);
+    let mut upcoming: Vec<&Task> = leaf_tasks(project)
+        .filter(|t| t.is_milestone && t.start > status_date && t.start <= horizon)
+        .collect();
+    upcoming.sort_by_key(|t| t.start);
+    let upcoming = upcoming
+        .into_iter()
+        .map(|t| format!("{} — {}", display_name(t), t.start.format("%d/%m/%Y")))
+        .collect();
+
+    let risk_horizon = status_date + chrono::Duration::days(RISK_HORIZON_DAYS);
+    let risks = leaf_tasks(project)
+        .filter(|t| matches!(t.priority, TaskPriority::High | TaskPriority::Critical))
+        .filter(|t| t.progress < 1.0 && t.start <= status_date && t.end <= risk_horizon)
+        .filter(|t| t.progress + 0.1 < t.expected_progress(status_date))
+        .map(|t| {
+            format!(
+                "{} [{}] — {:.0}% done, {:.0}% expected, due {}",
+                display_name(t),
+                t.priority.label(),
+                t.progress * 100.0,
+                t.expected_progress(status_date) * 100.0,
+                t.end.format("%d/%m/%Y")
+            )
+        })
+        .collect();
+
+    vec![
+        Section { title: "Completed this week", empty: "Nothing completed this week.", items: completed },
+        Section { title: "Slipped tasks", empty: "No slipped tasks.", items: slipped },
+        Section { title: "Upcoming milestones", empty: "No milestones in the next 30 days.", items: upcoming },
+        Section { title: "Risks", empty: "No high-priority tasks at risk.", items: risks },
+    ]
+}
+
+/// Overall completion: duration-weighted average progress of leaf tasks.
+pub(super) fn overall_progress(project: &Project) -> f32 {
+    let (done, total) = leaf_tasks(project)
+        .filter(|t| !t.is_milestone)
+        .fold((0.0, 0.0), |(done, total), t| {
+            let days = (t.end - t.start).num_days().max(1) as f32;
+            (done + t.progress * days, total + days)
+        });
+    if total > 0.0 { done / total } else { 0.0 }
+}
+
+pub(super) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Backslash the characters that would turn a task name into emphasis, code,
+/// a link or a table cell break.
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Build a status report for `project` as of `status_date`.
+pub fn build_status_report(project: &Project, status_date: NaiveDate, format: ReportFormat) -> String {
+    let sections = build_sections(project, status_date);
+    let summary = format!(
+        "Status date: {} · Overall progress: {:.0}%",
+        status_date.format("%d/%m/%Y"),
+        overall_progress(project) * 100.0
+    );
+
+    match format {
+        ReportFormat::Markdown => {
+            let mut out = format!("# {} — Status Report\n\n{}\n", escape_markdown(&project.name), summary);
+            if !project.description.trim().is_empty() {
+                out.push_str(&format!("\n## About this project\n\n{}\n", project.description.trim()));
+            }
+            for section in sections {
+                out.push_str(&format!("\n## {}\n\n", section.title));
+                if section.items.is_empty() {
+                    out.push_str(&format!("_{}_\n", section.empty));
+                }
+                for item in section.items {
+                    out.push_str(&format!("- {}\n", escape_markdown(&item)));
+                }
+            }
+            out
+        }
+        ReportFormat::Html => {
+            let title = escape_html(&project.name);
+            let mut out = format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0} — Status Report</title>\n\
+                 <style>body{{font-family:sans-serif;max-width:760px;margin:2em auto;background:{bg};color:{text};}}\
+                 h2{{border-bottom:1px solid {rule};}}.empty{{color:{muted};font-style:italic;}}</style>\n\
+                 </head>\n<body>\n<h1>{0} — Status Report</h1>\n<p>{1}</p>\n",
+                title,
+                escape_html(&summary),
+                bg = css(DOCUMENT.background),
+                text = css(DOCUMENT.text),
+                rule = css(DOCUMENT.rule),
+                muted = css(DOCUMENT.muted),
+            );
+            if !project.description.trim().is_empty() {
+                out.push_str("<h2>About this project</h2>\n");
+                for para in project.description.trim().split("\n\n") {
+                    out.push_str(&format!("<p>{}</p>\n", escape_html(para.trim()).replace('\n', "<br>")));
+                }
+            }
+            for section in sections {
+                out.push_str(&format!("<h2>{}</h2>\n", section.title));
+                if section.items.is_empty() {
+                    out.push_str(&format!("<p class=\"empty\">{}</p>\n", section.empty));
+                } else {
+                    out.push_str("<ul>\n");
+                    for item in section.items {
+                        out.push_str(&format!("<li>{}</li>\n", escape_html(&item)));
+                    }
+                    out.push_str("</ul>\n");
+                }
+            }
+            out.push_str("</body>\n</html>\n");
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_report_escapes_names() {
+        let mut project = Project::new("R&D | *core*");
+        let day = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+        let mut task = Task::new("fix_parser | [api]", day - chrono::Duration::days(2), day);
+        task.progress = 1.0;
+        project.tasks.push(task);
+        let report = build_status_report(&project, day, ReportFormat::Markdown);
+        assert!(report.starts_with("# R&D \\| \\*core\\* — Status Report"));
+        assert!(report.contains("- fix\\_parser \\| \\[api\\] (finished 10/05/2024)"));
+    }
+}
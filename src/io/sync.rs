@@ -0,0 +1,125 @@
+//! Journal files for shared-folder sync. Each user's changes to a project
+//! go to `<project file>.sync/<user>.journal.json`, which only that user
+//! writes, so planners on a network share never overwrite each other's
+//! journals even when the project file itself is saved by whoever is last.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+use crate::model::journal::{self, Change, Conflict, Journal, JournalEntry, Snapshot};
+use crate::model::Project;
+
+/// Seconds between checks of the other planners' journals.
+pub const POLL_INTERVAL: f64 = 10.0;
+
+/// Sync state of the open project.
+pub struct SyncSession {
+    /// The project as last agreed with the journals; edits since are ours.
+    pub base: Snapshot,
+    pub last_poll: f64,
+}
+
+impl SyncSession {
+    pub fn new(project: &Project) -> Self {
+        Self { base: Snapshot::of(project), last_poll: 0.0 }
+    }
+}
+
+/// Name your journal is written under: the "Your name" setting, else the
+/// login name.
+pub fn sync_user(user_name: &str) -> String {
+    let name = user_name.trim();
+    if !name.is_empty() {
+        return name.to_string();
+    }
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "user".to_string())
+}
+
+/// `<project file>.sync` next to the project.
+pub fn sync_dir(project_path: &Path) -> PathBuf {
+    let mut name = project_path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_else(|| "project".into());
+    name.push(".sync");
+    project_path.with_file_name(name)
+}
+
+fn journal_path(project_path: &Path, user: &str) -> PathBuf {
+    let file: String = user
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    sync_dir(project_path).join(format!("{}.journal.json", file))
+}
+
+/// Every readable journal next to the project. Journals that fail to parse
+/// (e.g. half-written over a slow share) are skipped until the next poll.
+pub fn read_journals(project_path: &Path) -> Vec<Journal> {
+    let Ok(entries) = std::fs::read_dir(sync_dir(project_path)) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".journal.json"))
+        .filter_map(|e| serde_json::from_str(&std::fs::read_to_string(e.path()).ok()?).ok())
+        .collect()
+}
+
+/// Append `changes` to `user`'s journal as a new entry and return its
+/// sequence number.
+pub fn append(project_path: &Path, user: &str, changes: Vec<Change>) -> Result<u64, String> {
+    let path = journal_path(project_path, user);
+    let mut journal = match std::fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| format!("Unreadable journal {}: {}", path.display(), e))?,
+        Err(_) => Journal { user: user.to_string(), entries: Vec::new() },
+    };
+    let seq = journal.entries.last().map_or(0, |e| e.seq) + 1;
+    journal.entries.push(JournalEntry { seq, at: Utc::now(), changes });
+    std::fs::create_dir_all(sync_dir(project_path)).map_err(|e| format!("Failed to create sync folder: {}", e))?;
+    let json = serde_json::to_string_pretty(&journal).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| format!("Failed to write journal: {}", e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("Failed to write journal: {}", e))?;
+    Ok(seq)
+}
+
+/// Merge journal entries the project has not seen yet, oldest first per
+/// user, and record them in `project.sync_seen`. Returns the number of
+/// changes applied and any conflicts with your unsaved edits.
+pub fn pull(project_path: &Path, project: &mut Project, base: &mut Snapshot) -> (usize, Vec<Conflict>) {
+    let mut applied = 0;
+    let mut conflicts = Vec::new();
+    for journal in read_journals(project_path) {
+        let seen = project.sync_seen.get(&journal.user).copied().unwrap_or(0);
+        let mut last = seen;
+        for entry in journal.entries.iter().filter(|e| e.seq > seen) {
+            let (n, found) = journal::merge(project, base, &journal.user, &entry.changes);
+            applied += n;
+            conflicts.retain(|c: &Conflict| !found.iter().any(|f| f.same_item(c)));
+            conflicts.extend(found);
+            last = last.max(entry.seq);
+        }
+        if last > seen {
+            project.sync_seen.insert(journal.user, last);
+        }
+    }
+    (applied, conflicts)
+}
+
+/// Journal your edits since `base` under `user`, then make them the new
+/// base. Returns the number of changes written.
+pub fn push(project_path: &Path, project: &mut Project, base: &mut Snapshot, user: &str) -> Result<usize, String> {
+    let changes = journal::diff(base, project);
+    if changes.is_empty() {
+        return Ok(0);
+    }
+    let count = changes.len();
+    let seq = append(project_path, user, changes)?;
+    project.sync_seen.insert(user.to_string(), seq);
+    *base = Snapshot::of(project);
+    Ok(count)
+}
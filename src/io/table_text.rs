@@ -0,0 +1,86 @@
+use crate::model::{Project, Task};
+use uuid::Uuid;
+
+/// Text table flavour for "Copy as Table".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    /// GitHub-style pipe table, for wikis and chat.
+    Markdown,
+    /// Tab-separated values; pastes into spreadsheets and email as a grid.
+    Tsv,
+}
+
+impl TableFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            TableFormat::Markdown => "Markdown",
+            TableFormat::Tsv => "Tab-separated",
+        }
+    }
+}
+
+const HEADERS: [&str; 5] = ["Task", "Start", "End", "Progress", "Assignee"];
+
+/// Names of the resources assigned to `task`, comma-separated.
+fn assignees(project: &Project, task: &Task) -> String {
+    task.assignments
+        .iter()
+        .filter_map(|a| project.resources.iter().find(|r| r.id == a.resource_id))
+        .map(|r| r.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Markdown cells must not contain pipes or line breaks.
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+fn tsv_cell(s: &str) -> String {
+    s.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Render the tasks with the given ids, in project order, as a text table of
+/// name, dates, progress and assignees.
+pub fn tasks_as_table(project: &Project, ids: &[Uuid], format: TableFormat) -> String {
+    let rows: Vec<[String; 5]> = project
+        .tasks
+        .iter()
+        .filter(|t| ids.contains(&t.id))
+        .map(|t| {
+            [
+                t.name.clone(),
+                t.start.format("%Y-%m-%d").to_string(),
+                t.end.format("%Y-%m-%d").to_string(),
+                if t.is_milestone {
+                    if t.is_achieved() { "Achieved" } else { "Pending" }.to_string()
+                } else {
+                    format!("{:.0}%", t.progress * 100.0)
+                },
+                assignees(project, t),
+            ]
+        })
+        .collect();
+
+    let mut out = String::new();
+    match format {
+        TableFormat::Markdown => {
+            out.push_str(&format!("| {} |\n", HEADERS.join(" | ")));
+            out.push_str("|---|---|---|---:|---|\n");
+            for row in &rows {
+                let cells: Vec<String> = row.iter().map(|c| markdown_cell(c)).collect();
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+        }
+        TableFormat::Tsv => {
+            out.push_str(&HEADERS.join("\t"));
+            out.push('\n');
+            for row in &rows {
+                let cells: Vec<String> = row.iter().map(|c| tsv_cell(c)).collect();
+                out.push_str(&cells.join("\t"));
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,59 @@
+//! Per-user view state of a project file — which parents are collapsed —
+//! kept in the app's config directory, so people sharing a file don't
+//! overwrite each other's outline.
+
+use crate::model::Task;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ViewState {
+    /// Parent tasks shown collapsed.
+    pub collapsed: Vec<Uuid>,
+}
+
+impl ViewState {
+    pub fn capture(tasks: &[Task]) -> Self {
+        Self { collapsed: tasks.iter().filter(|t| t.collapsed).map(|t| t.id).collect() }
+    }
+
+    pub fn apply(&self, tasks: &mut [Task]) {
+        for task in tasks {
+            task.collapsed = self.collapsed.contains(&task.id);
+        }
+    }
+}
+
+/// FNV-1a, so a file keeps the same state file across builds.
+fn path_hash(path: &Path) -> u64 {
+    path.to_string_lossy().bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn state_path(project_path: &Path) -> Option<PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "RustGanttApp")?;
+    let absolute = std::fs::canonicalize(project_path).unwrap_or_else(|_| project_path.to_path_buf());
+    let stem = project_path.file_stem().and_then(|s| s.to_str()).unwrap_or("project");
+    Some(
+        dirs.config_dir()
+            .join("view-state")
+            .join(format!("{}-{:016x}.json", stem, path_hash(&absolute))),
+    )
+}
+
+/// The saved view state of `project_path`, if there is one.
+pub fn load_view_state(project_path: &Path) -> Option<ViewState> {
+    let json = std::fs::read_to_string(state_path(project_path)?).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+pub fn save_view_state(project_path: &Path, state: &ViewState) -> Result<(), String> {
+    let path = state_path(project_path).ok_or("No config directory for view state")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to save view state: {}", e))
+}
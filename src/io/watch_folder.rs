@@ -0,0 +1,69 @@
+//! Polling watcher for a "drop folder": CSV files that appear there are
+//! handed to the app for import.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Seconds between folder scans.
+pub const SCAN_INTERVAL: f64 = 2.0;
+
+/// Size and modification time of a file when it was last scanned.
+type Stamp = (SystemTime, u64);
+
+pub struct FolderWatch {
+    pub dir: PathBuf,
+    /// Files seen so far, with their stamp and whether they were reported.
+    seen: HashMap<PathBuf, (Stamp, bool)>,
+    pub last_scan: f64,
+}
+
+fn csv_files(dir: &Path) -> Vec<(PathBuf, Stamp)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|x| x.to_str())
+                .is_some_and(|x| x.eq_ignore_ascii_case("csv"))
+        })
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            Some((e.path(), (meta.modified().ok()?, meta.len())))
+        })
+        .collect()
+}
+
+impl FolderWatch {
+    /// Start watching `dir`. Files already there are not imported.
+    pub fn new(dir: PathBuf, now: f64) -> Self {
+        let seen = csv_files(&dir).into_iter().map(|(path, stamp)| (path, (stamp, true))).collect();
+        Self { dir, seen, last_scan: now }
+    }
+
+    /// Look for new or rewritten CSV files. A file is reported once its size
+    /// and modification time are unchanged between two scans, so files still
+    /// being written are not picked up half-way.
+    pub fn scan(&mut self, now: f64) -> Vec<PathBuf> {
+        self.last_scan = now;
+        let mut ready = Vec::new();
+        for (path, stamp) in csv_files(&self.dir) {
+            match self.seen.get_mut(&path) {
+                Some((previous, reported)) if *previous == stamp => {
+                    if !*reported {
+                        *reported = true;
+                        ready.push(path);
+                    }
+                }
+                Some(entry) => *entry = (stamp, false),
+                None => {
+                    self.seen.insert(path, (stamp, false));
+                }
+            }
+        }
+        ready
+    }
+}
@@ -6,6 +6,11 @@ mod model;
 mod ui;
 
 fn main() -> eframe::Result<()> {
+    if let Err(e) = io::diagnostics::init_logging() {
+        eprintln!("Logging disabled: {}", e);
+    }
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let link = io::deep_link::from_args(&args);
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1280.0, 720.0])
@@ -17,6 +22,12 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "Rust Gantt App",
         options,
-        Box::new(|cc| Ok(Box::new(app::GanttApp::new(cc)))),
+        Box::new(|cc| {
+            let mut app = app::GanttApp::new(cc);
+            if let Some(link) = link {
+                app.open_deep_link(link);
+            }
+            Ok(Box::new(app))
+        }),
     )
 }
@@ -0,0 +1,59 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::task::Task;
+
+/// A task's dates and progress as of one save. A new entry is written only
+/// when one of them changed since the task's previous entry, so the log
+/// reads as the task's history. Stored in the project file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub task: Uuid,
+    pub at: DateTime<Utc>,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub progress: f32,
+}
+
+impl AuditEntry {
+    fn of(task: &Task, at: DateTime<Utc>) -> Self {
+        Self { task: task.id, at, start: task.start, end: task.end, progress: task.progress }
+    }
+
+    fn same_as(&self, task: &Task) -> bool {
+        self.start == task.start && self.end == task.end && (self.progress - task.progress).abs() < 0.005
+    }
+}
+
+/// Append an entry for every task whose dates or progress differ from its
+/// latest entry. Returns how many were added.
+pub fn record(log: &mut Vec<AuditEntry>, tasks: &[Task], at: DateTime<Utc>) -> usize {
+    let mut latest: std::collections::HashMap<Uuid, &AuditEntry> = std::collections::HashMap::new();
+    for entry in log.iter() {
+        latest.insert(entry.task, entry);
+    }
+    let new: Vec<AuditEntry> = tasks
+        .iter()
+        .filter(|t| !latest.get(&t.id).is_some_and(|e| e.same_as(t)))
+        .map(|t| AuditEntry::of(t, at))
+        .collect();
+    let added = new.len();
+    log.extend(new);
+    added
+}
+
+/// A task's entries, oldest first.
+pub fn task_history(log: &[AuditEntry], task: Uuid) -> Vec<&AuditEntry> {
+    log.iter().filter(|e| e.task == task).collect()
+}
+
+/// How many times the finish moved later across `history`, and by how many
+/// days in total.
+pub fn slips(history: &[&AuditEntry]) -> (usize, i64) {
+    history
+        .windows(2)
+        .map(|w| (w[1].end - w[0].end).num_days())
+        .filter(|&d| d > 0)
+        .fold((0, 0), |(n, total), d| (n + 1, total + d))
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::task::Task;
+
+/// Planned dates of one task at the time a baseline was captured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineDates {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// New baseline dates for one task, set after the project baseline, e.g.
+/// once a change request moving it was approved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rebaseline {
+    pub dates: BaselineDates,
+    /// Why it was re-baselined, such as the change request.
+    pub label: String,
+    pub captured: DateTime<Utc>,
+}
+
+/// A snapshot of planned task dates used to measure schedule drift.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub captured: DateTime<Utc>,
+    pub tasks: HashMap<Uuid, BaselineDates>,
+    /// Tasks re-baselined since; these dates replace the ones in `tasks`.
+    #[serde(default)]
+    pub interim: HashMap<Uuid, Rebaseline>,
+}
+
+impl Baseline {
+    /// Capture the current dates of every task.
+    pub fn capture(tasks: &[Task]) -> Self {
+        Self {
+            captured: Utc::now(),
+            tasks: tasks
+                .iter()
+                .map(|t| (t.id, BaselineDates { start: t.start, end: t.end }))
+                .collect(),
+            interim: HashMap::new(),
+        }
+    }
+
+    /// Baseline the current dates of `tasks` again under `label`, leaving
+    /// the rest of the baseline as it was. Returns the number re-baselined.
+    pub fn rebaseline<'a>(&mut self, tasks: impl IntoIterator<Item = &'a Task>, label: &str) -> usize {
+        let captured = Utc::now();
+        let mut count = 0;
+        for task in tasks {
+            let dates = BaselineDates { start: task.start, end: task.end };
+            self.interim.insert(task.id, Rebaseline { dates, label: label.trim().to_string(), captured });
+            count += 1;
+        }
+        count
+    }
+
+    /// The task's latest re-baselining, if it has one.
+    pub fn interim(&self, task_id: Uuid) -> Option<&Rebaseline> {
+        self.interim.get(&task_id)
+    }
+
+    /// Dates the task is measured against: its re-baselined dates, else
+    /// the project baseline's.
+    pub fn dates(&self, task_id: Uuid) -> Option<BaselineDates> {
+        self.interim(task_id).map(|r| r.dates).or_else(|| self.tasks.get(&task_id).copied())
+    }
+
+    /// Days the task's finish has moved past its baseline finish (negative
+    /// when it finishes early). `None` if the task is not in the baseline.
+    pub fn finish_slip_days(&self, task: &Task) -> Option<i64> {
+        self.dates(task.id).map(|b| (task.end - b.end).num_days())
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::NaiveDate;
+use egui::Color32;
+
+use super::project::Project;
+use super::task::{Dependency, DependencyKind, Task};
+
+/// Tasks per generated phase (one summary task followed by its children).
+const PHASE_SIZE: usize = 20;
+
+const PALETTE: [Color32; 6] = [
+    Color32::from_rgb(70, 130, 180),
+    Color32::from_rgb(60, 179, 113),
+    Color32::from_rgb(218, 112, 214),
+    Color32::from_rgb(106, 90, 205),
+    Color32::from_rgb(220, 20, 60),
+    Color32::from_rgb(210, 150, 60),
+];
+
+/// Small deterministic xorshift generator, so the same seed always yields the
+/// same fixture and timings can be compared between releases.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        if n == 0 { 0 } else { (self.next() % n as u64) as usize }
+    }
+}
+
+/// Generate a synthetic project with `task_count` tasks and up to
+/// `dependency_count` dependencies for measuring chart performance.
+///
+/// Tasks are grouped into phases of `PHASE_SIZE`, with a few milestones mixed
+/// in. Dependencies always point forward in the task list, so the fixture
+/// contains no cycles.
+pub fn synthetic_project(task_count: usize, dependency_count: usize, seed: u64) -> Project {
+    let mut rng = XorShift(seed.max(1));
+    let mut project = Project::new(format!("Benchmark {} tasks / {} deps", task_count, dependency_count));
+    let base = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap_or_default();
+
+    let mut phase_id = None;
+    let mut cursor = base;
+    for i in 0..task_count {
+        if i % PHASE_SIZE == 0 {
+            let mut phase = Task::new(format!("Phase {}", i / PHASE_SIZE + 1), cursor, cursor);
+            phase.color = PALETTE[(i / PHASE_SIZE) % PALETTE.len()];
+            phase_id = Some(phase.id);
+            project.tasks.push(phase);
+            continue;
+        }
+
+        let start = cursor + chrono::Duration::days(rng.below(4) as i64);
+        let mut task = if rng.below(12) == 0 {
+            Task::new_milestone(format!("Milestone {}", i), start)
+        } else {
+            let len = 1 + rng.below(15) as i64;
+            let mut t = Task::new(format!("Task {}", i), start, start + chrono::Duration::days(len));
+            t.progress = rng.below(11) as f32 / 10.0;
+            t.color = PALETTE[rng.below(PALETTE.len())];
+            t
+        };
+        task.parent_id = phase_id;
+        cursor = start + chrono::Duration::days(rng.below(3) as i64);
+        project.tasks.push(task);
+    }
+
+    let leaf_ids: Vec<_> = project
+        .tasks
+        .iter()
+        .filter(|t| t.parent_id.is_some())
+        .map(|t| t.id)
+        .collect();
+    if leaf_ids.len() >= 2 {
+        let mut attempts = 0;
+        while project.dependencies.len() < dependency_count && attempts < dependency_count * 4 {
+            attempts += 1;
+            let from = rng.below(leaf_ids.len() - 1);
+            // Mostly short hops, occasionally a long link across phases.
+            let span = if rng.below(8) == 0 { leaf_ids.len() - from - 1 } else { 3.min(leaf_ids.len() - from - 1) };
+            let to = from + 1 + rng.below(span);
+            let (from_task, to_task) = (leaf_ids[from], leaf_ids[to]);
+            if project
+                .dependencies
+                .iter()
+                .any(|d| d.from_task == from_task && d.to_task == to_task)
+            {
+                continue;
+            }
+            let kind = match rng.below(10) {
+                0 => DependencyKind::StartToStart,
+                1 => DependencyKind::FinishToFinish,
+                _ => DependencyKind::FinishToStart,
+            };
+            project.dependencies.push(Dependency::new(from_task, to_task, kind));
+        }
+    }
+
+    project.recalculate_parent_dates();
+    project
+}
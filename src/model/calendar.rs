@@ -0,0 +1,257 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// How weeks are numbered in the timeline header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum WeekNumbering {
+    /// ISO 8601: weeks start on Monday; week 1 contains the first Thursday.
+    #[default]
+    Iso,
+    /// US: weeks start on Sunday; week 1 contains January 1st.
+    Us,
+}
+
+impl WeekNumbering {
+    pub fn label(self) -> &'static str {
+        match self {
+            WeekNumbering::Iso => "ISO 8601 (Monday)",
+            WeekNumbering::Us => "US (Sunday)",
+        }
+    }
+}
+
+/// First day of the week, for week columns, week starts and snapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum WeekStart {
+    /// The day the week numbering scheme starts on.
+    #[default]
+    Numbering,
+    Monday,
+    Sunday,
+    Saturday,
+}
+
+impl WeekStart {
+    pub fn label(self) -> &'static str {
+        match self {
+            WeekStart::Numbering => "Same as numbering",
+            WeekStart::Monday => "Monday",
+            WeekStart::Sunday => "Sunday",
+            WeekStart::Saturday => "Saturday",
+        }
+    }
+
+    pub fn all() -> &'static [WeekStart] {
+        &[WeekStart::Numbering, WeekStart::Monday, WeekStart::Sunday, WeekStart::Saturday]
+    }
+}
+
+/// Project-wide calendar conventions used by the timeline header.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectCalendar {
+    /// Month (1–12) in which the fiscal year starts.
+    pub fiscal_year_start_month: u32,
+    pub week_numbering: WeekNumbering,
+    pub week_start_day: WeekStart,
+    /// Working hours in a full-time day, for hourly rates and work in hours.
+    pub hours_per_day: f32,
+}
+
+impl Default for ProjectCalendar {
+    fn default() -> Self {
+        Self {
+            fiscal_year_start_month: 1,
+            week_numbering: WeekNumbering::Iso,
+            week_start_day: WeekStart::Numbering,
+            hours_per_day: 8.0,
+        }
+    }
+}
+
+impl ProjectCalendar {
+    /// The weekday weeks start on.
+    pub fn first_weekday(&self) -> Weekday {
+        match (self.week_start_day, self.week_numbering) {
+            (WeekStart::Monday, _) | (WeekStart::Numbering, WeekNumbering::Iso) => Weekday::Mon,
+            (WeekStart::Sunday, _) | (WeekStart::Numbering, WeekNumbering::Us) => Weekday::Sun,
+            (WeekStart::Saturday, _) => Weekday::Sat,
+        }
+    }
+
+    /// First day of the week containing `date`.
+    pub fn week_start(&self, date: NaiveDate) -> NaiveDate {
+        let offset = (7 + date.weekday().num_days_from_monday() - self.first_weekday().num_days_from_monday()) % 7;
+        date - chrono::Duration::days(offset as i64)
+    }
+
+    /// The week start closest to `date`.
+    pub fn nearest_week_start(&self, date: NaiveDate) -> NaiveDate {
+        let start = self.week_start(date);
+        if (date - start).num_days() >= 4 {
+            start + chrono::Duration::days(7)
+        } else {
+            start
+        }
+    }
+
+    /// Whether `date` is a working day (Monday to Friday).
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        date.weekday().num_days_from_monday() < 5
+    }
+
+    /// Number of working days in `start..end` (end exclusive, like task
+    /// durations).
+    pub fn working_days_between(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        start.iter_days().take_while(|d| *d < end).filter(|d| self.is_working_day(*d)).count() as i64
+    }
+
+    /// `date` moved by `n` working days (backwards when negative). Weekend
+    /// days are skipped over and do not count.
+    pub fn add_working_days(&self, date: NaiveDate, n: i64) -> NaiveDate {
+        let step = chrono::Duration::days(n.signum());
+        let mut date = date;
+        let mut left = n.abs();
+        while left > 0 {
+            date += step;
+            if self.is_working_day(date) {
+                left -= 1;
+            }
+        }
+        date
+    }
+
+    /// Working days passed going from `from` to `to`: those in `(from, to]`
+    /// forwards, and negated, those in `[to, from)` backwards.
+    pub fn working_days_crossed(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        let day = chrono::Duration::days(1);
+        if to >= from {
+            self.working_days_between(from + day, to + day)
+        } else {
+            -self.working_days_between(to, from)
+        }
+    }
+
+    /// The span `(start, end)` moved towards `target` by the working days
+    /// crossed on the way, keeping its number of working days: the start
+    /// lands on a working day, and weekends the span now covers add to its
+    /// calendar length.
+    pub fn move_by_working_days(&self, (start, end): (NaiveDate, NaiveDate), target: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let steps = self.working_days_crossed(start, target);
+        if steps == 0 {
+            return (start, end);
+        }
+        let new_start = self.add_working_days(start, steps);
+        let working = self.working_days_between(start, end);
+        let new_end = if working == 0 {
+            new_start + (end - start)
+        } else {
+            // The last day worked, then the day after it, like task ends.
+            self.add_working_days(new_start - chrono::Duration::days(1), working) + chrono::Duration::days(1)
+        };
+        (new_start, new_end)
+    }
+
+    /// Number of the week containing `date`, as weeks start per
+    /// [`Self::first_weekday`]. The day that decides the number names the
+    /// whole week: its Thursday under ISO, so a week starting on a Sunday or
+    /// Saturday takes the number of the ISO week most of it falls in; under
+    /// US numbering a week holding January 1st is week 1.
+    pub fn week_number(&self, date: NaiveDate) -> u32 {
+        let start = self.week_start(date);
+        let mut days = start.iter_days().take(7);
+        match self.week_numbering {
+            WeekNumbering::Iso => days.find(|d| d.weekday() == Weekday::Thu).unwrap_or(date).iso_week().week(),
+            WeekNumbering::Us => match days.find(|d| d.ordinal() == 1) {
+                Some(_) => 1,
+                None => {
+                    let jan1 = NaiveDate::from_ymd_opt(start.year(), 1, 1).unwrap_or(start);
+                    (start.ordinal0() + jan1.weekday().num_days_from_sunday()) / 7 + 1
+                }
+            },
+        }
+    }
+
+    /// Fiscal year and quarter (1–4) of `date`. The fiscal year is named after
+    /// the calendar year in which it ends, e.g. FY2026 runs Jul 2025 – Jun 2026.
+    pub fn fiscal_quarter(&self, date: NaiveDate) -> (i32, u32) {
+        let start = self.fiscal_year_start_month.clamp(1, 12);
+        let months_in = (date.month() + 12 - start) % 12;
+        let year = if start == 1 || date.month() < start {
+            date.year()
+        } else {
+            date.year() + 1
+        };
+        (year, months_in / 3 + 1)
+    }
+
+    /// First day of the fiscal quarter containing `date`, and of the next.
+    pub fn quarter_bounds(&self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let start = self.fiscal_year_start_month.clamp(1, 12);
+        let into_quarter = (date.month() + 12 - start) % 3;
+        let month_start = date.with_day(1).unwrap_or(date);
+        let first = month_start - chrono::Months::new(into_quarter);
+        (first, first + chrono::Months::new(3))
+    }
+
+    /// Whether `date` is the first day of a fiscal quarter.
+    pub fn is_quarter_start(&self, date: NaiveDate) -> bool {
+        let start = self.fiscal_year_start_month.clamp(1, 12);
+        date.day() == 1 && (date.month() + 12 - start).is_multiple_of(3)
+    }
+
+    /// Header label such as `Q3 FY2026`, or `Q3 2026` when the fiscal year
+    /// matches the calendar year.
+    pub fn quarter_label(&self, date: NaiveDate) -> String {
+        let (year, quarter) = self.fiscal_quarter(date);
+        if self.fiscal_year_start_month <= 1 {
+            format!("Q{} {}", quarter, year)
+        } else {
+            format!("Q{} FY{}", quarter, year)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weeks_are_numbered_across_the_year_boundary() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let calendar = |week_numbering, week_start_day| ProjectCalendar { week_numbering, week_start_day, ..Default::default() };
+        let iso = calendar(WeekNumbering::Iso, WeekStart::Numbering);
+        // Monday 30 Dec 2024 starts ISO week 1 of 2025.
+        assert_eq!(iso.week_number(date(2024, 12, 30)), 1);
+        assert_eq!(iso.week_number(date(2024, 12, 29)), 52);
+        // A Sunday week takes the number of the ISO week six of its days are in.
+        let iso_sunday = calendar(WeekNumbering::Iso, WeekStart::Sunday);
+        assert_eq!(iso_sunday.week_number(date(2024, 12, 29)), 1);
+        assert_eq!(iso_sunday.week_number(date(2025, 1, 4)), 1);
+        assert_eq!(iso_sunday.week_number(date(2025, 1, 5)), 2);
+        let iso_saturday = calendar(WeekNumbering::Iso, WeekStart::Saturday);
+        assert_eq!(iso_saturday.week_number(date(2024, 12, 28)), 1);
+        // The US week of Sunday 29 Dec 2024 holds January 1st.
+        let us = calendar(WeekNumbering::Us, WeekStart::Numbering);
+        assert_eq!(us.week_number(date(2024, 12, 29)), 1);
+        assert_eq!(us.week_number(date(2024, 12, 28)), 52);
+        assert_eq!(us.week_number(date(2025, 1, 5)), 2);
+    }
+
+    #[test]
+    fn moving_by_working_days_keeps_the_working_duration() {
+        let calendar = ProjectCalendar::default();
+        let date = |day| NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+        // Monday 3 to Saturday 8: five working days.
+        let span = (date(3), date(8));
+        // Dragged two days on, it runs Wednesday to Tuesday across the weekend.
+        assert_eq!(calendar.move_by_working_days(span, date(5)), (date(5), date(12)));
+        // Let go on a Saturday, it starts on the Friday before.
+        assert_eq!(calendar.move_by_working_days(span, date(8)), (date(7), date(14)));
+        // Back across the weekend before; the weekend itself is no move.
+        assert_eq!(calendar.move_by_working_days(span, date(1)), span);
+        let may_31 = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+        assert_eq!(calendar.move_by_working_days(span, may_31), (may_31, date(7)));
+        assert_eq!(calendar.working_days_crossed(date(7), date(10)), 1);
+    }
+}
@@ -0,0 +1,670 @@
+//! Project mutations as values. The app applies every [`ProjectCommand`]
+//! the same way (undo step, parent rollup, modified time, status message),
+//! and a command can be stored, replayed or sent elsewhere.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::baseline::Baseline;
+use super::calendar::ProjectCalendar;
+use super::journal::{self, Change};
+use super::leveling::LevelingMove;
+use super::marker::{Annotation, DateBand, DateMarker};
+use super::project::{DropPlace, Project};
+use super::resource::{Assignment, Resource};
+use super::task::{Dependency, DependencyKind, Task, TaskPriority};
+use super::view::SavedView;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProjectCommand {
+    AddDependency(Dependency),
+    RemoveDependency { from: Uuid, to: Uuid },
+    /// Replace the link between the same two tasks: kind, lag or style.
+    UpdateDependency(Dependency),
+    /// Complete a task, or every task under a parent.
+    MarkDone(Uuid),
+    /// Mark milestones achieved.
+    AchieveMilestones(Vec<Uuid>),
+    SetProgress { id: Uuid, progress: f32 },
+    /// Move a task and its subtasks next to or into `target`.
+    MoveTask { id: Uuid, target: Uuid, place: DropPlace },
+    /// Move tasks and every link touching them to the trash.
+    Trash(Vec<Uuid>),
+    /// Put a trash entry's tasks and links back.
+    RestoreFromTrash(Uuid),
+    /// Drop a trash entry for good.
+    DeleteFromTrash(Uuid),
+    EmptyTrash,
+    /// Trash a parent, handing its children to `new_parent` first.
+    TrashPromoting { id: Uuid, new_parent: Option<Uuid> },
+    ConvertToMilestone(Uuid),
+    /// Turn a milestone into a task of `duration_days`.
+    ConvertToTask { id: Uuid, duration_days: i64 },
+    /// Copy a phase and its subtasks, shifted by `offset_days`.
+    DuplicatePhase { id: Uuid, offset_days: i64 },
+    /// Add an auto-dated milestone after a phase's finish.
+    AddCompletionMilestone(Uuid),
+    /// Shift tasks by calendar or working days.
+    ShiftTasks { ids: Vec<Uuid>, days: i64, working: bool },
+    /// Move the finish of tasks by calendar or working days.
+    ResizeTasks { ids: Vec<Uuid>, days: i64, working: bool },
+    /// Add a task, linked finish → start from `follows` when given.
+    AddTask { task: Box<Task>, follows: Option<Uuid> },
+    /// Add a task after the last subtask of its parent, linked from `follows`
+    /// when given.
+    AddSubtask { task: Box<Task>, follows: Option<Uuid> },
+    /// Replace a task with an edited copy of itself.
+    UpdateTask(Box<Task>),
+    SetPriority { ids: Vec<Uuid>, priority: TaskPriority },
+    SetColor {
+        ids: Vec<Uuid>,
+        #[serde(with = "super::task::color_serde")]
+        color: Color32,
+    },
+    Assign { ids: Vec<Uuid>, resource_id: Uuid },
+    Unassign { ids: Vec<Uuid>, resource_id: Uuid },
+    /// Assign a task to the resource of that name, adding it when missing.
+    AssignNewResource { id: Uuid, name: String },
+    /// Swap bar colors, e.g. for the palette of another theme.
+    Recolor(#[serde(with = "color_pairs_serde")] Vec<(Color32, Color32)>),
+    /// Rename tasks under a batch-rename pattern.
+    BatchRename { ids: Vec<Uuid>, pattern: String, start: u32, digits: usize },
+    /// Move tasks to the dates of the accepted leveling moves.
+    ApplyLeveling(Vec<LevelingMove>),
+    /// Copy another project's tasks under `parent`, shifted by `offset_days`.
+    ImportPhase { parent: Uuid, part: Box<Project>, offset_days: i64 },
+    /// Add tasks read from a file, assigned against `resources`; see
+    /// [`Project::import_external_tasks`].
+    ImportTasks { tasks: Vec<Task>, resources: Vec<Resource>, by_name: bool },
+    AddDependencies(Vec<Dependency>),
+    /// Add the tasks and links only another copy of the project has.
+    MergeFrom(Box<Project>),
+    /// Apply journaled changes as another planner made them.
+    ApplyChanges(Vec<Change>),
+    /// Record the tracker status (and, when known, progress) of linked tasks.
+    SyncLinks { statuses: Vec<(Uuid, String, Option<f32>)>, at: DateTime<Utc> },
+    SetIncludeTentative(bool),
+    /// Replace the date marker lines.
+    SetMarkers(Vec<DateMarker>),
+    /// Replace the shaded date bands.
+    SetBands(Vec<DateBand>),
+    /// Replace the chart notes.
+    SetAnnotations(Vec<Annotation>),
+    /// Replace the fiscal year, week and working-day conventions.
+    SetCalendar(ProjectCalendar),
+    /// Rename the project and replace its description.
+    SetProjectDetails { name: String, description: String },
+    AddResource(Resource),
+    /// Add resources by name and role; a name already there gets the role.
+    AddResourceList(Vec<(String, String)>),
+    /// Replace a resource with an edited copy of itself.
+    UpdateResource(Resource),
+    /// Remove a resource and its assignments.
+    RemoveResource(Uuid),
+    SetCurrency(String),
+    /// Capture the current dates of every task as the baseline.
+    SetBaseline,
+    /// Baseline the current dates of `ids` again, recorded under `label`.
+    Rebaseline { ids: Vec<Uuid>, label: String },
+    ClearBaseline,
+    /// Store a view, replacing the one of the same name.
+    SaveView(SavedView),
+    DeleteView(String),
+    /// Pin a task to the watch strip, or unpin it.
+    ToggleWatch(Uuid),
+}
+
+/// Serde helper for `Recolor`'s color pairs, each color stored as RGBA.
+mod color_pairs_serde {
+    use egui::Color32;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    type Rgba = [u8; 4];
+
+    pub fn serialize<S: Serializer>(pairs: &[(Color32, Color32)], serializer: S) -> Result<S::Ok, S::Error> {
+        let rgba = |c: &Color32| [c.r(), c.g(), c.b(), c.a()];
+        pairs.iter().map(|(a, b)| (rgba(a), rgba(b))).collect::<Vec<_>>().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(Color32, Color32)>, D::Error> {
+        let color = |c: Rgba| Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3]);
+        let pairs: Vec<(Rgba, Rgba)> = Deserialize::deserialize(deserializer)?;
+        Ok(pairs.into_iter().map(|(a, b)| (color(a), color(b))).collect())
+    }
+}
+
+/// Outcome of a command that applied.
+#[derive(Debug, Clone, Default)]
+pub struct Applied {
+    pub message: String,
+    /// Task to select afterwards, such as one the command created.
+    pub select: Option<Uuid>,
+}
+
+impl From<String> for Applied {
+    fn from(message: String) -> Self {
+        Self { message, select: None }
+    }
+}
+
+fn name(project: &Project, id: Uuid) -> String {
+    project.tasks.iter().find(|t| t.id == id).map(|t| t.name.clone()).unwrap_or_default()
+}
+
+fn day_unit(working: bool) -> &'static str {
+    if working { "working days" } else { "days" }
+}
+
+fn missing() -> String {
+    "The task no longer exists".to_string()
+}
+
+/// Change every task in `ids`, or fail when none of them exists.
+fn for_each(project: &mut Project, ids: &[Uuid], mut change: impl FnMut(&mut Task)) -> Result<Applied, String> {
+    let mut count = 0;
+    for task in project.tasks.iter_mut().filter(|t| ids.contains(&t.id)) {
+        change(task);
+        count += 1;
+    }
+    match count {
+        0 => Err(missing()),
+        n => Ok(format!("Updated {} tasks", n).into()),
+    }
+}
+
+impl ProjectCommand {
+    /// Whether applying the command to the same plan elsewhere has the same
+    /// result. Commands that create ids or timestamps, or only touch this
+    /// copy's trash, don't: their outcome is shared as the changes it made.
+    pub fn is_replayable(&self) -> bool {
+        !matches!(
+            self,
+            ProjectCommand::DuplicatePhase { .. }
+                | ProjectCommand::AddCompletionMilestone(_)
+                | ProjectCommand::AssignNewResource { .. }
+                | ProjectCommand::AddResourceList(_)
+                | ProjectCommand::ImportPhase { .. }
+                | ProjectCommand::ImportTasks { .. }
+                | ProjectCommand::RestoreFromTrash(_)
+                | ProjectCommand::DeleteFromTrash(_)
+                | ProjectCommand::EmptyTrash
+                | ProjectCommand::SetBaseline
+                | ProjectCommand::Rebaseline { .. }
+        )
+    }
+
+    /// Apply the command to `project`. On error the project is unchanged.
+    /// Undo, rollups and the modified time are left to the caller.
+    pub fn apply(self, project: &mut Project) -> Result<Applied, String> {
+        match self {
+            ProjectCommand::AddDependency(dep) => {
+                project.check_link(dep.from_task, dep.to_task)?;
+                let message = format!("Linked '{}' → '{}'", name(project, dep.from_task), name(project, dep.to_task));
+                project.dependencies.push(dep);
+                Ok(message.into())
+            }
+            ProjectCommand::RemoveDependency { from, to } => {
+                let before = project.dependencies.len();
+                project.dependencies.retain(|d| !(d.from_task == from && d.to_task == to));
+                if project.dependencies.len() == before {
+                    return Err("The dependency no longer exists".to_string());
+                }
+                Ok("Dependency removed".to_string().into())
+            }
+            ProjectCommand::UpdateDependency(dep) => {
+                let existing = project
+                    .dependencies
+                    .iter_mut()
+                    .find(|d| d.from_task == dep.from_task && d.to_task == dep.to_task)
+                    .ok_or("The dependency no longer exists")?;
+                let message = if (existing.kind, existing.lag_days) == (dep.kind, dep.lag_days) {
+                    "Link details updated".to_string()
+                } else {
+                    format!("Dependency set to {} {:+}d", dep.kind.short_label(), dep.lag_days)
+                };
+                *existing = dep;
+                Ok(message.into())
+            }
+            ProjectCommand::MarkDone(id) => {
+                let subtree = project.subtree_ids(id);
+                let parents: Vec<Uuid> = project.tasks.iter().filter_map(|t| t.parent_id).collect();
+                for task in project.tasks.iter_mut().filter(|t| subtree.contains(&t.id) && !parents.contains(&t.id)) {
+                    task.progress = 1.0;
+                    if !task.is_milestone {
+                        task.remaining_hours = Some(0.0);
+                    }
+                }
+                Ok(format!("Marked '{}' done", name(project, id)).into())
+            }
+            ProjectCommand::AchieveMilestones(ids) => {
+                let mut achieved = Vec::new();
+                for task in project.tasks.iter_mut().filter(|t| ids.contains(&t.id) && t.is_milestone) {
+                    task.set_achieved(true);
+                    achieved.push(task.name.clone());
+                }
+                Ok(match achieved.as_slice() {
+                    [] => return Err(missing()),
+                    [name] => format!("Milestone '{}' achieved", name),
+                    names => format!("{} milestones achieved", names.len()),
+                }
+                .into())
+            }
+            ProjectCommand::SetProgress { id, progress } => {
+                let task = project.tasks.iter_mut().find(|t| t.id == id).ok_or_else(missing)?;
+                task.progress = progress.clamp(0.0, 1.0);
+                let message = format!("'{}' {:.0}% done", task.name, task.progress * 100.0);
+                project.track_remaining(id);
+                Ok(message.into())
+            }
+            ProjectCommand::MoveTask { id, target, place } => {
+                project.move_task(id, target, place)?;
+                Ok(match place {
+                    DropPlace::Into => format!("Moved '{}' under '{}'", name(project, id), name(project, target)),
+                    _ => format!("Moved '{}'", name(project, id)),
+                }
+                .into())
+            }
+            ProjectCommand::Trash(ids) => {
+                if !project.tasks.iter().any(|t| ids.contains(&t.id)) {
+                    return Err(missing());
+                }
+                project.move_to_trash(&ids);
+                Ok(match ids.len() {
+                    1 => "Task moved to the trash".to_string(),
+                    n => format!("{} tasks moved to the trash", n),
+                }
+                .into())
+            }
+            ProjectCommand::RestoreFromTrash(entry_id) => {
+                let restored = project
+                    .restore_from_trash(entry_id)
+                    .ok_or("The trash entry no longer exists")?;
+                Ok(format!("Restored {} task(s) from the trash", restored).into())
+            }
+            ProjectCommand::DeleteFromTrash(entry_id) => {
+                let before = project.trash.len();
+                project.trash.retain(|e| e.id != entry_id);
+                if project.trash.len() == before {
+                    return Err("The trash entry no longer exists".to_string());
+                }
+                Ok("Deleted permanently".to_string().into())
+            }
+            ProjectCommand::EmptyTrash => {
+                if project.trash.is_empty() {
+                    return Err("The trash is empty".to_string());
+                }
+                project.trash.clear();
+                Ok("Trash emptied".to_string().into())
+            }
+            ProjectCommand::TrashPromoting { id, new_parent } => {
+                let parent = project.tasks.iter().find(|t| t.id == id).ok_or_else(missing)?;
+                let name = parent.name.clone();
+                let children: Vec<Uuid> = parent.children_ids(&project.tasks).into_iter().map(|c| c.id).collect();
+                for task in project.tasks.iter_mut().filter(|t| children.contains(&t.id)) {
+                    task.parent_id = new_parent;
+                }
+                project.move_to_trash(&[id]);
+                Ok(format!("Deleted '{}'; promoted {} subtask(s)", name, children.len()).into())
+            }
+            ProjectCommand::ConvertToMilestone(id) => {
+                if project.tasks.iter().any(|t| t.parent_id == Some(id)) {
+                    return Err("Parent tasks cannot be converted to milestones".to_string());
+                }
+                if !project.convert_to_milestone(id) {
+                    return Err(format!("'{}' is already a milestone", name(project, id)));
+                }
+                Ok(format!("Converted '{}' to a milestone", name(project, id)).into())
+            }
+            ProjectCommand::ConvertToTask { id, duration_days } => {
+                if !project.convert_to_task(id, duration_days) {
+                    return Err(format!("'{}' is not a milestone", name(project, id)));
+                }
+                Ok(format!("Converted '{}' to a {}-day task", name(project, id), duration_days).into())
+            }
+            ProjectCommand::DuplicatePhase { id, offset_days } => {
+                let copy = project.duplicate_subtree(id, offset_days).ok_or_else(missing)?;
+                Ok(Applied {
+                    message: format!("Duplicated '{}' shifted by {} days", name(project, id), offset_days),
+                    select: Some(copy),
+                })
+            }
+            ProjectCommand::AddCompletionMilestone(parent_id) => {
+                let id = project.add_completion_milestone(parent_id).ok_or_else(missing)?;
+                Ok(Applied {
+                    message: format!("Added '{}', following its phase's finish", name(project, id)),
+                    select: Some(id),
+                })
+            }
+            ProjectCommand::ShiftTasks { ids, days, working } => {
+                let moved = project.shift_tasks(&ids, days, working);
+                Ok(format!("Shifted {} task(s) by {:+} {}", moved, days, day_unit(working)).into())
+            }
+            ProjectCommand::ResizeTasks { ids, days, working } => {
+                let resized = project.resize_tasks(&ids, days, working);
+                Ok(format!("Resized {} task(s) by {:+} {}", resized, days, day_unit(working)).into())
+            }
+            ProjectCommand::AddTask { task, follows } => {
+                let id = task.id;
+                let follows = follows.filter(|p| project.tasks.iter().any(|t| t.id == *p));
+                project.tasks.push(*task);
+                let message = match follows {
+                    Some(previous) => {
+                        if let Err(e) = project.check_link(previous, id) {
+                            project.tasks.pop();
+                            return Err(e);
+                        }
+                        project.dependencies.push(Dependency::new(previous, id, DependencyKind::FinishToStart));
+                        format!("Task added after '{}'", name(project, previous))
+                    }
+                    None => "Task added".to_string(),
+                };
+                project.sort_tasks_grouped();
+                Ok(Applied { message, select: Some(id) })
+            }
+            ProjectCommand::AddSubtask { task, follows } => {
+                let (id, parent_id) = (task.id, task.parent_id.ok_or_else(missing)?);
+                // Insert after last child of parent (or right after parent if no children)
+                let insert_pos = project
+                    .tasks
+                    .iter()
+                    .rposition(|t| t.parent_id == Some(parent_id) || t.id == parent_id)
+                    .ok_or_else(missing)?
+                    + 1;
+                project.tasks.insert(insert_pos, *task);
+                if let Some(previous) = follows {
+                    if let Err(e) = project.check_link(previous, id) {
+                        project.tasks.remove(insert_pos);
+                        return Err(e);
+                    }
+                    project.dependencies.push(Dependency::new(previous, id, DependencyKind::FinishToStart));
+                }
+                let message = match follows {
+                    Some(_) => format!("Added subtask under '{}', following the previous one", name(project, parent_id)),
+                    None => format!("Added subtask under '{}'", name(project, parent_id)),
+                };
+                Ok(Applied { message, select: Some(id) })
+            }
+            ProjectCommand::UpdateTask(task) => {
+                let effort = |t: &Task| (t.progress, t.remaining_hours.is_some());
+                let existing = project.tasks.iter_mut().find(|t| t.id == task.id).ok_or_else(missing)?;
+                let ((was, tracked), (now, tracking)) = (effort(existing), effort(&task));
+                let id = task.id;
+                *existing = *task;
+                if was != now || (!tracked && tracking) {
+                    project.track_remaining(id);
+                }
+                Ok("Task updated".to_string().into())
+            }
+            ProjectCommand::SetPriority { ids, priority } => {
+                for_each(project, &ids, |t| t.priority = priority)
+            }
+            ProjectCommand::SetColor { ids, color } => for_each(project, &ids, |t| t.color = color),
+            ProjectCommand::Assign { ids, resource_id } => {
+                if !project.resources.iter().any(|r| r.id == resource_id) {
+                    return Err("The resource no longer exists".to_string());
+                }
+                for_each(project, &ids, |t| {
+                    if !t.assignments.iter().any(|a| a.resource_id == resource_id) {
+                        t.assignments.push(Assignment::new(resource_id));
+                    }
+                })
+            }
+            ProjectCommand::Unassign { ids, resource_id } => {
+                for_each(project, &ids, |t| t.assignments.retain(|a| a.resource_id != resource_id))
+            }
+            ProjectCommand::AssignNewResource { id, name } => {
+                if !project.tasks.iter().any(|t| t.id == id) {
+                    return Err(missing());
+                }
+                let resource_id = match project.resources.iter().find(|r| r.name.eq_ignore_ascii_case(&name)) {
+                    Some(existing) => existing.id,
+                    None => {
+                        let resource = Resource::new(name.clone());
+                        let id = resource.id;
+                        project.resources.push(resource);
+                        id
+                    }
+                };
+                if let Some(task) = project.tasks.iter_mut().find(|t| t.id == id) {
+                    if !task.assignments.iter().any(|a| a.resource_id == resource_id) {
+                        task.assignments.push(Assignment::new(resource_id));
+                        task.apply_effort(&project.resources);
+                    }
+                }
+                Ok(format!("Assigned '{}'", name).into())
+            }
+            ProjectCommand::Recolor(swaps) => {
+                let mut count = 0;
+                for task in &mut project.tasks {
+                    if let Some((_, new)) = swaps.iter().find(|(old, _)| *old == task.color) {
+                        task.color = *new;
+                        count += 1;
+                    }
+                }
+                Ok(format!("Recolored {} task(s)", count).into())
+            }
+            ProjectCommand::BatchRename { ids, pattern, start, digits } => {
+                match project.batch_rename(&ids, &pattern, start, digits) {
+                    0 => Err("No task names changed".to_string()),
+                    renamed => Ok(format!("Renamed {} task(s)", renamed).into()),
+                }
+            }
+            ProjectCommand::ApplyLeveling(moves) => {
+                let accepted: Vec<(Uuid, (NaiveDate, NaiveDate))> =
+                    moves.iter().filter(|m| m.accepted).map(|m| (m.task_id, m.to)).collect();
+                if accepted.is_empty() {
+                    return Err("No leveling moves are accepted".to_string());
+                }
+                for (id, (start, end)) in &accepted {
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == *id) {
+                        task.start = *start;
+                        task.end = *end;
+                    }
+                }
+                Ok(format!("Leveled resources: moved {} task(s)", accepted.len()).into())
+            }
+            ProjectCommand::ImportPhase { parent, part, offset_days } => {
+                if !project.tasks.iter().any(|t| t.id == parent) {
+                    return Err(missing());
+                }
+                let count = project.import_tasks(parent, &part, offset_days);
+                Ok(format!("Imported {} task(s) from '{}' into '{}'", count, part.name, name(project, parent)).into())
+            }
+            ProjectCommand::ImportTasks { mut tasks, resources, by_name } => {
+                if tasks.is_empty() {
+                    return Err("No tasks to import".to_string());
+                }
+                project.adopt_assignments(&resources, &mut tasks);
+                let (added, updated) = project.import_external_tasks(tasks, by_name);
+                Ok(match updated {
+                    0 => format!("{} task(s) added", added),
+                    _ => format!("{} added, {} updated", added, updated),
+                }
+                .into())
+            }
+            ProjectCommand::AddDependencies(deps) => {
+                if deps.is_empty() {
+                    return Err("No dependencies to add".to_string());
+                }
+                // Each link is checked with the ones before it in place.
+                let (before, count) = (project.dependencies.len(), deps.len());
+                for dep in deps {
+                    if let Err(e) = project.check_link(dep.from_task, dep.to_task) {
+                        let link = format!("'{}' → '{}'", name(project, dep.from_task), name(project, dep.to_task));
+                        project.dependencies.truncate(before);
+                        return Err(format!("Cannot add {}: {}", link, e));
+                    }
+                    project.dependencies.push(dep);
+                }
+                Ok(format!("Imported {} dependencies", count).into())
+            }
+            ProjectCommand::MergeFrom(other) => {
+                let added = project.merge_from(&other);
+                Ok(format!("Merged {} task(s) from disk", added).into())
+            }
+            ProjectCommand::ApplyChanges(changes) => {
+                if changes.is_empty() {
+                    return Err("No changes to apply".to_string());
+                }
+                for change in &changes {
+                    journal::apply_to_project(project, change);
+                }
+                Ok(format!("Merged {} change(s)", changes.len()).into())
+            }
+            ProjectCommand::SyncLinks { statuses, at } => {
+                let mut synced = 0;
+                for (id, status, progress) in statuses {
+                    let Some(task) = project.tasks.iter_mut().find(|t| t.id == id) else { continue };
+                    task.link_status = Some(status);
+                    task.last_synced = Some(at);
+                    synced += 1;
+                    if let Some(progress) = progress {
+                        task.progress = progress;
+                        project.track_remaining(id);
+                    }
+                }
+                Ok(format!("Synced {} linked task(s)", synced).into())
+            }
+            ProjectCommand::SetIncludeTentative(include) => {
+                project.include_tentative = include;
+                Ok(if include { "Tentative tasks count toward the schedule" } else { "Tentative tasks are left out of the schedule" }
+                    .to_string()
+                    .into())
+            }
+            ProjectCommand::SetMarkers(markers) => {
+                project.markers = markers;
+                Ok("Markers updated".to_string().into())
+            }
+            ProjectCommand::SetBands(bands) => {
+                project.bands = bands;
+                Ok("Bands updated".to_string().into())
+            }
+            ProjectCommand::SetAnnotations(annotations) => {
+                project.annotations = annotations;
+                Ok("Notes updated".to_string().into())
+            }
+            ProjectCommand::SetCalendar(calendar) => {
+                project.calendar = calendar;
+                Ok("Calendar settings updated".to_string().into())
+            }
+            ProjectCommand::SetProjectDetails { name, description } => {
+                if name.trim().is_empty() {
+                    return Err("The project needs a name".to_string());
+                }
+                project.name = name;
+                project.description = description;
+                Ok("Project properties updated".to_string().into())
+            }
+            ProjectCommand::AddResource(resource) => {
+                let message = format!("Added '{}'", resource.name);
+                project.resources.push(resource);
+                Ok(message.into())
+            }
+            ProjectCommand::AddResourceList(rows) => {
+                let (mut added, mut updated) = (0, 0);
+                for (name, role) in rows {
+                    match project.resources.iter_mut().find(|r| r.name.eq_ignore_ascii_case(&name)) {
+                        Some(existing) if !role.is_empty() && existing.role != role => {
+                            existing.role = role;
+                            updated += 1;
+                        }
+                        Some(_) => {}
+                        None => {
+                            let mut resource = Resource::new(name);
+                            resource.role = role;
+                            project.resources.push(resource);
+                            added += 1;
+                        }
+                    }
+                }
+                Ok(match updated {
+                    0 => format!("Added {} resource(s)", added),
+                    n => format!("Added {} resource(s); updated the role of {}", added, n),
+                }
+                .into())
+            }
+            ProjectCommand::UpdateResource(resource) => {
+                let existing = project
+                    .resources
+                    .iter_mut()
+                    .find(|r| r.id == resource.id)
+                    .ok_or("The resource no longer exists")?;
+                let message = format!("Updated '{}'", resource.name);
+                *existing = resource;
+                Ok(message.into())
+            }
+            ProjectCommand::RemoveResource(id) => {
+                if !project.resources.iter().any(|r| r.id == id) {
+                    return Err("The resource no longer exists".to_string());
+                }
+                project.remove_resource(id);
+                Ok("Resource removed".to_string().into())
+            }
+            ProjectCommand::SetCurrency(currency) => {
+                project.currency = currency;
+                Ok("Currency updated".to_string().into())
+            }
+            ProjectCommand::SetBaseline => {
+                project.baseline = Some(Baseline::capture(&project.tasks));
+                Ok(format!("Baseline set for {} tasks", project.tasks.len()).into())
+            }
+            ProjectCommand::Rebaseline { ids, label } => {
+                let baseline = project.baseline.as_mut().ok_or("Set a project baseline first")?;
+                let count = baseline.rebaseline(project.tasks.iter().filter(|t| ids.contains(&t.id)), &label);
+                Ok(format!("Re-baselined {} task(s)", count).into())
+            }
+            ProjectCommand::ClearBaseline => {
+                project.baseline.take().ok_or("There is no baseline to clear")?;
+                Ok("Baseline cleared".to_string().into())
+            }
+            ProjectCommand::SaveView(view) => {
+                let message = format!("Saved view '{}'", view.name);
+                match project.views.iter_mut().find(|v| v.name == view.name) {
+                    Some(existing) => *existing = view,
+                    None => project.views.push(view),
+                }
+                Ok(message.into())
+            }
+            ProjectCommand::DeleteView(view_name) => {
+                let index = project.views.iter().position(|v| v.name == view_name).ok_or("The view no longer exists")?;
+                project.views.remove(index);
+                Ok(format!("Deleted view '{}'", view_name).into())
+            }
+            ProjectCommand::ToggleWatch(id) => {
+                let task_name = project.tasks.iter().find(|t| t.id == id).ok_or_else(missing)?.name.clone();
+                Ok(match project.watch_list.iter().position(|w| *w == id) {
+                    Some(pos) => {
+                        project.watch_list.remove(pos);
+                        format!("Stopped watching '{}'", task_name)
+                    }
+                    None => {
+                        project.watch_list.push(id);
+                        format!("Watching '{}'", task_name)
+                    }
+                }
+                .into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_in_one_batch_cannot_close_a_cycle() {
+        let day = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let mut project = Project::new("Links");
+        project.tasks = vec![Task::new("A", day, day), Task::new("B", day, day)];
+        let (a, b) = (project.tasks[0].id, project.tasks[1].id);
+        let batch = vec![
+            Dependency::new(a, b, DependencyKind::FinishToStart),
+            Dependency::new(b, a, DependencyKind::FinishToStart),
+        ];
+        assert!(ProjectCommand::AddDependencies(batch).apply(&mut project).is_err());
+        assert!(project.dependencies.is_empty());
+    }
+}
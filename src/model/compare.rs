@@ -0,0 +1,135 @@
+//! Task-by-task comparison of two versions of a plan, e.g. the open project
+//! and a colleague's updated copy of it.
+
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use super::{Project, Task};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Only in the other file.
+    Added,
+    /// Only in the open project.
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeKind::Added => "Added",
+            ChangeKind::Removed => "Removed",
+            ChangeKind::Changed => "Changed",
+        }
+    }
+
+    pub fn all() -> &'static [ChangeKind] {
+        &[ChangeKind::Changed, ChangeKind::Added, ChangeKind::Removed]
+    }
+}
+
+/// One task that differs between the two files.
+#[derive(Debug, Clone)]
+pub struct TaskDiff {
+    pub kind: ChangeKind,
+    /// The task in the open project, if it exists there.
+    pub ours: Option<Uuid>,
+    /// The task in the other file, if it exists there.
+    pub theirs: Option<Uuid>,
+    /// What changed, e.g. "End 12/03 → 19/03".
+    pub changes: Vec<String>,
+}
+
+fn assignee_names(project: &Project, task: &Task) -> Vec<String> {
+    let mut names: Vec<String> = task
+        .assignments
+        .iter()
+        .filter_map(|a| project.resources.iter().find(|r| r.id == a.resource_id))
+        .map(|r| r.name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+fn field_changes(ours: &Project, a: &Task, theirs: &Project, b: &Task) -> Vec<String> {
+    let date = |d: chrono::NaiveDate| d.format("%d/%m/%Y").to_string();
+    let mut changes = Vec::new();
+    if a.name != b.name {
+        changes.push(format!("Name '{}' → '{}'", a.name, b.name));
+    }
+    if a.start != b.start {
+        changes.push(format!("Start {} → {}", date(a.start), date(b.start)));
+    }
+    if a.end != b.end {
+        changes.push(format!("End {} → {}", date(a.end), date(b.end)));
+    }
+    if (a.progress - b.progress).abs() >= 0.005 {
+        changes.push(format!("Progress {:.0}% → {:.0}%", a.progress * 100.0, b.progress * 100.0));
+    }
+    if a.priority != b.priority {
+        changes.push(format!("Priority {} → {}", a.priority.label(), b.priority.label()));
+    }
+    let (who_a, who_b) = (assignee_names(ours, a), assignee_names(theirs, b));
+    if who_a != who_b {
+        let list = |names: &[String]| if names.is_empty() { "nobody".to_string() } else { names.join(", ") };
+        changes.push(format!("Assigned {} → {}", list(&who_a), list(&who_b)));
+    }
+    if a.is_milestone != b.is_milestone {
+        changes.push(if b.is_milestone { "Became a milestone" } else { "No longer a milestone" }.to_string());
+    }
+    changes
+}
+
+/// Pair each task of `ours` with its counterpart in `theirs`: by id first,
+/// then by name for tasks whose name is unique on both sides (plans rebuilt
+/// from CSV get fresh ids).
+pub fn match_tasks(ours: &Project, theirs: &Project) -> HashMap<Uuid, Uuid> {
+    let their_ids: HashSet<Uuid> = theirs.tasks.iter().map(|t| t.id).collect();
+    let mut pairs: HashMap<Uuid, Uuid> =
+        ours.tasks.iter().filter(|t| their_ids.contains(&t.id)).map(|t| (t.id, t.id)).collect();
+    let unique_names = |project: &Project, taken: &dyn Fn(Uuid) -> bool| {
+        let mut by_name: HashMap<String, Option<Uuid>> = HashMap::new();
+        for task in project.tasks.iter().filter(|t| !taken(t.id)) {
+            by_name
+                .entry(task.name.trim().to_lowercase())
+                .and_modify(|id| *id = None)
+                .or_insert(Some(task.id));
+        }
+        by_name
+    };
+    let matched_theirs: HashSet<Uuid> = pairs.values().copied().collect();
+    let our_names = unique_names(ours, &|id| pairs.contains_key(&id));
+    let their_names = unique_names(theirs, &|id| matched_theirs.contains(&id));
+    for (name, ours_id) in our_names {
+        if let (Some(a), Some(Some(b))) = (ours_id, their_names.get(&name)) {
+            pairs.insert(a, *b);
+        }
+    }
+    pairs
+}
+
+/// Tasks added, removed or changed in `theirs` relative to `ours`, in the
+/// open project's task order with added tasks last.
+pub fn compare(ours: &Project, theirs: &Project) -> Vec<TaskDiff> {
+    let pairs = match_tasks(ours, theirs);
+    let their_tasks: HashMap<Uuid, &Task> = theirs.tasks.iter().map(|t| (t.id, t)).collect();
+    let mut diffs = Vec::new();
+    for task in &ours.tasks {
+        match pairs.get(&task.id).and_then(|id| their_tasks.get(id)) {
+            Some(other) => {
+                let changes = field_changes(ours, task, theirs, other);
+                if !changes.is_empty() {
+                    diffs.push(TaskDiff { kind: ChangeKind::Changed, ours: Some(task.id), theirs: Some(other.id), changes });
+                }
+            }
+            None => diffs.push(TaskDiff { kind: ChangeKind::Removed, ours: Some(task.id), theirs: None, changes: Vec::new() }),
+        }
+    }
+    let matched: HashSet<Uuid> = pairs.values().copied().collect();
+    for task in theirs.tasks.iter().filter(|t| !matched.contains(&t.id)) {
+        diffs.push(TaskDiff { kind: ChangeKind::Added, ours: None, theirs: Some(task.id), changes: Vec::new() });
+    }
+    diffs
+}
@@ -0,0 +1,79 @@
+//! "When will this really finish?" Forecasts extrapolate the progress each
+//! task has made since it actually started, at the rate it has kept so far.
+
+use chrono::{Duration, NaiveDate};
+
+use super::project::Project;
+use super::task::Task;
+
+/// The day work on `task` began: its planned start, or the first saved
+/// progress when that came earlier (see `model::audit`).
+pub fn actual_start(project: &Project, task: &Task) -> NaiveDate {
+    project
+        .audit
+        .iter()
+        .filter(|e| e.task == task.id && e.progress > 0.0)
+        .map(|e| e.at.date_naive())
+        .min()
+        .map_or(task.start, |first| first.min(task.start))
+}
+
+/// Forecast finish (exclusive, like task ends) of a task under way: the
+/// work left, done at the pace kept since the actual start. `None` for
+/// milestones, parents, and tasks not started or already done.
+pub fn task_forecast(project: &Project, task: &Task, today: NaiveDate) -> Option<NaiveDate> {
+    if task.is_milestone || task.progress <= 0.0 || task.progress >= 1.0 || task.has_children(&project.tasks) {
+        return None;
+    }
+    let elapsed = (today - actual_start(project, task)).num_days();
+    if elapsed <= 0 {
+        return None;
+    }
+    let per_day = task.progress / elapsed as f32;
+    let days_left = ((1.0 - task.progress) / per_day).ceil() as i64;
+    Some(today + Duration::days(days_left))
+}
+
+/// Forecast finish of the whole project: the latest forecast of the work
+/// still open, with tasks not yet started taken at their planned end.
+/// `None` when everything is done.
+pub fn project_forecast(project: &Project, today: NaiveDate) -> Option<NaiveDate> {
+    project
+        .tasks
+        .iter()
+        .filter(|t| project.is_counted(t) && t.progress < 1.0 && !t.has_children(&project.tasks))
+        .map(|t| task_forecast(project, t, today).unwrap_or(t.end))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, day).unwrap()
+    }
+
+    #[test]
+    fn extrapolates_the_pace_since_the_start() {
+        let mut project = Project::new("Forecast");
+        let mut task = Task::new("Build", date(1), date(11));
+        // A quarter done after 5 of 10 days: 15 more days at that pace.
+        task.progress = 0.25;
+        project.tasks.push(task.clone());
+        assert_eq!(task_forecast(&project, &task, date(6)), Some(date(21)));
+        assert_eq!(project_forecast(&project, date(6)), Some(date(21)));
+    }
+
+    #[test]
+    fn unstarted_and_finished_work_keep_their_plan() {
+        let mut project = Project::new("Forecast");
+        let waiting = Task::new("Waiting", date(10), date(20));
+        let mut done = Task::new("Done", date(1), date(5));
+        done.progress = 1.0;
+        project.tasks = vec![waiting.clone(), done.clone()];
+        assert_eq!(task_forecast(&project, &waiting, date(6)), None);
+        assert_eq!(task_forecast(&project, &done, date(6)), None);
+        assert_eq!(project_forecast(&project, date(6)), Some(date(20)));
+    }
+}
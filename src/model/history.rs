@@ -1,18 +1,117 @@
+use std::mem::size_of;
+
+use uuid::Uuid;
+
+use super::baseline::{Baseline, BaselineDates, Rebaseline};
+use super::calendar::ProjectCalendar;
+use super::marker::{Annotation, DateBand, DateMarker};
+use super::project::Project;
+use super::resource::{Assignment, Resource};
 use super::task::{Dependency, Task};
+use super::trash::TrashEntry;
+use super::view::SavedView;
 
-const MAX_HISTORY: usize = 50;
+/// Undo steps kept unless configured otherwise.
+pub const DEFAULT_DEPTH: usize = 50;
 
-/// A snapshot of the mutable project data (tasks + dependencies).
+/// A snapshot of the mutable project data (tasks, dependencies, the
+/// resources they are assigned to and the settings commands change).
 #[derive(Clone)]
 pub struct ProjectSnapshot {
     pub tasks: Vec<Task>,
     pub dependencies: Vec<Dependency>,
+    pub resources: Vec<Resource>,
+    pub currency: String,
+    pub baseline: Option<Baseline>,
+    pub views: Vec<SavedView>,
+    pub watch_list: Vec<Uuid>,
+    pub name: String,
+    pub description: String,
+    pub calendar: ProjectCalendar,
+    pub include_tentative: bool,
+    pub markers: Vec<DateMarker>,
+    pub bands: Vec<DateBand>,
+    pub annotations: Vec<Annotation>,
+    pub trash: Vec<TrashEntry>,
+    /// Rough memory held by the snapshot, in bytes.
+    bytes: usize,
+}
+
+impl ProjectSnapshot {
+    /// The state of `project` now, e.g. to push once a mutation succeeds.
+    pub fn of(project: &Project) -> Self {
+        Self {
+            tasks: project.tasks.clone(),
+            dependencies: project.dependencies.clone(),
+            resources: project.resources.clone(),
+            currency: project.currency.clone(),
+            baseline: project.baseline.clone(),
+            views: project.views.clone(),
+            watch_list: project.watch_list.clone(),
+            name: project.name.clone(),
+            description: project.description.clone(),
+            calendar: project.calendar,
+            include_tentative: project.include_tentative,
+            markers: project.markers.clone(),
+            bands: project.bands.clone(),
+            annotations: project.annotations.clone(),
+            trash: project.trash.clone(),
+            bytes: Self::estimate_bytes(project),
+        }
+    }
+
+    fn estimate_bytes(project: &Project) -> usize {
+        let text = |s: &Option<String>| s.as_ref().map_or(0, |s| s.len());
+        let task_bytes = |tasks: &[Task]| -> usize {
+            tasks
+                .iter()
+                .map(|t| {
+                    size_of::<Task>()
+                        + t.name.len()
+                        + t.description.len()
+                        + text(&t.group)
+                        + text(&t.link)
+                        + text(&t.link_status)
+                        + t.assignments.len() * size_of::<Assignment>()
+                })
+                .sum()
+        };
+        let dep_bytes = |dependencies: &[Dependency]| -> usize {
+            dependencies
+                .iter()
+                .map(|d| size_of::<Dependency>() + d.metadata.iter().map(|(k, v)| k.len() + v.len() + 48).sum::<usize>())
+                .sum()
+        };
+        let trash_bytes: usize = project
+            .trash
+            .iter()
+            .map(|e| size_of::<TrashEntry>() + task_bytes(&e.tasks) + dep_bytes(&e.dependencies))
+            .sum();
+        let resource_bytes: usize = project
+            .resources
+            .iter()
+            .map(|r| size_of::<Resource>() + r.name.len() + r.role.len() + r.absences.iter().map(|a| a.note.len() + 32).sum::<usize>())
+            .sum();
+        let marker_bytes: usize = project.markers.iter().map(|m| size_of::<DateMarker>() + m.name.len()).sum();
+        let band_bytes: usize = project.bands.iter().map(|b| size_of::<DateBand>() + b.name.len()).sum();
+        let note_bytes: usize = project.annotations.iter().map(|a| size_of::<Annotation>() + a.text.len()).sum();
+        let baseline_bytes = project.baseline.as_ref().map_or(0, |b| {
+            b.tasks.len() * size_of::<(Uuid, BaselineDates)>()
+                + b.interim.values().map(|r| size_of::<(Uuid, Rebaseline)>() + r.label.len()).sum::<usize>()
+        });
+        let view_bytes: usize = project.views.iter().map(|v| size_of::<SavedView>() + v.name.len() + v.search.len()).sum();
+        let text_bytes = project.name.len() + project.description.len() + marker_bytes + band_bytes + note_bytes;
+        let settings_bytes = baseline_bytes + view_bytes + project.watch_list.len() * size_of::<Uuid>() + project.currency.len();
+        task_bytes(&project.tasks) + dep_bytes(&project.dependencies) + resource_bytes + trash_bytes + text_bytes + settings_bytes
+    }
 }
 
 /// Undo/redo stack for project mutations.
 pub struct UndoHistory {
     past: Vec<ProjectSnapshot>,
     future: Vec<ProjectSnapshot>,
+    /// Most undo steps kept; the oldest are dropped first.
+    depth: usize,
 }
 
 impl UndoHistory {
@@ -20,47 +119,52 @@ impl UndoHistory {
         Self {
             past: Vec::new(),
             future: Vec::new(),
+            depth: DEFAULT_DEPTH,
         }
     }
 
-    /// Push a snapshot of the current state before a mutation is applied.
-    pub fn push(&mut self, tasks: &[Task], dependencies: &[Dependency]) {
-        if self.past.len() >= MAX_HISTORY {
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Keep at most `depth` undo steps (at least one), dropping the oldest.
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth.max(1);
+        let excess = self.past.len().saturating_sub(self.depth);
+        self.past.drain(..excess);
+    }
+
+    /// Undo and redo steps held.
+    pub fn len(&self) -> usize {
+        self.past.len() + self.future.len()
+    }
+
+    /// Rough memory held by all undo and redo steps, in bytes.
+    pub fn estimated_bytes(&self) -> usize {
+        self.past.iter().chain(&self.future).map(|s| s.bytes).sum()
+    }
+
+    /// Push a snapshot taken before a mutation that has since been applied.
+    pub fn push_snapshot(&mut self, snapshot: ProjectSnapshot) {
+        if self.past.len() >= self.depth {
             self.past.remove(0);
         }
-        self.past.push(ProjectSnapshot {
-            tasks: tasks.to_vec(),
-            dependencies: dependencies.to_vec(),
-        });
+        self.past.push(snapshot);
         // Any new action clears the redo stack.
         self.future.clear();
     }
 
     /// Undo: returns the previous snapshot (state to restore), saving the current state for redo.
-    pub fn undo(
-        &mut self,
-        current_tasks: &[Task],
-        current_deps: &[Dependency],
-    ) -> Option<ProjectSnapshot> {
+    pub fn undo(&mut self, current: &Project) -> Option<ProjectSnapshot> {
         let snapshot = self.past.pop()?;
-        self.future.push(ProjectSnapshot {
-            tasks: current_tasks.to_vec(),
-            dependencies: current_deps.to_vec(),
-        });
+        self.future.push(ProjectSnapshot::of(current));
         Some(snapshot)
     }
 
     /// Redo: returns the next snapshot, saving current state back to undo stack.
-    pub fn redo(
-        &mut self,
-        current_tasks: &[Task],
-        current_deps: &[Dependency],
-    ) -> Option<ProjectSnapshot> {
+    pub fn redo(&mut self, current: &Project) -> Option<ProjectSnapshot> {
         let snapshot = self.future.pop()?;
-        self.past.push(ProjectSnapshot {
-            tasks: current_tasks.to_vec(),
-            dependencies: current_deps.to_vec(),
-        });
+        self.past.push(ProjectSnapshot::of(current));
         Some(snapshot)
     }
 
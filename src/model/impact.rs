@@ -0,0 +1,132 @@
+//! What a task's links ask of its dates: the earliest start its
+//! predecessors allow, and the knock-on effect of moving it — which tasks
+//! down its links would have to move, and by how much, for every link to
+//! hold again. The chart shows the latter while a bar is dragged.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+
+use super::calendar::ProjectCalendar;
+use super::task::{Dependency, DependencyKind, Task};
+
+/// The earliest start `task` can have with every incoming link and its lag
+/// met, moved on to a working day. Finish links count the task's current
+/// duration. `None` without predecessors.
+pub fn earliest_start(
+    tasks: &[Task],
+    dependencies: &[Dependency],
+    calendar: &ProjectCalendar,
+    task: &Task,
+) -> Option<NaiveDate> {
+    let duration = task.end - task.start;
+    let mut date = dependencies
+        .iter()
+        .filter(|d| d.to_task == task.id)
+        .filter_map(|d| {
+            let from = tasks.iter().find(|t| t.id == d.from_task)?;
+            let lag = Duration::days(d.lag_days);
+            Some(match d.kind {
+                DependencyKind::FinishToStart => from.end + lag,
+                DependencyKind::StartToStart => from.start + lag,
+                DependencyKind::FinishToFinish => from.end + lag - duration,
+                DependencyKind::StartToFinish => from.start + lag - duration,
+            })
+        })
+        .max()?;
+    while !task.is_milestone && !calendar.is_working_day(date) {
+        date += Duration::days(1);
+    }
+    Some(date)
+}
+
+/// Days each task downstream of `id` would slip were `id` to run
+/// `after` instead of `before`: tasks already late on their links before the
+/// move only count for the extra slip the move adds.
+pub fn slip_impact(
+    tasks: &[Task],
+    dependencies: &[Dependency],
+    id: Uuid,
+    before: (NaiveDate, NaiveDate),
+    after: (NaiveDate, NaiveDate),
+) -> Vec<(Uuid, i64)> {
+    let was = downstream_slip(tasks, dependencies, id, before);
+    let mut out: Vec<(Uuid, i64)> = downstream_slip(tasks, dependencies, id, after)
+        .into_iter()
+        .filter_map(|(task, days)| {
+            let extra = days - was.get(&task).copied().unwrap_or(0);
+            (extra > 0).then_some((task, extra))
+        })
+        .collect();
+    out.sort_by_key(|&(task, _)| tasks.iter().position(|t| t.id == task));
+    out
+}
+
+/// Push the successors of `id`, placed at `dates`, as late as their links
+/// require, down the chain. Returns how far each pushed task moved.
+fn downstream_slip(
+    tasks: &[Task],
+    dependencies: &[Dependency],
+    id: Uuid,
+    dates: (NaiveDate, NaiveDate),
+) -> HashMap<Uuid, i64> {
+    let mut placed: HashMap<Uuid, (NaiveDate, NaiveDate)> = HashMap::from([(id, dates)]);
+    let mut slip: HashMap<Uuid, i64> = HashMap::new();
+    let mut queue = VecDeque::from([id]);
+    // Links cannot form cycles, but a hand-edited file could; stop anyway.
+    let mut budget = tasks.len() * dependencies.len().max(1);
+    while let Some(from) = queue.pop_front() {
+        let Some(&(from_start, from_end)) = placed.get(&from) else { continue };
+        for dep in dependencies.iter().filter(|d| d.from_task == from && d.to_task != id) {
+            let Some(to) = tasks.iter().find(|t| t.id == dep.to_task) else { continue };
+            let (start, end) = placed.get(&to.id).copied().unwrap_or((to.start, to.end));
+            let lag = Duration::days(dep.lag_days);
+            let late = match dep.kind {
+                DependencyKind::FinishToStart => (from_end + lag - start).num_days(),
+                DependencyKind::StartToStart => (from_start + lag - start).num_days(),
+                DependencyKind::FinishToFinish => (from_end + lag - end).num_days(),
+                DependencyKind::StartToFinish => (from_start + lag - end).num_days(),
+            };
+            if late <= 0 || budget == 0 {
+                continue;
+            }
+            budget -= 1;
+            let shift = Duration::days(late);
+            placed.insert(to.id, (start + shift, end + shift));
+            *slip.entry(to.id).or_default() += late;
+            queue.push_back(to.id);
+        }
+    }
+    slip
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, day).unwrap()
+    }
+
+    #[test]
+    fn only_the_slip_the_move_adds_counts() {
+        let design = Task::new("Design", date(1), date(5));
+        let build = Task::new("Build", date(8), date(12));
+        let test = Task::new("Test", date(12), date(14));
+        // Already two days late on its link before anything moves.
+        let docs = Task::new("Docs", date(3), date(6));
+        let links = vec![
+            Dependency::new(design.id, build.id, DependencyKind::FinishToStart),
+            Dependency::new(build.id, test.id, DependencyKind::FinishToStart),
+            Dependency::new(design.id, docs.id, DependencyKind::FinishToStart),
+        ];
+        let tasks = vec![design.clone(), build.clone(), test.clone(), docs.clone()];
+        // Design runs five days longer: Build slips two, Test with it, and
+        // Docs five on top of the two it was already late.
+        let impact = slip_impact(&tasks, &links, design.id, (date(1), date(5)), (date(1), date(10)));
+        assert_eq!(impact, vec![(build.id, 2), (test.id, 2), (docs.id, 5)]);
+        let impact = slip_impact(&tasks, &links, design.id, (date(1), date(5)), (date(1), date(7)));
+        assert_eq!(impact, vec![(docs.id, 2)]);
+    }
+}
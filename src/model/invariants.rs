@@ -0,0 +1,214 @@
+//! Structural invariants every project must satisfy, whatever the schedule
+//! says: unique ids, parents and links that point at existing tasks, no
+//! parent cycles, zero-length milestones and parents that span their
+//! children. A violation means a bug in the code that mutated the project,
+//! or a hand-edited file, rather than a planning problem (those are
+//! [`super::validation`]'s job).
+
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use super::project::Project;
+
+/// One broken invariant. `task` is the task to jump to, when there is one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub task: Option<Uuid>,
+    pub message: String,
+}
+
+fn violation(task: Option<Uuid>, message: String) -> Violation {
+    Violation { task, message }
+}
+
+/// Check the project's invariants. An empty list means it is consistent.
+pub fn validate(project: &Project) -> Vec<Violation> {
+    let mut out = Vec::new();
+    let mut by_id = HashMap::with_capacity(project.tasks.len());
+    for task in &project.tasks {
+        if by_id.insert(task.id, task).is_some() {
+            out.push(violation(Some(task.id), format!("Duplicate task id {} ('{}')", task.id, task.name)));
+        }
+    }
+
+    for task in &project.tasks {
+        if task.end < task.start {
+            out.push(violation(Some(task.id), format!("'{}' ends before it starts", task.name)));
+        }
+        if task.is_milestone && task.start != task.end {
+            out.push(violation(Some(task.id), format!("Milestone '{}' is not zero-length", task.name)));
+        }
+        let Some(parent_id) = task.parent_id else {
+            continue;
+        };
+        let Some(parent) = by_id.get(&parent_id) else {
+            out.push(violation(Some(task.id), format!("'{}' has a parent that does not exist", task.name)));
+            continue;
+        };
+        if parent.is_milestone {
+            out.push(violation(Some(task.id), format!("'{}' is under milestone '{}'", task.name, parent.name)));
+        }
+        // A cycle would have revisited a task within `tasks.len()` steps.
+        let mut ancestor = Some(parent_id);
+        let mut steps = 0;
+        while let Some(id) = ancestor {
+            if id == task.id || steps > project.tasks.len() {
+                out.push(violation(Some(task.id), format!("'{}' is its own ancestor", task.name)));
+                break;
+            }
+            ancestor = by_id.get(&id).and_then(|t| t.parent_id);
+            steps += 1;
+        }
+        if project.is_counted(task) && (task.start < parent.start || task.end > parent.end) {
+            out.push(violation(
+                Some(task.id),
+                format!("'{}' lies outside its parent '{}'", task.name, parent.name),
+            ));
+        }
+    }
+
+    let mut links = HashSet::new();
+    for dep in &project.dependencies {
+        let (from, to) = (by_id.get(&dep.from_task), by_id.get(&dep.to_task));
+        match (from, to) {
+            (Some(from), Some(to)) => {
+                if dep.from_task == dep.to_task {
+                    out.push(violation(Some(dep.to_task), format!("'{}' is linked to itself", to.name)));
+                } else if !links.insert((dep.from_task, dep.to_task)) {
+                    out.push(violation(
+                        Some(dep.to_task),
+                        format!("'{}' → '{}' is linked more than once", from.name, to.name),
+                    ));
+                }
+            }
+            _ => out.push(violation(
+                from.or(to).map(|t| t.id),
+                "A dependency refers to a task that does not exist".to_string(),
+            )),
+        }
+    }
+
+    for assigned in project.tasks.iter().filter(|t| {
+        t.assignments.iter().any(|a| !project.resources.iter().any(|r| r.id == a.resource_id))
+    }) {
+        out.push(violation(
+            Some(assigned.id),
+            format!("'{}' is assigned a resource that does not exist", assigned.name),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::task::{Dependency, DependencyKind};
+    use crate::model::{Assignment, Task};
+    use chrono::NaiveDate;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    /// Phase → (A → B, milestone M after B), plus an outside task C.
+    fn fixture() -> (Project, [Uuid; 5]) {
+        let mut project = Project::new("Fixture");
+        let phase = Task::new("Phase", date(1), date(10));
+        let mut a = Task::new("A", date(1), date(4));
+        let mut b = Task::new("B", date(5), date(10));
+        let mut m = Task::new_milestone("M", date(10));
+        let c = Task::new("C", date(11), date(12));
+        a.parent_id = Some(phase.id);
+        b.parent_id = Some(phase.id);
+        m.parent_id = Some(phase.id);
+        let ids = [phase.id, a.id, b.id, m.id, c.id];
+        project.tasks = vec![phase, a, b, m, c];
+        project.dependencies = vec![
+            Dependency::new(ids[1], ids[2], DependencyKind::FinishToStart),
+            Dependency::new(ids[2], ids[3], DependencyKind::FinishToStart),
+        ];
+        (project, ids)
+    }
+
+    fn task(project: &mut Project, id: Uuid) -> &mut Task {
+        project.tasks.iter_mut().find(|t| t.id == id).unwrap()
+    }
+
+    #[test]
+    fn fixture_is_consistent() {
+        let (project, _) = fixture();
+        assert_eq!(validate(&project), Vec::new());
+    }
+
+    #[test]
+    fn dangling_parent_is_reported() {
+        let (mut project, [_, a, ..]) = fixture();
+        task(&mut project, a).parent_id = Some(Uuid::new_v4());
+        let found = validate(&project);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].task, Some(a));
+    }
+
+    #[test]
+    fn parent_cycle_is_reported() {
+        let (mut project, [phase, a, ..]) = fixture();
+        task(&mut project, phase).parent_id = Some(a);
+        assert!(validate(&project).iter().any(|v| v.message.contains("own ancestor")));
+    }
+
+    #[test]
+    fn dangling_and_duplicate_links_are_reported() {
+        let (mut project, [_, a, b, ..]) = fixture();
+        project.dependencies.push(Dependency::new(a, Uuid::new_v4(), DependencyKind::FinishToStart));
+        project.dependencies.push(Dependency::new(a, b, DependencyKind::FinishToStart));
+        project.dependencies.push(Dependency::new(b, b, DependencyKind::FinishToStart));
+        assert_eq!(validate(&project).len(), 3);
+    }
+
+    #[test]
+    fn milestones_must_be_zero_length() {
+        let (mut project, [.., m, _]) = fixture();
+        task(&mut project, m).end = date(11);
+        assert!(validate(&project).iter().any(|v| v.task == Some(m) && v.message.contains("zero-length")));
+    }
+
+    #[test]
+    fn parents_must_contain_children_until_rolled_up() {
+        let (mut project, [_, a, ..]) = fixture();
+        task(&mut project, a).start = date(1) - chrono::Duration::days(3);
+        assert!(validate(&project).iter().any(|v| v.task == Some(a)));
+        project.recalculate_parent_dates();
+        assert_eq!(validate(&project), Vec::new());
+    }
+
+    #[test]
+    fn nested_parents_roll_up_from_the_bottom() {
+        let (mut project, [phase, a, ..]) = fixture();
+        let mut outer = Task::new("Outer", date(1), date(10));
+        outer.id = Uuid::new_v4();
+        task(&mut project, phase).parent_id = Some(outer.id);
+        project.tasks.insert(0, outer);
+        task(&mut project, a).end = date(20);
+        project.recalculate_parent_dates();
+        assert_eq!(validate(&project), Vec::new());
+    }
+
+    #[test]
+    fn missing_resources_are_reported() {
+        let (mut project, [_, a, ..]) = fixture();
+        task(&mut project, a).assignments.push(Assignment::new(Uuid::new_v4()));
+        assert_eq!(validate(&project).len(), 1);
+    }
+
+    #[test]
+    fn mutations_keep_the_fixture_consistent() {
+        let (mut project, [phase, ..]) = fixture();
+        let cloned = project.clone_subtree(phase, 7).unwrap();
+        project.tasks.extend(cloned.tasks);
+        project.dependencies.extend(cloned.dependencies);
+        project.add_completion_milestone(phase);
+        project.recalculate_parent_dates();
+        assert_eq!(validate(&project), Vec::new());
+    }
+}
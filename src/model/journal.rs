@@ -0,0 +1,450 @@
+//! Change journals for planners sharing a project over a network folder.
+//! Each user appends the tasks, links, resources and project settings they
+//! changed to their own journal next to the project file; the others merge
+//! those changes on load, on save and while the project is open. An item both sides changed
+//! since they last agreed on it is a [`Conflict`] for the user to settle.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::baseline::Baseline;
+use super::calendar::ProjectCalendar;
+use super::marker::{Annotation, DateBand, DateMarker};
+use super::project::Project;
+use super::resource::Resource;
+use super::task::{Dependency, Task};
+use super::timeline::DefaultZoom;
+use super::view::SavedView;
+
+/// One changed item, carrying its new state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Change {
+    Task(Task),
+    RemoveTask(Uuid),
+    Link(Dependency),
+    RemoveLink { from: Uuid, to: Uuid },
+    Resource(Resource),
+    RemoveResource(Uuid),
+    Settings(Box<Settings>),
+}
+
+/// What a change is about: changes with the same key replace each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Task(Uuid),
+    Link(Uuid, Uuid),
+    Resource(Uuid),
+    Settings,
+}
+
+/// The project-wide fields planners share besides tasks, links and
+/// resources. They travel as one item, so two planners changing any of
+/// them is a conflict. The trash, audit trail and weekly snapshots are
+/// kept by whoever saves.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Settings {
+    pub name: String,
+    pub description: String,
+    pub currency: String,
+    pub baseline: Option<Baseline>,
+    pub calendar: ProjectCalendar,
+    pub include_tentative: bool,
+    pub views: Vec<SavedView>,
+    pub default_zoom: Option<DefaultZoom>,
+    pub watch_list: Vec<Uuid>,
+    pub markers: Vec<DateMarker>,
+    pub bands: Vec<DateBand>,
+    pub annotations: Vec<Annotation>,
+}
+
+impl Settings {
+    pub fn of(project: &Project) -> Self {
+        Self {
+            name: project.name.clone(),
+            description: project.description.clone(),
+            currency: project.currency.clone(),
+            baseline: project.baseline.clone(),
+            calendar: project.calendar,
+            include_tentative: project.include_tentative,
+            views: project.views.clone(),
+            default_zoom: project.default_zoom,
+            watch_list: project.watch_list.clone(),
+            markers: project.markers.clone(),
+            bands: project.bands.clone(),
+            annotations: project.annotations.clone(),
+        }
+    }
+
+    fn apply_to(&self, project: &mut Project) {
+        let settings = self.clone();
+        project.name = settings.name;
+        project.description = settings.description;
+        project.currency = settings.currency;
+        project.baseline = settings.baseline;
+        project.calendar = settings.calendar;
+        project.include_tentative = settings.include_tentative;
+        project.views = settings.views;
+        project.default_zoom = settings.default_zoom;
+        project.watch_list = settings.watch_list;
+        project.markers = settings.markers;
+        project.bands = settings.bands;
+        project.annotations = settings.annotations;
+    }
+}
+
+impl Change {
+    fn key(&self) -> Key {
+        match self {
+            Change::Task(t) => Key::Task(t.id),
+            Change::RemoveTask(id) => Key::Task(*id),
+            Change::Link(d) => Key::Link(d.from_task, d.to_task),
+            Change::RemoveLink { from, to } => Key::Link(*from, *to),
+            Change::Resource(r) => Key::Resource(r.id),
+            Change::RemoveResource(id) => Key::Resource(*id),
+            Change::Settings(_) => Key::Settings,
+        }
+    }
+}
+
+/// Changes saved together by one user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Per-user sequence number, starting at 1.
+    pub seq: u64,
+    pub at: DateTime<Utc>,
+    pub changes: Vec<Change>,
+}
+
+/// A user's journal file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Journal {
+    pub user: String,
+    pub entries: Vec<JournalEntry>,
+}
+
+/// The shared parts of a plan, as last agreed with the other planners.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub tasks: Vec<Task>,
+    pub dependencies: Vec<Dependency>,
+    pub resources: Vec<Resource>,
+    pub settings: Settings,
+}
+
+impl Snapshot {
+    pub fn of(project: &Project) -> Self {
+        Self {
+            tasks: project.tasks.clone(),
+            dependencies: project.dependencies.clone(),
+            resources: project.resources.clone(),
+            settings: Settings::of(project),
+        }
+    }
+
+    /// Take on a change, as the new agreed state of its item.
+    fn apply(&mut self, change: &Change) {
+        match change {
+            Change::Settings(settings) => self.settings = (**settings).clone(),
+            _ => apply(&mut self.tasks, &mut self.dependencies, &mut self.resources, change),
+        }
+    }
+
+    /// Current agreed state of the item `key` refers to.
+    fn state(&self, key: Key) -> Change {
+        match key {
+            Key::Settings => Change::Settings(Box::new(self.settings.clone())),
+            _ => state(&self.tasks, &self.dependencies, &self.resources, key),
+        }
+    }
+}
+
+/// An item another planner changed while you changed it too.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub user: String,
+    /// Their version of the item.
+    pub theirs: Change,
+    /// Description of the item, e.g. "Task 'Design'".
+    pub label: String,
+}
+
+impl Conflict {
+    /// Whether both conflicts are about the same task, link, resource or the
+    /// project settings.
+    pub fn same_item(&self, other: &Conflict) -> bool {
+        self.theirs.key() == other.theirs.key()
+    }
+}
+
+/// Collapsing is a per-user view choice, not a change to share.
+fn same_task(a: &Task, b: &Task) -> bool {
+    Task { collapsed: a.collapsed, ..b.clone() } == *a
+}
+
+fn find_task(tasks: &[Task], id: Uuid) -> Option<&Task> {
+    tasks.iter().find(|t| t.id == id)
+}
+
+fn find_link(deps: &[Dependency], from: Uuid, to: Uuid) -> Option<&Dependency> {
+    deps.iter().find(|d| d.from_task == from && d.to_task == to)
+}
+
+/// Changes that turn `base` into `project`.
+pub fn diff(base: &Snapshot, project: &Project) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let settings = Settings::of(project);
+    if settings != base.settings {
+        changes.push(Change::Settings(Box::new(settings)));
+    }
+    for resource in &project.resources {
+        if base.resources.iter().find(|r| r.id == resource.id) != Some(resource) {
+            changes.push(Change::Resource(resource.clone()));
+        }
+    }
+    for resource in &base.resources {
+        if !project.resources.iter().any(|r| r.id == resource.id) {
+            changes.push(Change::RemoveResource(resource.id));
+        }
+    }
+    for task in &project.tasks {
+        if !find_task(&base.tasks, task.id).is_some_and(|b| same_task(b, task)) {
+            changes.push(Change::Task(task.clone()));
+        }
+    }
+    for task in &base.tasks {
+        if find_task(&project.tasks, task.id).is_none() {
+            changes.push(Change::RemoveTask(task.id));
+        }
+    }
+    for dep in &project.dependencies {
+        if find_link(&base.dependencies, dep.from_task, dep.to_task) != Some(dep) {
+            changes.push(Change::Link(dep.clone()));
+        }
+    }
+    for dep in &base.dependencies {
+        if find_link(&project.dependencies, dep.from_task, dep.to_task).is_none() {
+            changes.push(Change::RemoveLink { from: dep.from_task, to: dep.to_task });
+        }
+    }
+    changes
+}
+
+/// Current state of the task, link or resource `key` refers to, as a
+/// change that would recreate it.
+fn state(tasks: &[Task], deps: &[Dependency], resources: &[Resource], key: Key) -> Change {
+    match key {
+        Key::Task(id) => find_task(tasks, id).cloned().map_or(Change::RemoveTask(id), Change::Task),
+        Key::Link(from, to) => {
+            find_link(deps, from, to).cloned().map_or(Change::RemoveLink { from, to }, Change::Link)
+        }
+        Key::Resource(id) => resources.iter().find(|r| r.id == id).cloned().map_or(Change::RemoveResource(id), Change::Resource),
+        Key::Settings => unreachable!("the settings are not kept with the tasks"),
+    }
+}
+
+fn same(a: &Change, b: &Change) -> bool {
+    match (a, b) {
+        (Change::Task(a), Change::Task(b)) => same_task(a, b),
+        _ => a == b,
+    }
+}
+
+/// Apply one change to tasks, links or resources. New tasks go after their
+/// parent's subtree, or last.
+fn apply(tasks: &mut Vec<Task>, deps: &mut Vec<Dependency>, resources: &mut Vec<Resource>, change: &Change) {
+    match change {
+        Change::Task(task) => match tasks.iter_mut().find(|t| t.id == task.id) {
+            Some(existing) => *existing = Task { collapsed: existing.collapsed, ..task.clone() },
+            None => {
+                let at = task
+                    .parent_id
+                    .and_then(|p| tasks.iter().rposition(|t| t.id == p || t.parent_id == Some(p)))
+                    .map_or(tasks.len(), |i| i + 1);
+                tasks.insert(at, task.clone());
+            }
+        },
+        Change::RemoveTask(id) => {
+            tasks.retain(|t| t.id != *id);
+            deps.retain(|d| d.from_task != *id && d.to_task != *id);
+        }
+        Change::Link(dep) => match deps.iter_mut().find(|d| d.from_task == dep.from_task && d.to_task == dep.to_task) {
+            Some(existing) => *existing = dep.clone(),
+            None => deps.push(dep.clone()),
+        },
+        Change::RemoveLink { from, to } => deps.retain(|d| !(d.from_task == *from && d.to_task == *to)),
+        Change::Resource(resource) => match resources.iter_mut().find(|r| r.id == resource.id) {
+            Some(existing) => *existing = resource.clone(),
+            None => resources.push(resource.clone()),
+        },
+        Change::RemoveResource(id) => {
+            resources.retain(|r| r.id != *id);
+            for task in tasks.iter_mut() {
+                task.assignments.retain(|a| a.resource_id != *id);
+            }
+        }
+        Change::Settings(_) => {}
+    }
+}
+
+/// Apply a change to the project, e.g. when taking their side of a conflict.
+pub fn apply_to_project(project: &mut Project, change: &Change) {
+    match change {
+        Change::Settings(settings) => settings.apply_to(project),
+        _ => apply(&mut project.tasks, &mut project.dependencies, &mut project.resources, change),
+    }
+}
+
+/// Current state of the item `key` refers to in `project`.
+fn project_state(project: &Project, key: Key) -> Change {
+    match key {
+        Key::Settings => Change::Settings(Box::new(Settings::of(project))),
+        _ => state(&project.tasks, &project.dependencies, &project.resources, key),
+    }
+}
+
+fn label(project: &Project, base: &Snapshot, change: &Change) -> String {
+    let task_name = |id: Uuid| {
+        find_task(&project.tasks, id)
+            .or_else(|| find_task(&base.tasks, id))
+            .map(|t| t.name.clone())
+            .unwrap_or_else(|| "(deleted)".to_string())
+    };
+    match change.key() {
+        Key::Task(id) => format!("Task '{}'", task_name(id)),
+        Key::Link(from, to) => format!("Link '{}' → '{}'", task_name(from), task_name(to)),
+        Key::Resource(id) => format!(
+            "Resource '{}'",
+            project
+                .resources
+                .iter()
+                .chain(&base.resources)
+                .find(|r| r.id == id)
+                .map(|r| r.name.as_str())
+                .unwrap_or("?")
+        ),
+        Key::Settings => "Project settings".to_string(),
+    }
+}
+
+/// Merge another user's changes. Items you left alone since `base` take
+/// their version; items you changed the same way are already merged; the
+/// rest are returned as conflicts and keep your version for now. `base`
+/// moves to their version throughout, so your next [`diff`] only holds
+/// your own edits. Returns the number of changes applied and the conflicts.
+pub fn merge(project: &mut Project, base: &mut Snapshot, user: &str, changes: &[Change]) -> (usize, Vec<Conflict>) {
+    let mut applied = 0;
+    let mut conflicts = Vec::new();
+    for change in changes {
+        let key = change.key();
+        let ours = project_state(project, key);
+        let agreed = base.state(key);
+        if same(&ours, &agreed) {
+            apply_to_project(project, change);
+            applied += 1;
+        } else if !same(&ours, change) {
+            conflicts.push(Conflict {
+                user: user.to_string(),
+                theirs: change.clone(),
+                label: label(project, base, change),
+            });
+        }
+        base.apply(change);
+    }
+    // A later change to the same item supersedes an earlier conflict.
+    let mut latest: Vec<Conflict> = Vec::new();
+    for conflict in conflicts {
+        latest.retain(|c| !c.same_item(&conflict));
+        latest.push(conflict);
+    }
+    (applied, latest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    fn plan() -> Project {
+        let mut project = Project::new("Shared");
+        project.tasks = vec![Task::new("A", date(1), date(4)), Task::new("B", date(5), date(8))];
+        project
+    }
+
+    #[test]
+    fn diff_lists_only_what_changed() {
+        let mut project = plan();
+        let base = Snapshot::of(&project);
+        project.tasks[0].end = date(6);
+        project.tasks[1].collapsed = true;
+        let removed = project.tasks.remove(1).id;
+        project.tasks.push(Task::new("C", date(9), date(10)));
+        let changes = diff(&base, &project);
+        assert_eq!(changes.len(), 3);
+        assert!(changes.contains(&Change::RemoveTask(removed)));
+    }
+
+    #[test]
+    fn untouched_items_take_their_version() {
+        let mut ours = plan();
+        let mut base = Snapshot::of(&ours);
+        let mut theirs = ours.clone();
+        theirs.tasks[1].name = "B (renamed)".to_string();
+        let changes = diff(&Snapshot::of(&ours), &theirs);
+        let (applied, conflicts) = merge(&mut ours, &mut base, "sam", &changes);
+        assert_eq!((applied, conflicts.len()), (1, 0));
+        assert_eq!(ours.tasks[1].name, "B (renamed)");
+        assert!(diff(&base, &ours).is_empty());
+    }
+
+    #[test]
+    fn both_sides_changing_an_item_is_a_conflict() {
+        let mut ours = plan();
+        let mut base = Snapshot::of(&ours);
+        let mut theirs = ours.clone();
+        theirs.tasks[0].end = date(10);
+        let changes = diff(&base, &theirs);
+        ours.tasks[0].end = date(7);
+        let (applied, conflicts) = merge(&mut ours, &mut base, "sam", &changes);
+        assert_eq!((applied, conflicts.len()), (0, 1));
+        assert_eq!(ours.tasks[0].end, date(7));
+        // Keeping ours journals it as our change on top of theirs.
+        assert_eq!(diff(&base, &ours).len(), 1);
+        apply_to_project(&mut ours, &conflicts[0].theirs);
+        assert_eq!(ours.tasks[0].end, date(10));
+    }
+
+    #[test]
+    fn identical_edits_merge_silently() {
+        let mut ours = plan();
+        let mut base = Snapshot::of(&ours);
+        let mut theirs = ours.clone();
+        theirs.tasks[0].progress = 0.5;
+        ours.tasks[0].progress = 0.5;
+        let changes = diff(&base, &theirs);
+        let (_, conflicts) = merge(&mut ours, &mut base, "sam", &changes);
+        assert!(conflicts.is_empty());
+        assert!(diff(&base, &ours).is_empty());
+    }
+
+    #[test]
+    fn settings_and_removed_resources_are_journaled() {
+        let mut ours = plan();
+        ours.resources.push(Resource::new("Sam"));
+        let mut base = Snapshot::of(&ours);
+        let mut theirs = ours.clone();
+        theirs.markers.push(crate::model::DateMarker::new("Freeze", date(6)));
+        theirs.resources.clear();
+        let changes = diff(&base, &theirs);
+        assert_eq!(changes.len(), 2);
+        let (applied, conflicts) = merge(&mut ours, &mut base, "sam", &changes);
+        assert_eq!((applied, conflicts.len()), (2, 0));
+        assert_eq!(ours.markers.len(), 1);
+        assert!(ours.resources.is_empty());
+        assert!(diff(&base, &ours).is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+//! Swimlane layout: tasks grouped into lanes, with tasks that overlap in
+//! time stacked into as few sub-rows as the lane needs.
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+
+use super::task::TaskPriority;
+use super::view::{LaneGroup, RoadmapBucket};
+use super::{Project, Task};
+
+/// One swimlane: its title and the tasks on each of its sub-rows, in
+/// start order.
+pub struct Swimlane {
+    pub title: String,
+    pub rows: Vec<Vec<Uuid>>,
+}
+
+/// Days a task occupies, as a half-open range. Milestones take one day so
+/// they get a slot of their own.
+pub fn span(task: &Task) -> (NaiveDate, NaiveDate) {
+    (task.start, task.end.max(task.start + Duration::days(1)))
+}
+
+/// Give each half-open span a row so that no two spans on the same row
+/// overlap. First-fit in start order, which uses as few rows as the
+/// largest number of spans overlapping on any one day.
+pub fn pack_rows(spans: &[(NaiveDate, NaiveDate)]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..spans.len()).collect();
+    order.sort_by_key(|&i| spans[i]);
+    let mut row_ends: Vec<NaiveDate> = Vec::new();
+    let mut rows = vec![0; spans.len()];
+    for i in order {
+        let (start, end) = spans[i];
+        let row = match row_ends.iter().position(|&e| e <= start) {
+            Some(row) => row,
+            None => {
+                row_ends.push(end);
+                row_ends.len() - 1
+            }
+        };
+        row_ends[row] = end;
+        rows[i] = row;
+    }
+    rows
+}
+
+fn top_level_ancestor<'a>(project: &'a Project, task: &'a Task) -> &'a Task {
+    let mut current = task;
+    while let Some(parent) = current.parent_id.and_then(|pid| project.tasks.iter().find(|t| t.id == pid)) {
+        current = parent;
+    }
+    current
+}
+
+/// Lanes a task belongs to, as (sort key, title). A task assigned to
+/// several resources shows up in each of their lanes.
+fn lane_keys(project: &Project, task: &Task, group: LaneGroup) -> Vec<(usize, String)> {
+    match group {
+        LaneGroup::Phase => {
+            let phase = top_level_ancestor(project, task);
+            if phase.id == task.id {
+                vec![(usize::MAX, "Ungrouped".to_string())]
+            } else {
+                let order = project.tasks.iter().position(|t| t.id == phase.id).unwrap_or(usize::MAX);
+                vec![(order, phase.name.clone())]
+            }
+        }
+        LaneGroup::Assignee => {
+            let keys: Vec<(usize, String)> = task
+                .assignments
+                .iter()
+                .filter_map(|a| project.resources.iter().enumerate().find(|(_, r)| r.id == a.resource_id))
+                .map(|(i, r)| (i, r.name.clone()))
+                .collect();
+            if keys.is_empty() {
+                vec![(usize::MAX, "Unassigned".to_string())]
+            } else {
+                keys
+            }
+        }
+        LaneGroup::Priority => {
+            // Most urgent first.
+            let order = TaskPriority::all().len() - 1 - TaskPriority::all().iter().position(|&p| p == task.priority).unwrap_or(0);
+            let title = match task.priority {
+                TaskPriority::None => "No priority",
+                p => p.label(),
+            };
+            vec![(order, title.to_string())]
+        }
+    }
+}
+
+/// The quarter or month span a task touches, as a half-open range of whole
+/// buckets: what a roadmap chip covers.
+pub fn bucket_span(task: &Task, bucket: RoadmapBucket) -> (NaiveDate, NaiveDate) {
+    let (start, end) = span(task);
+    (bucket.floor(start), bucket.next(bucket.floor(end - Duration::days(1))))
+}
+
+/// Group the project's leaf tasks that pass `include` into lanes. With
+/// `stack`, tasks in a lane whose `span_of` ranges don't overlap share a
+/// sub-row; without it every task gets a row of its own.
+pub fn swimlanes(
+    project: &Project,
+    group: LaneGroup,
+    stack: bool,
+    span_of: impl Fn(&Task) -> (NaiveDate, NaiveDate),
+    include: impl Fn(&Task) -> bool,
+) -> Vec<Swimlane> {
+    let parents: HashSet<Uuid> = project.tasks.iter().filter_map(|t| t.parent_id).collect();
+    let mut lanes: BTreeMap<(usize, String), Vec<&Task>> = BTreeMap::new();
+    for task in project.tasks.iter().filter(|t| !parents.contains(&t.id) && include(t)) {
+        for key in lane_keys(project, task, group) {
+            lanes.entry(key).or_default().push(task);
+        }
+    }
+    lanes
+        .into_iter()
+        .map(|((_, title), mut tasks)| {
+            tasks.sort_by_key(|t| span(t));
+            let rows = if stack {
+                let spans: Vec<_> = tasks.iter().map(|t| span_of(t)).collect();
+                let slots = pack_rows(&spans);
+                let mut rows = vec![Vec::new(); slots.iter().max().map_or(0, |m| m + 1)];
+                for (task, slot) in tasks.iter().zip(slots) {
+                    rows[slot].push(task.id);
+                }
+                rows
+            } else {
+                tasks.iter().map(|t| vec![t.id]).collect()
+            };
+            Swimlane { title, rows }
+        })
+        .collect()
+}
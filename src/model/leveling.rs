@@ -0,0 +1,288 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::project::Project;
+use super::task::{DependencyKind, Task, TaskPriority};
+
+/// A proposed date change produced by the leveling pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelingMove {
+    pub task_id: Uuid,
+    pub from: (NaiveDate, NaiveDate),
+    pub to: (NaiveDate, NaiveDate),
+    /// Days the task can slip without moving a successor or the project end.
+    pub float_days: i64,
+    /// Whether the move is included when the proposal is applied.
+    pub accepted: bool,
+}
+
+impl LevelingMove {
+    pub fn delay_days(&self) -> i64 {
+        (self.to.0 - self.from.0).num_days()
+    }
+
+    pub fn within_float(&self) -> bool {
+        self.delay_days() <= self.float_days
+    }
+}
+
+fn priority_rank(priority: TaskPriority) -> u8 {
+    match priority {
+        TaskPriority::Critical => 4,
+        TaskPriority::High => 3,
+        TaskPriority::Medium => 2,
+        TaskPriority::Low => 1,
+        TaskPriority::None => 0,
+    }
+}
+
+/// Total float of a task: how far it can slip before it pushes a successor
+/// (per link type) or, without successors, the project finish.
+fn total_float(project: &Project, task: &Task, project_end: NaiveDate) -> i64 {
+    let mut float: Option<i64> = None;
+    for dep in project.dependencies.iter().filter(|d| d.from_task == task.id) {
+        let Some(succ) = project.tasks.iter().find(|t| t.id == dep.to_task) else {
+            continue;
+        };
+        let slack = match dep.kind {
+            DependencyKind::FinishToStart => (succ.start - task.end).num_days(),
+            DependencyKind::StartToStart => (succ.start - task.start).num_days(),
+            DependencyKind::FinishToFinish => (succ.end - task.end).num_days(),
+            DependencyKind::StartToFinish => (succ.end - task.start).num_days(),
+        };
+        float = Some(float.map_or(slack, |f: i64| f.min(slack)));
+    }
+    float
+        .unwrap_or_else(|| (project_end - task.end).num_days())
+        .max(0)
+}
+
+/// Propose delays that remove resource overallocation. A resource is
+/// overallocated when the allocation units of its overlapping tasks add up to
+/// more than 100%, or when one of them falls in the resource's absence.
+///
+/// Higher-priority tasks keep their dates; among equal priorities, tasks with
+/// the least float are scheduled first. A delay past a task's float (which
+/// moves a successor or the finish) is avoided where a task of the same
+/// priority sharing its resources can slip within its own float instead.
+/// Successors of a delayed task are delayed as far as their links require.
+/// The project itself is not modified.
+pub fn level_resources(project: &Project) -> Vec<LevelingMove> {
+    let Some(project_end) = project.tasks.iter().map(|t| t.end).max() else {
+        return Vec::new();
+    };
+
+    let mut order: Vec<(&Task, i64)> = project
+        .tasks
+        .iter()
+        .filter(|t| !t.is_milestone && !t.external && !t.assignments.is_empty() && !t.has_children(&project.tasks))
+        .map(|t| (t, total_float(project, t, project_end)))
+        .collect();
+    order.sort_by(|(a, fa), (b, fb)| {
+        priority_rank(b.priority)
+            .cmp(&priority_rank(a.priority))
+            .then(fa.cmp(fb))
+            .then(a.start.cmp(&b.start))
+    });
+
+    let mut moves = schedule(project, &order, project_end);
+    // Book a task delayed past its float ahead of the first task of the same
+    // priority with float that shares a resource with it; keep the new order
+    // when less float is overrun. The overrun shrinks each round, so this ends.
+    'improve: loop {
+        let late: Vec<Uuid> = moves.iter().filter(|m| !m.within_float()).map(|m| m.task_id).collect();
+        for id in late {
+            let Some(pos) = order.iter().position(|(t, _)| t.id == id) else {
+                continue;
+            };
+            let late_task = order[pos].0;
+            let resources: Vec<Uuid> = late_task.assignments.iter().map(|a| a.resource_id).collect();
+            let shares = |t: &Task| {
+                priority_rank(t.priority) <= priority_rank(late_task.priority)
+                    && t.assignments.iter().any(|a| resources.contains(&a.resource_id))
+            };
+            let Some(ahead) = order[..pos].iter().position(|(t, float)| *float > 0 && shares(t)) else {
+                continue;
+            };
+            let mut trial = order.clone();
+            let task = trial.remove(pos);
+            trial.insert(ahead, task);
+            let trial_moves = schedule(project, &trial, project_end);
+            if overrun(&trial_moves) < overrun(&moves) {
+                order = trial;
+                moves = trial_moves;
+                continue 'improve;
+            }
+        }
+        return moves;
+    }
+}
+
+/// Days the moves slip past their float in total.
+fn overrun(moves: &[LevelingMove]) -> i64 {
+    moves.iter().map(|m| (m.delay_days() - m.float_days).max(0)).sum()
+}
+
+/// Days the links into `task` push it now that its predecessors in
+/// `placed` may have moved. Links the plan already broke before leveling
+/// only count for what they add.
+fn link_push(project: &Project, task: &Task, placed: &HashMap<Uuid, (NaiveDate, NaiveDate)>) -> i64 {
+    project
+        .dependencies
+        .iter()
+        .filter(|d| d.to_task == task.id)
+        .filter_map(|d| {
+            let from = project.tasks.iter().find(|t| t.id == d.from_task)?;
+            let &now = placed.get(&from.id)?;
+            let late = |(start, end): (NaiveDate, NaiveDate)| {
+                let days = match d.kind {
+                    DependencyKind::FinishToStart => end - task.start,
+                    DependencyKind::StartToStart => start - task.start,
+                    DependencyKind::FinishToFinish => end - task.end,
+                    DependencyKind::StartToFinish => start - task.end,
+                };
+                days.num_days() + d.lag_days
+            };
+            Some(late(now) - late((from.start, from.end)).max(0))
+        })
+        .max()
+        .unwrap_or(0)
+        .max(0)
+}
+
+/// Place every task after its predecessors: the tasks in `order` (in that
+/// order) at the first day from their own start that their links allow and
+/// their resources have room; the rest where their links push them.
+fn schedule(project: &Project, order: &[(&Task, i64)], project_end: NaiveDate) -> Vec<LevelingMove> {
+    let rank: HashMap<Uuid, usize> = order.iter().enumerate().map(|(i, (t, _))| (t.id, i)).collect();
+    let mut pending: Vec<&Task> = project.tasks.iter().filter(|t| !t.has_children(&project.tasks)).collect();
+    let leaves: std::collections::HashSet<Uuid> = pending.iter().map(|t| t.id).collect();
+    pending.sort_by_key(|t| rank.get(&t.id).map_or(0, |r| r + 1));
+    let mut placed: HashMap<Uuid, (NaiveDate, NaiveDate)> = HashMap::new();
+    // Booked (start, end, units) intervals per resource.
+    let mut booked: HashMap<Uuid, Vec<(NaiveDate, NaiveDate, f32)>> = HashMap::new();
+    let mut moves = Vec::new();
+    while !pending.is_empty() {
+        let ready = |t: &&Task| {
+            project
+                .dependencies
+                .iter()
+                .filter(|d| d.to_task == t.id && leaves.contains(&d.from_task))
+                .all(|d| placed.contains_key(&d.from_task))
+        };
+        // Links cannot form cycles, but a hand-edited file could; go on anyway.
+        let task = pending.remove(pending.iter().position(ready).unwrap_or(0));
+        let duration = task.end - task.start;
+        let mut start = task.start + chrono::Duration::days(link_push(project, task, &placed));
+        let float_days = match rank.get(&task.id) {
+            Some(&i) => order[i].1,
+            None => total_float(project, task, project_end),
+        };
+        // Step past booked intervals until every assigned resource has room.
+        while rank.contains_key(&task.id) {
+            let end = start + duration;
+            let mut free_from: Option<NaiveDate> = None;
+            for a in &task.assignments {
+                // A task cannot run through its resource's absence; retry
+                // the day after the absence ends.
+                let resource = project.resources.iter().find(|r| r.id == a.resource_id);
+                let away = resource.into_iter().flat_map(|r| &r.absences).filter(|absence| {
+                    let (from, to) = (absence.from.max(start), (absence.to + chrono::Duration::days(1)).min(end));
+                    from.iter_days().take_while(|d| *d < to).any(|d| project.calendar.is_working_day(d))
+                });
+                if let Some(back) = away.map(|absence| absence.to + chrono::Duration::days(1)).max() {
+                    free_from = free_from.max(Some(back));
+                    continue;
+                }
+                let Some(intervals) = booked.get(&a.resource_id) else {
+                    continue;
+                };
+                let mut day = start;
+                while day < end {
+                    let covering = intervals.iter().filter(|(s, e, _)| *s <= day && day < *e);
+                    let load: f32 = covering.clone().map(|(_, _, u)| u).sum();
+                    if load + a.units > 1.0 + f32::EPSILON {
+                        // Retry once the earliest booking covering this day ends.
+                        let next = covering.map(|(_, e, _)| *e).min();
+                        free_from = free_from.max(next);
+                        break;
+                    }
+                    day += chrono::Duration::days(1);
+                }
+            }
+            match free_from {
+                Some(next) if next > start => start = next,
+                _ => break,
+            }
+        }
+        let end = start + duration;
+        if rank.contains_key(&task.id) {
+            for a in &task.assignments {
+                booked.entry(a.resource_id).or_default().push((start, end, a.units));
+            }
+        }
+        placed.insert(task.id, (start, end));
+        if start != task.start {
+            moves.push(LevelingMove {
+                task_id: task.id,
+                from: (task.start, task.end),
+                to: (start, end),
+                float_days,
+                accepted: true,
+            });
+        }
+    }
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::task::Dependency;
+    use crate::model::{Assignment, Resource};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    /// A and B both need the designer from the 4th to the 8th. A has plenty
+    /// of float; B feeds C, so any delay to B moves C and the finish.
+    fn clash(a_priority: TaskPriority, b_priority: TaskPriority) -> (Project, [Uuid; 3]) {
+        let mut project = Project::new("Test");
+        let designer = Resource::new("Designer");
+        let mut a = Task::new("A", date(4), date(8));
+        let mut b = Task::new("B", date(4), date(8));
+        let c = Task::new("C", date(8), date(20));
+        a.priority = a_priority;
+        b.priority = b_priority;
+        a.assignments.push(Assignment::new(designer.id));
+        b.assignments.push(Assignment::new(designer.id));
+        project.dependencies.push(Dependency::new(b.id, c.id, DependencyKind::FinishToStart));
+        let ids = [a.id, b.id, c.id];
+        project.resources.push(designer);
+        project.tasks = vec![a, b, c];
+        (project, ids)
+    }
+
+    #[test]
+    fn delays_within_float_before_pushing_the_finish() {
+        let (project, [a, ..]) = clash(TaskPriority::Medium, TaskPriority::Medium);
+        let moves = level_resources(&project);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].task_id, a);
+        assert!(moves[0].within_float());
+    }
+
+    #[test]
+    fn delays_the_lower_priority_task_and_its_successors() {
+        let (project, [_, b, c]) = clash(TaskPriority::High, TaskPriority::Low);
+        let moves = level_resources(&project);
+        let to = |id| moves.iter().find(|m| m.task_id == id).map(|m| m.to);
+        assert_eq!(moves.len(), 2);
+        assert_eq!(to(b), Some((date(8), date(12))));
+        // C still starts when B finishes.
+        assert_eq!(to(c), Some((date(12), date(24))));
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A named vertical line at a fixed date, such as a code freeze or a
+/// go/no-go meeting. Markers are stored in the project file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateMarker {
+    pub id: Uuid,
+    pub name: String,
+    pub date: NaiveDate,
+}
+
+impl DateMarker {
+    pub fn new(name: impl Into<String>, date: NaiveDate) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            date,
+        }
+    }
+}
+
+/// A named, shaded date range drawn behind the bars, such as a holiday
+/// shutdown or a sprint. `end` is inclusive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateBand {
+    pub id: Uuid,
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateBand {
+    pub fn new(name: impl Into<String>, start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            start,
+            end: end.max(start),
+        }
+    }
+}
+
+/// A free-floating text note on the chart, such as "External audit
+/// window", placed at a date on a task's row (or near the top of the chart)
+/// and optionally pointing an arrow at another date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub id: Uuid,
+    pub text: String,
+    pub date: NaiveDate,
+    /// Task whose row the note sits on; `None` floats it below the header.
+    #[serde(default)]
+    pub task: Option<Uuid>,
+    /// Date the arrow points at, if the note has one.
+    #[serde(default)]
+    pub arrow_to: Option<NaiveDate>,
+    /// Row the arrow points at; `None` keeps it on the note's own row.
+    #[serde(default)]
+    pub arrow_task: Option<Uuid>,
+}
+
+impl Annotation {
+    pub fn new(text: impl Into<String>, date: NaiveDate) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            text: text.into(),
+            date,
+            task: None,
+            arrow_to: None,
+            arrow_task: None,
+        }
+    }
+}
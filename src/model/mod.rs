@@ -1,9 +1,36 @@
+pub mod audit;
+pub mod baseline;
+pub mod benchmark;
+pub mod calendar;
+pub mod command;
+pub mod compare;
+pub mod forecast;
 pub mod history;
+pub mod impact;
+pub mod invariants;
+pub mod journal;
+pub mod lanes;
+pub mod leveling;
+pub mod marker;
+pub mod navigation;
 pub mod project;
+pub mod resource;
+pub mod snapshot;
 pub mod task;
 pub mod timeline;
+pub mod trash;
+pub mod validation;
+pub mod view;
 
+pub use baseline::Baseline;
+pub use calendar::ProjectCalendar;
+pub use command::ProjectCommand;
 pub use history::UndoHistory;
+pub use invariants::validate;
+pub use marker::{Annotation, DateBand, DateMarker};
+pub use navigation::{NavigationHistory, SelectionHistory};
 pub use project::Project;
+pub use resource::{Assignment, Resource};
 pub use task::Task;
-pub use timeline::{TimelineScale, TimelineViewport};
+pub use timeline::{TimelineScale, TimelineViewport, ZoomPreset};
+pub use view::SavedView;
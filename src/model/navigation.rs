@@ -0,0 +1,189 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::timeline::TimelineScale;
+
+const MAX_NAVIGATION: usize = 50;
+/// A viewport must stay unchanged this long (seconds) before it counts as a
+/// place the user has been, so a zoom gesture or smooth scroll is one entry.
+const SETTLE_SECONDS: f64 = 0.5;
+/// Zoom changes smaller than this factor are not recorded.
+const ZOOM_THRESHOLD: f32 = 1.1;
+/// Vertical scrolls shorter than this (pixels) are not recorded.
+const SCROLL_Y_THRESHOLD: f32 = 300.0;
+
+/// Where the chart is looking: zoom, scale and scroll position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavigationState {
+    pub pixels_per_day: f32,
+    pub scale: TimelineScale,
+    /// Date at the left edge of the chart.
+    pub left_date: NaiveDate,
+    pub scroll_y: f32,
+}
+
+impl NavigationState {
+    /// Whether moving from `self` to `other` is a jump worth going back to:
+    /// a scale switch, a zoom, or a scroll of more than half a screen.
+    fn is_jump_to(&self, other: &NavigationState, visible_days: f32) -> bool {
+        let zoom = (self.pixels_per_day / other.pixels_per_day).max(other.pixels_per_day / self.pixels_per_day);
+        let days = (self.left_date - other.left_date).num_days().abs() as f32;
+        self.scale != other.scale
+            || zoom > ZOOM_THRESHOLD
+            || days > visible_days / 2.0
+            || (self.scroll_y - other.scroll_y).abs() > SCROLL_Y_THRESHOLD
+    }
+}
+
+/// Back/forward history of viewport positions, kept apart from the undo
+/// stack so navigating never touches the project.
+#[derive(Default)]
+pub struct NavigationHistory {
+    back: Vec<NavigationState>,
+    forward: Vec<NavigationState>,
+    /// The last position the viewport settled at.
+    current: Option<NavigationState>,
+    /// A position seen recently that has not settled yet, and when it was first seen.
+    pending: Option<(NavigationState, f64)>,
+}
+
+impl NavigationHistory {
+    /// Feed the viewport position of this frame. Once it has stayed put for a
+    /// moment and differs enough from the previous settled position, the
+    /// previous one is pushed onto the back stack.
+    /// Returns true while a position is still settling.
+    pub fn observe(&mut self, state: NavigationState, now: f64, visible_days: f32) -> bool {
+        let Some(current) = self.current else {
+            self.current = Some(state);
+            return false;
+        };
+        if state == current {
+            self.pending = None;
+            return false;
+        }
+        match self.pending {
+            Some((pending, since)) if pending == state => {
+                if now - since < SETTLE_SECONDS {
+                    return true;
+                }
+                if current.is_jump_to(&state, visible_days) {
+                    if self.back.len() >= MAX_NAVIGATION {
+                        self.back.remove(0);
+                    }
+                    self.back.push(current);
+                    self.forward.clear();
+                }
+                self.current = Some(state);
+                self.pending = None;
+                false
+            }
+            _ => {
+                self.pending = Some((state, now));
+                true
+            }
+        }
+    }
+
+    /// Step back; returns the position to restore.
+    pub fn back(&mut self) -> Option<NavigationState> {
+        let target = self.back.pop()?;
+        if let Some(current) = self.current {
+            self.forward.push(current);
+        }
+        self.current = Some(target);
+        self.pending = None;
+        Some(target)
+    }
+
+    /// Step forward again; returns the position to restore.
+    pub fn forward(&mut self) -> Option<NavigationState> {
+        let target = self.forward.pop()?;
+        if let Some(current) = self.current {
+            self.back.push(current);
+        }
+        self.current = Some(target);
+        self.pending = None;
+        Some(target)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Back/forward history of selected tasks, for hopping between tasks far
+/// apart. Clearing the selection is not a step, so going back right after
+/// it selects the same task again.
+#[derive(Default)]
+pub struct SelectionHistory {
+    back: Vec<Uuid>,
+    forward: Vec<Uuid>,
+    /// The task selected last.
+    current: Option<Uuid>,
+}
+
+impl SelectionHistory {
+    /// Feed this frame's selection.
+    pub fn observe(&mut self, selected: Option<Uuid>) {
+        let Some(selected) = selected else { return };
+        if self.current == Some(selected) {
+            return;
+        }
+        if let Some(current) = self.current {
+            if self.back.len() >= MAX_NAVIGATION {
+                self.back.remove(0);
+            }
+            self.back.push(current);
+        }
+        self.forward.clear();
+        self.current = Some(selected);
+    }
+
+    /// Step back, skipping tasks for which `exists` is false; returns the
+    /// task to select. With nothing selected, that is the last selected task.
+    pub fn back(&mut self, selected: Option<Uuid>, exists: impl Fn(Uuid) -> bool) -> Option<Uuid> {
+        if selected.is_none() {
+            return self.current.filter(|id| exists(*id));
+        }
+        while let Some(target) = self.back.pop() {
+            if exists(target) {
+                self.forward.extend(self.current);
+                self.current = Some(target);
+                return Some(target);
+            }
+        }
+        None
+    }
+
+    /// Step forward again; returns the task to select.
+    pub fn forward(&mut self, exists: impl Fn(Uuid) -> bool) -> Option<Uuid> {
+        while let Some(target) = self.forward.pop() {
+            if exists(target) {
+                self.back.extend(self.current);
+                self.current = Some(target);
+                return Some(target);
+            }
+        }
+        None
+    }
+
+    pub fn can_go_back(&self, selected: Option<Uuid>) -> bool {
+        !self.back.is_empty() || (selected.is_none() && self.current.is_some())
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -1,7 +1,72 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::task::{Dependency, Task};
+use super::audit::AuditEntry;
+use super::baseline::Baseline;
+use super::calendar::ProjectCalendar;
+use super::marker::{Annotation, DateBand, DateMarker};
+use super::resource::Resource;
+use super::task::{Dependency, DependencyKind, Task};
+use super::trash::TrashEntry;
+use super::view::SavedView;
+
+/// The week a template's plan starts in, so templates line up however old
+/// the project they were made from.
+pub const TEMPLATE_DAY_ZERO: &str = "2001-01-01";
+
+/// Where a moved task lands relative to the row it is dropped on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DropPlace {
+    /// Just above, under the same parent.
+    Before,
+    /// As the last child.
+    Into,
+    /// Just below (after its subtasks), under the same parent.
+    After,
+}
+
+/// Tasks copied with fresh ids, ready to be inserted into a project.
+#[derive(Debug, Clone, Default)]
+pub struct ClonedTasks {
+    pub tasks: Vec<Task>,
+    /// Links between the copied tasks; links to tasks outside the copy are
+    /// dropped.
+    pub dependencies: Vec<Dependency>,
+    /// Original id → new id.
+    pub id_map: std::collections::HashMap<uuid::Uuid, uuid::Uuid>,
+}
+
+impl ClonedTasks {
+    /// Give `tasks` fresh ids and shift their dates by `offset_days`. Parent
+    /// links inside the set follow the new ids; a parent outside the set is
+    /// kept as is, so the copy can sit next to its original.
+    pub fn new(tasks: &[&Task], dependencies: &[Dependency], offset_days: i64) -> Self {
+        let id_map: std::collections::HashMap<uuid::Uuid, uuid::Uuid> =
+            tasks.iter().map(|t| (t.id, uuid::Uuid::new_v4())).collect();
+        let offset = chrono::Duration::days(offset_days);
+        let tasks = tasks
+            .iter()
+            .map(|t| {
+                let mut copy = (*t).clone();
+                copy.id = id_map[&t.id];
+                copy.start = t.start + offset;
+                copy.end = t.end + offset;
+                copy.parent_id = t.parent_id.map(|p| id_map.get(&p).copied().unwrap_or(p));
+                copy
+            })
+            .collect();
+        let dependencies = dependencies
+            .iter()
+            .filter_map(|d| {
+                let mut copy = d.clone();
+                copy.from_task = *id_map.get(&d.from_task)?;
+                copy.to_task = *id_map.get(&d.to_task)?;
+                Some(copy)
+            })
+            .collect();
+        Self { tasks, dependencies, id_map }
+    }
+}
 
 /// A Gantt project containing tasks, dependencies, and metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,8 +75,57 @@ pub struct Project {
     #[serde(default = "default_version")]
     pub version: u32,
     pub name: String,
+    /// Project description / notes (Markdown).
+    #[serde(default)]
+    pub description: String,
     pub tasks: Vec<Task>,
     pub dependencies: Vec<Dependency>,
+    /// Currency code or symbol used for resource rates and costs.
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// People or teams that tasks can be assigned to.
+    #[serde(default)]
+    pub resources: Vec<Resource>,
+    /// Snapshot of planned dates used for slippage markers.
+    #[serde(default)]
+    pub baseline: Option<Baseline>,
+    /// Fiscal year and week-numbering conventions.
+    #[serde(default)]
+    pub calendar: ProjectCalendar,
+    /// Count tentative tasks in rollups, costs, exports and critical links.
+    #[serde(default)]
+    pub include_tentative: bool,
+    /// Named views (zoom, scroll and filters) selectable from the Views menu.
+    #[serde(default)]
+    pub views: Vec<SavedView>,
+    /// Scale and zoom to open the project at, instead of the app's default.
+    #[serde(default)]
+    pub default_zoom: Option<super::timeline::DefaultZoom>,
+    /// Tasks pinned to the "Watching" strip, in the order they were pinned.
+    #[serde(default)]
+    pub watch_list: Vec<uuid::Uuid>,
+    /// Named vertical marker lines (code freeze, go/no-go, …).
+    #[serde(default)]
+    pub markers: Vec<DateMarker>,
+    /// Named shaded date ranges (holiday shutdown, sprints, …).
+    #[serde(default)]
+    pub bands: Vec<DateBand>,
+    /// Free-floating text notes and arrows on the chart.
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    /// Deleted tasks that can still be restored, newest last.
+    #[serde(default)]
+    pub trash: Vec<TrashEntry>,
+    /// Per-task history of dates and progress, one entry per saved change.
+    #[serde(default)]
+    pub audit: Vec<AuditEntry>,
+    /// Task dates as last saved each week, oldest first (when enabled).
+    #[serde(default)]
+    pub snapshots: Vec<super::snapshot::WeeklySnapshot>,
+    /// Last journal entry merged from each user when syncing over a shared
+    /// folder (see `io::sync`).
+    #[serde(default)]
+    pub sync_seen: std::collections::BTreeMap<String, u64>,
     pub created: DateTime<Utc>,
     pub modified: DateTime<Utc>,
 }
@@ -20,13 +134,33 @@ fn default_version() -> u32 {
     1
 }
 
+fn default_currency() -> String {
+    "USD".to_string()
+}
+
 impl Default for Project {
     fn default() -> Self {
         Self {
             version: 2,
             name: "Untitled Project".to_string(),
+            description: String::new(),
             tasks: Vec::new(),
             dependencies: Vec::new(),
+            currency: default_currency(),
+            resources: Vec::new(),
+            baseline: None,
+            calendar: ProjectCalendar::default(),
+            include_tentative: false,
+            views: Vec::new(),
+            default_zoom: None,
+            watch_list: Vec::new(),
+            markers: Vec::new(),
+            bands: Vec::new(),
+            annotations: Vec::new(),
+            trash: Vec::new(),
+            audit: Vec::new(),
+            snapshots: Vec::new(),
+            sync_seen: Default::default(),
             created: Utc::now(),
             modified: Utc::now(),
         }
@@ -46,23 +180,77 @@ impl Project {
         self.modified = Utc::now();
     }
 
-    /// Recalculate every parent task's start/end/progress from its children.
-    /// Call after any mutation that may change child dates or progress.
+    /// Recalculate every parent task's start/end/progress from its children,
+    /// and move auto-dated milestones onto their predecessors' latest finish.
+    /// Call after any mutation that may change dates, progress or links.
     pub fn recalculate_parent_dates(&mut self) {
-        // Collect parent IDs that have children.
-        let parent_ids: Vec<uuid::Uuid> = self
+        self.rollup_parents();
+        if self.track_predecessor_milestones() {
+            self.rollup_parents();
+        }
+    }
+
+    /// Re-estimate a task's remaining hours from its progress, e.g. after
+    /// the progress changed. Parents and milestones carry no estimate.
+    pub fn track_remaining(&mut self, id: uuid::Uuid) {
+        if self.tasks.iter().any(|t| t.parent_id == Some(id)) {
+            return;
+        }
+        let hours_per_day = self.calendar.hours_per_day;
+        let Some(task) = self.tasks.iter_mut().find(|t| t.id == id && !t.is_milestone) else {
+            return;
+        };
+        let planned = task.planned_hours(&self.resources, hours_per_day);
+        task.remaining_hours = Some((planned * (1.0 - task.progress)).max(0.0));
+    }
+
+    /// When the task would finish if its remaining hours were worked from
+    /// `today` (or its start, if later) at the assigned units on working
+    /// days. `None` without an estimate or when nothing is left.
+    pub fn estimated_finish(&self, task: &Task, today: NaiveDate) -> Option<NaiveDate> {
+        let remaining = task.remaining_hours.filter(|h| *h > 0.0 && !task.is_milestone && task.progress < 1.0)?;
+        let units = task.delivered_units(&self.resources);
+        let per_day = self.calendar.hours_per_day * if units > 0.0 { units } else { 1.0 };
+        let days = (remaining / per_day.max(0.1)).ceil() as i64;
+        // The last day worked, counting the first day if it is a working day;
+        // the finish is the day after, like task ends.
+        let first = today.max(task.start);
+        let last = self.calendar.add_working_days(first - chrono::Duration::days(1), days);
+        Some(last + chrono::Duration::days(1))
+    }
+
+    /// Whether `task` counts towards rollups, costs and exports: always for
+    /// committed work, and for tentative work only when it is included.
+    pub fn is_counted(&self, task: &Task) -> bool {
+        !task.tentative || self.include_tentative
+    }
+
+    fn rollup_parents(&mut self) {
+        // Collect parent IDs that have children, deepest first so nested
+        // phases see their sub-phases' updated dates.
+        let mut parent_ids: Vec<uuid::Uuid> = self
             .tasks
             .iter()
             .filter_map(|t| t.parent_id)
             .collect::<std::collections::HashSet<_>>()
             .into_iter()
             .collect();
+        let depth = |id: uuid::Uuid| {
+            let mut depth = 0;
+            let mut parent = self.tasks.iter().find(|t| t.id == id).and_then(|t| t.parent_id);
+            while let Some(p) = parent.filter(|_| depth < self.tasks.len()) {
+                depth += 1;
+                parent = self.tasks.iter().find(|t| t.id == p).and_then(|t| t.parent_id);
+            }
+            depth
+        };
+        parent_ids.sort_by_cached_key(|id| std::cmp::Reverse(depth(*id)));
 
         for pid in parent_ids {
             let children: Vec<_> = self
                 .tasks
                 .iter()
-                .filter(|t| t.parent_id == Some(pid))
+                .filter(|t| t.parent_id == Some(pid) && self.is_counted(t))
                 .cloned()
                 .collect();
 
@@ -72,50 +260,1042 @@ impl Project {
 
             let new_start = children.iter().map(|t| t.start).min().unwrap();
             let new_end   = children.iter().map(|t| t.end).max().unwrap();
-            let new_prog  = children.iter().map(|t| t.progress).sum::<f32>()
-                / children.len() as f32;
+            let weight    = children.iter().map(|t| t.rollup_weight()).sum::<f32>();
+            // A phase of only milestones progresses by the share achieved.
+            let new_prog  = if weight > 0.0 {
+                children.iter().map(|t| t.progress * t.rollup_weight()).sum::<f32>() / weight
+            } else {
+                children.iter().map(|t| t.progress).sum::<f32>() / children.len() as f32
+            };
 
             if let Some(parent) = self.tasks.iter_mut().find(|t| t.id == pid) {
                 parent.start    = new_start;
                 parent.end      = new_end;
-                parent.progress = new_prog;
+                parent.progress = parent.progress_override.unwrap_or(new_prog);
+            }
+        }
+    }
+
+    /// Move every milestone with `auto_date` to the latest date its incoming
+    /// links require (finish for FS/FF, start for SS/SF, plus lag). Repeats until stable
+    /// so chains of auto milestones settle. Returns true if any date changed.
+    fn track_predecessor_milestones(&mut self) -> bool {
+        let mut any_changed = false;
+        for _ in 0..self.tasks.len().max(1) {
+            let mut changed = false;
+            for i in 0..self.tasks.len() {
+                if !(self.tasks[i].is_milestone && self.tasks[i].auto_date) {
+                    continue;
+                }
+                let id = self.tasks[i].id;
+                let latest = self
+                    .dependencies
+                    .iter()
+                    .filter(|d| d.to_task == id)
+                    .filter_map(|d| {
+                        let pred = self.tasks.iter().find(|t| t.id == d.from_task)?;
+                        let anchor = match d.kind {
+                            DependencyKind::FinishToStart | DependencyKind::FinishToFinish => pred.end,
+                            DependencyKind::StartToStart | DependencyKind::StartToFinish => pred.start,
+                        };
+                        Some(anchor + chrono::Duration::days(d.lag_days))
+                    })
+                    .max();
+                if let Some(date) = latest {
+                    let task = &mut self.tasks[i];
+                    if task.start != date || task.end != date {
+                        task.start = date;
+                        task.end = date;
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+            any_changed = true;
+        }
+        any_changed
+    }
+
+    /// Every milestone, and whether it has predecessors and all of them are
+    /// done. Milestones without incoming links are never ready.
+    pub fn milestone_readiness(&self) -> std::collections::HashMap<uuid::Uuid, bool> {
+        self.tasks
+            .iter()
+            .filter(|t| t.is_milestone)
+            .map(|milestone| {
+                let mut predecessors = self
+                    .dependencies
+                    .iter()
+                    .filter(|d| d.to_task == milestone.id)
+                    .map(|d| self.tasks.iter().find(|t| t.id == d.from_task))
+                    .peekable();
+                let ready = predecessors.peek().is_some()
+                    && predecessors.all(|p| p.is_some_and(|p| p.progress >= 1.0));
+                (milestone.id, ready)
+            })
+            .collect()
+    }
+
+    /// Turn a regular task into a milestone on its finish date, so links into
+    /// and out of it keep pointing at the same point in time. Parent tasks
+    /// cannot be converted. Returns false if nothing changed.
+    pub fn convert_to_milestone(&mut self, id: uuid::Uuid) -> bool {
+        if self.tasks.iter().any(|t| t.parent_id == Some(id)) {
+            return false;
+        }
+        let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) else {
+            return false;
+        };
+        if task.is_milestone {
+            return false;
+        }
+        task.is_milestone = true;
+        task.start = task.end;
+        task.set_achieved(task.progress >= 1.0);
+        self.recalculate_parent_dates();
+        true
+    }
+
+    /// Add a milestone marking the end of the phase `parent_id`, placed
+    /// right after the phase and linked from it with `auto_date`, so it
+    /// follows the phase's finish as its subtasks move. Returns its id.
+    pub fn add_completion_milestone(&mut self, parent_id: uuid::Uuid) -> Option<uuid::Uuid> {
+        let parent = self.tasks.iter().find(|t| t.id == parent_id)?;
+        let mut milestone = Task::new_milestone(format!("{} complete", parent.name), parent.end);
+        milestone.parent_id = parent.parent_id;
+        milestone.color = parent.color;
+        milestone.auto_date = true;
+        let id = milestone.id;
+        let subtree = self.subtree_ids(parent_id);
+        let index = self.tasks.iter().rposition(|t| subtree.contains(&t.id)).map_or(self.tasks.len(), |i| i + 1);
+        self.tasks.insert(index, milestone);
+        self.dependencies.push(Dependency::new(parent_id, id, DependencyKind::FinishToStart));
+        self.recalculate_parent_dates();
+        Some(id)
+    }
+
+    /// Turn a milestone into a task lasting `duration_days`, ending on the
+    /// milestone date so its successors stay satisfied. Returns false if
+    /// nothing changed.
+    pub fn convert_to_task(&mut self, id: uuid::Uuid, duration_days: i64) -> bool {
+        let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) else {
+            return false;
+        };
+        if !task.is_milestone {
+            return false;
+        }
+        task.is_milestone = false;
+        task.start = task.end - chrono::Duration::days(duration_days.max(1));
+        self.recalculate_parent_dates();
+        true
+    }
+
+    /// Remove the given tasks and every link touching them, keeping them in
+    /// the trash as one entry.
+    pub fn move_to_trash(&mut self, ids: &[uuid::Uuid]) {
+        let touches = |d: &Dependency| ids.contains(&d.from_task) || ids.contains(&d.to_task);
+        let tasks: Vec<Task> = self.tasks.iter().filter(|t| ids.contains(&t.id)).cloned().collect();
+        if tasks.is_empty() {
+            return;
+        }
+        let dependencies: Vec<Dependency> = self.dependencies.iter().filter(|d| touches(d)).cloned().collect();
+        self.tasks.retain(|t| !ids.contains(&t.id));
+        self.dependencies.retain(|d| !touches(d));
+        self.trash.push(TrashEntry {
+            id: uuid::Uuid::new_v4(),
+            tasks,
+            dependencies,
+            deleted_at: Utc::now(),
+        });
+    }
+
+    /// Put a trash entry's tasks back. Tasks whose parent no longer exists
+    /// become top-level; links are restored where both ends exist. Tasks
+    /// already back in the project (e.g. after an undo) are skipped.
+    /// Returns the number of tasks restored.
+    pub fn restore_from_trash(&mut self, entry_id: uuid::Uuid) -> Option<usize> {
+        let index = self.trash.iter().position(|e| e.id == entry_id)?;
+        let entry = self.trash.remove(index);
+        let mut restored = 0;
+        for mut task in entry.tasks {
+            if self.tasks.iter().any(|t| t.id == task.id) {
+                continue;
+            }
+            if let Some(pid) = task.parent_id {
+                if !self.tasks.iter().any(|t| t.id == pid) {
+                    task.parent_id = None;
+                }
+            }
+            // Back under its parent when it has one, otherwise at the end.
+            let pos = task
+                .parent_id
+                .and_then(|pid| self.tasks.iter().rposition(|t| t.id == pid || t.parent_id == Some(pid)))
+                .map(|p| p + 1)
+                .unwrap_or(self.tasks.len());
+            self.tasks.insert(pos, task);
+            restored += 1;
+        }
+        for dep in entry.dependencies {
+            let ends_exist = [dep.from_task, dep.to_task].iter().all(|id| self.tasks.iter().any(|t| t.id == *id));
+            let duplicate = self.dependencies.iter().any(|d| d.from_task == dep.from_task && d.to_task == dep.to_task);
+            if ends_exist && !duplicate {
+                self.dependencies.push(dep);
+            }
+        }
+        self.recalculate_parent_dates();
+        Some(restored)
+    }
+
+    /// Drop trash entries deleted more than `retention_days` ago. Returns the
+    /// number of entries dropped.
+    pub fn purge_trash(&mut self, retention_days: u32, now: DateTime<Utc>) -> usize {
+        let before = self.trash.len();
+        let cutoff = now - chrono::Duration::days(retention_days as i64);
+        self.trash.retain(|e| e.deleted_at >= cutoff);
+        before - self.trash.len()
+    }
+
+    /// Whether `ancestor` is a parent, grandparent, … of `id`.
+    pub fn is_ancestor(&self, ancestor: uuid::Uuid, id: uuid::Uuid) -> bool {
+        let mut current = self.tasks.iter().find(|t| t.id == id).and_then(|t| t.parent_id);
+        while let Some(pid) = current {
+            if pid == ancestor {
+                return true;
             }
+            current = self.tasks.iter().find(|t| t.id == pid).and_then(|t| t.parent_id);
         }
+        false
     }
 
-    /// Re-order tasks so every parent is immediately followed by its children.
-    /// Top-level tasks keep their relative order; children keep their relative
-    /// order within each group.
-    pub fn sort_tasks_grouped(&mut self) {
-        let mut result: Vec<super::task::Task> = Vec::with_capacity(self.tasks.len());
-        // Separate top-level items preserving their order.
-        let top_level: Vec<_> = self
+    /// Check that a link `from` → `to` may be added: not to itself, not a
+    /// duplicate, not between a task and its own ancestor (its dates roll up
+    /// from the other end) and not closing a cycle. Links may cross phases.
+    pub fn check_link(&self, from: uuid::Uuid, to: uuid::Uuid) -> Result<(), String> {
+        if from == to {
+            return Err("A task cannot depend on itself".to_string());
+        }
+        if self.dependencies.iter().any(|d| d.from_task == from && d.to_task == to) {
+            return Err("These tasks are already linked".to_string());
+        }
+        if self.is_ancestor(from, to) || self.is_ancestor(to, from) {
+            return Err("A task cannot be linked to its own parent phase".to_string());
+        }
+        // A path to -> … -> from would close a loop.
+        let mut stack = vec![to];
+        let mut seen = std::collections::HashSet::new();
+        while let Some(id) = stack.pop() {
+            if id == from {
+                return Err("This link would create a circular dependency".to_string());
+            }
+            if seen.insert(id) {
+                stack.extend(self.dependencies.iter().filter(|d| d.from_task == id).map(|d| d.to_task));
+            }
+        }
+        Ok(())
+    }
+
+    /// Move `id` and its subtasks next to or into `target`, reparenting it
+    /// to match. The moved block keeps its internal order.
+    pub fn move_task(&mut self, id: uuid::Uuid, target: uuid::Uuid, place: DropPlace) -> Result<(), String> {
+        if id == target || self.is_ancestor(id, target) {
+            return Err("A task cannot be moved into its own subtasks".to_string());
+        }
+        let target_task = self.tasks.iter().find(|t| t.id == target).ok_or("Drop target not found")?;
+        let new_parent = match place {
+            DropPlace::Into if target_task.is_milestone => return Err("Milestones cannot have subtasks".to_string()),
+            DropPlace::Into => Some(target),
+            DropPlace::Before | DropPlace::After => target_task.parent_id,
+        };
+        let ids = self.subtree_ids(id);
+        // Links between a task and its ancestors are not allowed, so neither
+        // is a move that would create one.
+        let mut ancestors = Vec::new();
+        let mut current = new_parent;
+        while let Some(pid) = current {
+            ancestors.push(pid);
+            current = self.tasks.iter().find(|t| t.id == pid).and_then(|t| t.parent_id);
+        }
+        let crosses = |a: uuid::Uuid, b: uuid::Uuid| ids.contains(&a) && ancestors.contains(&b);
+        if self.dependencies.iter().any(|d| crosses(d.from_task, d.to_task) || crosses(d.to_task, d.from_task)) {
+            return Err("The task is linked to its new parent; remove the link first".to_string());
+        }
+        let (mut moved, rest): (Vec<Task>, Vec<Task>) =
+            std::mem::take(&mut self.tasks).into_iter().partition(|t| ids.contains(&t.id));
+        self.tasks = rest;
+        if let Some(task) = moved.iter_mut().find(|t| t.id == id) {
+            task.parent_id = new_parent;
+        }
+        let index = match place {
+            DropPlace::Before => self.tasks.iter().position(|t| t.id == target),
+            DropPlace::Into | DropPlace::After => {
+                let target_ids = self.subtree_ids(target);
+                self.tasks.iter().rposition(|t| target_ids.contains(&t.id)).map(|i| i + 1)
+            }
+        }
+        .unwrap_or(self.tasks.len());
+        self.tasks.splice(index..index, moved);
+        self.recalculate_parent_dates();
+        Ok(())
+    }
+
+    /// `root_id` and all of its descendants, parents before their children.
+    pub fn subtree_ids(&self, root_id: uuid::Uuid) -> Vec<uuid::Uuid> {
+        let mut subtree: Vec<uuid::Uuid> = vec![root_id];
+        let mut i = 0;
+        while i < subtree.len() {
+            let pid = subtree[i];
+            subtree.extend(self.tasks.iter().filter(|t| t.parent_id == Some(pid)).map(|t| t.id));
+            i += 1;
+        }
+        subtree
+    }
+
+    /// A standalone project holding `root_id` and its descendants, with the
+    /// dependencies between them and the resources they use. The root
+    /// becomes a top-level task; ids are kept so the parts can be matched
+    /// up again later.
+    pub fn extract_subtree(&self, root_id: uuid::Uuid) -> Option<Project> {
+        let root = self.tasks.iter().find(|t| t.id == root_id)?;
+        let ids = self.subtree_ids(root_id);
+        let tasks: Vec<Task> = self
             .tasks
             .iter()
-            .filter(|t| t.parent_id.is_none())
+            .filter(|t| ids.contains(&t.id))
+            .map(|t| {
+                let mut copy = t.clone();
+                if t.id == root_id {
+                    copy.parent_id = None;
+                }
+                copy
+            })
+            .collect();
+        let dependencies = self
+            .dependencies
+            .iter()
+            .filter(|d| ids.contains(&d.from_task) && ids.contains(&d.to_task))
+            .cloned()
+            .collect();
+        let resources = self
+            .resources
+            .iter()
+            .filter(|r| tasks.iter().any(|t| t.assignments.iter().any(|a| a.resource_id == r.id)))
+            .cloned()
+            .collect();
+        let mut part = Project::new(root.name.clone());
+        part.tasks = tasks;
+        part.dependencies = dependencies;
+        part.resources = resources;
+        part.currency = self.currency.clone();
+        part.calendar = self.calendar;
+        part.include_tentative = self.include_tentative;
+        part.markers = self.markers.clone();
+        part.bands = self.bands.clone();
+        part.annotations = self
+            .annotations
+            .iter()
+            .filter(|a| a.task.is_none_or(|id| part.tasks.iter().any(|t| t.id == id)))
             .cloned()
             .collect();
+        Some(part)
+    }
 
-        for parent in top_level {
-            let pid = parent.id;
-            result.push(parent);
-            // Append children in their current relative order.
-            let children: Vec<_> = self
-                .tasks
+    /// Copy a task and all of its descendants with fresh ids, shifting every
+    /// date by `offset_days`. The copied root keeps its parent; links between
+    /// tasks inside the subtree are copied and links to outside tasks are
+    /// dropped. Nothing is added to the project.
+    pub fn clone_subtree(&self, root_id: uuid::Uuid, offset_days: i64) -> Option<ClonedTasks> {
+        self.tasks.iter().find(|t| t.id == root_id)?;
+        let subtree = self.subtree_ids(root_id);
+        let tasks: Vec<&Task> = self.tasks.iter().filter(|t| subtree.contains(&t.id)).collect();
+        Some(ClonedTasks::new(&tasks, &self.dependencies, offset_days))
+    }
+
+    /// Copy a task and all of its descendants, shifting every date by
+    /// `offset_days` (see [`Self::clone_subtree`]). The copy is inserted right
+    /// after the original group. Returns the id of the new root task.
+    pub fn duplicate_subtree(&mut self, root_id: uuid::Uuid, offset_days: i64) -> Option<uuid::Uuid> {
+        let mut cloned = self.clone_subtree(root_id, offset_days)?;
+        let new_root = cloned.id_map[&root_id];
+        if let Some(root) = cloned.tasks.iter_mut().find(|t| t.id == new_root) {
+            root.name = format!("{} (copy)", root.name);
+        }
+
+        // Insert after the last task belonging to the original subtree.
+        let subtree = self.subtree_ids(root_id);
+        let insert_at = self
+            .tasks
+            .iter()
+            .rposition(|t| subtree.contains(&t.id))
+            .map_or(self.tasks.len(), |i| i + 1);
+        self.tasks.splice(insert_at..insert_at, cloned.tasks);
+        self.dependencies.extend(cloned.dependencies);
+        self.recalculate_parent_dates();
+        Some(new_root)
+    }
+
+    /// New names for `ids` (in the given order) under a batch-rename
+    /// `pattern`, numbered from `start`; see [`expand_rename_pattern`].
+    /// Returns `(id, old name, new name)` for each task found.
+    pub fn batch_rename_preview(
+        &self,
+        ids: &[uuid::Uuid],
+        pattern: &str,
+        start: u32,
+        digits: usize,
+    ) -> Vec<(uuid::Uuid, String, String)> {
+        ids.iter()
+            .filter_map(|id| self.tasks.iter().find(|t| t.id == *id))
+            .enumerate()
+            .map(|(i, t)| {
+                let parent = t
+                    .parent_id
+                    .and_then(|pid| self.tasks.iter().find(|p| p.id == pid))
+                    .map(|p| p.name.as_str())
+                    .unwrap_or("");
+                let new_name = expand_rename_pattern(pattern, start + i as u32, digits, &t.name, parent);
+                (t.id, t.name.clone(), new_name)
+            })
+            .collect()
+    }
+
+    /// Apply [`Self::batch_rename_preview`]. Returns the number of tasks
+    /// whose name changed.
+    pub fn batch_rename(&mut self, ids: &[uuid::Uuid], pattern: &str, start: u32, digits: usize) -> usize {
+        let mut renamed = 0;
+        for (id, old, new) in self.batch_rename_preview(ids, pattern, start, digits) {
+            if old != new {
+                if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                    task.name = new;
+                    renamed += 1;
+                }
+            }
+        }
+        renamed
+    }
+
+    /// Insert all tasks of `other` under `parent_id`, shifting their dates by
+    /// `offset_days`. Imported tasks get fresh ids; their hierarchy and the
+    /// links between them are kept, and top-level tasks become children of
+    /// the parent. Resources are matched by name, or added when missing.
+    /// Returns the number of tasks imported.
+    pub fn import_tasks(&mut self, parent_id: uuid::Uuid, other: &Project, offset_days: i64) -> usize {
+        if !self.tasks.iter().any(|t| t.id == parent_id) || other.tasks.is_empty() {
+            return 0;
+        }
+        let resource_map = self.merge_resources(&other.resources);
+        let all: Vec<&Task> = other.tasks.iter().collect();
+        let mut cloned = ClonedTasks::new(&all, &other.dependencies, offset_days);
+        let new_ids: std::collections::HashSet<uuid::Uuid> = cloned.id_map.values().copied().collect();
+        for task in &mut cloned.tasks {
+            task.parent_id = Some(task.parent_id.filter(|p| new_ids.contains(p)).unwrap_or(parent_id));
+            task.assignments.retain(|a| resource_map.contains_key(&a.resource_id));
+            for a in &mut task.assignments {
+                a.resource_id = resource_map[&a.resource_id];
+            }
+        }
+        let count = cloned.tasks.len();
+
+        // Insert after the last task currently under the parent.
+        let subtree = self.subtree_ids(parent_id);
+        let insert_at = self
+            .tasks
+            .iter()
+            .rposition(|t| subtree.contains(&t.id))
+            .map_or(self.tasks.len(), |i| i + 1);
+        self.tasks.splice(insert_at..insert_at, cloned.tasks);
+        // The other file may hold links this one refuses, such as a cycle.
+        for dep in cloned.dependencies {
+            match self.check_link(dep.from_task, dep.to_task) {
+                Ok(()) => self.dependencies.push(dep),
+                Err(e) => tracing::warn!("Skipping imported link: {}", e),
+            }
+        }
+        self.recalculate_parent_dates();
+        count
+    }
+
+    /// Match `resources` from another file to this project's by name, adding
+    /// the ones it lacks. Returns their ids here by their ids there.
+    fn merge_resources(&mut self, resources: &[Resource]) -> std::collections::HashMap<uuid::Uuid, uuid::Uuid> {
+        let mut resource_map = std::collections::HashMap::new();
+        for resource in resources {
+            let id = match self.resources.iter().find(|r| r.name.eq_ignore_ascii_case(&resource.name)) {
+                Some(existing) => existing.id,
+                None => {
+                    let mut copy = resource.clone();
+                    copy.id = uuid::Uuid::new_v4();
+                    self.resources.push(copy.clone());
+                    copy.id
+                }
+            };
+            resource_map.insert(resource.id, id);
+        }
+        resource_map
+    }
+
+    /// Point the assignments of `tasks`, made against `resources`, at this
+    /// project's resources of the same names, adding any it lacks.
+    pub fn adopt_assignments(&mut self, resources: &[Resource], tasks: &mut [Task]) {
+        let resource_map = self.merge_resources(resources);
+        for task in tasks {
+            task.assignments.retain(|a| resource_map.contains_key(&a.resource_id));
+            for a in &mut task.assignments {
+                a.resource_id = resource_map[&a.resource_id];
+            }
+        }
+    }
+
+    /// Move `date` by `days`, counting only working days when `working`.
+    fn offset_date(&self, date: NaiveDate, days: i64, working: bool) -> NaiveDate {
+        if working {
+            self.calendar.add_working_days(date, days)
+        } else {
+            date + chrono::Duration::days(days)
+        }
+    }
+
+    /// The given ids minus summary tasks, whose dates roll up from their
+    /// children.
+    fn leaf_ids(&self, ids: &[uuid::Uuid]) -> Vec<uuid::Uuid> {
+        ids.iter()
+            .copied()
+            .filter(|id| !self.tasks.iter().any(|t| t.parent_id == Some(*id)))
+            .collect()
+    }
+
+    /// Shift the given tasks by `days` (calendar or working days). Summary
+    /// tasks are skipped. Returns the number of tasks moved.
+    pub fn shift_tasks(&mut self, ids: &[uuid::Uuid], days: i64, working: bool) -> usize {
+        let leaves = self.leaf_ids(ids);
+        let moved: Vec<(uuid::Uuid, NaiveDate, NaiveDate)> = self
+            .tasks
+            .iter()
+            .filter(|t| leaves.contains(&t.id))
+            .map(|t| (t.id, self.offset_date(t.start, days, working), self.offset_date(t.end, days, working)))
+            .collect();
+        for (id, start, end) in &moved {
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.id == *id) {
+                task.start = *start;
+                task.end = *end;
+            }
+        }
+        self.recalculate_parent_dates();
+        moved.len()
+    }
+
+    /// Move every date in the plan by `days`: tasks, markers, bands and
+    /// notes.
+    pub fn shift_all_dates(&mut self, days: i64) {
+        let shift = chrono::Duration::days(days);
+        for task in &mut self.tasks {
+            task.start += shift;
+            task.end += shift;
+        }
+        for marker in &mut self.markers {
+            marker.date += shift;
+        }
+        for band in &mut self.bands {
+            band.start += shift;
+            band.end += shift;
+        }
+        for note in &mut self.annotations {
+            note.date += shift;
+            note.arrow_to = note.arrow_to.map(|d| d + shift);
+        }
+    }
+
+    /// A copy to start new projects from: no progress, estimates, issue
+    /// status, baseline or history, and the earliest task moved into the
+    /// week of [`TEMPLATE_DAY_ZERO`]. Whole weeks are moved so every task
+    /// keeps its weekday, and with it its working days.
+    pub fn to_template(&self) -> Project {
+        let mut template = Project {
+            baseline: None,
+            trash: Vec::new(),
+            audit: Vec::new(),
+            snapshots: Vec::new(),
+            sync_seen: Default::default(),
+            created: Utc::now(),
+            modified: Utc::now(),
+            ..self.clone()
+        };
+        for task in &mut template.tasks {
+            task.progress = 0.0;
+            task.progress_override = None;
+            task.remaining_hours = None;
+            task.link_status = None;
+            task.last_synced = None;
+        }
+        template.move_to_week_of(NaiveDate::parse_from_str(TEMPLATE_DAY_ZERO, "%Y-%m-%d").unwrap_or_default());
+        template.recalculate_parent_dates();
+        template
+    }
+
+    /// Shift the plan by whole weeks so its earliest task starts in the week
+    /// of `date`, each task on the same weekday as before.
+    pub fn move_to_week_of(&mut self, date: NaiveDate) {
+        if let Some(earliest) = self.tasks.iter().map(|t| t.start).min() {
+            self.shift_all_dates((self.calendar.week_start(date) - self.calendar.week_start(earliest)).num_days());
+        }
+    }
+
+    /// After task `id`'s start and finish moved by `start_days` and
+    /// `end_days`, move its successors, and theirs down the chain, so every
+    /// link keeps the gap it had: finish links follow the finish, start
+    /// links the start. Each task moves once. Returns the number moved.
+    pub fn keep_link_gaps(&mut self, id: uuid::Uuid, start_days: i64, end_days: i64) -> usize {
+        let mut seen = self.subtree_ids(id);
+        let mut queue = std::collections::VecDeque::from([(id, start_days, end_days)]);
+        let mut moved = 0;
+        while let Some((from, start_days, end_days)) = queue.pop_front() {
+            let next: Vec<(uuid::Uuid, i64)> = self
+                .dependencies
                 .iter()
-                .filter(|t| t.parent_id == Some(pid))
-                .cloned()
+                .filter(|d| d.from_task == from)
+                .map(|d| match d.kind {
+                    DependencyKind::FinishToStart | DependencyKind::FinishToFinish => (d.to_task, end_days),
+                    DependencyKind::StartToStart | DependencyKind::StartToFinish => (d.to_task, start_days),
+                })
                 .collect();
-            result.extend(children);
+            for (successor, days) in next {
+                if days == 0 || seen.contains(&successor) {
+                    continue;
+                }
+                let subtree = self.subtree_ids(successor);
+                seen.extend(&subtree);
+                self.shift_tasks(&subtree, days, false);
+                moved += 1;
+                queue.push_back((successor, days, days));
+            }
         }
+        moved
+    }
 
-        // Any orphaned tasks (parent_id set but parent not found) go at the end.
-        for t in &self.tasks {
-            if!result.iter().any(|r| r.id == t.id) {
-                result.push(t.clone());
+    /// Extend (or, when negative, shorten) the given tasks by moving their
+    /// finish date. Summary tasks and milestones are skipped, and a task is
+    /// never shortened past its start. Returns the number of tasks changed.
+    pub fn resize_tasks(&mut self, ids: &[uuid::Uuid], days: i64, working: bool) -> usize {
+        let leaves = self.leaf_ids(ids);
+        let resized: Vec<(uuid::Uuid, NaiveDate)> = self
+            .tasks
+            .iter()
+            .filter(|t| leaves.contains(&t.id) && !t.is_milestone)
+            .map(|t| (t.id, self.offset_date(t.end, days, working).max(t.start)))
+            .collect();
+        for (id, end) in &resized {
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.id == *id) {
+                task.end = *end;
             }
         }
+        self.recalculate_parent_dates();
+        resized.len()
+    }
+
+    /// Cost of a task from its assignments: delivered units × duration ×
+    /// daily rate.
+    /// Parent tasks sum the cost of their children.
+    pub fn task_cost(&self, task: &Task) -> f64 {
+        if task.has_children(&self.tasks) {
+            return self
+                .tasks
+                .iter()
+                .filter(|t| t.parent_id == Some(task.id) && self.is_counted(t))
+                .map(|t| self.task_cost(t))
+                .sum();
+        }
+        self.assignment_cost(task, None)
+    }
+
+    /// Cost of one resource across all tasks it is assigned to.
+    pub fn resource_cost(&self, resource_id: uuid::Uuid) -> f64 {
+        self.tasks
+            .iter()
+            .filter(|t| !t.has_children(&self.tasks) && self.is_counted(t))
+            .map(|t| self.assignment_cost(t, Some(resource_id)))
+            .sum()
+    }
+
+    /// Cost of a leaf task's assignments, optionally limited to one resource.
+    fn assignment_cost(&self, task: &Task, only: Option<uuid::Uuid>) -> f64 {
+        if task.is_milestone {
+            return 0.0;
+        }
+        let days = (task.end - task.start).num_days().max(0) as f64;
+        task.assignments
+            .iter()
+            .filter(|a| only.is_none_or(|id| a.resource_id == id))
+            .filter_map(|a| {
+                let resource = self.resources.iter().find(|r| r.id == a.resource_id)?;
+                Some(resource.delivered_units(a.units) as f64 * days * resource.daily_rate(self.calendar.hours_per_day))
+            })
+            .sum()
+    }
+
+    /// Total cost of all top-level tasks (children are included via their parents).
+    pub fn total_cost(&self) -> f64 {
+        self.tasks
+            .iter()
+            .filter(|t| t.parent_id.is_none() && self.is_counted(t))
+            .map(|t| self.task_cost(t))
+            .sum()
+    }
+
+    /// Format an amount in the project currency.
+    pub fn format_cost(&self, amount: f64) -> String {
+        format!("{:.2} {}", amount, self.currency)
+    }
+
+    /// Remove a resource and every assignment that refers to it.
+    pub fn remove_resource(&mut self, id: uuid::Uuid) {
+        self.resources.retain(|r| r.id != id);
+        for task in &mut self.tasks {
+            task.assignments.retain(|a| a.resource_id != id);
+        }
+    }
+
+    /// Merge tasks and dependencies from another copy of this project (e.g. the
+    /// version currently on disk). Items that only exist in `other` are added;
+    /// items present in both keep the local version, and tasks deleted here
+    /// (still in the trash) stay deleted. Returns the number of tasks added.
+    pub fn merge_from(&mut self, other: &Project) -> usize {
+        let mut added = 0;
+        let trashed: std::collections::HashSet<uuid::Uuid> =
+            self.trash.iter().flat_map(|entry| entry.tasks.iter().map(|t| t.id)).collect();
+        for task in &other.tasks {
+            if !trashed.contains(&task.id) && !self.tasks.iter().any(|t| t.id == task.id) {
+                self.tasks.push(task.clone());
+                added += 1;
+            }
+        }
+        for dep in &other.dependencies {
+            let exists = self.dependencies.iter().any(|d| {
+                d.from_task == dep.from_task && d.to_task == dep.to_task
+            });
+            let endpoints_exist = self.tasks.iter().any(|t| t.id == dep.from_task)
+                && self.tasks.iter().any(|t| t.id == dep.to_task);
+            if !exists && endpoints_exist {
+                self.dependencies.push(dep.clone());
+            }
+        }
+        self.sort_tasks_grouped();
+        self.recalculate_parent_dates();
+        added
+    }
+
+    /// Bring in tasks read from an external file. With `by_name`, a task whose
+    /// name matches an existing task (ignoring case) updates that task's
+    /// dates and progress instead of being added. Returns (added, updated).
+    pub fn import_external_tasks(&mut self, tasks: Vec<Task>, by_name: bool) -> (usize, usize) {
+        let (mut added, mut updated) = (0, 0);
+        let mut taken: std::collections::HashSet<uuid::Uuid> = self.tasks.iter().map(|t| t.id).collect();
+        // Ids that were replaced because they collided, so parent links
+        // within the batch can follow them.
+        let mut renamed = std::collections::HashMap::new();
+        let start = self.tasks.len();
+        for mut task in tasks {
+            let existing = by_name
+                .then(|| self.tasks.iter_mut().find(|t| t.name.eq_ignore_ascii_case(&task.name)))
+                .flatten();
+            match existing {
+                Some(t) => {
+                    t.start = task.start;
+                    t.end = task.end;
+                    t.progress = task.progress;
+                    if !task.assignments.is_empty() {
+                        t.assignments = task.assignments;
+                    }
+                    updated += 1;
+                }
+                None => {
+                    if !taken.insert(task.id) {
+                        let id = uuid::Uuid::new_v4();
+                        renamed.insert(task.id, id);
+                        task.id = id;
+                        taken.insert(id);
+                    }
+                    self.tasks.push(task);
+                    added += 1;
+                }
+            }
+        }
+        for task in &mut self.tasks[start..] {
+            task.parent_id = task.parent_id.map(|p| renamed.get(&p).copied().unwrap_or(p)).filter(|p| taken.contains(p));
+        }
+        self.sort_tasks_grouped();
+        self.recalculate_parent_dates();
+        (added, updated)
+    }
+
+    /// Re-order tasks so every parent is immediately followed by its
+    /// subtasks, at any depth. Top-level tasks keep their relative order, and
+    /// so do the children of each parent. Tasks whose parent is missing (or
+    /// that sit in a parent cycle) go last, each with its own subtasks.
+    /// Returns true if any task moved.
+    pub fn sort_tasks_grouped(&mut self) -> bool {
+        let ids: std::collections::HashSet<uuid::Uuid> = self.tasks.iter().map(|t| t.id).collect();
+        let mut children: std::collections::HashMap<uuid::Uuid, Vec<usize>> = std::collections::HashMap::new();
+        for (i, task) in self.tasks.iter().enumerate() {
+            if let Some(pid) = task.parent_id {
+                children.entry(pid).or_default().push(i);
+            }
+        }
+        let mut order = Vec::with_capacity(self.tasks.len());
+        let mut placed = vec![false; self.tasks.len()];
+        let roots = (0..self.tasks.len()).filter(|&i| self.tasks[i].parent_id.is_none());
+        let stray = (0..self.tasks.len()).filter(|&i| self.tasks[i].parent_id.is_some_and(|p| !ids.contains(&p)));
+        // Anything still unplaced after those is in a cycle.
+        let rest = 0..self.tasks.len();
+        for root in roots.chain(stray).chain(rest) {
+            let mut stack = vec![root];
+            while let Some(i) = stack.pop() {
+                if std::mem::replace(&mut placed[i], true) {
+                    continue;
+                }
+                order.push(i);
+                if let Some(kids) = children.get(&self.tasks[i].id) {
+                    stack.extend(kids.iter().rev());
+                }
+            }
+        }
+        if order.iter().enumerate().all(|(pos, &i)| pos == i) {
+            return false;
+        }
+        let mut slots: Vec<Option<Task>> = std::mem::take(&mut self.tasks).into_iter().map(Some).collect();
+        self.tasks = order.into_iter().filter_map(|i| slots[i].take()).collect();
+        true
+    }
+}
+
+/// Expand a batch-rename pattern such as `Sprint {n} – {name}`: `{n}` is the
+/// sequence number zero-padded to `digits`, `{name}` the current name and
+/// `{parent}` the parent task's name. Placeholders are only read from the
+/// pattern, never from the names put in.
+pub fn expand_rename_pattern(pattern: &str, n: u32, digits: usize, name: &str, parent: &str) -> String {
+    let number = format!("{:0width$}", n, width = digits);
+    let placeholders = [("{n}", number.as_str()), ("{name}", name), ("{parent}", parent)];
+    let mut out = String::with_capacity(pattern.len() + name.len());
+    let mut rest = pattern;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        rest = &rest[open..];
+        match placeholders.iter().find(|(placeholder, _)| rest.starts_with(placeholder)) {
+            Some((placeholder, value)) => {
+                out.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                out.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    /// Phase → (A → B), plus an outside task C linked from A.
+    fn sample() -> (Project, [uuid::Uuid; 4]) {
+        let mut project = Project::new("Test");
+        let phase = Task::new("Phase", date(1), date(10));
+        let mut a = Task::new("A", date(1), date(4));
+        let mut b = Task::new("B", date(5), date(10));
+        let c = Task::new("C", date(11), date(12));
+        a.parent_id = Some(phase.id);
+        b.parent_id = Some(phase.id);
+        let ids = [phase.id, a.id, b.id, c.id];
+        project.tasks = vec![phase, a, b, c];
+        project.dependencies = vec![
+            Dependency::new(ids[1], ids[2], DependencyKind::FinishToStart),
+            Dependency::new(ids[1], ids[3], DependencyKind::FinishToStart),
+        ];
+        (project, ids)
+    }
+
+    fn assert_consistent(project: &Project) {
+        let ids: std::collections::HashSet<_> = project.tasks.iter().map(|t| t.id).collect();
+        assert_eq!(ids.len(), project.tasks.len(), "duplicate task ids");
+        for task in &project.tasks {
+            assert!(task.parent_id.is_none_or(|p| ids.contains(&p)), "dangling parent");
+        }
+        for dep in &project.dependencies {
+            assert!(ids.contains(&dep.from_task) && ids.contains(&dep.to_task), "dangling link");
+        }
+    }
+
+    #[test]
+    fn milestones_are_ready_once_every_predecessor_is_done() {
+        let (mut project, [_, a, b, _]) = sample();
+        let milestones = [Task::new_milestone("Gate", date(12)), Task::new_milestone("Lone", date(12))];
+        let [gate, lone] = [milestones[0].id, milestones[1].id];
+        project.tasks.extend(milestones);
+        project.dependencies.push(Dependency::new(a, gate, DependencyKind::FinishToStart));
+        project.dependencies.push(Dependency::new(b, gate, DependencyKind::FinishToStart));
+        project.tasks.iter_mut().find(|t| t.id == a).unwrap().progress = 1.0;
+        assert_eq!(project.milestone_readiness().get(&gate), Some(&false));
+        project.tasks.iter_mut().find(|t| t.id == b).unwrap().progress = 1.0;
+        assert_eq!(project.milestone_readiness().get(&gate), Some(&true));
+        assert_eq!(project.milestone_readiness().get(&lone), Some(&false));
+    }
+
+    #[test]
+    fn clone_subtree_remaps_ids_and_internal_links() {
+        let (project, [phase, a, b, _]) = sample();
+        let cloned = project.clone_subtree(phase, 7).unwrap();
+        assert_eq!(cloned.tasks.len(), 3);
+        assert!(cloned.tasks.iter().all(|t| ![phase, a, b].contains(&t.id)));
+        let new_a = cloned.id_map[&a];
+        let new_b = cloned.id_map[&b];
+        assert_eq!(cloned.tasks.iter().find(|t| t.id == new_a).unwrap().parent_id, Some(cloned.id_map[&phase]));
+        assert_eq!(cloned.tasks.iter().find(|t| t.id == new_a).unwrap().start, date(8));
+        // Only the A → B link is inside the subtree.
+        assert_eq!(cloned.dependencies.len(), 1);
+        assert_eq!((cloned.dependencies[0].from_task, cloned.dependencies[0].to_task), (new_a, new_b));
+    }
+
+    #[test]
+    fn clone_subtree_keeps_outside_parent() {
+        let (project, [phase, a, ..]) = sample();
+        let cloned = project.clone_subtree(a, 0).unwrap();
+        assert_eq!(cloned.tasks.len(), 1);
+        assert_eq!(cloned.tasks[0].parent_id, Some(phase));
+        assert!(cloned.dependencies.is_empty());
+        assert!(project.clone_subtree(uuid::Uuid::new_v4(), 0).is_none());
+    }
+
+    #[test]
+    fn duplicate_subtree_inserts_after_original() {
+        let (mut project, [phase, ..]) = sample();
+        let new_root = project.duplicate_subtree(phase, 14).unwrap();
+        assert_eq!(project.tasks.len(), 7);
+        assert_eq!(project.tasks[3].id, new_root);
+        assert_eq!(project.tasks[3].name, "Phase (copy)");
+        assert_eq!(project.dependencies.len(), 3);
+        assert_consistent(&project);
+    }
+
+    #[test]
+    fn import_tasks_twice_does_not_collide() {
+        let (other, _) = sample();
+        let (mut project, [phase, ..]) = sample();
+        assert_eq!(project.import_tasks(phase, &other, 0), 4);
+        assert_eq!(project.import_tasks(phase, &other, 0), 4);
+        assert_eq!(project.tasks.len(), 12);
+        // Both links of each import are internal to `other`.
+        assert_eq!(project.dependencies.len(), 6);
+        assert_consistent(&project);
+        let top_level = project.tasks.iter().filter(|t| t.parent_id.is_none()).count();
+        assert_eq!(top_level, 2);
+    }
+
+    #[test]
+    fn import_tasks_skips_links_that_close_a_cycle() {
+        let (mut other, [phase_there, a, b, _]) = sample();
+        other.dependencies.push(Dependency::new(b, a, DependencyKind::FinishToStart));
+        other.dependencies.push(Dependency::new(phase_there, a, DependencyKind::StartToStart));
+        let (mut project, [phase, ..]) = sample();
+        assert_eq!(project.import_tasks(phase, &other, 0), 4);
+        // A → B and A → C come in; B → A and the link from A's own phase don't.
+        assert_eq!(project.dependencies.len(), 4);
+        assert_consistent(&project);
+    }
+
+    #[test]
+    fn import_external_tasks_replaces_colliding_ids() {
+        let (mut project, [phase, a, ..]) = sample();
+        let mut copy = project.tasks[0].clone();
+        copy.name = "Other phase".to_string();
+        let mut child = Task::new("Child", date(2), date(3));
+        child.parent_id = Some(phase);
+        let mut orphan = Task::new("Orphan", date(2), date(3));
+        orphan.parent_id = Some(uuid::Uuid::new_v4());
+        let (added, updated) = project.import_external_tasks(vec![copy, child, orphan], false);
+        assert_eq!((added, updated), (3, 0));
+        assert_consistent(&project);
+        let new_phase = project.tasks.iter().find(|t| t.name == "Other phase").unwrap().id;
+        assert_ne!(new_phase, phase);
+        let child = project.tasks.iter().find(|t| t.name == "Child").unwrap();
+        assert_eq!(child.parent_id, Some(new_phase));
+        assert_eq!(project.tasks.iter().find(|t| t.name == "Orphan").unwrap().parent_id, None);
+        assert_eq!(project.tasks.iter().find(|t| t.id == a).unwrap().parent_id, Some(phase));
+    }
+
+    #[test]
+    fn templates_drop_progress_and_start_in_week_zero() {
+        let (mut project, [phase, a, b, _]) = sample();
+        project.tasks[1].progress = 1.0;
+        project.tasks[2].progress = 0.4;
+        project.baseline = Some(Baseline::capture(&project.tasks));
+        let template = project.to_template();
+        assert!(template.baseline.is_none());
+        assert!(template.tasks.iter().all(|t| t.progress == 0.0));
+        let task = |id| template.tasks.iter().find(|t| t.id == id).unwrap();
+        // A started Friday 1 March 2024; it keeps its weekday in week zero.
+        assert_eq!(task(a).start, NaiveDate::from_ymd_opt(2001, 1, 5).unwrap());
+        assert_eq!(task(b).end - task(b).start, date(10) - date(5));
+        assert_eq!(task(phase).progress, 0.0);
+    }
+
+    #[test]
+    fn grouping_puts_subtasks_under_their_parent_at_any_depth() {
+        let (mut project, [phase, a, _, c]) = sample();
+        let mut step = Task::new("Step", date(2), date(3));
+        step.parent_id = Some(a);
+        let mut stray = Task::new("Stray", date(2), date(3));
+        stray.parent_id = Some(uuid::Uuid::new_v4());
+        // As left by a hand merge: a grandchild first, the phase's children
+        // split around an unrelated task.
+        project.tasks.insert(0, step.clone());
+        project.tasks.insert(1, stray.clone());
+        let b = project.tasks.remove(4);
+        project.tasks.push(b.clone());
+        assert!(project.sort_tasks_grouped());
+        let order: Vec<_> = project.tasks.iter().map(|t| t.id).collect();
+        assert_eq!(order, vec![phase, a, step.id, b.id, c, stray.id]);
+        assert!(!project.sort_tasks_grouped());
+    }
+
+    #[test]
+    fn merging_keeps_trashed_tasks_deleted() {
+        let (mut project, [_, a, b, c]) = sample();
+        let mut other = project.clone();
+        project.move_to_trash(&[c]);
+        let extra = Task::new("Extra", date(2), date(3));
+        other.tasks.push(extra.clone());
+        assert_eq!(project.merge_from(&other), 1);
+        assert!(!project.tasks.iter().any(|t| t.id == c));
+        assert!(project.tasks.iter().any(|t| t.id == extra.id));
+        assert!(!project.dependencies.iter().any(|d| d.to_task == c));
+        assert!(project.dependencies.iter().any(|d| d.from_task == a && d.to_task == b));
+        assert_consistent(&project);
+    }
+
+    #[test]
+    fn rename_patterns_expand_placeholders_once() {
+        assert_eq!(expand_rename_pattern("Sprint {n} – {name}", 7, 2, "Login", "Web"), "Sprint 07 – Login");
+        assert_eq!(expand_rename_pattern("{parent}: {name}", 1, 1, "{parent} fix", "Web"), "Web: {parent} fix");
+        assert_eq!(expand_rename_pattern("{n}{n} {other} {", 3, 0, "x", "y"), "33 {other} {");
+        assert_eq!(expand_rename_pattern("  {name}  ", 1, 1, "Trim me", ""), "Trim me");
+    }
 
-        self.tasks = result;
+    #[test]
+    fn keeping_link_gaps_moves_the_chain() {
+        let (mut project, [_, a, b, c]) = sample();
+        project.dependencies.push(Dependency::new(b, c, DependencyKind::FinishToStart));
+        project.tasks[1].end = date(6);
+        assert_eq!(project.keep_link_gaps(a, 0, 2), 2);
+        let dates = |id| project.tasks.iter().find(|t| t.id == id).map(|t| (t.start, t.end)).unwrap();
+        assert_eq!(dates(b), (date(7), date(12)));
+        // C is reached from A and from B but moves only once.
+        assert_eq!(dates(c), (date(13), date(14)));
     }
 }
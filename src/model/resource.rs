@@ -0,0 +1,159 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::calendar::ProjectCalendar;
+use super::task::Task;
+
+/// Unit a resource's cost rate is expressed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RateUnit {
+    #[default]
+    PerHour,
+    PerDay,
+}
+
+impl RateUnit {
+    pub fn label(self) -> &'static str {
+        match self {
+            RateUnit::PerHour => "/ hour",
+            RateUnit::PerDay => "/ day",
+        }
+    }
+
+    pub fn all() -> &'static [RateUnit] {
+        &[RateUnit::PerHour, RateUnit::PerDay]
+    }
+}
+
+/// A person or team that work can be assigned to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Resource {
+    pub id: Uuid,
+    pub name: String,
+    /// Job title or function, e.g. "Designer".
+    #[serde(default)]
+    pub role: String,
+    /// Cost rate in the project currency, per `rate_unit`.
+    #[serde(default)]
+    pub rate: f64,
+    #[serde(default)]
+    pub rate_unit: RateUnit,
+    /// Full-time equivalent: 1.0 = full time, 0.5 = half time.
+    #[serde(default = "default_units")]
+    pub fte: f32,
+    /// Weekdays, Monday first, this resource does not work on top of the
+    /// project calendar's weekends (e.g. a four-day week).
+    #[serde(default)]
+    pub days_off: [bool; 7],
+    /// Vacations and other days away.
+    #[serde(default)]
+    pub absences: Vec<Absence>,
+}
+
+/// Days a resource is away, e.g. a vacation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Absence {
+    pub from: NaiveDate,
+    /// Last day away.
+    pub to: NaiveDate,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl Absence {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.from <= date && date <= self.to
+    }
+}
+
+impl Resource {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            role: String::new(),
+            rate: 0.0,
+            rate_unit: RateUnit::PerHour,
+            fte: 1.0,
+            days_off: [false; 7],
+            absences: Vec::new(),
+        }
+    }
+
+    /// Whether this resource works on `date`: a project working day that is
+    /// neither one of its days off nor inside an absence.
+    pub fn is_available(&self, calendar: &ProjectCalendar, date: NaiveDate) -> bool {
+        calendar.is_working_day(date)
+            && !self.days_off[date.weekday().num_days_from_monday() as usize]
+            && !self.absences.iter().any(|a| a.contains(date))
+    }
+
+    /// Allocation units this resource can take on `date`: its FTE on days it
+    /// works, nothing otherwise.
+    pub fn capacity(&self, calendar: &ProjectCalendar, date: NaiveDate) -> f32 {
+        if self.is_available(calendar, date) { self.fte } else { 0.0 }
+    }
+
+    /// Cost of one full-time day of this resource, with `hours_per_day`
+    /// working hours in a day.
+    pub fn daily_rate(&self, hours_per_day: f32) -> f64 {
+        match self.rate_unit {
+            RateUnit::PerHour => self.rate * hours_per_day as f64,
+            RateUnit::PerDay => self.rate,
+        }
+    }
+
+    /// Units of work this resource actually delivers when booked at `units`:
+    /// a half-time resource does half a day's work per day, however much it
+    /// is booked.
+    pub fn delivered_units(&self, units: f32) -> f32 {
+        units.min(self.fte)
+    }
+}
+
+/// Assignment of a resource to a task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Assignment {
+    pub resource_id: Uuid,
+    /// Allocation units: 1.0 = full time, 0.5 = half time.
+    #[serde(default = "default_units")]
+    pub units: f32,
+}
+
+fn default_units() -> f32 {
+    1.0
+}
+
+impl Assignment {
+    pub fn new(resource_id: Uuid) -> Self {
+        Self {
+            resource_id,
+            units: 1.0,
+        }
+    }
+}
+
+/// Allocation of one resource for each of `days` consecutive days starting at
+/// `from`, summed over all tasks it is assigned to (1.0 = fully booked).
+/// External tasks don't book the team's resources and are not counted.
+pub fn daily_load(tasks: &[Task], resource_id: Uuid, from: NaiveDate, days: usize) -> Vec<f32> {
+    let mut load = vec![0.0; days];
+    for task in tasks.iter().filter(|t| !t.is_milestone && !t.external) {
+        let units: f32 = task
+            .assignments
+            .iter()
+            .filter(|a| a.resource_id == resource_id)
+            .map(|a| a.units)
+            .sum();
+        if units <= 0.0 {
+            continue;
+        }
+        let first = (task.start - from).num_days().max(0) as usize;
+        let last = ((task.end - from).num_days().max(0) as usize).min(days);
+        for slot in load.iter_mut().take(last).skip(first) {
+            *slot += units;
+        }
+    }
+    load
+}
@@ -0,0 +1,85 @@
+//! Weekly snapshots of every task's dates, kept in the project file when
+//! enabled, so how the plan evolved can be replayed week by week.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::baseline::BaselineDates;
+use super::calendar::ProjectCalendar;
+use super::task::Task;
+
+/// The plan's task dates as last saved in one week.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklySnapshot {
+    /// First day of the week, under the project's week start.
+    pub week: NaiveDate,
+    pub taken: DateTime<Utc>,
+    pub tasks: HashMap<Uuid, BaselineDates>,
+}
+
+impl WeeklySnapshot {
+    pub fn dates(&self, task_id: Uuid) -> Option<BaselineDates> {
+        self.tasks.get(&task_id).copied()
+    }
+
+    /// Tasks in `tasks` whose dates now differ from this snapshot's.
+    pub fn moved_since(&self, tasks: &[Task]) -> usize {
+        tasks
+            .iter()
+            .filter(|t| self.dates(t.id).is_some_and(|d| (d.start, d.end) != (t.start, t.end)))
+            .count()
+    }
+}
+
+/// Record the current dates as the snapshot of `now`'s week, replacing one
+/// taken earlier that week. Returns true if the week is new.
+pub fn record_week(
+    snapshots: &mut Vec<WeeklySnapshot>,
+    tasks: &[Task],
+    calendar: &ProjectCalendar,
+    now: DateTime<Utc>,
+) -> bool {
+    let snapshot = WeeklySnapshot {
+        week: calendar.week_start(now.date_naive()),
+        taken: now,
+        tasks: tasks.iter().map(|t| (t.id, BaselineDates { start: t.start, end: t.end })).collect(),
+    };
+    match snapshots.iter_mut().find(|s| s.week == snapshot.week) {
+        Some(existing) => {
+            *existing = snapshot;
+            false
+        }
+        None => {
+            snapshots.push(snapshot);
+            snapshots.sort_by_key(|s| s.week);
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn one_snapshot_per_week() {
+        let calendar = ProjectCalendar::default();
+        let mut task = Task::new("Build", NaiveDate::from_ymd_opt(2024, 6, 3).unwrap(), NaiveDate::from_ymd_opt(2024, 6, 7).unwrap());
+        let mut snapshots = Vec::new();
+        let monday = Utc.with_ymd_and_hms(2024, 6, 3, 9, 0, 0).unwrap();
+        assert!(record_week(&mut snapshots, std::slice::from_ref(&task), &calendar, monday));
+        // A later save the same week replaces that week's snapshot.
+        task.end = NaiveDate::from_ymd_opt(2024, 6, 10).unwrap();
+        let friday = Utc.with_ymd_and_hms(2024, 6, 7, 17, 0, 0).unwrap();
+        assert!(!record_week(&mut snapshots, std::slice::from_ref(&task), &calendar, friday));
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].dates(task.id).map(|d| d.end), Some(task.end));
+        let next_week = Utc.with_ymd_and_hms(2024, 6, 11, 9, 0, 0).unwrap();
+        assert!(record_week(&mut snapshots, std::slice::from_ref(&task), &calendar, next_week));
+        assert_eq!(snapshots.len(), 2);
+    }
+}
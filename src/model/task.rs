@@ -1,8 +1,10 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use egui::Color32;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::resource::{Assignment, Resource};
+
 /// Task priority level.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum TaskPriority {
@@ -75,7 +77,6 @@ impl DependencyKind {
         }
     }
 
-    #[allow(dead_code)]
     pub fn all() -> &'static [DependencyKind] {
         &[
             DependencyKind::FinishToStart,
@@ -86,17 +87,141 @@ impl DependencyKind {
     }
 }
 
+/// Stroke pattern used to draw a dependency arrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LineStyle {
+    #[default]
+    Solid,
+    Dashed,
+}
+
+impl LineStyle {
+    pub fn label(self) -> &'static str {
+        match self {
+            LineStyle::Solid => "Solid",
+            LineStyle::Dashed => "Dashed",
+        }
+    }
+
+    pub fn all() -> &'static [LineStyle] {
+        &[LineStyle::Solid, LineStyle::Dashed]
+    }
+}
+
+/// Optional fill pattern drawn over a task bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BarPattern {
+    #[default]
+    None,
+    /// Diagonal hatch, e.g. for tentative work.
+    Hatch,
+    /// Dot grid, e.g. for work done outside the team.
+    Dots,
+}
+
+impl BarPattern {
+    pub fn label(self) -> &'static str {
+        match self {
+            BarPattern::None => "None",
+            BarPattern::Hatch => "Diagonal hatch",
+            BarPattern::Dots => "Dotted",
+        }
+    }
+
+    pub fn all() -> &'static [BarPattern] {
+        &[BarPattern::None, BarPattern::Hatch, BarPattern::Dots]
+    }
+}
+
+/// Which date of a task a reminder counts back from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ReminderAnchor {
+    #[default]
+    Start,
+    Finish,
+}
+
+impl ReminderAnchor {
+    pub fn label(self) -> &'static str {
+        match self {
+            ReminderAnchor::Start => "start",
+            ReminderAnchor::Finish => "finish",
+        }
+    }
+
+    pub fn all() -> &'static [ReminderAnchor] {
+        &[ReminderAnchor::Start, ReminderAnchor::Finish]
+    }
+}
+
+/// A reminder shown `days_before` days ahead of a task's start or finish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub days_before: u32,
+    pub anchor: ReminderAnchor,
+}
+
+impl Default for Reminder {
+    fn default() -> Self {
+        Self { days_before: 3, anchor: ReminderAnchor::Start }
+    }
+}
+
 /// A dependency link between two tasks.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Dependency {
     pub from_task: Uuid,
     pub to_task: Uuid,
     #[serde(default)]
     pub kind: DependencyKind,
+    /// Optional arrow color overriding the theme's dependency color.
+    #[serde(default, with = "opt_color_serde")]
+    pub color: Option<Color32>,
+    #[serde(default)]
+    pub line_style: LineStyle,
+    /// Critical links are drawn with the theme's critical style, taking
+    /// precedence over `color`.
+    #[serde(default)]
+    pub critical: bool,
+    /// Days added after the predecessor's date (negative for lead time).
+    #[serde(default)]
+    pub lag_days: i64,
+    /// Free-form key/value details, e.g. a PO number. The `note` key holds
+    /// the explanation shown with the link.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub metadata: std::collections::BTreeMap<String, String>,
 }
 
+impl Dependency {
+    pub fn new(from_task: Uuid, to_task: Uuid, kind: DependencyKind) -> Self {
+        Self {
+            from_task,
+            to_task,
+            kind,
+            color: None,
+            line_style: LineStyle::Solid,
+            critical: false,
+            lag_days: 0,
+            metadata: Default::default(),
+        }
+    }
+
+    /// The link's note, if it has a non-empty one.
+    pub fn note(&self) -> Option<&str> {
+        self.metadata.get(NOTE_KEY).map(|n| n.trim()).filter(|n| !n.is_empty())
+    }
+
+    /// Metadata other than the note, for listing.
+    pub fn details(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.metadata.iter().filter(|(k, _)| k.as_str() != NOTE_KEY)
+    }
+}
+
+/// Metadata key of a dependency's note.
+pub const NOTE_KEY: &str = "note";
+
 /// A single task or milestone in the Gantt chart.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Task {
     pub id: Uuid,
     pub name: String,
@@ -110,6 +235,9 @@ pub struct Task {
     /// Parent task id for hierarchy/phases.
     #[serde(default)]
     pub parent_id: Option<Uuid>,
+    /// Judged progress for a parent task, used instead of the rollup.
+    #[serde(default)]
+    pub progress_override: Option<f32>,
     /// Whether this parent task's children are collapsed.
     #[serde(default)]
     pub collapsed: bool,
@@ -122,8 +250,46 @@ pub struct Task {
     /// Display color for the task bar (stored as RGBA).
     #[serde(with = "color_serde")]
     pub color: Color32,
+    /// Fill pattern drawn over the bar.
+    #[serde(default)]
+    pub pattern: BarPattern,
+    /// Pencilled-in work: drawn faded and hatched, and left out of rollups,
+    /// costs and exports unless the project includes tentative work.
+    #[serde(default)]
+    pub tentative: bool,
+    /// Owned by another team or vendor: drawn greyed with a dashed border,
+    /// left out of resource load and flagged in reports.
+    #[serde(default)]
+    pub external: bool,
     /// If true, this is a milestone (rendered as a diamond, zero-duration).
     pub is_milestone: bool,
+    /// For milestones: follow the latest finish of the predecessors instead of
+    /// a fixed date.
+    #[serde(default)]
+    pub auto_date: bool,
+    /// Resources assigned to this task.
+    #[serde(default)]
+    pub assignments: Vec<Assignment>,
+    /// Total work in person-days. When set, the task is effort-driven: its
+    /// duration follows from the work and the assigned units.
+    #[serde(default)]
+    pub work_days: Option<f32>,
+    /// Hours of work left, estimated from the progress when it changes or
+    /// entered by hand. Drives the estimated finish.
+    #[serde(default)]
+    pub remaining_hours: Option<f32>,
+    /// Optional reminder ahead of the start or finish.
+    #[serde(default)]
+    pub reminder: Option<Reminder>,
+    /// URL of a linked GitHub issue/PR or Jira ticket.
+    #[serde(default)]
+    pub link: Option<String>,
+    /// Status of the linked item as of the last refresh.
+    #[serde(default)]
+    pub link_status: Option<String>,
+    /// When the linked item was last pulled.
+    #[serde(default)]
+    pub last_synced: Option<DateTime<Utc>>,
 }
 
 impl Task {
@@ -137,11 +303,23 @@ impl Task {
             progress: 0.0,
             group: None,
             parent_id: None,
+            progress_override: None,
             collapsed: false,
             priority: TaskPriority::None,
             description: String::new(),
             color: Color32::from_rgb(70, 130, 180), // Steel blue
+            pattern: BarPattern::None,
+            tentative: false,
+            external: false,
             is_milestone: false,
+            auto_date: false,
+            assignments: Vec::new(),
+            work_days: None,
+            remaining_hours: None,
+            reminder: None,
+            link: None,
+            link_status: None,
+            last_synced: None,
         }
     }
 
@@ -155,11 +333,112 @@ impl Task {
             progress: 0.0,
             group: None,
             parent_id: None,
+            progress_override: None,
             collapsed: false,
             priority: TaskPriority::None,
             description: String::new(),
             color: Color32::from_rgb(255, 165, 0), // Orange
+            pattern: BarPattern::None,
+            tentative: false,
+            external: false,
             is_milestone: true,
+            auto_date: false,
+            assignments: Vec::new(),
+            work_days: None,
+            remaining_hours: None,
+            reminder: None,
+            link: None,
+            link_status: None,
+            last_synced: None,
+        }
+    }
+
+    /// Units of work delivered per day: the assigned units, each capped at
+    /// its resource's FTE.
+    pub fn delivered_units(&self, resources: &[Resource]) -> f32 {
+        self.assignments
+            .iter()
+            .map(|a| match resources.iter().find(|r| r.id == a.resource_id) {
+                Some(resource) => resource.delivered_units(a.units),
+                None => a.units,
+            })
+            .sum()
+    }
+
+    /// For effort-driven tasks, set the end date from the work and the units
+    /// the assigned resources deliver (at least one day). Returns true if the
+    /// end changed.
+    pub fn apply_effort(&mut self, resources: &[Resource]) -> bool {
+        let (Some(work), units) = (self.work_days, self.delivered_units(resources)) else {
+            return false;
+        };
+        if self.is_milestone || units <= 0.0 {
+            return false;
+        }
+        let days = (work / units).ceil().max(1.0) as i64;
+        let end = self.start + chrono::Duration::days(days);
+        let changed = end != self.end;
+        self.end = end;
+        changed
+    }
+
+    /// Planned work in hours: the effort when effort-driven, otherwise the
+    /// duration at the assigned units (one person when unassigned).
+    pub fn planned_hours(&self, resources: &[Resource], hours_per_day: f32) -> f32 {
+        let units = self.delivered_units(resources);
+        let units = if units > 0.0 { units } else { 1.0 };
+        let days = self.work_days.unwrap_or_else(|| (self.end - self.start).num_days() as f32 * units);
+        days * hours_per_day
+    }
+
+    /// Whether a milestone has been reached. Milestones carry no partial
+    /// progress: `progress` is 1.0 when achieved and 0.0 otherwise.
+    pub fn is_achieved(&self) -> bool {
+        self.is_milestone && self.progress >= 1.0
+    }
+
+    pub fn set_achieved(&mut self, achieved: bool) {
+        self.progress = if achieved { 1.0 } else { 0.0 };
+    }
+
+    /// Weight of this task in its parent's progress rollup: the planned work
+    /// when effort-driven, otherwise the duration in days (at least one).
+    /// Milestones represent no work and weigh nothing.
+    pub fn rollup_weight(&self) -> f32 {
+        if self.is_milestone {
+            return 0.0;
+        }
+        self.work_days
+            .unwrap_or_else(|| (self.end - self.start).num_days() as f32)
+            .max(1.0)
+    }
+
+    /// Progress the task should have reached by `date` if work were spread
+    /// evenly over its duration.
+    pub fn expected_progress(&self, date: NaiveDate) -> f32 {
+        let total = (self.end - self.start).num_days().max(1) as f32;
+        ((date - self.start).num_days() as f32 / total).clamp(0.0, 1.0)
+    }
+
+    /// The date the reminder counts back from, if the task has one.
+    pub fn reminder_target(&self) -> Option<NaiveDate> {
+        self.reminder.map(|r| match r.anchor {
+            ReminderAnchor::Start => self.start,
+            ReminderAnchor::Finish => self.end,
+        })
+    }
+
+    /// Whether the reminder is active on `today`: from `days_before` days
+    /// ahead of the target date up to the date itself, while the task is
+    /// not complete.
+    pub fn reminder_due(&self, today: NaiveDate) -> bool {
+        match (self.reminder, self.reminder_target()) {
+            (Some(r), Some(target)) => {
+                self.progress < 1.0
+                    && today <= target
+                    && today >= target - chrono::Duration::days(r.days_before as i64)
+            }
+            _ => false,
         }
     }
 
@@ -175,7 +454,7 @@ impl Task {
 }
 
 /// Serde helper for `Color32`.
-mod color_serde {
+pub(super) mod color_serde {
     use egui::Color32;
     use serde::{self, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -197,3 +476,26 @@ mod color_serde {
         ))
     }
 }
+
+/// Serde helper for `Option<Color32>`.
+mod opt_color_serde {
+    use egui::Color32;
+    use serde::{self, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(color: &Option<Color32>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        color
+            .map(|c| [c.r(), c.g(), c.b(), c.a()])
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Color32>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let rgba: Option<[u8; 4]> = Deserialize::deserialize(deserializer)?;
+        Ok(rgba.map(|c| Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3])))
+    }
+}
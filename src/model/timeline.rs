@@ -1,13 +1,83 @@
 use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Zoom limits, in pixels per day.
+pub const MIN_PIXELS_PER_DAY: f32 = 2.0;
+pub const MAX_PIXELS_PER_DAY: f32 = 80.0;
+
+/// Zooms at which [`TimelineViewport::follow_zoom`] changes scale, as
+/// (leave the finer scale below, return to it at or above). The gap between
+/// the two keeps the header from flickering when zooming around a threshold.
+const WEEKS_BELOW_DAYS: (f32, f32) = (16.0, 24.0);
+const MONTHS_BELOW_WEEKS: (f32, f32) = (5.0, 7.0);
 
 /// Controls what scale the timeline displays.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TimelineScale {
     Days,
     Weeks,
     Months,
 }
 
+/// One-keystroke zoom levels that set both the scale and the zoom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoomPreset {
+    Day,
+    Week,
+    Month,
+    /// The whole timeline in the chart's width.
+    Fit,
+}
+
+impl ZoomPreset {
+    pub fn label(self) -> &'static str {
+        match self {
+            ZoomPreset::Day => "Day",
+            ZoomPreset::Week => "Week",
+            ZoomPreset::Month => "Month",
+            ZoomPreset::Fit => "Fit",
+        }
+    }
+
+    pub fn all() -> &'static [ZoomPreset] {
+        &[ZoomPreset::Day, ZoomPreset::Week, ZoomPreset::Month, ZoomPreset::Fit]
+    }
+}
+
+/// The scale and zoom a project opens at, saved in the project file.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DefaultZoom {
+    pub scale: TimelineScale,
+    pub pixels_per_day: f32,
+}
+
+/// Room left on the timeline before the first task and after the last.
+/// Long projects get `percent` of their span when that is more than the
+/// fixed days.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewportPadding {
+    pub before_days: u32,
+    pub after_days: u32,
+    pub percent: f32,
+}
+
+impl Default for ViewportPadding {
+    fn default() -> Self {
+        Self { before_days: 7, after_days: 30, percent: 5.0 }
+    }
+}
+
+impl ViewportPadding {
+    /// The timeline range for tasks spanning `first`..`last`.
+    pub fn around(&self, first: NaiveDate, last: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let share = ((last - first).num_days() as f32 * self.percent.max(0.0) / 100.0).ceil() as i64;
+        let before = share.max(self.before_days as i64);
+        let after = share.max(self.after_days as i64);
+        (first - chrono::Duration::days(before), last + chrono::Duration::days(after))
+    }
+}
+
 /// Manages the visible viewport of the timeline.
 #[derive(Debug, Clone)]
 pub struct TimelineViewport {
@@ -19,6 +89,9 @@ pub struct TimelineViewport {
     pub scale: TimelineScale,
     /// Pixels per day (controls zoom level).
     pub pixels_per_day: f32,
+    /// Zooming in and out switches the scale once the labels of the
+    /// current one would be too cramped or too sparse to read.
+    pub auto_scale: bool,
 }
 
 impl TimelineViewport {
@@ -28,6 +101,7 @@ impl TimelineViewport {
             end,
             scale: TimelineScale::Weeks,
             pixels_per_day: 18.0,
+            auto_scale: true,
         }
     }
 
@@ -44,12 +118,111 @@ impl TimelineViewport {
 
     /// Zoom in (increase pixels per day).
     pub fn zoom_in(&mut self) {
-        self.pixels_per_day = (self.pixels_per_day * 1.2).min(80.0);
+        self.pixels_per_day = (self.pixels_per_day * 1.2).min(MAX_PIXELS_PER_DAY);
+        self.follow_zoom();
     }
 
     /// Zoom out (decrease pixels per day).
     pub fn zoom_out(&mut self) {
-        self.pixels_per_day = (self.pixels_per_day / 1.2).max(2.0);
+        self.pixels_per_day = (self.pixels_per_day / 1.2).max(MIN_PIXELS_PER_DAY);
+        self.follow_zoom();
+    }
+
+    /// With [`auto_scale`](Self::auto_scale) on, move to the scale that
+    /// suits the current zoom, one step at a time and with hysteresis.
+    pub fn follow_zoom(&mut self) {
+        if !self.auto_scale {
+            return;
+        }
+        let ppd = self.pixels_per_day;
+        self.scale = match self.scale {
+            TimelineScale::Days if ppd < WEEKS_BELOW_DAYS.0 => TimelineScale::Weeks,
+            TimelineScale::Weeks if ppd >= WEEKS_BELOW_DAYS.1 => TimelineScale::Days,
+            TimelineScale::Weeks if ppd < MONTHS_BELOW_WEEKS.0 => TimelineScale::Months,
+            TimelineScale::Months if ppd >= MONTHS_BELOW_WEEKS.1 => TimelineScale::Weeks,
+            scale => scale,
+        };
+    }
+
+    /// Fit the whole timeline into `width` pixels, picking the scale that
+    /// suits the resulting zoom.
+    pub fn fit_to_width(&mut self, width: f32) {
+        self.fit_days((self.end - self.start).num_days(), width);
+    }
+
+    /// Zoom so `days` fill `width` pixels, picking the scale that suits the
+    /// resulting zoom.
+    pub fn fit_days(&mut self, days: i64, width: f32) {
+        let days = days.max(1) as f32;
+        self.pixels_per_day = (width / days).clamp(MIN_PIXELS_PER_DAY, MAX_PIXELS_PER_DAY);
+        self.scale = if self.pixels_per_day < 6.0 {
+            TimelineScale::Months
+        } else if self.pixels_per_day < 20.0 {
+            TimelineScale::Weeks
+        } else {
+            TimelineScale::Days
+        };
+    }
+
+    /// Switch to `preset`; `width` is the chart width used by Fit.
+    pub fn apply_preset(&mut self, preset: ZoomPreset, width: f32) {
+        let (scale, pixels_per_day) = match preset {
+            ZoomPreset::Day => (TimelineScale::Days, 40.0),
+            ZoomPreset::Week => (TimelineScale::Weeks, 18.0),
+            ZoomPreset::Month => (TimelineScale::Months, 4.0),
+            ZoomPreset::Fit => return self.fit_to_width(width),
+        };
+        self.scale = scale;
+        self.pixels_per_day = pixels_per_day;
+    }
+
+    /// The current scale and zoom, to save as a project's default.
+    pub fn zoom(&self) -> DefaultZoom {
+        DefaultZoom { scale: self.scale, pixels_per_day: self.pixels_per_day }
     }
 
+    /// Switch to a saved scale and zoom.
+    pub fn set_zoom(&mut self, zoom: DefaultZoom) {
+        self.scale = zoom.scale;
+        self.pixels_per_day = zoom.pixels_per_day.clamp(MIN_PIXELS_PER_DAY, MAX_PIXELS_PER_DAY);
+    }
+
+    /// The preset the viewport is currently at, if any.
+    pub fn active_preset(&self, width: f32) -> Option<ZoomPreset> {
+        ZoomPreset::all().iter().copied().find(|&preset| {
+            let mut probe = self.clone();
+            probe.apply_preset(preset, width);
+            probe.scale == self.scale && (probe.pixels_per_day - self.pixels_per_day).abs() < 0.01
+        })
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zooming_switches_scale_with_hysteresis() {
+        let day = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let mut viewport = TimelineViewport::new(day, day + chrono::Duration::days(90));
+        viewport.apply_preset(ZoomPreset::Day, 0.0);
+        while viewport.scale == TimelineScale::Days {
+            viewport.zoom_out();
+        }
+        assert_eq!(viewport.scale, TimelineScale::Weeks);
+        // One step back in is still inside the gap: the scale stays.
+        viewport.zoom_in();
+        assert_eq!(viewport.scale, TimelineScale::Weeks);
+        while viewport.scale != TimelineScale::Months {
+            viewport.zoom_out();
+        }
+        assert!(viewport.pixels_per_day < 5.0);
+        viewport.auto_scale = false;
+        for _ in 0..30 {
+            viewport.zoom_in();
+        }
+        assert_eq!(viewport.scale, TimelineScale::Months);
+        assert_eq!(viewport.pixels_per_day, MAX_PIXELS_PER_DAY);
+    }
 }
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::task::{Dependency, Task};
+
+/// Tasks removed by one delete, kept with the links that touched them so
+/// they can be restored later. Stored in the project file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: Uuid,
+    /// The deleted tasks, parents before their children.
+    pub tasks: Vec<Task>,
+    /// Links from or to any of the deleted tasks.
+    pub dependencies: Vec<Dependency>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashEntry {
+    /// Name of the first (top-most) deleted task, for listing.
+    pub fn title(&self) -> &str {
+        self.tasks.first().map(|t| t.name.as_str()).unwrap_or("")
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::project::Project;
+use super::task::Task;
+
+/// Kind of scheduling problem found by [`check_schedule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// A subtask starts before or ends after its parent.
+    OutsideParent,
+    /// Two tasks book the same resource beyond its capacity at once.
+    ResourceOverlap,
+    /// Every day of the task (or the milestone's date) is a non-working day.
+    NonWorkingDays,
+    /// Two tasks under the same parent share a resource and overlap in time.
+    SiblingOverlap,
+}
+
+impl IssueKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            IssueKind::OutsideParent => "Outside parent",
+            IssueKind::ResourceOverlap => "Resource overlap",
+            IssueKind::NonWorkingDays => "Non-working days",
+            IssueKind::SiblingOverlap => "Sibling overlap",
+        }
+    }
+
+    pub fn all() -> [IssueKind; 4] {
+        [
+            IssueKind::OutsideParent,
+            IssueKind::ResourceOverlap,
+            IssueKind::SiblingOverlap,
+            IssueKind::NonWorkingDays,
+        ]
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            IssueKind::OutsideParent => egui_phosphor::regular::BRACKETS_CURLY,
+            IssueKind::ResourceOverlap => egui_phosphor::regular::USERS,
+            IssueKind::NonWorkingDays => egui_phosphor::regular::CALENDAR_X,
+            IssueKind::SiblingOverlap => egui_phosphor::regular::INTERSECT,
+        }
+    }
+}
+
+/// One problem found in the schedule. `tasks` lists the tasks involved, the
+/// one to jump to first.
+#[derive(Debug, Clone)]
+pub struct ScheduleIssue {
+    pub kind: IssueKind,
+    pub tasks: Vec<Uuid>,
+    pub message: String,
+}
+
+fn day_range(start: NaiveDate, end: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    start.iter_days().take_while(move |d| *d < end)
+}
+
+/// Subtasks whose dates fall outside their parent's span. Parents roll up
+/// from their counted children, so this catches tentative work left out of
+/// the rollup and files edited by hand.
+fn outside_parent(project: &Project, out: &mut Vec<ScheduleIssue>) {
+    for child in &project.tasks {
+        let Some(parent) = child.parent_id.and_then(|pid| project.tasks.iter().find(|t| t.id == pid)) else {
+            continue;
+        };
+        if child.start < parent.start || child.end > parent.end {
+            out.push(ScheduleIssue {
+                kind: IssueKind::OutsideParent,
+                tasks: vec![child.id, parent.id],
+                message: format!(
+                    "'{}' ({} → {}) lies outside '{}' ({} → {})",
+                    child.name,
+                    child.start.format("%d/%m"),
+                    child.end.format("%d/%m"),
+                    parent.name,
+                    parent.start.format("%d/%m"),
+                    parent.end.format("%d/%m")
+                ),
+            });
+        }
+    }
+}
+
+/// Pairs of tasks sharing a resource whose combined units exceed its FTE on
+/// a working day they overlap.
+fn resource_overlaps(project: &Project, out: &mut Vec<ScheduleIssue>) {
+    let leaves: Vec<&Task> = project
+        .tasks
+        .iter()
+        .filter(|t| !t.is_milestone && !t.assignments.is_empty() && !t.has_children(&project.tasks) && project.is_counted(t))
+        .collect();
+    for resource in &project.resources {
+        let booked: Vec<(&Task, f32)> = leaves
+            .iter()
+            .filter_map(|t| t.assignments.iter().find(|a| a.resource_id == resource.id).map(|a| (*t, a.units)))
+            .collect();
+        for (i, (a, a_units)) in booked.iter().enumerate() {
+            for (b, b_units) in &booked[i + 1..] {
+                let (from, to) = (a.start.max(b.start), a.end.min(b.end));
+                if from >= to || a_units + b_units <= resource.fte + f32::EPSILON {
+                    continue;
+                }
+                if !day_range(from, to).any(|d| resource.is_available(&project.calendar, d)) {
+                    continue;
+                }
+                out.push(ScheduleIssue {
+                    kind: IssueKind::ResourceOverlap,
+                    tasks: vec![a.id, b.id],
+                    message: format!(
+                        "'{}' and '{}' both need {} from {} to {}",
+                        a.name,
+                        b.name,
+                        resource.name,
+                        from.format("%d/%m"),
+                        (to - chrono::Duration::days(1)).format("%d/%m")
+                    ),
+                });
+            }
+        }
+    }
+}
+
+/// Two sibling tasks booked on the same resource over the same dates.
+#[derive(Debug, Clone)]
+pub struct SiblingConflict {
+    pub a: Uuid,
+    pub b: Uuid,
+    pub resource: Uuid,
+    /// First day of the overlap.
+    pub from: NaiveDate,
+    /// Day after the overlap ends.
+    pub to: NaiveDate,
+}
+
+/// Pairs of tasks with the same parent (or both top-level) that share a
+/// resource and overlap in time, whatever the units booked. Siblings usually
+/// run in sequence, so this flags plans that double-book someone by mistake.
+pub fn sibling_conflicts(project: &Project) -> Vec<SiblingConflict> {
+    let mut groups: std::collections::HashMap<Option<Uuid>, Vec<&Task>> = std::collections::HashMap::new();
+    for task in project.tasks.iter().filter(|t| !t.is_milestone && !t.assignments.is_empty() && project.is_counted(t)) {
+        groups.entry(task.parent_id).or_default().push(task);
+    }
+    let mut out = Vec::new();
+    for siblings in groups.values() {
+        for (i, a) in siblings.iter().enumerate() {
+            for b in &siblings[i + 1..] {
+                let (from, to) = (a.start.max(b.start), a.end.min(b.end));
+                if from >= to {
+                    continue;
+                }
+                for assignment in &a.assignments {
+                    if b.assignments.iter().any(|x| x.resource_id == assignment.resource_id) {
+                        out.push(SiblingConflict { a: a.id, b: b.id, resource: assignment.resource_id, from, to });
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+fn sibling_overlaps(project: &Project, out: &mut Vec<ScheduleIssue>) {
+    let name = |id: Uuid| project.tasks.iter().find(|t| t.id == id).map_or("?", |t| t.name.as_str());
+    for conflict in sibling_conflicts(project) {
+        let resource = project.resources.iter().find(|r| r.id == conflict.resource).map_or("?", |r| r.name.as_str());
+        out.push(ScheduleIssue {
+            kind: IssueKind::SiblingOverlap,
+            tasks: vec![conflict.a, conflict.b],
+            message: format!(
+                "'{}' and '{}' both assign {} from {} to {}",
+                name(conflict.a),
+                name(conflict.b),
+                resource,
+                conflict.from.format("%d/%m"),
+                (conflict.to - chrono::Duration::days(1)).format("%d/%m")
+            ),
+        });
+    }
+}
+
+/// Tasks that only cover non-working days, and milestones on one.
+fn non_working(project: &Project, out: &mut Vec<ScheduleIssue>) {
+    for task in project.tasks.iter().filter(|t| !t.has_children(&project.tasks)) {
+        let working = if task.is_milestone || task.end <= task.start {
+            project.calendar.is_working_day(task.start)
+        } else {
+            day_range(task.start, task.end).any(|d| project.calendar.is_working_day(d))
+        };
+        if !working {
+            out.push(ScheduleIssue {
+                kind: IssueKind::NonWorkingDays,
+                tasks: vec![task.id],
+                message: format!(
+                    "'{}' is scheduled only on non-working days ({})",
+                    task.name,
+                    task.start.format("%a %d/%m")
+                ),
+            });
+        }
+    }
+}
+
+/// Scan the project for date conflicts: subtasks outside their parent,
+/// overbooked resources and work scheduled entirely on non-working days.
+/// With `siblings`, also siblings sharing a resource at the same time.
+pub fn check_schedule(project: &Project, siblings: bool) -> Vec<ScheduleIssue> {
+    let mut issues = Vec::new();
+    outside_parent(project, &mut issues);
+    resource_overlaps(project, &mut issues);
+    if siblings {
+        sibling_overlaps(project, &mut issues);
+    }
+    non_working(project, &mut issues);
+    issues
+}
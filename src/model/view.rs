@@ -0,0 +1,215 @@
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::calendar::ProjectCalendar;
+use super::task::TaskPriority;
+use super::timeline::TimelineScale;
+
+/// What the task bar fill color represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ColorMode {
+    /// Each task's own color.
+    #[default]
+    Own,
+    Priority,
+    Status,
+    Assignee,
+    Health,
+    /// The color of the task's top-level phase.
+    Phase,
+}
+
+impl ColorMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorMode::Own => "Own Color",
+            ColorMode::Priority => "Priority",
+            ColorMode::Status => "Status",
+            ColorMode::Assignee => "Assignee",
+            ColorMode::Health => "Health",
+            ColorMode::Phase => "Phase",
+        }
+    }
+
+    pub fn all() -> &'static [ColorMode] {
+        &[
+            ColorMode::Own,
+            ColorMode::Priority,
+            ColorMode::Status,
+            ColorMode::Assignee,
+            ColorMode::Health,
+            ColorMode::Phase,
+        ]
+    }
+}
+
+/// What the faint tint behind each chart row represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RowTint {
+    #[default]
+    Off,
+    Priority,
+    Health,
+}
+
+impl RowTint {
+    pub fn label(self) -> &'static str {
+        match self {
+            RowTint::Off => "Off",
+            RowTint::Priority => "Priority",
+            RowTint::Health => "Health",
+        }
+    }
+
+    pub fn all() -> &'static [RowTint] {
+        &[RowTint::Off, RowTint::Priority, RowTint::Health]
+    }
+}
+
+/// What the swimlane view groups tasks by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LaneGroup {
+    /// The task's top-level phase.
+    #[default]
+    Phase,
+    Assignee,
+    Priority,
+}
+
+impl LaneGroup {
+    pub fn label(self) -> &'static str {
+        match self {
+            LaneGroup::Phase => "Phase",
+            LaneGroup::Assignee => "Assignee",
+            LaneGroup::Priority => "Priority",
+        }
+    }
+
+    pub fn all() -> &'static [LaneGroup] {
+        &[LaneGroup::Phase, LaneGroup::Assignee, LaneGroup::Priority]
+    }
+}
+
+/// Column width of the roadmap view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RoadmapBucket {
+    #[default]
+    Quarter,
+    Month,
+}
+
+impl RoadmapBucket {
+    pub fn label(self) -> &'static str {
+        match self {
+            RoadmapBucket::Quarter => "Quarters",
+            RoadmapBucket::Month => "Months",
+        }
+    }
+
+    pub fn all() -> &'static [RoadmapBucket] {
+        &[RoadmapBucket::Quarter, RoadmapBucket::Month]
+    }
+
+    fn months(self) -> u32 {
+        match self {
+            RoadmapBucket::Quarter => 3,
+            RoadmapBucket::Month => 1,
+        }
+    }
+
+    /// First day of the bucket containing `date`.
+    pub fn floor(self, date: NaiveDate) -> NaiveDate {
+        let month0 = date.month0() - date.month0() % self.months();
+        NaiveDate::from_ymd_opt(date.year(), month0 + 1, 1).unwrap_or(date)
+    }
+
+    /// First day of the bucket after the one starting at `start`.
+    pub fn next(self, start: NaiveDate) -> NaiveDate {
+        start.checked_add_months(Months::new(self.months())).unwrap_or(start)
+    }
+
+    /// Column heading, e.g. "Q3 2026" or "Sep 2026".
+    pub fn title(self, start: NaiveDate) -> String {
+        match self {
+            RoadmapBucket::Quarter => format!("Q{} {}", start.month0() / 3 + 1, start.year()),
+            RoadmapBucket::Month => start.format("%b %Y").to_string(),
+        }
+    }
+}
+
+/// Layout of the chart's frozen task-name column.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NameGutter {
+    pub show: bool,
+    /// Width in points; `None` fits the longest visible name.
+    pub width: Option<f32>,
+    /// Wrap long names onto as many lines as the row fits instead of
+    /// truncating them with an ellipsis.
+    pub wrap: bool,
+}
+
+/// A named snapshot of how the project is being looked at: zoom, scale,
+/// scroll position, filters and bar coloring. Saved views are stored in the project file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedView {
+    pub name: String,
+    /// Zoom level (pixels per day).
+    pub pixels_per_day: f32,
+    pub scale: TimelineScale,
+    /// Date at the left edge of the chart. Stored as a date rather than a
+    /// pixel offset so the view survives changes to the project's date range.
+    pub left_date: NaiveDate,
+    /// Vertical scroll offset of the chart, in pixels.
+    #[serde(default)]
+    pub scroll_y: f32,
+    #[serde(default)]
+    pub search: String,
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    pub color_mode: ColorMode,
+    /// Name column layout; views saved before it existed leave it alone.
+    #[serde(default)]
+    pub name_gutter: Option<NameGutter>,
+}
+
+/// Part of the timeline an export or printout covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportRange {
+    #[default]
+    Project,
+    ThisQuarter,
+    /// First and last day, inclusive.
+    Custom(NaiveDate, NaiveDate),
+}
+
+impl ExportRange {
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportRange::Project => "Whole project",
+            ExportRange::ThisQuarter => "This quarter",
+            ExportRange::Custom(..) => "Custom range",
+        }
+    }
+
+    /// Dates to crop to, the end exclusive like task ends; `None` for the
+    /// whole project.
+    pub fn dates(self, calendar: &ProjectCalendar, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        match self {
+            ExportRange::Project => None,
+            ExportRange::ThisQuarter => Some(calendar.quarter_bounds(today)),
+            ExportRange::Custom(from, to) => Some((from, to.max(from) + chrono::Duration::days(1))),
+        }
+    }
+}
+
+/// Whether a task shows within `from..to`: bars that overlap it, milestones
+/// that fall in it.
+pub fn in_range(task: &super::task::Task, (from, to): (NaiveDate, NaiveDate)) -> bool {
+    if task.is_milestone {
+        from <= task.start && task.start < to
+    } else {
+        task.start < to && task.end > from
+    }
+}
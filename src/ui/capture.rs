@@ -0,0 +1,200 @@
+//! "Capture Region…": drag a rectangle over the chart, then copy that part
+//! of the window as an image or save it as a PNG, at the display's full
+//! pixel density.
+
+use std::sync::Arc;
+
+use crate::ui::theme;
+use egui::{Color32, ColorImage, Context, Pos2, Rect, RichText};
+
+/// Regions smaller than this (in points) are taken for a stray click.
+const MIN_SIZE: f32 = 8.0;
+
+pub enum Capture {
+    /// Waiting for a rectangle to be dragged out, from `from`.
+    Selecting { from: Option<Pos2> },
+    /// A screenshot was requested; it is cut to this region on arrival.
+    Requested(Rect),
+    /// The captured region, to copy or save.
+    Ready {
+        image: Arc<ColorImage>,
+        texture: Option<egui::TextureHandle>,
+    },
+}
+
+impl Capture {
+    pub fn new() -> Self {
+        Capture::Selecting { from: None }
+    }
+}
+
+pub enum CaptureAction {
+    None,
+    Cancel,
+    Copy(Arc<ColorImage>),
+    Save(Arc<ColorImage>),
+}
+
+/// Run the capture one frame on: the selection overlay over `bounds`, the
+/// pending screenshot, or the preview with its buttons.
+pub fn show_capture(ctx: &Context, capture: &mut Capture, bounds: Rect) -> CaptureAction {
+    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        return CaptureAction::Cancel;
+    }
+    match capture {
+        Capture::Selecting { from } => {
+            if let Some(region) = select_region(ctx, from, bounds) {
+                // Request the shot in a frame without the overlay on it.
+                *capture = Capture::Requested(region);
+                ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+                ctx.request_repaint();
+            }
+            CaptureAction::None
+        }
+        Capture::Requested(region) => {
+            let shot = ctx.input(|i| {
+                i.raw.events.iter().find_map(|e| match e {
+                    egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                    _ => None,
+                })
+            });
+            if let Some(shot) = shot {
+                let image = shot.region(region, Some(ctx.pixels_per_point()));
+                *capture = Capture::Ready { image: Arc::new(image), texture: None };
+            }
+            ctx.request_repaint();
+            CaptureAction::None
+        }
+        Capture::Ready { image, texture } => show_preview(ctx, image, texture),
+    }
+}
+
+/// Dim `bounds` around the rectangle being dragged. Returns the region
+/// once the drag ends.
+fn select_region(ctx: &Context, from: &mut Option<Pos2>, bounds: Rect) -> Option<Rect> {
+    let mut done = None;
+    egui::Area::new(egui::Id::new("capture-region"))
+        .order(egui::Order::Foreground)
+        .fixed_pos(bounds.min)
+        .show(ctx, |ui| {
+            let response = ui.allocate_rect(Rect::from_min_size(bounds.min, bounds.size()), egui::Sense::drag());
+            ctx.set_cursor_icon(egui::CursorIcon::Crosshair);
+            if response.drag_started() {
+                *from = response.interact_pointer_pos();
+            }
+            let pointer = response.interact_pointer_pos().or(ctx.pointer_latest_pos());
+            let region = from.zip(pointer).map(|(a, b)| Rect::from_two_pos(a, b).intersect(bounds));
+            if response.drag_stopped() {
+                done = region.filter(|r| r.width() >= MIN_SIZE && r.height() >= MIN_SIZE);
+                *from = None;
+                if done.is_some() {
+                    // Leave this frame's chart uncovered for the screenshot.
+                    return;
+                }
+            }
+
+            let painter = ui.painter();
+            let shade = Color32::from_black_alpha(110);
+            match region.filter(|_| from.is_some()) {
+                Some(r) => {
+                    painter.rect_filled(Rect::from_min_max(bounds.min, egui::pos2(bounds.max.x, r.top())), 0.0, shade);
+                    painter.rect_filled(Rect::from_min_max(egui::pos2(bounds.min.x, r.bottom()), bounds.max), 0.0, shade);
+                    painter.rect_filled(Rect::from_min_max(egui::pos2(bounds.min.x, r.top()), r.left_bottom()), 0.0, shade);
+                    painter.rect_filled(Rect::from_min_max(r.right_top(), egui::pos2(bounds.max.x, r.bottom())), 0.0, shade);
+                    painter.rect_stroke(r, 0.0, egui::Stroke::new(1.5, theme::accent()));
+                    let ppp = ctx.pixels_per_point();
+                    painter.text(
+                        r.right_bottom() + egui::vec2(0.0, 4.0),
+                        egui::Align2::RIGHT_TOP,
+                        format!("{:.0} × {:.0} px", r.width() * ppp, r.height() * ppp),
+                        egui::FontId::proportional(11.0),
+                        theme::text_primary(),
+                    );
+                }
+                None => {
+                    painter.rect_filled(bounds, 0.0, shade);
+                    painter.text(
+                        bounds.center(),
+                        egui::Align2::CENTER_CENTER,
+                        "Drag over the part of the chart to capture · Esc to cancel",
+                        egui::FontId::proportional(13.0),
+                        Color32::WHITE,
+                    );
+                }
+            }
+        });
+    done
+}
+
+fn show_preview(
+    ctx: &Context,
+    image: &Arc<ColorImage>,
+    texture: &mut Option<egui::TextureHandle>,
+) -> CaptureAction {
+    let mut action = CaptureAction::None;
+    let texture = texture.get_or_insert_with(|| {
+        ctx.load_texture("capture-preview", ColorImage::clone(image), egui::TextureOptions::LINEAR)
+    });
+    let layout = theme::layout();
+    egui::Window::new(RichText::new("Capture Region").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            let size = texture.size_vec2();
+            let scale = (layout.dialog_width / size.x).min(240.0 / size.y).min(1.0);
+            ui.vertical_centered(|ui| {
+                ui.add(egui::Image::new((texture.id(), size * scale)));
+            });
+            ui.label(
+                RichText::new(format!("{} × {} px", image.width(), image.height()))
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let copy_btn = egui::Button::new(RichText::new("Copy").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], copy_btn).clicked() {
+                    action = CaptureAction::Copy(image.clone());
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Save…")).clicked() {
+                    action = CaptureAction::Save(image.clone());
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    action = CaptureAction::Cancel;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    action
+}
+
+/// Put `image` on the system clipboard.
+pub fn copy_image(image: &ColorImage) -> Result<(), String> {
+    let mut clipboard = arboard::Clipboard::new().map_err(|e| format!("Clipboard unavailable: {}", e))?;
+    clipboard
+        .set_image(arboard::ImageData {
+            width: image.width(),
+            height: image.height(),
+            bytes: std::borrow::Cow::Borrowed(image.as_raw()),
+        })
+        .map_err(|e| format!("Failed to copy the image: {}", e))
+}
+
+/// Write `image` to `path` as a PNG.
+pub fn save_png(image: &ColorImage, path: &std::path::Path) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), image.width() as u32, image.height() as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(image.as_raw()))
+        .map_err(|e| format!("Failed to write the image: {}", e))
+}
@@ -0,0 +1,206 @@
+use crate::model::view::{ColorMode, RowTint};
+use crate::model::task::TaskPriority;
+use crate::model::{Project, Task};
+use crate::ui::theme;
+use chrono::NaiveDate;
+use egui::{Color32, Context, RichText};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const NOT_STARTED: Color32 = Color32::from_rgb(130, 140, 155);
+const DONE: Color32 = Color32::from_rgb(80, 170, 100);
+
+pub fn priority_color(priority: TaskPriority) -> Color32 {
+    match priority {
+        TaskPriority::Critical => Color32::from_rgb(220, 60, 60),
+        TaskPriority::High => Color32::from_rgb(220, 140, 40),
+        TaskPriority::Medium => Color32::from_rgb(200, 180, 40),
+        TaskPriority::Low => Color32::from_rgb(80, 160, 80),
+        TaskPriority::None => theme::text_dim(),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    NotStarted,
+    InProgress,
+    Done,
+    Overdue,
+}
+
+fn status(task: &Task, today: NaiveDate) -> Status {
+    if task.progress >= 1.0 {
+        Status::Done
+    } else if task.end < today {
+        Status::Overdue
+    } else if task.progress > 0.0 || task.start <= today {
+        Status::InProgress
+    } else {
+        Status::NotStarted
+    }
+}
+
+fn status_entries() -> [(Status, &'static str, Color32); 4] {
+    [
+        (Status::NotStarted, "Not started", NOT_STARTED),
+        (Status::InProgress, "In progress", theme::accent()),
+        (Status::Done, "Done", DONE),
+        (Status::Overdue, "Overdue", theme::slip_major()),
+    ]
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Health {
+    OnTrack,
+    AtRisk,
+    Late,
+    Done,
+}
+
+/// Late: past its end date. At risk: more than 10% behind the progress
+/// expected by today, or finishing after its baseline.
+fn health(project: &Project, task: &Task, today: NaiveDate) -> Health {
+    let slip = project.baseline.as_ref().and_then(|b| b.finish_slip_days(task)).unwrap_or(0);
+    if task.progress >= 1.0 {
+        Health::Done
+    } else if task.end < today {
+        Health::Late
+    } else if slip > 0 || task.progress + 0.1 < task.expected_progress(today) {
+        Health::AtRisk
+    } else {
+        Health::OnTrack
+    }
+}
+
+fn health_entries() -> [(Health, &'static str, Color32); 4] {
+    [
+        (Health::OnTrack, "On track", DONE),
+        (Health::AtRisk, "At risk", theme::slip_moderate()),
+        (Health::Late, "Late", theme::slip_major()),
+        (Health::Done, "Done", NOT_STARTED),
+    ]
+}
+
+fn resource_color(project: &Project, resource_id: Uuid) -> Color32 {
+    project
+        .resources
+        .iter()
+        .position(|r| r.id == resource_id)
+        .map(theme::task_color)
+        .unwrap_or_else(theme::text_dim)
+}
+
+fn top_level_ancestor<'a>(project: &'a Project, task: &'a Task) -> &'a Task {
+    let mut current = task;
+    while let Some(parent) = current.parent_id.and_then(|pid| project.tasks.iter().find(|t| t.id == pid)) {
+        current = parent;
+    }
+    current
+}
+
+/// Fill colors to draw instead of each task's own color. Empty for
+/// [`ColorMode::Own`]; stored task colors are never changed.
+pub fn bar_colors(project: &Project, mode: ColorMode, today: NaiveDate) -> HashMap<Uuid, Color32> {
+    if mode == ColorMode::Own {
+        return HashMap::new();
+    }
+    project
+        .tasks
+        .iter()
+        .map(|task| {
+            let color = match mode {
+                ColorMode::Own => task.color,
+                ColorMode::Priority => priority_color(task.priority),
+                ColorMode::Status => {
+                    let s = status(task, today);
+                    status_entries().iter().find(|e| e.0 == s).map_or(task.color, |e| e.2)
+                }
+                ColorMode::Health => {
+                    let h = health(project, task, today);
+                    health_entries().iter().find(|e| e.0 == h).map_or(task.color, |e| e.2)
+                }
+                ColorMode::Assignee => task
+                    .assignments
+                    .first()
+                    .map_or(theme::text_dim(), |a| resource_color(project, a.resource_id)),
+                ColorMode::Phase => top_level_ancestor(project, task).color,
+            };
+            (task.id, color)
+        })
+        .collect()
+}
+
+/// Colors for the faint row tint behind chart rows. Only rows worth a look
+/// are tinted: tasks with a priority, or tasks at risk or late.
+pub fn row_tints(project: &Project, tint: RowTint, today: NaiveDate) -> HashMap<Uuid, Color32> {
+    project
+        .tasks
+        .iter()
+        .filter_map(|task| {
+            let color = match tint {
+                RowTint::Off => return None,
+                RowTint::Priority if task.priority == TaskPriority::None => return None,
+                RowTint::Priority => priority_color(task.priority),
+                RowTint::Health => match health(project, task, today) {
+                    Health::AtRisk => theme::slip_moderate(),
+                    Health::Late => theme::slip_major(),
+                    Health::OnTrack | Health::Done => return None,
+                },
+            };
+            Some((task.id, color))
+        })
+        .collect()
+}
+
+/// Legend rows (label, color) for the active mode.
+pub fn legend_entries(project: &Project, mode: ColorMode) -> Vec<(String, Color32)> {
+    match mode {
+        ColorMode::Own => Vec::new(),
+        ColorMode::Priority => TaskPriority::all()
+            .iter()
+            .map(|&p| {
+                let label = if p == TaskPriority::None { "No priority" } else { p.label() };
+                (label.to_string(), priority_color(p))
+            })
+            .collect(),
+        ColorMode::Status => status_entries().iter().map(|e| (e.1.to_string(), e.2)).collect(),
+        ColorMode::Health => health_entries().iter().map(|e| (e.1.to_string(), e.2)).collect(),
+        ColorMode::Assignee => project
+            .resources
+            .iter()
+            .map(|r| (r.name.clone(), resource_color(project, r.id)))
+            .chain(std::iter::once(("Unassigned".to_string(), theme::text_dim())))
+            .collect(),
+        ColorMode::Phase => project
+            .tasks
+            .iter()
+            .filter(|t| t.parent_id.is_none() && t.has_children(&project.tasks))
+            .map(|t| (t.name.clone(), t.color))
+            .collect(),
+    }
+}
+
+/// Draw the legend for the active color mode in the bottom-right corner.
+pub fn show_legend(ctx: &Context, mode: ColorMode, entries: &[(String, Color32)]) {
+    if entries.is_empty() {
+        return;
+    }
+    let rect = ctx.available_rect();
+    egui::Area::new(egui::Id::new("color-by-legend"))
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .pivot(egui::Align2::RIGHT_BOTTOM)
+        .fixed_pos(egui::pos2(rect.right() - 16.0, rect.bottom() - 16.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(RichText::new(format!("Color: {}", mode.label())).strong().size(11.0));
+                for (label, color) in entries {
+                    ui.horizontal(|ui| {
+                        let (swatch, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                        ui.painter().rect_filled(swatch, egui::Rounding::same(2.0), *color);
+                        ui.label(RichText::new(label).size(10.5).color(theme::text_secondary()));
+                    });
+                }
+            });
+        });
+}
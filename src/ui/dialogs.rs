@@ -1,4 +1,5 @@
 use crate::app::GanttApp;
+use crate::model::ProjectCommand;
 use crate::ui::theme;
 use egui::{Color32, Context, RichText, Window};
 
@@ -52,6 +53,28 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
                     ui.end_row();
                 });
 
+            ui.add_space(4.0);
+            egui::CollapsingHeader::new(RichText::new("More options").color(theme::text_secondary()))
+                .id_salt("add_task_more")
+                .show(ui, |ui| add_task_more_options(app, ui));
+
+            if let Some(previous) = app.follow_up_from {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!(
+                            "{}  Follows '{}' (finish → start)",
+                            egui_phosphor::regular::ARROW_BEND_DOWN_RIGHT,
+                            app.task_name(previous)
+                        ))
+                        .size(10.5)
+                        .color(theme::text_secondary()),
+                    );
+                    if ui.small_button(egui_phosphor::regular::X).on_hover_text("Don't link").clicked() {
+                        app.follow_up_from = None;
+                    }
+                });
+            }
+
             ui.add_space(6.0);
             ui.separator();
             ui.add_space(4.0);
@@ -66,6 +89,12 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
                     app.create_task_from_dialog();
                     should_close = true;
                 }
+                let follow_up = ui
+                    .add(egui::Button::new("Create + Follow-up").min_size(egui::vec2(0.0, 28.0)))
+                    .on_hover_text("Create this task and start the next one where it ends, linked from it (Ctrl+Enter)");
+                if follow_up.clicked() || ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Enter)) {
+                    app.create_follow_up_from_dialog();
+                }
                 if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
                     should_close = true;
                 }
@@ -81,6 +110,9 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
     if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
         app.show_add_task = false;
     }
+    if !app.show_add_task {
+        app.follow_up_from = None;
+    }
     // If the window title bar close button was clicked
     if let Some(resp) = resp {
         if resp.response.clicked_elsewhere() && !resp.response.has_focus() {
@@ -89,6 +121,87 @@ pub fn show_add_task_dialog(app: &mut GanttApp, ctx: &Context) {
     }
 }
 
+/// Priority, phase, color, assignee and predecessor of the task being added.
+fn add_task_more_options(app: &mut GanttApp, ui: &mut egui::Ui) {
+    use crate::model::task::TaskPriority;
+
+    let none = "— None —";
+    let name_of = |id: Option<uuid::Uuid>| id.map_or(none.to_string(), |id| app.task_name(id));
+    let (parent_label, predecessor_label) = (name_of(app.new_task_parent), name_of(app.follow_up_from));
+    egui::Grid::new("add_task_more_grid")
+        .num_columns(2)
+        .spacing([12.0, 8.0])
+        .show(ui, |ui| {
+            ui.label(RichText::new("Priority").color(theme::text_secondary()));
+            egui::ComboBox::from_id_salt("add_task_priority")
+                .selected_text(format!("{} {}", app.new_task_priority.icon(), app.new_task_priority.label()))
+                .show_ui(ui, |ui| {
+                    for p in TaskPriority::all() {
+                        ui.selectable_value(&mut app.new_task_priority, *p, format!("{} {}", p.icon(), p.label()));
+                    }
+                });
+            ui.end_row();
+
+            // Like the editor, only top-level tasks can be phases.
+            ui.label(RichText::new("Phase / Parent").color(theme::text_secondary()));
+            egui::ComboBox::from_id_salt("add_task_parent")
+                .width(200.0)
+                .selected_text(parent_label)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut app.new_task_parent, None, none);
+                    for t in app.project.tasks.iter().filter(|t| t.parent_id.is_none() && !t.is_milestone) {
+                        ui.selectable_value(&mut app.new_task_parent, Some(t.id), &t.name);
+                    }
+                });
+            ui.end_row();
+
+            ui.label(RichText::new("Color").color(theme::text_secondary()));
+            ui.horizontal(|ui| {
+                let mut color = app.new_task_custom_color.unwrap_or_else(|| {
+                    let parent = app.new_task_parent.and_then(|p| app.project.tasks.iter().find(|t| t.id == p));
+                    app.new_task_color(parent)
+                });
+                if ui.color_edit_button_srgba(&mut color).changed() {
+                    app.new_task_custom_color = Some(color);
+                }
+                if app.new_task_custom_color.is_some() && ui.small_button("Default").clicked() {
+                    app.new_task_custom_color = None;
+                }
+            });
+            ui.end_row();
+
+            ui.label(RichText::new("Assigned to").color(theme::text_secondary()));
+            let assignee = app
+                .new_task_assignee
+                .and_then(|id| app.project.resources.iter().find(|r| r.id == id))
+                .map_or(none.to_string(), |r| r.name.clone());
+            egui::ComboBox::from_id_salt("add_task_assignee")
+                .width(200.0)
+                .selected_text(assignee)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut app.new_task_assignee, None, none);
+                    for r in &app.project.resources {
+                        ui.selectable_value(&mut app.new_task_assignee, Some(r.id), &r.name);
+                    }
+                });
+            ui.end_row();
+
+            ui.label(RichText::new("Predecessor").color(theme::text_secondary()));
+            egui::ComboBox::from_id_salt("add_task_predecessor")
+                .width(200.0)
+                .selected_text(predecessor_label)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut app.follow_up_from, None, none);
+                    for t in &app.project.tasks {
+                        ui.selectable_value(&mut app.follow_up_from, Some(t.id), &t.name);
+                    }
+                })
+                .response
+                .on_hover_text("Link from this task, finish → start");
+            ui.end_row();
+        });
+}
+
 /// Render the "About" dialog.
 pub fn show_about_dialog(app: &mut GanttApp, ctx: &Context) {
     let mut should_close = false;
@@ -117,3 +230,3192 @@ pub fn show_about_dialog(app: &mut GanttApp, ctx: &Context) {
         app.show_about = false;
     }
 }
+
+/// Render the "File changed on disk" dialog shown when saving would overwrite
+/// modifications made by another process since the project was loaded.
+pub fn show_save_conflict_dialog(app: &mut GanttApp, ctx: &Context) {
+    let layout = theme::layout();
+    Window::new(RichText::new("File Changed on Disk").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(
+                    "The project file was modified by another program after it was opened. \
+                     How do you want to save?",
+                )
+                .color(theme::text_secondary()),
+            );
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new("Merge keeps your edits and adds tasks only present on disk.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let merge_btn = egui::Button::new(RichText::new("Merge").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([70.0, 28.0], merge_btn).clicked() {
+                    app.save_conflict_merge();
+                }
+                if ui.add_sized([70.0, 28.0], egui::Button::new("Overwrite")).clicked() {
+                    app.save_conflict_overwrite();
+                }
+                if ui.add_sized([70.0, 28.0], egui::Button::new("Save As…")).clicked() {
+                    app.save_conflict_save_as();
+                }
+                if ui.add_sized([60.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    app.show_save_conflict = false;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_save_conflict = false;
+    }
+}
+
+/// Render the "Live Session" dialog: host the open plan for co-editing,
+/// join another instance's session, or leave the current one.
+pub fn show_live_session_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let layout = theme::layout();
+    Window::new(RichText::new("Live Session (Experimental)").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(
+                    "Everyone in the session edits the same plan and sees the others' changes within a moment. \
+                     The host's plan is the one shared, and only the host saves it to its file.",
+                )
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            if let Some(live) = &app.live {
+                let state = match (live.hosting, live.ready) {
+                    (true, _) => format!("Hosting on {} — {} connected", live.address, live.peer_count()),
+                    (false, true) => format!("Joined {}", live.address),
+                    (false, false) => format!("Connected to {}, waiting for the plan…", live.address),
+                };
+                ui.label(RichText::new(state).color(theme::text_primary()));
+                if live.hosting {
+                    ui.horizontal(|ui| {
+                        ui.label("Token");
+                        ui.label(RichText::new(&live.token).monospace());
+                        if ui.small_button("Copy").on_hover_text("Those joining need this token").clicked() {
+                            ui.ctx().copy_text(live.token.clone());
+                        }
+                    });
+                }
+                ui.add_space(6.0);
+                let label = if live.hosting { "Stop Hosting" } else { "Leave" };
+                if ui.add_sized([100.0, 28.0], egui::Button::new(label)).clicked() {
+                    app.leave_live_session();
+                }
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label("Listen on");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.live_interface)
+                            .hint_text(crate::io::live::DEFAULT_INTERFACE)
+                            .desired_width(110.0),
+                    )
+                    .on_hover_text("127.0.0.1 keeps the session on this machine; use this machine's LAN address to share it");
+                    ui.label("Port");
+                    ui.add(egui::DragValue::new(&mut app.live_port).range(1024..=65535));
+                    let can_host = !app.live_interface.trim().is_empty();
+                    if ui
+                        .add_enabled(can_host, egui::Button::new("Host"))
+                        .on_hover_text("Share this plan with those you give the session token")
+                        .clicked()
+                    {
+                        app.host_live_session();
+                    }
+                });
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.label("Host");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.live_join_address)
+                            .hint_text("hostname:port")
+                            .desired_width(180.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Token");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.live_join_token)
+                            .hint_text("from the host")
+                            .desired_width(180.0),
+                    );
+                    let can_join = !app.live_join_address.trim().is_empty() && !app.live_join_token.trim().is_empty();
+                    if ui
+                        .add_enabled(can_join, egui::Button::new("Join"))
+                        .on_hover_text("Replace the open plan with the host's")
+                        .clicked()
+                    {
+                        app.join_live_session();
+                    }
+                });
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            let done_btn = egui::Button::new(RichText::new("Done").color(Color32::WHITE))
+                .fill(theme::accent())
+                .rounding(egui::Rounding::same(4.0));
+            if ui.add_sized([80.0, 28.0], done_btn).clicked() {
+                should_close = true;
+            }
+            ui.add_space(2.0);
+        });
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_live_session = false;
+    }
+}
+
+/// Render the "Sync Conflicts" dialog: items another planner changed in
+/// the shared folder while you changed them too, each settled by keeping
+/// your version or taking theirs.
+pub fn show_sync_conflicts_dialog(app: &mut GanttApp, ctx: &Context) {
+    let layout = theme::layout();
+    let mut resolve: Option<(Option<usize>, bool)> = None;
+    Window::new(RichText::new("Sync Conflicts").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("These items were changed in the shared folder while you were editing them.")
+                    .color(theme::text_secondary()),
+            );
+            ui.add_space(6.0);
+            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for (i, conflict) in app.sync_conflicts.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(&conflict.label).size(11.0));
+                        ui.label(RichText::new(format!("by {}", conflict.user)).size(10.0).color(theme::text_dim()));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("Take theirs").clicked() {
+                                resolve = Some((Some(i), true));
+                            }
+                            if ui.small_button("Keep mine").clicked() {
+                                resolve = Some((Some(i), false));
+                            }
+                        });
+                    });
+                }
+            });
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.add_sized([110.0, 28.0], egui::Button::new("Keep All Mine")).clicked() {
+                    resolve = Some((None, false));
+                }
+                if ui.add_sized([110.0, 28.0], egui::Button::new("Take All Theirs")).clicked() {
+                    resolve = Some((None, true));
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if let Some((index, take_theirs)) = resolve {
+        app.resolve_sync_conflict(index, take_theirs);
+    }
+}
+
+/// Render the "Resources" dialog for adding, renaming and removing resources.
+pub fn show_resources_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut remove: Option<uuid::Uuid> = None;
+    let mut edited: Vec<crate::model::Resource> = Vec::new();
+    let mut added: Option<ProjectCommand> = None;
+    let mut currency = app.project.currency.clone();
+    let layout = theme::layout();
+    Window::new(RichText::new("Resources").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.visuals_mut().extreme_bg_color = theme::bg_field();
+            ui.add_space(4.0);
+            if app.project.resources.is_empty() {
+                ui.label(
+                    RichText::new("No resources yet. Add one below or from the task editor.")
+                        .size(10.5)
+                        .color(theme::text_dim()),
+                );
+            }
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Currency").color(theme::text_secondary()));
+                ui.add(egui::TextEdit::singleline(&mut currency).desired_width(60.0));
+            });
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical().max_height(260.0).show(ui, |ui| {
+                let tasks = &app.project.tasks;
+                let currency = app.project.currency.clone();
+                let hours_per_day = app.project.calendar.hours_per_day;
+                for original in &app.project.resources {
+                    // Fields edit a copy; changes go in as one command.
+                    let mut renamed = false;
+                    let mut draft = original.clone();
+                    let resource = &mut draft;
+                    let count = tasks
+                        .iter()
+                        .filter(|t| t.assignments.iter().any(|a| a.resource_id == resource.id))
+                        .count();
+                    ui.horizontal(|ui| {
+                        renamed |= ui
+                            .add(egui::TextEdit::singleline(&mut resource.name).desired_width(120.0))
+                            .changed();
+                        renamed |= ui
+                            .add(
+                                egui::DragValue::new(&mut resource.rate)
+                                    .range(0.0..=100_000.0)
+                                    .speed(1.0)
+                                    .suffix(format!(" {}", currency)),
+                            )
+                            .on_hover_text("Cost rate")
+                            .changed();
+                        egui::ComboBox::from_id_salt(("rate-unit", resource.id))
+                            .selected_text(resource.rate_unit.label())
+                            .width(60.0)
+                            .show_ui(ui, |ui| {
+                                for unit in crate::model::resource::RateUnit::all() {
+                                    renamed |= ui
+                                        .selectable_value(&mut resource.rate_unit, *unit, unit.label())
+                                        .changed();
+                                }
+                            });
+                        renamed |= ui
+                            .add(
+                                egui::DragValue::new(&mut resource.fte)
+                                    .range(0.0..=10.0)
+                                    .speed(0.05)
+                                    .fixed_decimals(2)
+                                    .suffix(" FTE"),
+                            )
+                            .on_hover_text(format!(
+                                "Full-time equivalent ({:.1} h per day): caps the work and cost an assignment counts, and sets the capacity line in the Resource Load view",
+                                resource.fte * hours_per_day
+                            ))
+                            .changed();
+                        ui.label(
+                            RichText::new(format!("{} task{}", count, if count == 1 { "" } else { "s" }))
+                                .size(10.5)
+                                .color(theme::text_dim()),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let del = egui::Button::new(
+                                RichText::new(egui_phosphor::regular::TRASH).color(theme::text_dim()),
+                            )
+                            .frame(false);
+                            if ui.add(del).on_hover_text("Remove resource and its assignments").clicked() {
+                                remove = Some(resource.id);
+                            }
+                        });
+                    });
+                    renamed |= resource_work_pattern(resource, ui);
+                    if renamed {
+                        edited.push(draft);
+                    }
+                }
+            });
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                let resp = ui.add(
+                    egui::TextEdit::singleline(&mut app.new_resource_name)
+                        .hint_text("New resource name…")
+                        .desired_width(180.0),
+                );
+                let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                let can_add = !app.new_resource_name.trim().is_empty();
+                if (ui.add_enabled(can_add, egui::Button::new("Add")).clicked() || enter) && can_add {
+                    let name = app.new_resource_name.trim().to_string();
+                    added = Some(ProjectCommand::AddResource(crate::model::Resource::new(name)));
+                    app.new_resource_name.clear();
+                }
+            });
+            let pasted = crate::io::paste_list::read_resources(&app.paste_resources);
+            if paste_list_box(
+                ui,
+                "paste-resources",
+                "Paste a list of resources",
+                "Name and role, one per line — e.g. two columns copied from a spreadsheet",
+                &mut app.paste_resources,
+                (pasted.rows.len(), pasted.skipped),
+            ) {
+                added = Some(ProjectCommand::AddResourceList(pasted.rows));
+                app.paste_resources.clear();
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            if ui.add_sized([80.0, 28.0], egui::Button::new("Close")).clicked() {
+                should_close = true;
+            }
+            ui.add_space(2.0);
+        });
+    let new_step = GanttApp::edit_starts_step(ctx, "resources-undo-step");
+    for resource in edited {
+        app.apply_command(ProjectCommand::UpdateResource(resource), new_step);
+    }
+    if currency != app.project.currency {
+        app.apply_command(ProjectCommand::SetCurrency(currency), new_step);
+    }
+    if let Some(command) = added {
+        app.apply_command(command, true);
+    }
+    if let Some(id) = remove {
+        app.apply_command(ProjectCommand::RemoveResource(id), true);
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_resources = false;
+    }
+}
+
+/// A collapsed box to paste a two-column list into, with a count of the
+/// lines read. Returns true when its Add button is clicked.
+fn paste_list_box(
+    ui: &mut egui::Ui,
+    id: &str,
+    title: &str,
+    hint: &str,
+    text: &mut String,
+    (rows, skipped): (usize, usize),
+) -> bool {
+    let mut add = false;
+    egui::CollapsingHeader::new(RichText::new(title).size(10.5).color(theme::text_secondary()))
+        .id_salt(id)
+        .show(ui, |ui| {
+            ui.add(egui::TextEdit::multiline(text).hint_text(hint).desired_rows(4).desired_width(f32::INFINITY));
+            ui.horizontal(|ui| {
+                add = ui.add_enabled(rows > 0, egui::Button::new(format!("Add {}", rows))).clicked();
+                if skipped > 0 {
+                    ui.label(
+                        RichText::new(format!("{} line(s) not understood, skipped", skipped))
+                            .size(10.0)
+                            .color(theme::text_dim()),
+                    );
+                }
+            });
+        });
+    add
+}
+
+/// Render the "CSV Status Mapping" dialog: the progress each imported
+/// status value stands for. Edits apply to the next import.
+pub fn show_status_mapping_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut mapping = app.theme_manager.settings().status_mapping.clone();
+    let layout = theme::layout();
+    Window::new(RichText::new("CSV Status Mapping").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.visuals_mut().extreme_bg_color = theme::bg_field();
+            ui.add_space(4.0);
+            let mut remove = None;
+            egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                egui::Grid::new("status_mapping_grid")
+                    .num_columns(3)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Status").color(theme::text_secondary()));
+                        ui.label(RichText::new("Progress").color(theme::text_secondary()));
+                        ui.end_row();
+                        for (i, rule) in mapping.rules.iter_mut().enumerate() {
+                            ui.add(egui::TextEdit::singleline(&mut rule.status).desired_width(180.0));
+                            let mut percent = rule.progress * 100.0;
+                            if ui.add(egui::DragValue::new(&mut percent).range(0.0..=100.0).suffix(" %")).changed() {
+                                rule.progress = percent / 100.0;
+                            }
+                            if ui.small_button(egui_phosphor::regular::X).on_hover_text("Remove").clicked() {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+            if let Some(i) = remove {
+                mapping.rules.remove(i);
+            }
+            if ui.small_button("Add Status").clicked() {
+                mapping.rules.push(crate::io::csv_import::StatusRule { status: String::new(), progress: 0.0 });
+            }
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("Matched ignoring case. Other statuses import as not started, with a warning.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let done_btn = egui::Button::new(RichText::new("Done").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], done_btn).clicked() {
+                    should_close = true;
+                }
+                if ui.add(egui::Button::new("Reset").min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    mapping = Default::default();
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if mapping != app.theme_manager.settings().status_mapping {
+        app.theme_manager.update_settings(|s| s.status_mapping = mapping);
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_status_mapping = false;
+    }
+}
+
+/// Days off and absences of one resource, under a collapsed header in the
+/// Resources dialog. Returns whether anything changed.
+fn resource_work_pattern(resource: &mut crate::model::Resource, ui: &mut egui::Ui) -> bool {
+    let mut changed = false;
+    let away = resource.days_off.iter().filter(|off| **off).count() + resource.absences.len();
+    let mut title = if away == 0 { "Working pattern".to_string() } else { format!("Working pattern ({} set)", away) };
+    if !resource.role.trim().is_empty() {
+        title = format!("{} · {}", resource.role.trim(), title);
+    }
+    egui::CollapsingHeader::new(RichText::new(title).size(10.5).color(theme::text_secondary()))
+        .id_salt(("work-pattern", resource.id))
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Role").color(theme::text_secondary()));
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut resource.role).hint_text("e.g. Designer").desired_width(140.0))
+                    .changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Works").color(theme::text_secondary()));
+                for (day, label) in ["Mon", "Tue", "Wed", "Thu", "Fri"].iter().enumerate() {
+                    let mut works = !resource.days_off[day];
+                    if ui.checkbox(&mut works, *label).changed() {
+                        resource.days_off[day] = !works;
+                        changed = true;
+                    }
+                }
+            });
+            let mut remove = None;
+            for (i, absence) in resource.absences.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Away").color(theme::text_secondary()));
+                    changed |= ui
+                        .add(egui_extras::DatePickerButton::new(&mut absence.from).id_salt(&format!("away_from_{}_{}", resource.id, i)))
+                        .changed();
+                    ui.label("to");
+                    changed |= ui
+                        .add(egui_extras::DatePickerButton::new(&mut absence.to).id_salt(&format!("away_to_{}_{}", resource.id, i)))
+                        .changed();
+                    absence.to = absence.to.max(absence.from);
+                    changed |= ui
+                        .add(egui::TextEdit::singleline(&mut absence.note).hint_text("Note").desired_width(90.0))
+                        .changed();
+                    if ui.small_button(egui_phosphor::regular::X).on_hover_text("Remove").clicked() {
+                        remove = Some(i);
+                    }
+                });
+            }
+            if let Some(i) = remove {
+                resource.absences.remove(i);
+                changed = true;
+            }
+            if ui.small_button("Add Absence").clicked() {
+                let today = chrono::Local::now().date_naive();
+                resource.absences.push(crate::model::resource::Absence {
+                    from: today,
+                    to: today + chrono::Duration::days(4),
+                    note: String::new(),
+                });
+                changed = true;
+            }
+        });
+    changed
+}
+
+/// Render the "Project Properties" dialog: project name and Markdown notes.
+pub fn show_project_properties_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut description = app.project.description.clone();
+    let layout = theme::layout();
+    Window::new(RichText::new("Project Properties").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.visuals_mut().extreme_bg_color = theme::bg_field();
+            ui.add_space(4.0);
+            ui.label(RichText::new("Name").color(theme::text_secondary()));
+            ui.add(egui::TextEdit::singleline(&mut app.edit_project_name).desired_width(f32::INFINITY));
+            ui.add_space(6.0);
+            ui.label(RichText::new("Description / notes").color(theme::text_secondary()));
+            ui.add(
+                egui::TextEdit::multiline(&mut description)
+                    .hint_text("Goals, scope, stakeholders… (Markdown)")
+                    .desired_rows(8)
+                    .desired_width(f32::INFINITY),
+            );
+            ui.label(
+                RichText::new("Shown in Project Statistics and included in status reports.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let done_btn = egui::Button::new(RichText::new("Done").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], done_btn).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    let name = match app.edit_project_name.trim() {
+        "" => app.project.name.clone(),
+        name => name.to_string(),
+    };
+    if name != app.project.name || description != app.project.description {
+        let new_step = GanttApp::edit_starts_step(ctx, "properties-undo-step");
+        if !app.apply_command(ProjectCommand::SetProjectDetails { name, description }, new_step) {
+            app.edit_project_name = app.project.name.clone();
+        }
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_project_properties = false;
+    }
+}
+
+/// Render the "Project Settings" dialog: calendar conventions and other
+/// options stored in the project file.
+pub fn show_project_settings_dialog(app: &mut GanttApp, ctx: &Context) {
+    use crate::model::calendar::{WeekNumbering, WeekStart};
+
+    const MONTHS: [&str; 12] = [
+        "January", "February", "March", "April", "May", "June",
+        "July", "August", "September", "October", "November", "December",
+    ];
+
+    let mut should_close = false;
+    let mut calendar = app.project.calendar;
+    let mut include_tentative = app.project.include_tentative;
+    let layout = theme::layout();
+    Window::new(RichText::new("Project Settings").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            egui::Grid::new("project_settings_grid")
+                .num_columns(2)
+                .spacing([12.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Fiscal year starts").color(theme::text_secondary()));
+                    let month_idx = calendar.fiscal_year_start_month.clamp(1, 12) as usize - 1;
+                    egui::ComboBox::from_id_salt("fiscal_start_month")
+                        .selected_text(MONTHS[month_idx])
+                        .show_ui(ui, |ui| {
+                            for (i, name) in MONTHS.iter().enumerate() {
+                                ui.selectable_value(&mut calendar.fiscal_year_start_month, i as u32 + 1, *name);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(RichText::new("Week numbering").color(theme::text_secondary()));
+                    ui.vertical(|ui| {
+                        for scheme in [WeekNumbering::Iso, WeekNumbering::Us] {
+                            ui.radio_value(&mut calendar.week_numbering, scheme, scheme.label());
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(RichText::new("Weeks start on").color(theme::text_secondary()));
+                    egui::ComboBox::from_id_salt("week_start_day")
+                        .selected_text(calendar.week_start_day.label())
+                        .show_ui(ui, |ui| {
+                            for &day in WeekStart::all() {
+                                ui.selectable_value(&mut calendar.week_start_day, day, day.label());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(RichText::new("Working day").color(theme::text_secondary()));
+                    ui.add(
+                        egui::DragValue::new(&mut calendar.hours_per_day)
+                            .range(1.0..=24.0)
+                            .speed(0.25)
+                            .suffix(" hours"),
+                    )
+                    .on_hover_text("Hours in a full-time day: converts hourly rates into daily cost");
+                    ui.end_row();
+
+                    ui.label(RichText::new("Tentative work").color(theme::text_secondary()));
+                    ui.checkbox(&mut include_tentative, "Include in rollups and exports");
+                    ui.end_row();
+                });
+            ui.add_space(6.0);
+            let today = chrono::Local::now().date_naive();
+            ui.label(
+                RichText::new(format!(
+                    "Today is W{}, {}",
+                    calendar.week_number(today),
+                    calendar.quarter_label(today)
+                ))
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let done_btn = egui::Button::new(RichText::new("Done").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], done_btn).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if calendar != app.project.calendar {
+        let new_step = GanttApp::edit_starts_step(ctx, "settings-undo-step");
+        app.apply_command(ProjectCommand::SetCalendar(calendar), new_step);
+    }
+    if include_tentative != app.project.include_tentative {
+        app.apply_command(ProjectCommand::SetIncludeTentative(include_tentative), true);
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_project_settings = false;
+    }
+}
+
+/// Render the "Markers, Bands & Notes" dialog: named vertical lines such as
+/// a code freeze, shaded ranges such as a sprint, and free-floating notes
+/// with optional arrows, edited in place.
+pub fn show_markers_dialog(app: &mut GanttApp, ctx: &Context) {
+    use crate::model::{Annotation, DateBand, DateMarker};
+
+    let mut should_close = false;
+    let mut markers = app.project.markers.clone();
+    let mut bands = app.project.bands.clone();
+    let mut remove: Option<usize> = None;
+    let mut remove_band: Option<usize> = None;
+    let mut annotations = app.project.annotations.clone();
+    let mut remove_note: Option<usize> = None;
+    let mut holidays_added: Option<usize> = None;
+    let task_names: Vec<(uuid::Uuid, String)> = app.project.tasks.iter().map(|t| (t.id, t.name.clone())).collect();
+    let row_label = |task: Option<uuid::Uuid>, none: &str| {
+        task.and_then(|id| task_names.iter().find(|(t, _)| *t == id))
+            .map_or(none.to_string(), |(_, name)| name.clone())
+    };
+    let layout = theme::layout();
+    Window::new(RichText::new("Markers, Bands & Notes").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            if markers.is_empty() {
+                ui.label(
+                    RichText::new("No markers yet. Markers are drawn as labelled lines across the chart.")
+                        .size(10.0)
+                        .color(theme::text_dim()),
+                );
+            }
+            egui::Grid::new("markers_grid")
+                .num_columns(3)
+                .spacing([8.0, 6.0])
+                .show(ui, |ui| {
+                    for (i, marker) in markers.iter_mut().enumerate() {
+                        ui.add(egui::TextEdit::singleline(&mut marker.name).desired_width(180.0));
+                        ui.add(egui_extras::DatePickerButton::new(&mut marker.date).id_salt(&format!("marker_date_{}", marker.id)));
+                        if ui
+                            .add(egui::Button::new(RichText::new(egui_phosphor::regular::TRASH).color(theme::text_dim())).frame(false))
+                            .on_hover_text("Remove marker")
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(4.0);
+            if ui.button(egui_phosphor::regular::PLUS.to_string() + "  Add Marker").clicked() {
+                markers.push(DateMarker::new("Marker", chrono::Local::now().date_naive()));
+            }
+            let pasted = crate::io::paste_list::read_holidays(&app.paste_holidays);
+            if paste_list_box(
+                ui,
+                "paste-holidays",
+                "Paste a list of holidays",
+                "Date and name, one per line — e.g. two columns copied from a spreadsheet",
+                &mut app.paste_holidays,
+                (pasted.rows.len(), pasted.skipped),
+            ) {
+                let before = markers.len();
+                for (date, name) in pasted.rows {
+                    if !markers.iter().any(|m| m.date == date && m.name == name) {
+                        markers.push(DateMarker::new(name, date));
+                    }
+                }
+                app.paste_holidays.clear();
+                holidays_added = Some(markers.len() - before);
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.label(RichText::new("Shading bands").color(theme::text_secondary()));
+            ui.add_space(2.0);
+            egui::Grid::new("bands_grid")
+                .num_columns(4)
+                .spacing([8.0, 6.0])
+                .show(ui, |ui| {
+                    for (i, band) in bands.iter_mut().enumerate() {
+                        ui.add(egui::TextEdit::singleline(&mut band.name).desired_width(130.0));
+                        ui.add(egui_extras::DatePickerButton::new(&mut band.start).id_salt(&format!("band_start_{}", band.id)));
+                        ui.add(egui_extras::DatePickerButton::new(&mut band.end).id_salt(&format!("band_end_{}", band.id)));
+                        if ui
+                            .add(egui::Button::new(RichText::new(egui_phosphor::regular::TRASH).color(theme::text_dim())).frame(false))
+                            .on_hover_text("Remove band")
+                            .clicked()
+                        {
+                            remove_band = Some(i);
+                        }
+                        ui.end_row();
+                        band.end = band.end.max(band.start);
+                    }
+                });
+            ui.add_space(4.0);
+            if ui.button(egui_phosphor::regular::PLUS.to_string() + "  Add Band").clicked() {
+                let today = chrono::Local::now().date_naive();
+                bands.push(DateBand::new("Band", today, today + chrono::Duration::days(13)));
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.label(RichText::new("Notes").color(theme::text_secondary()));
+            ui.add_space(2.0);
+            egui::Grid::new("annotations_grid")
+                .num_columns(4)
+                .spacing([8.0, 6.0])
+                .show(ui, |ui| {
+                    for (i, note) in annotations.iter_mut().enumerate() {
+                        ui.add(egui::TextEdit::singleline(&mut note.text).desired_width(130.0));
+                        ui.add(egui_extras::DatePickerButton::new(&mut note.date).id_salt(&format!("note_date_{}", note.id)));
+                        egui::ComboBox::from_id_salt(("note_task", note.id))
+                            .width(110.0)
+                            .selected_text(row_label(note.task, "Above the chart"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut note.task, None, "Above the chart");
+                                for (id, name) in &task_names {
+                                    ui.selectable_value(&mut note.task, Some(*id), name);
+                                }
+                            });
+                        if ui
+                            .add(egui::Button::new(RichText::new(egui_phosphor::regular::TRASH).color(theme::text_dim())).frame(false))
+                            .on_hover_text("Remove note")
+                            .clicked()
+                        {
+                            remove_note = Some(i);
+                        }
+                        ui.end_row();
+
+                        let mut arrow = note.arrow_to.is_some();
+                        if ui.checkbox(&mut arrow, "Arrow to").changed() {
+                            note.arrow_to = arrow.then_some(note.date + chrono::Duration::days(7));
+                        }
+                        if let Some(to) = note.arrow_to.as_mut() {
+                            ui.add(egui_extras::DatePickerButton::new(to).id_salt(&format!("note_arrow_{}", note.id)));
+                            egui::ComboBox::from_id_salt(("note_arrow_task", note.id))
+                                .width(110.0)
+                                .selected_text(row_label(note.arrow_task, "Same row"))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut note.arrow_task, None, "Same row");
+                                    for (id, name) in &task_names {
+                                        ui.selectable_value(&mut note.arrow_task, Some(*id), name);
+                                    }
+                                });
+                        } else {
+                            note.arrow_task = None;
+                        }
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(4.0);
+            if ui.button(egui_phosphor::regular::PLUS.to_string() + "  Add Note").clicked() {
+                annotations.push(Annotation::new("Note", chrono::Local::now().date_naive()));
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let done_btn = egui::Button::new(RichText::new("Done").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], done_btn).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    let closing = should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape));
+    let new_step = GanttApp::edit_starts_step(ctx, "markers-undo-step");
+    if let Some(i) = remove {
+        markers.remove(i);
+    }
+    // Sorting waits for the dialog to close so rows don't jump while edited.
+    if closing {
+        markers.sort_by_key(|m| m.date);
+    }
+    if markers != app.project.markers {
+        app.apply_command(ProjectCommand::SetMarkers(markers), new_step);
+    }
+    if let Some(added) = holidays_added {
+        app.status_message = format!("Added {} holiday marker(s)", added);
+    }
+    if let Some(i) = remove_band {
+        bands.remove(i);
+    }
+    if bands != app.project.bands {
+        app.apply_command(ProjectCommand::SetBands(bands), new_step);
+    }
+    if let Some(i) = remove_note {
+        annotations.remove(i);
+    }
+    if annotations != app.project.annotations {
+        app.apply_command(ProjectCommand::SetAnnotations(annotations), new_step);
+    }
+    if closing {
+        app.show_markers = false;
+    }
+}
+
+/// Render the "Trash" dialog: deleted tasks, newest first, with restore and
+/// permanent-delete buttons and the retention period.
+pub fn show_trash_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut restore: Option<uuid::Uuid> = None;
+    let mut forget: Option<uuid::Uuid> = None;
+    let mut empty = false;
+    let mut retention = app.theme_manager.settings().trash_retention_days;
+    let layout = theme::layout();
+    let now = chrono::Utc::now();
+    Window::new(RichText::new("Trash").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            if app.project.trash.is_empty() {
+                ui.label(RichText::new("The trash is empty.").size(10.0).color(theme::text_dim()));
+            }
+            egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                egui::Grid::new("trash_grid")
+                    .num_columns(4)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        for entry in app.project.trash.iter().rev() {
+                            let extra = entry.tasks.len().saturating_sub(1);
+                            let title = if extra > 0 {
+                                format!("{} (+{} subtask{})", entry.title(), extra, if extra == 1 { "" } else { "s" })
+                            } else {
+                                entry.title().to_string()
+                            };
+                            ui.label(RichText::new(title).color(theme::text_primary()));
+                            let age = (now - entry.deleted_at).num_days();
+                            ui.label(
+                                RichText::new(match age {
+                                    0 => "today".to_string(),
+                                    1 => "yesterday".to_string(),
+                                    d => format!("{} days ago", d),
+                                })
+                                .size(10.0)
+                                .color(theme::text_dim()),
+                            );
+                            if ui
+                                .add(egui::Button::new(RichText::new(egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE).color(theme::text_secondary())).frame(false))
+                                .on_hover_text("Restore with its dependencies")
+                                .clicked()
+                            {
+                                restore = Some(entry.id);
+                            }
+                            if ui
+                                .add(egui::Button::new(RichText::new(egui_phosphor::regular::X).color(theme::text_dim())).frame(false))
+                                .on_hover_text("Delete permanently")
+                                .clicked()
+                            {
+                                forget = Some(entry.id);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Keep deleted tasks for").color(theme::text_secondary()));
+                ui.add(egui::DragValue::new(&mut retention).range(1..=365).suffix(" days"));
+            });
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let done_btn = egui::Button::new(RichText::new("Done").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], done_btn).clicked() {
+                    should_close = true;
+                }
+                let has_entries = !app.project.trash.is_empty();
+                if ui.add_enabled(has_entries, egui::Button::new("Empty Trash").min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    empty = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if retention != app.theme_manager.settings().trash_retention_days {
+        app.theme_manager.update_settings(|s| s.trash_retention_days = retention);
+    }
+    if let Some(id) = restore {
+        app.restore_from_trash(id);
+    }
+    if let Some(id) = forget {
+        app.apply_command(ProjectCommand::DeleteFromTrash(id), true);
+    }
+    if empty {
+        app.apply_command(ProjectCommand::EmptyTrash, true);
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_trash = false;
+    }
+}
+
+/// Render the "New Task Defaults" dialog: duration, priority, color and
+/// milestone naming used for tasks created from the Add Task dialog and for
+/// new subtasks. Changes are saved with the app settings as they are made.
+pub fn show_new_task_defaults_dialog(app: &mut GanttApp, ctx: &Context) {
+    use crate::model::task::TaskPriority;
+    use crate::ui::theme_manager::NewTaskColor;
+
+    let mut should_close = false;
+    let mut defaults = app.theme_manager.settings().new_task.clone();
+    let layout = theme::layout();
+    Window::new(RichText::new("New Task Defaults").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            egui::Grid::new("new_task_defaults_grid")
+                .num_columns(2)
+                .spacing([12.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Duration").color(theme::text_secondary()));
+                    ui.add(egui::DragValue::new(&mut defaults.duration_days).range(1..=365).suffix(" days"));
+                    ui.end_row();
+
+                    ui.label(RichText::new("Priority").color(theme::text_secondary()));
+                    egui::ComboBox::from_id_salt("new_task_priority")
+                        .selected_text(defaults.priority.label())
+                        .show_ui(ui, |ui| {
+                            for &priority in TaskPriority::all() {
+                                ui.selectable_value(&mut defaults.priority, priority, priority.label());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(RichText::new("Color").color(theme::text_secondary()));
+                    ui.vertical(|ui| {
+                        for option in NewTaskColor::all() {
+                            ui.horizontal(|ui| {
+                                ui.radio_value(&mut defaults.color, option, option.label());
+                                if option == NewTaskColor::Fixed {
+                                    ui.color_edit_button_srgb(&mut defaults.fixed_color);
+                                }
+                            });
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(RichText::new("Milestone name").color(theme::text_secondary()));
+                    ui.add_sized(
+                        [220.0, 24.0],
+                        egui::TextEdit::singleline(&mut defaults.milestone_name).hint_text("Milestone {n}"),
+                    );
+                    ui.end_row();
+                });
+            ui.add_space(4.0);
+            let today = chrono::Local::now().date_naive();
+            ui.label(
+                RichText::new(format!(
+                    "Used when a milestone is added without a name, e.g. \"{}\". {{n}} is its number, {{date}} its date.",
+                    defaults.milestone_name(1, today)
+                ))
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.label(RichText::new("Subtasks").strong().color(theme::text_secondary()));
+            egui::Grid::new("new_subtask_defaults_grid")
+                .num_columns(2)
+                .spacing([12.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Duration").color(theme::text_secondary()));
+                    ui.horizontal(|ui| {
+                        let mut own = defaults.subtask_duration_days.is_some();
+                        if ui.checkbox(&mut own, "Own").on_hover_text("Otherwise the same as new tasks").changed() {
+                            defaults.subtask_duration_days = own.then_some(defaults.duration_days);
+                        }
+                        if let Some(days) = &mut defaults.subtask_duration_days {
+                            ui.add(egui::DragValue::new(days).range(1..=365).suffix(" days"));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(RichText::new("Name").color(theme::text_secondary()));
+                    ui.add_sized(
+                        [220.0, 24.0],
+                        egui::TextEdit::singleline(&mut defaults.subtask_name).hint_text("{parent} – item {n}"),
+                    );
+                    ui.end_row();
+
+                    ui.label(RichText::new("From parent").color(theme::text_secondary()));
+                    ui.vertical(|ui| {
+                        ui.checkbox(&mut defaults.subtask_parent_color, "Color");
+                        ui.checkbox(&mut defaults.subtask_parent_assignees, "Assignees");
+                    });
+                    ui.end_row();
+
+                    ui.label(RichText::new("Sequence").color(theme::text_secondary()));
+                    ui.checkbox(&mut defaults.chain_subtasks, "Chain subtasks (waterfall)").on_hover_text(
+                        "Each new subtask starts when the previous one ends and is linked from it, finish → start",
+                    );
+                    ui.end_row();
+                });
+            ui.label(
+                RichText::new(format!(
+                    "e.g. \"{}\". {{parent}} is the parent's name, {{n}} the subtask's number under it.",
+                    defaults.subtask_name("Design", 3)
+                ))
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let done_btn = egui::Button::new(RichText::new("Done").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], done_btn).clicked() {
+                    should_close = true;
+                }
+                if ui.add(egui::Button::new("Reset").min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    defaults = Default::default();
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if defaults != app.theme_manager.settings().new_task {
+        app.theme_manager.update_settings(|s| s.new_task = defaults);
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_new_task_defaults = false;
+    }
+}
+
+/// Render the "Watch Folder" dialog: pick the folder whose new CSV files
+/// are imported into the open project, and whether they append or update.
+pub fn show_watch_folder_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut settings = app.theme_manager.settings().clone();
+    let layout = theme::layout();
+    Window::new(RichText::new("Watch Folder").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("CSV files saved into this folder are imported into the open project.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                let folder = match &settings.watch_folder {
+                    Some(dir) => dir.display().to_string(),
+                    None => "Not watching".to_string(),
+                };
+                ui.label(RichText::new(folder).color(theme::text_primary()));
+                if ui.button("Choose…").clicked() {
+                    if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                        settings.watch_folder = Some(dir);
+                    }
+                }
+            });
+            ui.add_space(4.0);
+            ui.radio_value(&mut settings.watch_merge_by_name, true, "Update tasks with the same name, add the rest");
+            ui.radio_value(&mut settings.watch_merge_by_name, false, "Always add as new tasks");
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let done_btn = egui::Button::new(RichText::new("Done").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], done_btn).clicked() {
+                    should_close = true;
+                }
+                let watching = settings.watch_folder.is_some();
+                if ui
+                    .add_enabled(watching, egui::Button::new("Stop Watching").min_size(egui::vec2(80.0, 28.0)))
+                    .clicked()
+                {
+                    settings.watch_folder = None;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    let current = app.theme_manager.settings();
+    if settings.watch_folder != current.watch_folder || settings.watch_merge_by_name != current.watch_merge_by_name {
+        app.status_message = match &settings.watch_folder {
+            Some(dir) => format!("Watching {} for CSV files", dir.display()),
+            None => "Stopped watching for CSV files".to_string(),
+        };
+        app.theme_manager.update_settings(|s| {
+            s.watch_folder = settings.watch_folder;
+            s.watch_merge_by_name = settings.watch_merge_by_name;
+        });
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_watch_folder = false;
+    }
+}
+
+/// Render the "Schedule Check" panel: date conflicts grouped by kind. The
+/// list is refreshed whenever the project changes; clicking an issue selects
+/// and scrolls to the task involved.
+pub fn show_schedule_check_panel(app: &mut GanttApp, ctx: &Context) {
+    use crate::model::validation::{check_schedule, IssueKind};
+
+    let mut siblings = app.theme_manager.settings().check_sibling_overlaps;
+    if app.schedule_check.as_ref().is_some_and(|(at, _)| *at != app.project.modified) {
+        app.schedule_check = Some((app.project.modified, check_schedule(&app.project, siblings)));
+    }
+    let Some((_, issues)) = &app.schedule_check else {
+        return;
+    };
+    let mut open = true;
+    let mut jump: Option<uuid::Uuid> = None;
+    egui::Window::new(RichText::new("Schedule Check").strong().size(14.0))
+        .open(&mut open)
+        .collapsible(true)
+        .resizable(true)
+        .default_width(theme::layout().dialog_width)
+        .show(ctx, |ui| {
+            ui.checkbox(&mut siblings, "Flag siblings sharing a resource")
+                .on_hover_text("Tasks under the same parent assigned to the same person over the same dates");
+            if issues.is_empty() {
+                ui.label(
+                    RichText::new(format!("{}  No conflicts found.", egui_phosphor::regular::CHECK_CIRCLE))
+                        .color(theme::text_secondary()),
+                );
+                return;
+            }
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                for kind in IssueKind::all() {
+                    let of_kind: Vec<_> = issues.iter().filter(|i| i.kind == kind).collect();
+                    if of_kind.is_empty() {
+                        continue;
+                    }
+                    ui.add_space(4.0);
+                    ui.label(
+                        RichText::new(format!("{}  {} ({})", kind.icon(), kind.label(), of_kind.len()))
+                            .size(11.0)
+                            .strong()
+                            .color(theme::slip_moderate()),
+                    );
+                    for issue in of_kind {
+                        let row = ui
+                            .add(
+                                egui::Label::new(RichText::new(&issue.message).size(11.0).color(theme::text_secondary()))
+                                    .sense(egui::Sense::click()),
+                            )
+                            .on_hover_text("Select and scroll to this task");
+                        if row.clicked() {
+                            jump = issue.tasks.first().copied();
+                        }
+                        // Notes on the links into the task often explain the problem.
+                        for dep in app.project.dependencies.iter().filter(|d| issue.tasks.first() == Some(&d.to_task)) {
+                            if let Some(note) = dep.note() {
+                                ui.label(
+                                    RichText::new(format!("    ↳ {}: {}", app.task_name(dep.from_task), note))
+                                        .size(10.0)
+                                        .italics()
+                                        .color(theme::text_dim()),
+                                );
+                            }
+                        }
+                    }
+                }
+            });
+        });
+    if siblings != app.theme_manager.settings().check_sibling_overlaps {
+        app.theme_manager.update_settings(|s| s.check_sibling_overlaps = siblings);
+        app.schedule_check = Some((app.project.modified, check_schedule(&app.project, siblings)));
+    }
+    if let Some(id) = jump {
+        app.selected_task = Some(id);
+        app.scroll_to_task(id);
+    }
+    if !open {
+        app.schedule_check = None;
+    }
+}
+
+/// Render the "Project Check" panel: broken structural invariants, such as
+/// links to missing tasks. Refreshed whenever the project changes; clicking
+/// a problem selects and scrolls to the task involved.
+pub fn show_project_check_panel(app: &mut GanttApp, ctx: &Context) {
+    if app.project_check.as_ref().is_some_and(|(at, _)| *at != app.project.modified) {
+        app.project_check = Some((app.project.modified, crate::model::validate(&app.project)));
+    }
+    let Some((_, violations)) = &app.project_check else {
+        return;
+    };
+    let mut open = true;
+    let mut jump: Option<uuid::Uuid> = None;
+    egui::Window::new(RichText::new("Project Check").strong().size(14.0))
+        .open(&mut open)
+        .collapsible(true)
+        .resizable(true)
+        .default_width(theme::layout().dialog_width)
+        .show(ctx, |ui| {
+            if violations.is_empty() {
+                ui.label(
+                    RichText::new(format!("{}  The project is consistent.", egui_phosphor::regular::CHECK_CIRCLE))
+                        .color(theme::text_secondary()),
+                );
+                return;
+            }
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                for violation in violations {
+                    let row = ui.add(
+                        egui::Label::new(
+                            RichText::new(format!("{}  {}", egui_phosphor::regular::WARNING, violation.message))
+                                .size(11.0)
+                                .color(theme::text_secondary()),
+                        )
+                        .sense(egui::Sense::click()),
+                    );
+                    if violation.task.is_some() && row.on_hover_text("Select and scroll to this task").clicked() {
+                        jump = violation.task;
+                    }
+                }
+            });
+        });
+    if let Some(id) = jump {
+        app.selected_task = Some(id);
+        app.scroll_to_task(id);
+    }
+    if !open {
+        app.project_check = None;
+    }
+}
+
+/// Render the "Compare With" panel: tasks added, removed or changed in the
+/// other file, each shown side by side with its counterpart. Clicking a row
+/// selects the task in the open project.
+pub fn show_compare_panel(app: &mut GanttApp, ctx: &Context) {
+    use crate::model::compare::{compare, ChangeKind};
+
+    let Some((path, other)) = &app.comparison else {
+        return;
+    };
+    let diffs = compare(&app.project, other);
+    let describe = |task: Option<&crate::model::Task>| match task {
+        Some(t) if t.is_milestone => format!("{}  ◆ {}", t.name, t.start.format("%d/%m/%Y")),
+        Some(t) => format!("{}  {} – {}", t.name, t.start.format("%d/%m/%Y"), t.end.format("%d/%m/%Y")),
+        None => "—".to_string(),
+    };
+    let mut open = true;
+    let mut overlay = app.compare_overlay;
+    let mut jump: Option<uuid::Uuid> = None;
+    let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    egui::Window::new(RichText::new(format!("Compare With {}", file_name)).strong().size(14.0))
+        .open(&mut open)
+        .collapsible(true)
+        .resizable(true)
+        .default_width(theme::layout().dialog_width * 1.6)
+        .show(ctx, |ui| {
+            ui.checkbox(&mut overlay, "Show the other file's bars behind the chart");
+            if diffs.is_empty() {
+                ui.label(
+                    RichText::new(format!("{}  The plans are identical.", egui_phosphor::regular::CHECK_CIRCLE))
+                        .color(theme::text_secondary()),
+                );
+                return;
+            }
+            egui::ScrollArea::vertical().max_height(420.0).show(ui, |ui| {
+                egui::Grid::new("compare-grid").striped(true).num_columns(3).show(ui, |ui| {
+                    ui.label(RichText::new("This file").size(11.0).strong());
+                    ui.label(RichText::new(file_name.as_str()).size(11.0).strong());
+                    ui.label(RichText::new("Differences").size(11.0).strong());
+                    ui.end_row();
+                    for &kind in ChangeKind::all() {
+                        let of_kind: Vec<_> = diffs.iter().filter(|d| d.kind == kind).collect();
+                        if of_kind.is_empty() {
+                            continue;
+                        }
+                        let color = match kind {
+                            ChangeKind::Added => theme::accent(),
+                            ChangeKind::Removed => theme::slip_major(),
+                            ChangeKind::Changed => theme::slip_moderate(),
+                        };
+                        ui.label(RichText::new(format!("{} ({})", kind.label(), of_kind.len())).size(11.0).strong().color(color));
+                        ui.end_row();
+                        for diff in of_kind {
+                            let ours = diff.ours.and_then(|id| app.project.tasks.iter().find(|t| t.id == id));
+                            let theirs = diff.theirs.and_then(|id| other.tasks.iter().find(|t| t.id == id));
+                            let row = ui
+                                .add(
+                                    egui::Label::new(RichText::new(describe(ours)).size(11.0).color(theme::text_secondary()))
+                                        .sense(egui::Sense::click()),
+                                )
+                                .on_hover_text("Select and scroll to this task");
+                            if row.clicked() {
+                                jump = diff.ours;
+                            }
+                            ui.label(RichText::new(describe(theirs)).size(11.0).color(theme::text_secondary()));
+                            ui.label(RichText::new(diff.changes.join("\n")).size(10.5).color(theme::text_dim()));
+                            ui.end_row();
+                        }
+                    }
+                });
+            });
+        });
+    app.compare_overlay = overlay;
+    if let Some(id) = jump {
+        app.selected_task = Some(id);
+        app.scroll_to_task(id);
+    }
+    if !open {
+        app.comparison = None;
+    }
+}
+
+/// Render the "Keyboard Shortcuts" dialog: every registered command with its
+/// bindings, and buttons to record, clear or reset each one.
+pub fn show_shortcuts_dialog(app: &mut GanttApp, ctx: &Context) {
+    use crate::ui::keymap::{format_shortcut, Command};
+
+    // Capture the next key press for the command being rebound.
+    if let Some(command) = app.shortcut_recording {
+        let pressed = ctx.input(|i| {
+            i.events.iter().find_map(|e| match e {
+                egui::Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+                _ => None,
+            })
+        });
+        if let Some((key, modifiers)) = pressed {
+            app.shortcut_recording = None;
+            if key != egui::Key::Escape {
+                let mut mods = egui::Modifiers::NONE;
+                if modifiers.command || modifiers.ctrl {
+                    mods = mods | egui::Modifiers::COMMAND;
+                }
+                if modifiers.shift {
+                    mods = mods | egui::Modifiers::SHIFT;
+                }
+                if modifiers.alt {
+                    mods = mods | egui::Modifiers::ALT;
+                }
+                let shortcut = egui::KeyboardShortcut::new(mods, key);
+                app.theme_manager.update_settings(|s| s.keymap.set(command, Some(shortcut)));
+                app.status_message = format!("{} bound to {}", command.label(), format_shortcut(&shortcut));
+            }
+            // Don't let the same press close the dialog.
+            ctx.input_mut(|i| i.events.clear());
+        }
+    }
+
+    let mut should_close = false;
+    let keymap = app.theme_manager.settings().keymap.clone();
+    let mut record = None;
+    let mut clear = None;
+    let mut reset = None;
+    let mut reset_all = false;
+    Window::new(RichText::new("Keyboard Shortcuts").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([440.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            egui::Grid::new("shortcuts_grid")
+                .num_columns(3)
+                .spacing([12.0, 6.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for &command in Command::all() {
+                        ui.label(RichText::new(command.label()).color(theme::text_secondary()));
+                        let bindings = keymap.bindings(command);
+                        let text = if app.shortcut_recording == Some(command) {
+                            RichText::new("Press a key…").italics().color(theme::accent())
+                        } else if bindings.is_empty() {
+                            RichText::new("—").color(theme::text_dim())
+                        } else {
+                            let joined: Vec<String> = bindings.iter().map(format_shortcut).collect();
+                            RichText::new(joined.join(", ")).monospace()
+                        };
+                        ui.label(text);
+                        ui.horizontal(|ui| {
+                            if ui.small_button("Record").clicked() {
+                                record = Some(command);
+                            }
+                            if ui.small_button("Clear").clicked() {
+                                clear = Some(command);
+                            }
+                            if ui
+                                .add_enabled(!keymap.is_default(command), egui::Button::new("Reset").small())
+                                .clicked()
+                            {
+                                reset = Some(command);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new("Assigning a shortcut removes it from any other command. Esc cancels recording.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let done_btn = egui::Button::new(RichText::new("Done").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], done_btn).clicked() {
+                    should_close = true;
+                }
+                if ui.add_sized([100.0, 28.0], egui::Button::new("Reset All")).clicked() {
+                    reset_all = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+
+    if let Some(command) = record {
+        app.shortcut_recording = Some(command);
+    }
+    if let Some(command) = clear {
+        app.theme_manager.update_settings(|s| s.keymap.set(command, None));
+    }
+    if let Some(command) = reset {
+        app.theme_manager.update_settings(|s| s.keymap.reset(command));
+    }
+    if reset_all {
+        app.theme_manager.update_settings(|s| s.keymap.reset_all());
+    }
+    if should_close || (app.shortcut_recording.is_none() && ctx.input(|i| i.key_pressed(egui::Key::Escape))) {
+        app.show_shortcuts = false;
+        app.shortcut_recording = None;
+    }
+}
+
+/// Render the "Save View" dialog, naming the current zoom, scroll and filters.
+pub fn show_save_view_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut save = false;
+    let layout = theme::layout();
+    Window::new(RichText::new("Save View").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(RichText::new("View name").color(theme::text_secondary()));
+            let resp = ui.add(
+                egui::TextEdit::singleline(&mut app.new_view_name)
+                    .hint_text("e.g. Exec summary")
+                    .desired_width(f32::INFINITY),
+            );
+            if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                save = true;
+            }
+            let exists = app.project.views.iter().any(|v| v.name == app.new_view_name.trim());
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(if exists {
+                    "A view with this name exists and will be replaced."
+                } else {
+                    "Saves zoom, scale, scroll position and filters with the project."
+                })
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let save_btn = egui::Button::new(RichText::new("Save").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], save_btn).clicked() {
+                    save = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    let name = app.new_view_name.trim().to_string();
+    if save && !name.is_empty() {
+        app.save_current_view(name);
+        should_close = true;
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_save_view = false;
+    }
+}
+
+/// Render the progress of a CSV file being read for import, with Cancel.
+pub fn show_import_progress_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut cancel = false;
+    let Some(job) = app.import_job.as_ref() else {
+        return;
+    };
+    let file_name = job.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    Window::new(RichText::new(format!("Reading {}", file_name)).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([theme::layout().dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.add(egui::ProgressBar::new(job.fraction()).show_percentage());
+            ui.add_space(2.0);
+            ui.label(
+                RichText::new(format!(
+                    "{} rows read · {:.1} of {:.1} MB",
+                    job.rows_read(),
+                    job.fraction() as f64 * job.total as f64 / 1_048_576.0,
+                    job.total as f64 / 1_048_576.0
+                ))
+                .size(10.5)
+                .color(theme::text_secondary()),
+            );
+            ui.add_space(6.0);
+            if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                cancel = true;
+            }
+            ui.add_space(2.0);
+        });
+    if cancel || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        // Dropping the job stops its reader.
+        app.import_job = None;
+        app.status_message = "CSV import cancelled".to_string();
+    }
+}
+
+/// Render the CSV import preview: detected columns, one row per data line
+/// with its warnings and an include checkbox, and Import / Cancel.
+pub fn show_import_preview_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut import = false;
+    let Some((path, preview)) = app.import_preview.as_mut() else {
+        return;
+    };
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    Window::new(RichText::new(format!("Import Preview — {}", file_name)).strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([640.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(RichText::new("Detected columns").size(10.5).strong());
+            ui.horizontal_wrapped(|ui| {
+                for (header, field) in &preview.columns {
+                    let (text, color) = match field {
+                        Some(field) => (format!("{} → {}", header, field), theme::text_secondary()),
+                        None => (format!("{} (ignored)", header), theme::text_dim()),
+                    };
+                    ui.label(RichText::new(text).size(10.5).color(color));
+                    ui.label(RichText::new("·").color(theme::text_dim()));
+                }
+            });
+            ui.add_space(6.0);
+
+            if preview.dates.ambiguous > 0 {
+                use crate::io::csv_import::DateOrder;
+                let evidence = preview.dates;
+                let hint = match (evidence.day_first, evidence.month_first) {
+                    (0, 0) => "No date in the file settles the order.".to_string(),
+                    (d, m) => format!(
+                        "{} looks likely: {} date(s) only fit day first, {} only month first.",
+                        evidence.inferred().label(),
+                        d,
+                        m
+                    ),
+                };
+                ui.label(
+                    RichText::new(format!(
+                        "{}  {} date(s) such as 01/02/2025 can be read either way. {}",
+                        egui_phosphor::regular::WARNING,
+                        evidence.ambiguous,
+                        hint
+                    ))
+                    .size(10.5)
+                    .color(theme::slip_moderate()),
+                );
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Dates are").size(10.5));
+                    for order in [DateOrder::DayFirst, DateOrder::MonthFirst] {
+                        let chosen = preview.date_order_confirmed && preview.date_order == order;
+                        if ui.selectable_label(chosen, order.label()).clicked() {
+                            preview.set_date_order(order);
+                            preview.date_order_confirmed = true;
+                        }
+                    }
+                });
+                ui.add_space(6.0);
+            }
+
+            ui.horizontal(|ui| {
+                let importable = preview.rows.iter().filter(|r| r.task.is_some()).count();
+                let included = preview.rows.iter().filter(|r| r.include).count();
+                let warned = preview.rows.iter().filter(|r| !r.warnings.is_empty()).count();
+                ui.label(
+                    RichText::new(format!(
+                        "{} of {} rows included · {} with warnings",
+                        included,
+                        preview.rows.len(),
+                        warned
+                    ))
+                    .size(10.5)
+                    .color(theme::text_secondary()),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("None").clicked() {
+                        preview.rows.iter_mut().for_each(|r| r.include = false);
+                    }
+                    if ui.add_enabled(importable > 0, egui::Button::new("All").small()).clicked() {
+                        preview.rows.iter_mut().filter(|r| r.task.is_some()).for_each(|r| r.include = true);
+                    }
+                });
+            });
+            ui.add_space(2.0);
+
+            // Only the visible rows are laid out, so huge files stay smooth.
+            let row_height = ui.spacing().interact_size.y + 4.0;
+            let total = preview.rows.len();
+            egui::ScrollArea::vertical().max_height(320.0).show_rows(ui, row_height, total + 1, |ui, range| {
+                egui::Grid::new("import_preview_grid")
+                    .num_columns(6)
+                    .striped(true)
+                    .spacing([10.0, 4.0])
+                    .show(ui, |ui| {
+                        for header in ["", "Line", "Name", "Start", "End", "Warnings"] {
+                            ui.label(RichText::new(header).size(10.5).strong());
+                        }
+                        ui.end_row();
+                        let visible = range.start.min(total)..range.end.saturating_sub(1).min(total);
+                        for row in &mut preview.rows[visible] {
+                            ui.add_enabled(row.task.is_some(), egui::Checkbox::without_text(&mut row.include));
+                            ui.label(RichText::new(row.line.to_string()).size(10.5).color(theme::text_dim()));
+                            match &row.task {
+                                Some(task) => {
+                                    ui.label(RichText::new(&task.name).size(10.5));
+                                    ui.label(RichText::new(task.start.format("%d/%m/%Y").to_string()).size(10.5));
+                                    ui.label(RichText::new(task.end.format("%d/%m/%Y").to_string()).size(10.5));
+                                }
+                                None => {
+                                    ui.label(RichText::new("—").color(theme::text_dim()));
+                                    ui.label("");
+                                    ui.label("");
+                                }
+                            }
+                            let color = if row.task.is_none() { theme::slip_major() } else { theme::slip_moderate() };
+                            ui.label(RichText::new(row.warnings.join("; ")).size(10.0).color(color));
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let any_included = preview.rows.iter().any(|r| r.include);
+                let import_btn = egui::Button::new(RichText::new("Import").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0))
+                    .min_size(egui::vec2(80.0, 28.0));
+                if ui
+                    .add_enabled(any_included && preview.date_order_confirmed, import_btn)
+                    .on_disabled_hover_text("Choose how dates are written first")
+                    .clicked()
+                {
+                    import = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if import {
+        app.finish_csv_import();
+    } else if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.import_preview = None;
+        app.status_message = "CSV import cancelled".to_string();
+    }
+}
+
+/// Which tasks the "Adjust Dates" dialog applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateScope {
+    /// The selected task and all of its subtasks.
+    Selection,
+    /// Every task matching the current search and priority filter.
+    Filtered,
+}
+
+/// What the "Adjust Dates" dialog does to each task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateAdjust {
+    /// Move start and finish by the same amount.
+    Shift,
+    /// Move only the finish, extending or shortening the task.
+    Resize,
+}
+
+/// Render the "Adjust Dates" dialog: shift or extend/shorten a set of tasks
+/// by N calendar or working days in one undo step.
+pub fn show_batch_dates_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut apply = false;
+    let layout = theme::layout();
+    let targets = app.batch_date_targets();
+    let leaves = targets
+        .iter()
+        .filter(|id| !app.project.tasks.iter().any(|t| t.parent_id == Some(**id)))
+        .count();
+    Window::new(RichText::new("Adjust Dates").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut app.batch_date_scope, DateScope::Selection, "Selected task and subtasks");
+                ui.radio_value(&mut app.batch_date_scope, DateScope::Filtered, "Filtered tasks");
+            });
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut app.batch_date_mode, DateAdjust::Shift, "Shift");
+                ui.radio_value(&mut app.batch_date_mode, DateAdjust::Resize, "Extend / shorten");
+            });
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("By").color(theme::text_secondary()));
+                ui.add(egui::DragValue::new(&mut app.batch_date_days).range(-3650..=3650));
+                ui.radio_value(&mut app.batch_date_working, false, "days");
+                ui.radio_value(&mut app.batch_date_working, true, "working days");
+            });
+            ui.add_space(4.0);
+            let hint = if targets.is_empty() {
+                match app.batch_date_scope {
+                    DateScope::Selection => "Select a task or phase first.".to_string(),
+                    DateScope::Filtered => "No tasks match the current filter.".to_string(),
+                }
+            } else {
+                let direction = match (app.batch_date_mode, app.batch_date_days >= 0) {
+                    (DateAdjust::Shift, true) => "later",
+                    (DateAdjust::Shift, false) => "earlier",
+                    (DateAdjust::Resize, true) => "longer",
+                    (DateAdjust::Resize, false) => "shorter",
+                };
+                let unit = if app.batch_date_working { "working day(s)" } else { "day(s)" };
+                format!(
+                    "{} task(s) will be {} {} {} {}. Phases follow their subtasks.",
+                    leaves,
+                    if app.batch_date_mode == DateAdjust::Shift { "moved" } else { "made" },
+                    app.batch_date_days.abs(),
+                    unit,
+                    direction
+                )
+            };
+            ui.label(RichText::new(hint).size(10.0).color(theme::text_dim()));
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let apply_btn = egui::Button::new(RichText::new("Apply").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                let can_apply = leaves > 0 && app.batch_date_days != 0;
+                if ui.add_enabled(can_apply, apply_btn.min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if apply {
+        app.apply_batch_dates(&targets);
+        should_close = true;
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_batch_dates = false;
+    }
+}
+
+/// Which tasks the batch rename dialog applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameScope {
+    /// Children of the selected phase (or of the selected task's parent).
+    Phase,
+    /// Every task matching the current search and priority filter.
+    Filtered,
+}
+
+/// Render the "Batch Rename" dialog: a name pattern with `{n}`, `{name}` and
+/// `{parent}` placeholders applied to a phase or the filtered tasks, with a
+/// preview of the new names.
+pub fn show_batch_rename_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut apply = false;
+    let layout = theme::layout();
+    let targets = app.batch_rename_targets();
+    let preview = app.project.batch_rename_preview(
+        &targets,
+        &app.rename_pattern,
+        app.rename_start,
+        app.rename_digits,
+    );
+    Window::new(RichText::new("Batch Rename").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut app.rename_scope, RenameScope::Phase, "Selected phase");
+                ui.radio_value(&mut app.rename_scope, RenameScope::Filtered, "Filtered tasks");
+            });
+            ui.add_space(4.0);
+            ui.label(RichText::new("Pattern").color(theme::text_secondary()));
+            ui.add(
+                egui::TextEdit::singleline(&mut app.rename_pattern)
+                    .hint_text("e.g. Sprint {n} – {name}")
+                    .desired_width(f32::INFINITY),
+            );
+            ui.label(
+                RichText::new("{n} number · {name} current name · {parent} phase name")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Start at").color(theme::text_secondary()));
+                ui.add(egui::DragValue::new(&mut app.rename_start).range(0..=9999));
+                ui.add_space(8.0);
+                ui.label(RichText::new("Digits").color(theme::text_secondary()));
+                ui.add(egui::DragValue::new(&mut app.rename_digits).range(1..=4));
+            });
+            ui.add_space(6.0);
+            if preview.is_empty() {
+                ui.label(
+                    RichText::new(match app.rename_scope {
+                        RenameScope::Phase => "Select a phase or one of its subtasks.",
+                        RenameScope::Filtered => "No tasks match the current filter.",
+                    })
+                    .size(10.5)
+                    .color(theme::text_dim()),
+                );
+            } else {
+                ui.label(RichText::new(format!("Preview ({} tasks)", preview.len())).size(10.5).strong());
+                egui::ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                    for (_, old, new) in &preview {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(old).size(10.5).color(theme::text_dim()));
+                            ui.label(RichText::new(egui_phosphor::regular::ARROW_RIGHT).size(9.0).color(theme::text_dim()));
+                            ui.label(RichText::new(new).size(10.5).color(theme::text_primary()));
+                        });
+                    }
+                });
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let rename_btn = egui::Button::new(RichText::new("Rename").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                let can_apply = !preview.is_empty() && !app.rename_pattern.trim().is_empty();
+                if ui.add_enabled(can_apply, rename_btn.min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if apply {
+        app.batch_rename(&targets);
+        should_close = true;
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_batch_rename = false;
+    }
+}
+
+/// Render the "Status Report" dialog: pick a status date and format, then
+/// copy the report to the clipboard or save it to a file.
+pub fn show_status_report_dialog(app: &mut GanttApp, ctx: &Context) {
+    use crate::io::status_report::ReportFormat;
+
+    let mut should_close = false;
+    let mut save = false;
+    let layout = theme::layout();
+    Window::new(RichText::new("Status Report").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            egui::Grid::new("status_report_grid")
+                .num_columns(2)
+                .spacing([12.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Status date").color(theme::text_secondary()));
+                    ui.add(egui_extras::DatePickerButton::new(&mut app.status_date).id_salt("dlg_status_date"));
+                    ui.end_row();
+
+                    ui.label(RichText::new("Format").color(theme::text_secondary()));
+                    ui.horizontal(|ui| {
+                        for format in [ReportFormat::Markdown, ReportFormat::Html] {
+                            let chosen = app.report_template.is_none() && app.report_format == format;
+                            if ui.radio(chosen, format.label()).clicked() {
+                                app.report_format = format;
+                                app.report_template = None;
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(RichText::new("Template").color(theme::text_secondary()));
+                    ui.horizontal_wrapped(|ui| {
+                        for template in &app.report_templates {
+                            let chosen = app.report_template.as_ref() == Some(template);
+                            if ui
+                                .radio(chosen, format!("{} (.{})", template.name, template.extension))
+                                .on_hover_text(template.path.display().to_string())
+                                .clicked()
+                            {
+                                app.report_template = Some(template.clone());
+                            }
+                        }
+                        if ui
+                            .small_button("Templates Folder")
+                            .on_hover_text("Add <name>.<extension>.hbs Handlebars files here; reopen this dialog to see them")
+                            .clicked()
+                        {
+                            match crate::io::report_template::ensure_templates_dir() {
+                                Ok(dir) => {
+                                    let _ = open::that(&dir);
+                                }
+                                Err(e) => app.status_message = e,
+                            }
+                        }
+                    });
+                    ui.end_row();
+                });
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new(if app.report_template.is_some() {
+                    "Laid out by your template, from the project, the report's sections and every task."
+                } else {
+                    "Includes work completed this week, slipped tasks, upcoming milestones and risks."
+                })
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let copy_btn = egui::Button::new(RichText::new("Copy").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], copy_btn).clicked() {
+                    match app.status_report_text() {
+                        Ok(report) => {
+                            ui.ctx().copy_text(report);
+                            app.status_message = "Status report copied to clipboard".to_string();
+                            should_close = true;
+                        }
+                        Err(e) => app.status_message = e,
+                    }
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Save…")).clicked() {
+                    save = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if save {
+        app.export_status_report();
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_status_report = false;
+    }
+}
+
+/// Render the "Print Schedule" dialog: page layout options for the
+/// printable HTML export.
+pub fn show_print_export_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut save = false;
+    let layout = theme::layout();
+    Window::new(RichText::new("Print Schedule").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            export_range_picker(&mut app.export_range, ui);
+            ui.add_space(4.0);
+            ui.checkbox(&mut app.print_page_per_phase, "Start each top-level phase on a new page");
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(if app.export_range != crate::model::view::ExportRange::Project {
+                    "Pages span the chosen dates; bars are cut at their edges."
+                } else if app.print_page_per_phase {
+                    "Each page is fitted to its phase's own date range."
+                } else {
+                    "One page spanning the whole project."
+                })
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+            ui.label(
+                RichText::new("Saves an HTML file; open it in a browser to print or save as PDF.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let save_btn = egui::Button::new(RichText::new("Save…").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], save_btn).clicked() {
+                    save = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if save {
+        app.export_print();
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_print_export = false;
+    }
+}
+
+/// Whole project, this quarter or custom dates for the printout and slides.
+fn export_range_picker(range: &mut crate::model::view::ExportRange, ui: &mut egui::Ui) {
+    use crate::model::view::ExportRange;
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Dates").color(theme::text_secondary()));
+        let today = chrono::Local::now().date_naive();
+        let custom = match *range {
+            ExportRange::Custom(from, to) => ExportRange::Custom(from, to),
+            _ => ExportRange::Custom(today, today + chrono::Duration::days(90)),
+        };
+        for option in [ExportRange::Project, ExportRange::ThisQuarter, custom] {
+            let selected = std::mem::discriminant(range) == std::mem::discriminant(&option);
+            if ui.selectable_label(selected, option.label()).clicked() && !selected {
+                *range = option;
+            }
+        }
+    });
+    if let ExportRange::Custom(from, to) = range {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("From").color(theme::text_secondary()));
+            ui.add(egui_extras::DatePickerButton::new(from).id_salt("export_range_from"));
+            ui.label(RichText::new("to").color(theme::text_secondary()));
+            ui.add(egui_extras::DatePickerButton::new(to).id_salt("export_range_to"));
+        });
+        *to = (*to).max(*from);
+    }
+}
+
+/// Render the "Export PowerPoint" dialog: the dates the slides cover.
+pub fn show_pptx_export_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut save = false;
+    let layout = theme::layout();
+    Window::new(RichText::new("Export PowerPoint").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            export_range_picker(&mut app.export_range, ui);
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("A schedule slide fitted to the dates, and a table of their milestones.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let save_btn = egui::Button::new(RichText::new("Save…").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], save_btn).clicked() {
+                    save = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if save {
+        app.export_pptx();
+    }
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_pptx_export = false;
+    }
+}
+
+/// Render the "Project Statistics" dialog: schedule, progress and cost totals.
+pub fn show_statistics_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let layout = theme::layout();
+    let project = &app.project;
+    Window::new(RichText::new("Project Statistics").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            let today = chrono::Local::now().date_naive();
+            let leaves: Vec<_> = project
+                .tasks
+                .iter()
+                .filter(|t| !t.has_children(&project.tasks))
+                .collect();
+            let milestones = leaves.iter().filter(|t| t.is_milestone).count();
+            let completed = leaves.iter().filter(|t| t.progress >= 1.0).count();
+            let overdue = leaves
+                .iter()
+                .filter(|t| t.end < today && t.progress < 1.0)
+                .count();
+            let start = project.tasks.iter().map(|t| t.start).min();
+            let end = project.tasks.iter().map(|t| t.end).max();
+
+            if !project.description.trim().is_empty() {
+                ui.add_space(4.0);
+                ui.label(RichText::new(project.description.trim()).size(11.0).color(theme::text_secondary()));
+                ui.add_space(2.0);
+                ui.separator();
+            }
+            ui.add_space(4.0);
+            egui::Grid::new("stats_grid")
+                .num_columns(2)
+                .spacing([16.0, 6.0])
+                .show(ui, |ui| {
+                    let row = |ui: &mut egui::Ui, label: &str, value: String| {
+                        ui.label(RichText::new(label).color(theme::text_secondary()));
+                        ui.label(RichText::new(value).color(theme::text_primary()));
+                        ui.end_row();
+                    };
+                    row(ui, "Tasks", (leaves.len() - milestones).to_string());
+                    row(ui, "Milestones", milestones.to_string());
+                    row(ui, "Completed", format!("{} / {}", completed, leaves.len()));
+                    row(ui, "Overdue", overdue.to_string());
+                    if let (Some(s), Some(e)) = (start, end) {
+                        row(
+                            ui,
+                            "Span",
+                            format!("{} → {} ({} days)", s.format("%d/%m/%Y"), e.format("%d/%m/%Y"), (e - s).num_days()),
+                        );
+                    }
+                    if let (Some(forecast), Some(e)) = (crate::model::forecast::project_forecast(project, today), end) {
+                        let slip = (forecast - e).num_days();
+                        let versus = match slip {
+                            0 => "on plan".to_string(),
+                            d if d > 0 => format!("{} days late", d),
+                            d => format!("{} days early", -d),
+                        };
+                        row(ui, "Forecast finish", format!("{} ({})", forecast.format("%d/%m/%Y"), versus));
+                    }
+                    row(ui, "Total cost", project.format_cost(project.total_cost()));
+                });
+
+            if !project.resources.is_empty() {
+                ui.add_space(6.0);
+                ui.label(RichText::new("Cost by resource").size(10.0).color(theme::text_dim()).strong());
+                egui::Grid::new("stats_cost_grid")
+                    .num_columns(2)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        for resource in &project.resources {
+                            let cost = project.resource_cost(resource.id);
+                            ui.label(RichText::new(&resource.name).color(theme::text_secondary()));
+                            ui.label(project.format_cost(cost));
+                            ui.end_row();
+                        }
+                    });
+            }
+
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            if ui.add_sized([80.0, 28.0], egui::Button::new("Close")).clicked() {
+                should_close = true;
+            }
+            ui.add_space(2.0);
+        });
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_statistics = false;
+    }
+}
+
+/// Render the resource leveling preview, listing the proposed moves (also
+/// drawn as ghost bars in the chart) so they can be reviewed before applying.
+pub fn show_leveling_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut apply = false;
+    let mut should_close = false;
+    let layout = theme::layout();
+    let tasks = &app.project.tasks;
+    let Some(moves) = app.leveling_preview.as_mut() else {
+        return;
+    };
+    Window::new(RichText::new("Level Resources").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::RIGHT_BOTTOM, [-16.0, -40.0])
+        .fixed_size([layout.dialog_width * 1.4, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("Proposed delays to remove overallocation. Preview shown as dashed bars.")
+                    .size(10.5)
+                    .color(theme::text_secondary()),
+            );
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                egui::Grid::new("leveling_grid")
+                    .num_columns(4)
+                    .spacing([10.0, 4.0])
+                    .show(ui, |ui| {
+                        for m in moves.iter_mut() {
+                            let name = tasks
+                                .iter()
+                                .find(|t| t.id == m.task_id)
+                                .map(|t| t.name.as_str())
+                                .unwrap_or("?");
+                            ui.checkbox(&mut m.accepted, name);
+                            ui.label(
+                                RichText::new(format!(
+                                    "{} → {}",
+                                    m.from.0.format("%d/%m"),
+                                    m.to.0.format("%d/%m")
+                                ))
+                                .size(11.0),
+                            );
+                            ui.label(RichText::new(format!("+{}d", m.delay_days())).size(11.0));
+                            if m.within_float() {
+                                ui.label(RichText::new("within float").size(10.0).color(theme::text_dim()));
+                            } else {
+                                ui.label(
+                                    RichText::new(format!("exceeds float by {}d", m.delay_days() - m.float_days))
+                                        .size(10.0)
+                                        .color(theme::slip_moderate()),
+                                );
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let apply_btn = egui::Button::new(RichText::new("Apply").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], apply_btn).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if apply {
+        app.apply_leveling();
+    } else if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.leveling_preview = None;
+    }
+}
+
+/// Render the "Duplicate Phase" dialog, asking how far to shift the copy.
+pub fn show_duplicate_phase_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let layout = theme::layout();
+    Window::new(RichText::new("Duplicate Phase").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Shift dates by").color(theme::text_secondary()));
+                ui.add(
+                    egui::DragValue::new(&mut app.duplicate_offset_days)
+                        .range(-3650..=3650)
+                        .suffix(" days"),
+                );
+            });
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new("Copies the phase, its subtasks and the links between them.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let duplicate_btn = egui::Button::new(RichText::new("Duplicate").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], duplicate_btn).clicked() {
+                    app.duplicate_phase_from_dialog();
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.duplicate_phase_target = None;
+    }
+}
+
+/// Render the "Re-baseline" dialog: the label the selected tasks' new
+/// baseline dates are recorded under.
+pub fn show_rebaseline_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut apply = false;
+    let layout = theme::layout();
+    let count = app.rebaseline_targets().len();
+    let Some(label) = app.rebaseline_label.as_mut() else {
+        return;
+    };
+    Window::new(RichText::new("Re-baseline").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.visuals_mut().extreme_bg_color = theme::bg_field();
+            ui.add_space(4.0);
+            ui.label(RichText::new("Reason").color(theme::text_secondary()));
+            let resp = ui.add(
+                egui::TextEdit::singleline(label)
+                    .hint_text("e.g. CR-42 approved")
+                    .desired_width(f32::INFINITY),
+            );
+            resp.request_focus();
+            if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                apply = true;
+            }
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new(format!(
+                    "{} task(s) get their current dates as their baseline. The rest keep the project baseline.",
+                    count
+                ))
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let apply_btn = egui::Button::new(RichText::new("Re-baseline").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], apply_btn).clicked() {
+                    apply = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if apply {
+        let label = app.rebaseline_label.take().unwrap_or_default();
+        app.rebaseline_selection(&label);
+    } else if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.rebaseline_label = None;
+    }
+}
+
+/// Recent files and templates for the welcome window, looked up once.
+pub struct Welcome {
+    /// Recent projects that still exist.
+    pub recent: Vec<std::path::PathBuf>,
+    /// Project templates, as (name, path).
+    pub templates: Vec<(String, std::path::PathBuf)>,
+}
+
+impl Welcome {
+    pub fn load(recent_files: &[std::path::PathBuf]) -> Self {
+        Self {
+            recent: recent_files.iter().filter(|p| p.exists()).cloned().collect(),
+            templates: crate::io::project_template::list_templates(),
+        }
+    }
+}
+
+/// Render the welcome window shown at launch: start a blank project, open
+/// one (recent or from disk), import a CSV, start from a template or look
+/// around the sample. Closing it leaves the blank project.
+pub fn show_welcome_dialog(app: &mut GanttApp, ctx: &Context) {
+    enum Choice {
+        Blank,
+        Open,
+        ImportCsv,
+        Sample,
+        Recent(std::path::PathBuf),
+        Template(std::path::PathBuf),
+    }
+    let mut choice = None;
+    let mut should_close = false;
+    let layout = theme::layout();
+    let Some(Welcome { recent, templates }) = &app.welcome else {
+        return;
+    };
+    let heading = |ui: &mut egui::Ui, text: &str| {
+        ui.add_space(6.0);
+        ui.label(RichText::new(text).size(11.0).strong().color(theme::text_secondary()));
+    };
+    Window::new(RichText::new("Welcome").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal_wrapped(|ui| {
+                let blank_btn = egui::Button::new(RichText::new("New Blank").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], blank_btn).clicked() {
+                    choice = Some(Choice::Blank);
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Open…")).clicked() {
+                    choice = Some(Choice::Open);
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Import CSV…")).clicked() {
+                    choice = Some(Choice::ImportCsv);
+                }
+            });
+
+            heading(ui, "Recent");
+            if recent.is_empty() {
+                ui.label(RichText::new("Projects you open or save show up here.").size(10.0).color(theme::text_dim()));
+            }
+            for path in recent {
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+                if ui.link(name).on_hover_text(path.display().to_string()).clicked() {
+                    choice = Some(Choice::Recent(path.clone()));
+                }
+            }
+
+            heading(ui, "Templates");
+            if templates.is_empty() {
+                ui.label(
+                    RichText::new("Save a plan with File → Save As Template… to start new ones from it here.")
+                        .size(10.0)
+                        .color(theme::text_dim()),
+                );
+            }
+            for (name, path) in templates {
+                if ui.link(name).on_hover_text("Start a new project from this template, this week").clicked() {
+                    choice = Some(Choice::Template(path.clone()));
+                }
+            }
+
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.link("Explore the sample project").clicked() {
+                    choice = Some(Choice::Sample);
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add_sized([80.0, 28.0], egui::Button::new("Close")).clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+            ui.add_space(2.0);
+        });
+    if let Some(choice) = choice {
+        app.welcome = None;
+        match choice {
+            Choice::Blank => app.new_project(),
+            Choice::Open => app.open_project(),
+            Choice::ImportCsv => app.import_csv(),
+            Choice::Sample => app.load_sample_project(),
+            Choice::Recent(path) => app.open_recent(path),
+            Choice::Template(path) => app.new_from_template(&path),
+        }
+    } else if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.welcome = None;
+    }
+}
+
+/// Render the "Save As Template" dialog: the name the template is saved
+/// under.
+pub fn show_save_template_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut save = false;
+    let layout = theme::layout();
+    let Some(name) = app.template_name.as_mut() else {
+        return;
+    };
+    Window::new(RichText::new("Save As Template").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.visuals_mut().extreme_bg_color = theme::bg_field();
+            ui.add_space(4.0);
+            ui.label(RichText::new("Template name").color(theme::text_secondary()));
+            let resp = ui.add(egui::TextEdit::singleline(name).desired_width(f32::INFINITY));
+            resp.request_focus();
+            if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                save = true;
+            }
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new(
+                    "Progress, estimates, the baseline and history are left out, and the plan is moved to start \
+                     in week zero. A template with the same name is replaced.",
+                )
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let save_btn = egui::Button::new(RichText::new("Save").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], save_btn).clicked() {
+                    save = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if save {
+        let name = app.template_name.take().unwrap_or_default();
+        app.save_as_template(&name);
+    } else if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.template_name = None;
+    }
+}
+
+/// Render the "Import into Phase" dialog: summary of the loaded file and the
+/// number of days to shift its dates by.
+pub fn show_import_phase_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut import = false;
+    let layout = theme::layout();
+    let Some((parent_id, part)) = &app.import_phase else {
+        return;
+    };
+    let parent_name = app.task_name(*parent_id);
+    let count = part.tasks.len();
+    let first = part.tasks.iter().map(|t| t.start).min();
+    let last = part.tasks.iter().map(|t| t.end).max();
+    Window::new(RichText::new("Import into Phase").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(format!("{} task(s) will be added under '{}'.", count, parent_name))
+                    .color(theme::text_secondary()),
+            );
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Shift dates by").color(theme::text_secondary()));
+                ui.add(
+                    egui::DragValue::new(&mut app.import_offset_days)
+                        .range(-3650..=3650)
+                        .suffix(" days"),
+                );
+            });
+            if let (Some(first), Some(last)) = (first, last) {
+                let offset = chrono::Duration::days(app.import_offset_days);
+                ui.add_space(4.0);
+                ui.label(
+                    RichText::new(format!(
+                        "Imported span: {} → {}",
+                        (first + offset).format("%d/%m/%Y"),
+                        (last + offset).format("%d/%m/%Y")
+                    ))
+                    .size(10.0)
+                    .color(theme::text_dim()),
+                );
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let import_btn = egui::Button::new(RichText::new("Import").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], import_btn).clicked() {
+                    import = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if import {
+        app.import_phase_from_dialog();
+    } else if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.import_phase = None;
+    }
+}
+
+/// Most matches listed in the "Link Tasks" dialog.
+const LINK_MATCHES: usize = 8;
+
+/// A link being set up from the keyboard: the selected task, the name
+/// typed to find the other one, and the kind of link.
+pub struct LinkDraft {
+    pub from: uuid::Uuid,
+    pub query: String,
+    /// The highlighted match, linked to on confirm.
+    pub target: Option<uuid::Uuid>,
+    pub kind: crate::model::task::DependencyKind,
+    /// Focus the search field on the first frame only, so Tab can move on
+    /// to the kinds.
+    focus: bool,
+}
+
+impl LinkDraft {
+    pub fn new(from: uuid::Uuid) -> Self {
+        Self {
+            from,
+            query: String::new(),
+            target: None,
+            kind: crate::model::task::DependencyKind::FinishToStart,
+            focus: true,
+        }
+    }
+}
+
+/// Render the "Link Tasks" dialog: type part of a task's name, pick it with
+/// ↑/↓ (or a click), choose the kind and press Enter to link the selected
+/// task to it.
+pub fn show_link_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut link = false;
+    let layout = theme::layout();
+    let Some(draft) = app.link_draft.as_mut() else {
+        return;
+    };
+    let Some(from) = app.project.tasks.iter().find(|t| t.id == draft.from) else {
+        app.link_draft = None;
+        return;
+    };
+    let query = draft.query.trim().to_lowercase();
+    let matches: Vec<&crate::model::Task> = app
+        .project
+        .tasks
+        .iter()
+        .filter(|t| t.id != draft.from && t.name.to_lowercase().contains(&query))
+        .take(LINK_MATCHES)
+        .collect();
+    if !draft.target.is_some_and(|id| matches.iter().any(|t| t.id == id)) {
+        draft.target = matches.first().map(|t| t.id);
+    }
+    let step = ctx.input_mut(|i| {
+        i.count_and_consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown) as i64
+            - i.count_and_consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp) as i64
+    });
+    if step != 0 && !matches.is_empty() {
+        let at = matches.iter().position(|t| Some(t.id) == draft.target).unwrap_or(0) as i64;
+        draft.target = Some(matches[(at + step).clamp(0, matches.len() as i64 - 1) as usize].id);
+    }
+    let problem = draft.target.and_then(|to| app.project.check_link(draft.from, to).err());
+    if draft.focus {
+        // The key that opened the dialog must not land in the search field.
+        ctx.input_mut(|i| i.events.retain(|e| !matches!(e, egui::Event::Text(_))));
+    }
+
+    Window::new(RichText::new("Link Tasks").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.visuals_mut().extreme_bg_color = theme::bg_field();
+            ui.add_space(4.0);
+            ui.label(RichText::new(format!("From '{}' to", from.name)).color(theme::text_secondary()));
+            let resp = ui.add(
+                egui::TextEdit::singleline(&mut draft.query)
+                    .hint_text("Type part of a task name")
+                    .desired_width(f32::INFINITY),
+            );
+            if std::mem::take(&mut draft.focus) {
+                resp.request_focus();
+            }
+            ui.add_space(4.0);
+            if matches.is_empty() {
+                ui.label(RichText::new("No task matches").size(11.0).color(theme::text_dim()));
+            }
+            for task in &matches {
+                if ui.selectable_label(draft.target == Some(task.id), &task.name).clicked() {
+                    draft.target = Some(task.id);
+                }
+            }
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Kind").color(theme::text_secondary()));
+                for kind in crate::model::task::DependencyKind::all() {
+                    ui.radio_value(&mut draft.kind, *kind, kind.short_label()).on_hover_text(kind.description());
+                }
+            });
+            ui.label(RichText::new(draft.kind.description()).size(10.0).color(theme::text_dim()));
+            if let Some(problem) = &problem {
+                ui.add_space(4.0);
+                ui.label(RichText::new(problem).size(11.0).color(theme::slip_major()));
+            }
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let link_btn = egui::Button::new(RichText::new("Link").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                let can_link = draft.target.is_some() && problem.is_none();
+                if ui.add_enabled(can_link, link_btn.min_size(egui::vec2(80.0, 28.0))).clicked() {
+                    link = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+                if can_link && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    link = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if link {
+        app.link_from_dialog();
+    } else if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.link_draft = None;
+    }
+}
+
+/// What happens to the children when a parent task is deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParentDeleteMode {
+    /// Delete the parent together with everything under it.
+    Subtree,
+    /// Move the children up one level, under the parent's own parent.
+    PromoteToGrandparent,
+    /// Move the children to the top level.
+    PromoteToTopLevel,
+}
+
+/// Render the "Delete Parent Task" dialog: delete the whole subtree, or keep
+/// the children (and their links) by promoting them.
+pub fn show_delete_parent_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let mut delete = false;
+    let layout = theme::layout();
+    let Some(id) = app.delete_parent_target else {
+        return;
+    };
+    let name = app.task_name(id);
+    let descendants = app.project.subtree_ids(id).len() - 1;
+    let grandparent = app
+        .project
+        .tasks
+        .iter()
+        .find(|t| t.id == id)
+        .and_then(|t| t.parent_id)
+        .map(|pid| app.task_name(pid));
+    Window::new(RichText::new("Delete Parent Task").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(format!("'{}' has {} subtask(s). What should happen to them?", name, descendants))
+                    .color(theme::text_secondary()),
+            );
+            ui.add_space(4.0);
+            ui.radio_value(&mut app.delete_parent_mode, ParentDeleteMode::Subtree, "Delete them too");
+            if let Some(grandparent) = &grandparent {
+                ui.radio_value(
+                    &mut app.delete_parent_mode,
+                    ParentDeleteMode::PromoteToGrandparent,
+                    format!("Move them under '{}'", grandparent),
+                );
+            }
+            ui.radio_value(&mut app.delete_parent_mode, ParentDeleteMode::PromoteToTopLevel, "Move them to the top level");
+            ui.add_space(2.0);
+            ui.label(
+                RichText::new("Promoted tasks keep their dependencies.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let delete_btn = egui::Button::new(RichText::new("Delete").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], delete_btn).clicked() {
+                    delete = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if delete {
+        app.delete_parent_from_dialog();
+    } else if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.delete_parent_target = None;
+    }
+}
+
+/// Render the "Convert to Task" dialog, asking how long the former milestone
+/// should last. The milestone date becomes the task's finish date.
+pub fn show_convert_to_task_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let layout = theme::layout();
+    Window::new(RichText::new("Convert to Task").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Duration").color(theme::text_secondary()));
+                ui.add(
+                    egui::DragValue::new(&mut app.convert_duration_days)
+                        .range(1..=3650)
+                        .suffix(" days"),
+                );
+            });
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new("The milestone date becomes the finish date; links are kept.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let convert_btn = egui::Button::new(RichText::new("Convert").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], convert_btn).clicked() {
+                    app.convert_to_task_from_dialog();
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.convert_task_target = None;
+    }
+}
+
+/// Render the hidden "Generate Benchmark Project" dialog (Ctrl+Shift+F12),
+/// which replaces the current project with a synthetic fixture.
+pub fn show_benchmark_dialog(app: &mut GanttApp, ctx: &Context) {
+    let mut should_close = false;
+    let layout = theme::layout();
+    Window::new(RichText::new("Generate Benchmark Project").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            egui::Grid::new("benchmark_grid")
+                .num_columns(2)
+                .spacing([12.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Tasks").color(theme::text_secondary()));
+                    ui.add(egui::DragValue::new(&mut app.benchmark_tasks).range(1..=100_000).speed(10.0));
+                    ui.end_row();
+
+                    ui.label(RichText::new("Dependencies").color(theme::text_secondary()));
+                    ui.add(egui::DragValue::new(&mut app.benchmark_deps).range(0..=200_000).speed(10.0));
+                    ui.end_row();
+
+                    ui.label(RichText::new("Seed").color(theme::text_secondary()));
+                    ui.add(egui::DragValue::new(&mut app.benchmark_seed));
+                    ui.end_row();
+                });
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new("The same numbers and seed always produce the same layout.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let generate_btn = egui::Button::new(RichText::new("Generate").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], generate_btn).clicked() {
+                    app.generate_benchmark_project();
+                    should_close = true;
+                }
+                if ui.add_sized([80.0, 28.0], egui::Button::new("Cancel")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.show_benchmark = false;
+    }
+}
+
+/// Render the offer to recolor tasks after a theme switch: each clashing
+/// colour beside the palette colour it would become.
+pub fn show_palette_remap_dialog(app: &mut GanttApp, ctx: &Context) {
+    let Some(remap) = app.palette_remap.clone() else {
+        return;
+    };
+    let mut should_close = false;
+    let layout = theme::layout();
+    Window::new(RichText::new("Recolor Tasks?").strong().size(14.0))
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([layout.dialog_width, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(format!(
+                "{} task(s) use colors that are not in the '{}' palette.",
+                remap.task_count(),
+                remap.theme
+            ));
+            ui.add_space(6.0);
+            egui::ScrollArea::vertical().max_height(220.0).show(ui, |ui| {
+                for (old, new, count) in &remap.swaps {
+                    ui.horizontal(|ui| {
+                        let swatch = |ui: &mut egui::Ui, color: Color32| {
+                            let (rect, _) = ui.allocate_exact_size(egui::vec2(28.0, 14.0), egui::Sense::hover());
+                            ui.painter().rect_filled(rect, 3.0, color);
+                        };
+                        swatch(ui, *old);
+                        ui.label(RichText::new("→").color(theme::text_secondary()));
+                        swatch(ui, *new);
+                        ui.label(RichText::new(format!("{} task(s)", count)).color(theme::text_dim()));
+                    });
+                }
+            });
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new("Recoloring can be undone.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+            ui.add_space(6.0);
+            ui.separator();
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let recolor_btn = egui::Button::new(RichText::new("Recolor").color(Color32::WHITE))
+                    .fill(theme::accent())
+                    .rounding(egui::Rounding::same(4.0));
+                if ui.add_sized([80.0, 28.0], recolor_btn).clicked() {
+                    app.apply_palette_remap();
+                }
+                if ui.add_sized([100.0, 28.0], egui::Button::new("Keep Colors")).clicked() {
+                    should_close = true;
+                }
+            });
+            ui.add_space(2.0);
+        });
+    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        app.palette_remap = None;
+    }
+}
+
+/// Render the "Plan History" window: a slider over the project's weekly
+/// snapshots, the chosen week drawn faintly behind the bars.
+pub fn show_plan_history_window(app: &mut GanttApp, ctx: &Context) {
+    let mut open = true;
+    let snapshots = &app.project.snapshots;
+    egui::Window::new(RichText::new("Plan History").strong().size(14.0))
+        .open(&mut open)
+        .collapsible(true)
+        .resizable(false)
+        .default_width(theme::layout().dialog_width)
+        .show(ctx, |ui| {
+            let Some(last) = snapshots.len().checked_sub(1) else {
+                ui.label(
+                    RichText::new("No snapshots yet. Turn on File → Keep Weekly Snapshots; each save then records that week's plan.")
+                        .color(theme::text_secondary()),
+                );
+                return;
+            };
+            app.plan_history_week = app.plan_history_week.min(last);
+            ui.spacing_mut().slider_width = theme::layout().dialog_width - 40.0;
+            ui.add_enabled(
+                last > 0,
+                egui::Slider::new(&mut app.plan_history_week, 0..=last)
+                    .show_value(false)
+                    .step_by(1.0),
+            );
+            let week = &snapshots[app.plan_history_week];
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(format!("Week of {}", week.week.format("%d %b %Y"))).strong());
+                ui.label(
+                    RichText::new(format!("{} of {}", app.plan_history_week + 1, snapshots.len()))
+                        .size(10.0)
+                        .color(theme::text_dim()),
+                );
+            });
+            let moved = week.moved_since(&app.project.tasks);
+            let added = app.project.tasks.iter().filter(|t| week.dates(t.id).is_none()).count();
+            ui.label(
+                RichText::new(format!("Since then: {} task(s) moved, {} added", moved, added))
+                    .size(11.0)
+                    .color(theme::text_secondary()),
+            );
+            ui.label(
+                RichText::new("The faint bars behind the chart show the plan as it was that week.")
+                    .size(10.0)
+                    .color(theme::text_dim()),
+            );
+        });
+    if !open {
+        app.show_plan_history = false;
+    }
+}
@@ -1,6 +1,9 @@
-use crate::model::task::TaskPriority;
+use crate::model::task::{Task, TaskPriority};
+use chrono::NaiveDate;
+use crate::model::Project;
 use crate::ui::theme;
 use egui::{RichText, Ui};
+use uuid::Uuid;
 
 /// Active filter state used to decide which tasks are visible.
 #[derive(Clone, Default)]
@@ -10,23 +13,116 @@ pub struct FilterState {
     pub priority: Option<TaskPriority>,
     pub only_overdue: bool,
     pub only_in_progress: bool,
+    /// Only tasks assigned to this resource ("my tasks").
+    pub assignee: Option<Uuid>,
+    /// Only the tasks of the focused phase, the phase included.
+    pub within: Option<Vec<Uuid>>,
+    /// Filters set from the task table's column headers.
+    pub columns: ColumnFilters,
+}
+
+/// Spreadsheet-style filters on the task table's columns.
+#[derive(Clone, Default, PartialEq)]
+pub struct ColumnFilters {
+    /// Only tasks starting on or after this date.
+    pub start_from: Option<NaiveDate>,
+    /// Only tasks ending on or before this date.
+    pub end_by: Option<NaiveDate>,
+    /// Only tasks this far done, as a percent range.
+    pub progress: Option<(f32, f32)>,
+    /// Only tasks assigned to this resource.
+    pub assignee: Option<Uuid>,
+}
+
+impl ColumnFilters {
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    pub fn matches(&self, task: &Task) -> bool {
+        let percent = (task.progress * 100.0).round();
+        self.start_from.is_none_or(|d| task.start >= d)
+            && self.end_by.is_none_or(|d| task.end <= d)
+            && self.progress.is_none_or(|(lo, hi)| (lo..=hi).contains(&percent))
+            && self.assignee.is_none_or(|id| task.assignments.iter().any(|a| a.resource_id == id))
+    }
 }
 
 impl FilterState {
-    #[allow(dead_code)]
     pub fn is_active(&self) -> bool {
         !self.search.is_empty()
             || self.priority.is_some()
             || self.only_overdue
             || self.only_in_progress
+            || self.assignee.is_some()
+            || self.within.is_some()
+            || self.columns.is_active()
+    }
+
+    /// Whether `id` is inside the focused phase, if any.
+    pub fn in_focus(&self, id: Uuid) -> bool {
+        self.within.as_ref().is_none_or(|ids| ids.contains(&id))
+    }
+
+    /// Whether `task` itself passes the search, priority, assignee and
+    /// column filters.
+    pub fn matches(&self, task: &Task) -> bool {
+        self.in_focus(task.id)
+            && self.columns.matches(task)
+            && self.assignee.is_none_or(|id| task.assignments.iter().any(|a| a.resource_id == id))
+            && task_matches(&task.name, &task.description, task.priority, &self.search, self.priority)
+    }
+}
+
+pub enum FocusAction {
+    /// Show all tasks again.
+    Exit,
+    /// Focus on an enclosing phase instead.
+    Focus(Uuid),
+}
+
+/// Render the path from the project down to the focused phase, each
+/// enclosing phase a link to focus on it, and a button to leave focus.
+pub fn show_focus_breadcrumb(project: &Project, focus: Uuid, ui: &mut Ui) -> Option<FocusAction> {
+    let mut path = Vec::new();
+    let mut next = Some(focus);
+    while let Some(task) = next.and_then(|id| project.tasks.iter().find(|t| t.id == id)) {
+        path.insert(0, task);
+        next = task.parent_id;
     }
+    let mut action = None;
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 4.0;
+        ui.label(RichText::new(egui_phosphor::regular::CROSSHAIR).color(theme::accent()));
+        let name = if project.name.is_empty() { "All tasks" } else { project.name.as_str() };
+        if ui.link(RichText::new(name).size(11.0)).on_hover_text("Show all tasks").clicked() {
+            action = Some(FocusAction::Exit);
+        }
+        for task in &path {
+            ui.label(RichText::new(egui_phosphor::regular::CARET_RIGHT).size(10.0).color(theme::text_dim()));
+            if task.id == focus {
+                ui.label(RichText::new(&task.name).size(11.0).strong().color(theme::text_primary()));
+            } else if ui.link(RichText::new(&task.name).size(11.0)).clicked() {
+                action = Some(FocusAction::Focus(task.id));
+            }
+        }
+        if ui.small_button(egui_phosphor::regular::X).on_hover_text("Exit focus").clicked() {
+            action = Some(FocusAction::Exit);
+        }
+    });
+    action
 }
 
-/// Render the filter / search bar.
+/// Render the filter / search bar. `only_mine` is the "Only my tasks"
+/// toggle; `my_name` is shown in its tooltip (empty when no name is set).
+/// The clear button also clears the table's `columns` filters.
 /// Returns true if the filter state changed.
 pub fn show_filter_bar(
     search_query: &mut String,
     filter_priority: &mut Option<TaskPriority>,
+    only_mine: &mut bool,
+    columns: &mut ColumnFilters,
+    my_name: &str,
     ui: &mut Ui,
 ) -> bool {
     let mut changed = false;
@@ -36,9 +132,10 @@ pub fn show_filter_bar(
     let avail = ui.available_width();
     let combo_w = 100.0;
     let clear_w = 18.0;
-    let spacing = ui.spacing().item_spacing.x * 2.0 + 6.0;
-    let has_filter = !search_query.is_empty() || filter_priority.is_some();
-    let search_w = (avail - combo_w - spacing - if has_filter { clear_w + 4.0 } else { 0.0 })
+    let mine_w = 24.0;
+    let spacing = ui.spacing().item_spacing.x * 3.0 + 6.0;
+    let has_filter = !search_query.is_empty() || filter_priority.is_some() || *only_mine || columns.is_active();
+    let search_w = (avail - combo_w - mine_w - spacing - if has_filter { clear_w + 4.0 } else { 0.0 })
         .max(40.0);
 
     ui.horizontal(|ui| {
@@ -85,6 +182,26 @@ pub fn show_filter_bar(
                 }
             });
 
+        // Only my tasks — the resource named in the settings
+        let mine_color = if *only_mine { theme::accent() } else { theme::text_dim() };
+        let mine_hint = if my_name.is_empty() {
+            "Only my tasks — set your name in View → My Name first".to_string()
+        } else {
+            format!("Only tasks assigned to {}", my_name)
+        };
+        if ui
+            .add_sized(
+                [mine_w, 22.0],
+                egui::Button::new(RichText::new(egui_phosphor::regular::USER).size(13.0).color(mine_color))
+                    .selected(*only_mine),
+            )
+            .on_hover_text(mine_hint)
+            .clicked()
+        {
+            *only_mine = !*only_mine;
+            changed = true;
+        }
+
         // Clear button — only visible when a filter is active
         if has_filter {
             if ui
@@ -97,6 +214,8 @@ pub fn show_filter_bar(
             {
                 search_query.clear();
                 *filter_priority = None;
+                *only_mine = false;
+                *columns = ColumnFilters::default();
                 changed = true;
             }
         }
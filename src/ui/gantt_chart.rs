@@ -1,12 +1,41 @@
-use crate::model::{Task, TimelineScale, TimelineViewport};
-use crate::model::task::{Dependency, DependencyKind};
+use crate::model::{Annotation, Baseline, DateBand, DateMarker, ProjectCalendar, Resource, Task, TimelineScale, TimelineViewport};
+use crate::model::task::{BarPattern, Dependency, DependencyKind, LineStyle};
+use crate::model::view::NameGutter;
+use crate::ui::hover_card;
 use crate::ui::theme;
+use crate::ui::theme_def::{SummaryBarStyle, SummaryProgress};
 use chrono::{Datelike, NaiveDate};
 use egui::{Color32, Id, Pos2, Rect, Rounding, Sense, Stroke, Ui, Vec2};
 use uuid::Uuid;
 
 fn header_height() -> f32 { theme::header_height() }
 
+/// Short fixed stub length — arrows exit/enter each bar by this amount before turning.
+const DEP_STUB: f32 = 10.0;
+
+/// Links sharing one bar edge get individually staggered anchors up to this
+/// count; busier edges are bundled into a single shared trunk instead.
+const MAX_STAGGERED_LINKS: usize = 4;
+
+/// Width (px) of the band along the chart edges where a drag auto-scrolls.
+const AUTOSCROLL_EDGE: f32 = 40.0;
+/// Auto-scroll speed (px per frame) with the pointer at the very edge.
+const AUTOSCROLL_SPEED: f32 = 12.0;
+
+/// A bar being moved or resized: where it started and where it is now, for
+/// the ghost outline and date badge.
+struct DragGhost {
+    task: Uuid,
+    from: (NaiveDate, NaiveDate),
+    to: (NaiveDate, NaiveDate),
+    /// Days the dragged edge (or the whole bar) has moved.
+    delta: i64,
+    /// Vertical extent of the bar.
+    y: egui::Rangef,
+    pointer: Pos2,
+    milestone: bool,
+}
+
 #[derive(Debug, Clone)]
 struct DragSnapshot {
     start: NaiveDate,
@@ -21,14 +50,75 @@ pub struct ChartInteraction {
     pub changed: bool,
     /// A new dependency to add (created via Shift+drag).
     pub new_dependency: Option<Dependency>,
-    /// A dependency to remove (right-clicked on arrow).
+    /// A dependency to remove (from the arrow context menu).
     pub remove_dependency: Option<(Uuid, Uuid)>,
+    /// A dependency whose style was edited (from the arrow context menu).
+    pub update_dependency: Option<Dependency>,
     /// A parent task whose collapsed state should be toggled.
     pub toggle_collapse: Option<Uuid>,
     /// Request to add a subtask under this parent id.
     pub add_subtask: Option<Uuid>,
     /// Request to delete this task.
     pub delete_task: Option<Uuid>,
+    /// Request a milestone that tracks this phase's end.
+    pub completion_milestone: Option<Uuid>,
+    /// Request to duplicate this phase (asks for a date offset).
+    pub duplicate_phase: Option<Uuid>,
+    /// Request to export this phase to its own file.
+    pub export_phase: Option<Uuid>,
+    /// Request to show only this phase and fit the timeline to it.
+    pub focus_phase: Option<Uuid>,
+    /// Request to import a file's tasks under this phase.
+    pub import_into_phase: Option<Uuid>,
+    /// Keep this task's hover card open in its own window.
+    pub pin_card: Option<Uuid>,
+    /// The timeline header was clicked at this date: add or remove a guide.
+    pub toggle_guide: Option<NaiveDate>,
+    /// A date drag is near the chart edge: scroll by this many pixels
+    /// (negative = left) and grow the viewport if needed.
+    pub drag_autoscroll: Option<f32>,
+    /// Request to turn this task into a milestone.
+    pub convert_to_milestone: Option<Uuid>,
+    /// Request to turn this milestone into a task (asks for a duration).
+    pub convert_to_task: Option<Uuid>,
+    /// The name gutter was resized or reset to auto-fit.
+    pub name_gutter: Option<NameGutter>,
+    /// Request to mark this task (and its subtasks) complete.
+    pub mark_done: Option<Uuid>,
+    /// A bar was dragged this frame: the task and the dates it had before,
+    /// so the app can carry the move on to its successors.
+    pub moved_from: Option<(Uuid, (NaiveDate, NaiveDate))>,
+    /// A summary bar was dragged: shift the parent's subtree by this many
+    /// days (working days when dragging by working days), starting a new
+    /// undo step when the flag is set.
+    pub shift_subtree: Option<(Uuid, i64, bool)>,
+    /// The bar toolbar's Edit button: focus the editor on this task.
+    pub edit_task: Option<Uuid>,
+    /// Number of task rows laid out this frame (for the performance overlay).
+    pub visible_rows: usize,
+    /// Number of paint-list entries the chart added this frame.
+    pub shapes_painted: usize,
+    /// Current scroll offset of the chart area.
+    pub scroll_offset: Vec2,
+}
+
+impl ChartInteraction {
+    /// Only what changes the view, not the project: for a project open
+    /// read-only.
+    pub fn view_only(self) -> Self {
+        Self {
+            toggle_collapse: self.toggle_collapse,
+            focus_phase: self.focus_phase,
+            pin_card: self.pin_card,
+            toggle_guide: self.toggle_guide,
+            drag_autoscroll: self.drag_autoscroll,
+            name_gutter: self.name_gutter,
+            visible_rows: self.visible_rows,
+            shapes_painted: self.shapes_painted,
+            scroll_offset: self.scroll_offset,
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for ChartInteraction {
@@ -37,29 +127,299 @@ impl Default for ChartInteraction {
             changed: false,
             new_dependency: None,
             remove_dependency: None,
+            update_dependency: None,
             toggle_collapse: None,
             add_subtask: None,
             delete_task: None,
+            completion_milestone: None,
+            duplicate_phase: None,
+            export_phase: None,
+            focus_phase: None,
+            import_into_phase: None,
+            drag_autoscroll: None,
+            toggle_guide: None,
+            pin_card: None,
+            convert_to_milestone: None,
+            convert_to_task: None,
+            name_gutter: None,
+            mark_done: None,
+            moved_from: None,
+            shift_subtree: None,
+            edit_task: None,
+            visible_rows: 0,
+            shapes_painted: 0,
+            scroll_offset: Vec2::ZERO,
         }
     }
 }
 
+/// Open context menu for a dependency arrow.
+#[derive(Debug, Clone)]
+struct DepMenuState {
+    from_task: Uuid,
+    to_task: Uuid,
+    pos: Pos2,
+}
+
+/// Open chooser for several dependency arrows under the same right-click.
+#[derive(Debug, Clone)]
+struct DepPickState {
+    /// (from, to) of each arrow, nearest first.
+    candidates: Vec<(Uuid, Uuid)>,
+    pos: Pos2,
+}
+
+/// How close (in points) the pointer must be to an arrow's line to hit it.
+const ARROW_HIT_DISTANCE: f32 = 5.0;
+
 /// State for creating a dependency link via Shift+drag.
 #[derive(Debug, Clone)]
 struct LinkDragState {
     from_task: Uuid,
     from_point: Pos2,
+    /// The drag began on the left half of the source bar, so the link
+    /// leaves from its start rather than its finish.
+    from_start: bool,
+    /// Started from the bar toolbar: follows the pointer without a held
+    /// button until the next click picks the target.
+    picking: bool,
+}
+
+/// Buttons on the selected bar's toolbar.
+enum BarAction {
+    Edit,
+    Link,
+    AddSubtask,
+    MarkDone,
+    Delete,
+}
+
+/// Small floating toolbar just above `bar` (below it near the top of the
+/// chart) with the selected task's common actions.
+fn draw_bar_toolbar(ui: &Ui, pane: Id, bar: Rect, done: bool) -> Option<BarAction> {
+    use egui_phosphor::regular as icons;
+
+    let clip = ui.clip_rect();
+    let x = bar.left().max(clip.left() + 4.0);
+    let (pos, pivot) = if bar.top() - 30.0 < clip.top() {
+        (Pos2::new(x, bar.bottom() + 4.0), egui::Align2::LEFT_TOP)
+    } else {
+        (Pos2::new(x, bar.top() - 4.0), egui::Align2::LEFT_BOTTOM)
+    };
+    let mut action = None;
+    egui::Area::new(pane.with("bar-toolbar"))
+        .fixed_pos(pos)
+        .pivot(pivot)
+        .show(ui.ctx(), |ui| {
+            egui::Frame::popup(ui.style())
+                .inner_margin(egui::Margin::symmetric(4.0, 2.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing.x = 2.0;
+                        let buttons = [
+                            (icons::PENCIL_SIMPLE, "Edit", BarAction::Edit),
+                            (icons::LINK, "Link to another task: click the target bar (Esc cancels)", BarAction::Link),
+                            (icons::TREE_STRUCTURE, "Add subtask", BarAction::AddSubtask),
+                            (icons::CHECK, if done { "Already done" } else { "Mark done" }, BarAction::MarkDone),
+                            (icons::TRASH, "Delete", BarAction::Delete),
+                        ];
+                        for (icon, hint, bar_action) in buttons {
+                            let enabled = !(done && matches!(bar_action, BarAction::MarkDone));
+                            let button = egui::Button::new(egui::RichText::new(icon).size(13.0).color(theme::text_secondary())).frame(false);
+                            if ui.add_enabled(enabled, button).on_hover_text(hint).clicked() {
+                                action = Some(bar_action);
+                            }
+                        }
+                    });
+                });
+        });
+    action
+}
+
+/// Dependency kind for a link between the given source and target edges.
+fn link_kind(from_start: bool, to_finish: bool) -> DependencyKind {
+    match (from_start, to_finish) {
+        (false, false) => DependencyKind::FinishToStart,
+        (false, true) => DependencyKind::FinishToFinish,
+        (true, false) => DependencyKind::StartToStart,
+        (true, true) => DependencyKind::StartToFinish,
+    }
+}
+
+/// Bar edge nearest to `ptr`: `(anchor point, is the finish edge)`.
+fn nearest_anchor(rect: Rect, ptr: Pos2) -> (Pos2, bool) {
+    if ptr.x > rect.center().x {
+        (Pos2::new(rect.right(), rect.center().y), true)
+    } else {
+        (Pos2::new(rect.left(), rect.center().y), false)
+    }
+}
+
+/// Highlight a bar edge as a link anchor.
+fn draw_link_anchor(painter: &egui::Painter, rect: Rect, anchor: Pos2) {
+    painter.rect_stroke(rect.expand(1.5), Rounding::same(3.0), Stroke::new(1.0, with_alpha(theme::dep_creating(), 120)));
+    painter.line_segment(
+        [Pos2::new(anchor.x, rect.top() - 2.0), Pos2::new(anchor.x, rect.bottom() + 2.0)],
+        Stroke::new(3.0, theme::dep_creating()),
+    );
+    painter.circle_filled(anchor, 3.5, theme::dep_creating());
+}
+
+/// Slips up to this many days are drawn in the minor color.
+const SLIP_MINOR_DAYS: i64 = 2;
+/// Slips up to this many days are drawn in the moderate color; longer ones are major.
+const SLIP_MODERATE_DAYS: i64 = 7;
+
+/// How long the highlight pulse lasts after scrolling to a task, in seconds.
+const PULSE_DURATION: f64 = 1.2;
+
+/// Extra read-only information drawn on top of the task bars.
+#[derive(Default)]
+pub struct ChartOverlays<'a> {
+    /// Baseline used for slippage markers and tooltips.
+    pub baseline: Option<&'a Baseline>,
+    /// Proposed (task, start, end) dates drawn as dashed outlines on the
+    /// task's row, e.g. while previewing a leveling pass.
+    pub ghost_bars: &'a [(Uuid, NaiveDate, NaiveDate)],
+    /// (task, start, end) dates from another version of the plan, drawn
+    /// faintly behind the task's bar.
+    pub compare_bars: &'a [(Uuid, NaiveDate, NaiveDate)],
+    /// (task, from, to) ranges tinted as conflicts on the task's bar, e.g.
+    /// where siblings double-book a resource.
+    pub conflicts: &'a [(Uuid, NaiveDate, NaiveDate)],
+    /// Week numbering and fiscal quarters for the timeline header.
+    pub calendar: ProjectCalendar,
+    /// Show links touching tentative tasks in the critical style too.
+    pub include_tentative: bool,
+    /// Fill colors replacing task colors when bars are colored by data.
+    pub bar_colors: Option<&'a std::collections::HashMap<Uuid, Color32>>,
+    /// Faint per-row background tints (e.g. by priority).
+    pub row_tints: Option<&'a std::collections::HashMap<Uuid, Color32>>,
+    /// Reference guide lines placed by clicking the timeline header.
+    pub guides: &'a [NaiveDate],
+    /// The project's named marker lines.
+    pub markers: &'a [DateMarker],
+    /// The project's named shading bands.
+    pub bands: &'a [DateBand],
+    /// The project's free-floating notes and arrows.
+    pub annotations: &'a [Annotation],
+    /// Resources, for the assignees on the hover card.
+    pub resources: &'a [Resource],
+    /// Seconds a bar must be hovered before its card appears.
+    pub hover_delay: f32,
+    /// Frozen task-name column at the chart's left edge.
+    pub name_gutter: NameGutter,
+    /// Show the action toolbar over the selected bar (off for read-only charts).
+    pub bar_toolbar: bool,
+    /// (task, estimated finish) for tasks forecast to run past their end.
+    pub late_finishes: &'a [(Uuid, NaiveDate)],
+    /// (task, forecast finish) drawn as a dashed extension of the bar.
+    pub forecast_bars: &'a [(Uuid, NaiveDate)],
+    /// Only these tasks get rows, e.g. the phase in focus.
+    pub only: Option<&'a [Uuid]>,
+    /// Dragging a bar moves it by whole working days, keeping its working
+    /// duration, instead of by calendar days.
+    pub working_day_drag: bool,
+}
+
+impl ChartOverlays<'_> {
+    fn fill(&self, task: &Task) -> Color32 {
+        self.bar_colors.and_then(|m| m.get(&task.id)).copied().unwrap_or(task.color)
+    }
+}
+
+/// A one-off request to move the chart's scroll position.
+#[derive(Debug, Clone, Copy)]
+pub enum ChartScroll {
+    /// Scroll so this task's bar is visible and briefly pulse it.
+    ToTask(Uuid),
+    /// Jump to an exact scroll offset (e.g. when applying a saved view).
+    ToOffset(Vec2),
+}
+
+/// An animated change of zoom and position, so big jumps keep the user's
+/// sense of where they are. The centre of the chart glides between two
+/// dates while the zoom changes geometrically.
+#[derive(Debug, Clone)]
+pub struct ViewTransition {
+    from_ppd: f32,
+    to_ppd: f32,
+    /// Days from `origin` at the horizontal centre of the chart.
+    from_center: f32,
+    to_center: f32,
+    origin: NaiveDate,
+    from_y: f32,
+    to_y: f32,
+    to_scale: TimelineScale,
+    /// Scroll request issued when the move ends (e.g. to pulse a task).
+    then: Option<ChartScroll>,
+    started: Option<f64>,
+}
+
+impl ViewTransition {
+    /// Move from the current view (`scroll` within `width` pixels) to the
+    /// zoom and scale of `target`, centred `to_center` days after the
+    /// viewport start at vertical offset `to_y`.
+    pub fn new(
+        viewport: &TimelineViewport,
+        scroll: Vec2,
+        width: f32,
+        target: &TimelineViewport,
+        to_center: f32,
+        to_y: f32,
+        then: Option<ChartScroll>,
+    ) -> Self {
+        Self {
+            from_ppd: viewport.pixels_per_day,
+            to_ppd: target.pixels_per_day,
+            from_center: (scroll.x + width / 2.0) / viewport.pixels_per_day,
+            to_center,
+            origin: viewport.start,
+            from_y: scroll.y,
+            to_y,
+            to_scale: target.scale,
+            then,
+            started: None,
+        }
+    }
+
+    /// Advance to time `now`, updating the zoom and returning the scroll for
+    /// this frame and whether the move is finished.
+    pub fn step(&mut self, viewport: &mut TimelineViewport, width: f32, now: f64, duration: f32) -> (ChartScroll, bool) {
+        let started = *self.started.get_or_insert(now);
+        let t = if duration > 0.0 { ((now - started) as f32 / duration).min(1.0) } else { 1.0 };
+        let eased = t * t * (3.0 - 2.0 * t);
+        viewport.pixels_per_day = self.from_ppd * (self.to_ppd / self.from_ppd).powf(eased);
+        // The viewport may have been extended meanwhile; keep the dates fixed.
+        let shift = (self.origin - viewport.start).num_days() as f32;
+        let center = shift + self.from_center + (self.to_center - self.from_center) * eased;
+        let x = (center * viewport.pixels_per_day - width / 2.0).max(0.0);
+        let y = self.from_y + (self.to_y - self.from_y) * eased;
+        let scroll = ChartScroll::ToOffset(Vec2::new(x, y));
+        if t < 1.0 {
+            return (scroll, false);
+        }
+        viewport.pixels_per_day = self.to_ppd;
+        viewport.scale = self.to_scale;
+        (self.then.unwrap_or(scroll), true)
+    }
 }
 
 /// Render the Gantt chart area (right panel).
 pub fn show_gantt_chart(
     tasks: &mut [Task],
     dependencies: &[Dependency],
+    overlays: &ChartOverlays,
     viewport: &mut TimelineViewport,
     selected_task: &mut Option<Uuid>,
+    scroll: Option<ChartScroll>,
     ui: &mut Ui,
 ) -> ChartInteraction {
     let mut interaction = ChartInteraction::default();
+    // Salt for every id the chart stores state under, so several chart panes
+    // can show the same tasks without sharing drags, menus or animations.
+    let pane = ui.id();
+    let baseline = overlays.baseline;
     let available = ui.available_size();
     let row_height = scaled_row_height(viewport);
     let row_padding = scaled_row_padding(viewport);
@@ -71,6 +431,9 @@ pub fn show_gantt_chart(
         .iter()
         .enumerate()
         .filter_map(|(i, t)| {
+            if overlays.only.is_some_and(|only| !only.contains(&t.id)) {
+                return None;
+            }
             if let Some(pid) = t.parent_id {
                 let parent_collapsed = tasks.iter().find(|p| p.id == pid).map(|p| p.collapsed).unwrap_or(false);
                 if parent_collapsed { return None; }
@@ -80,10 +443,21 @@ pub fn show_gantt_chart(
         .collect();
 
     let chart_height = hh + (visible_rows.len() as f32 * (row_height + row_padding)) + 40.0;
+    interaction.visible_rows = visible_rows.len();
 
-    egui::ScrollArea::both()
-        .auto_shrink([false, false])
+    let scroll_to = match scroll {
+        Some(ChartScroll::ToTask(id)) => Some(id),
+        _ => None,
+    };
+    // Holding M turns drags into measurements instead of moves or scrolls.
+    let measuring = !ui.ctx().wants_keyboard_input() && ui.input(|i| i.key_down(egui::Key::M));
+    let mut scroll_area = egui::ScrollArea::both().auto_shrink([false, false]).drag_to_scroll(!measuring);
+    if let Some(ChartScroll::ToOffset(offset)) = scroll {
+        scroll_area = scroll_area.scroll_offset(offset);
+    }
+    let output = scroll_area
         .show(ui, |ui| {
+            let first_shape = paint_list_len(ui);
             let (response, painter) = ui.allocate_painter(
                 Vec2::new(chart_width, chart_height.max(available.y)),
                 Sense::click(),
@@ -113,6 +487,10 @@ pub fn show_gantt_chart(
             let origin = response.rect.min;
             let mut consumed_click = false;
             let shift_held = ui.input(|i| i.modifiers.shift);
+            let bar_drag = !shift_held && !measuring;
+            // Alt while dragging snaps dates to the start of a week.
+            let week_snap = ui.input(|i| i.modifiers.alt).then_some(&overlays.calendar);
+            let working_drag = overlays.working_day_drag.then_some(&overlays.calendar);
             let mut reorder_request: Option<(usize, usize)> = None;
             let mut reorder_preview_target: Option<usize> = None;
 
@@ -123,38 +501,43 @@ pub fn show_gantt_chart(
                 theme::bg_dark(),
             );
 
-            // Draw alternating row backgrounds (only for visible rows)
-            for (vis_i, &task_i) in visible_rows.iter().enumerate() {
-                let _task = &tasks[task_i];
+            // Draw alternating row backgrounds (only for visible rows), batched
+            // into one mesh plus one shape list for the row borders.
+            let mut row_mesh = egui::Mesh::default();
+            let mut row_borders: Vec<egui::Shape> = Vec::with_capacity(visible_rows.len());
+            let (bg_even, bg_odd) = (theme::bg_panel(), theme::bg_dark());
+            let border_stroke = Stroke::new(0.5, theme::border_subtle());
+            for vis_i in 0..visible_rows.len() {
                 let y = origin.y + hh + vis_i as f32 * (row_height + row_padding);
-                let row_bg = if vis_i % 2 == 0 {
-                    theme::bg_panel()
-                } else {
-                    theme::bg_dark()
-                };
-                painter.rect_filled(
+                let mut row_bg = if vis_i % 2 == 0 { bg_even } else { bg_odd };
+                if let Some(tint) = overlays.row_tints.and_then(|m| m.get(&tasks[visible_rows[vis_i]].id)) {
+                    row_bg = row_bg.lerp_to_gamma(*tint, 0.14);
+                }
+                row_mesh.add_colored_rect(
                     Rect::from_min_size(
                         Pos2::new(origin.x, y),
                         Vec2::new(chart_width, row_height + row_padding),
                     ),
-                    0.0,
                     row_bg,
                 );
                 // Row bottom border
-                painter.line_segment(
+                row_borders.push(egui::Shape::line_segment(
                     [
                         Pos2::new(origin.x, y + row_height + row_padding),
                         Pos2::new(origin.x + chart_width, y + row_height + row_padding),
                     ],
-                    Stroke::new(0.5, theme::border_subtle()),
-                );
+                    border_stroke,
+                ));
             }
+            painter.add(egui::Shape::mesh(row_mesh));
+            painter.extend(row_borders);
 
             // Shade weekends in the gantt body so they stand out clearly.
             draw_weekend_bands(
                 &painter,
                 origin,
                 viewport,
+                &overlays.calendar,
                 chart_width,
                 origin.y + chart_height,
             );
@@ -164,9 +547,19 @@ pub fn show_gantt_chart(
                 &painter,
                 origin,
                 viewport,
+                &overlays.calendar,
                 chart_width,
                 origin.y + chart_height,
             );
+            let selected_range = selected_task
+                .and_then(|id| tasks.iter().find(|t| t.id == id))
+                .map(|t| (t.start, t.end));
+            if let Some(range) = selected_range {
+                draw_selection_range(&painter, origin, viewport, range, chart_width, origin.y + chart_height);
+            }
+
+            draw_date_bands(&painter, origin, viewport, overlays.bands, origin.y + chart_height);
+            draw_marker_lines(&painter, origin, viewport, overlays.markers, origin.y + chart_height);
 
             // Animated row Y positions for smooth reorder transitions.
             // Only visible rows get a Y slot; collapsed children are not assigned a Y.
@@ -178,7 +571,7 @@ pub fn show_gantt_chart(
                 let task = &tasks[task_i];
                 let target_y =
                     origin.y + hh + vis_i as f32 * (row_height + row_padding) + row_padding;
-                let anim_id = Id::new(("row-y", task.id));
+                let anim_id = pane.with(("row-y", task.id));
                 let animated_y = ui.ctx().animate_value_with_time(anim_id, target_y, anim_dur);
                 if (animated_y - target_y).abs() > 0.25 {
                     animating_rows = true;
@@ -218,17 +611,59 @@ pub fn show_gantt_chart(
                 })
                 .collect();
 
+            let pulse_id = pane.with("chart-scroll-pulse");
+            if let Some(target) = scroll_to {
+                if let Some(&(_, rect)) = task_positions.get(&target) {
+                    ui.scroll_to_rect(rect.expand(24.0), Some(egui::Align::Center));
+                    let now = ui.input(|i| i.time);
+                    ui.ctx().data_mut(|d| d.insert_temp(pulse_id, (target, now)));
+                }
+            }
+
+            // Bars from the compared file, faint and behind everything else.
+            for &(task_id, start, end) in overlays.compare_bars {
+                if let Some(&(_, rect)) = task_positions.get(&task_id) {
+                    let x0 = origin.x + viewport.date_to_x(start);
+                    let x1 = (origin.x + viewport.date_to_x(end)).max(x0 + 6.0);
+                    let other = Rect::from_min_max(Pos2::new(x0, rect.top() - 2.0), Pos2::new(x1, rect.bottom() + 2.0));
+                    let rounding = Rounding::same(theme::bar_rounding());
+                    painter.rect_filled(other, rounding, with_alpha(theme::text_secondary(), 40));
+                    painter.rect_stroke(other, rounding, Stroke::new(1.0, with_alpha(theme::text_secondary(), 110)));
+                }
+            }
+
             // ── Draw dependency arrows (BEHIND bars) ─────────────────
+            // Links touching excluded tentative work are never drawn as critical.
+            let excluded: std::collections::HashSet<Uuid> = if overlays.include_tentative {
+                Default::default()
+            } else {
+                tasks.iter().filter(|t| t.tentative).map(|t| t.id).collect()
+            };
+            let anchors = dependency_anchors(dependencies, &task_positions);
             for dep in dependencies {
                 if let (Some(&(_, from_rect)), Some(&(_, to_rect))) =
                     (task_positions.get(&dep.from_task), task_positions.get(&dep.to_task))
                 {
-                    let (start_pt, end_pt) = dependency_endpoints(from_rect, to_rect, dep.kind);
-                    draw_dependency_arrow(&painter, start_pt, end_pt, dep.kind, with_alpha(theme::dep_arrow(), 180), 1.4);
+                    let (start_pt, end_pt) =
+                        dependency_endpoints(from_rect, to_rect, dep.kind, anchors.offset(dep));
+                    let counts = !excluded.contains(&dep.from_task) && !excluded.contains(&dep.to_task);
+                    let (color, width) = dependency_style(dep, counts);
+                    draw_dependency_arrow(&painter, start_pt, end_pt, dep.kind, color, width, dep.line_style);
                 }
             }
 
+            draw_dependency_bundles(&painter, &anchors);
+
+            // Task bar bodies are collected into one shape list and painted
+            // through this placeholder, so they still sit behind handles,
+            // labels and overlays drawn later in the frame.
+            let bar_shapes_idx = painter.add(egui::Shape::Noop);
+            let mut bar_shapes: Vec<egui::Shape> = Vec::new();
+
             let mut hovered_task: Option<Uuid> = None;
+            // Pointer x of a drag that is moving dates, for edge auto-scroll.
+            let mut drag_pointer_x: Option<f32> = None;
+            let mut drag_ghost: Option<DragGhost> = None;
 
             // Draw task bars — iterate only visible rows.
             let vis_count = visible_rows.len();
@@ -246,7 +681,12 @@ pub fn show_gantt_chart(
                 if is_parent_task {
                     // ── Summary / parent bar ─────────────────────────
                     let task = &tasks[task_i];
-                    let summary_rect = draw_summary_bar(&painter, origin, viewport, task, y, row_height, is_selected);
+                    let children: Vec<&Task> = tasks
+                        .iter()
+                        .filter(|t| t.parent_id == Some(task_id) && (overlays.include_tentative || !t.tentative))
+                        .collect();
+                    let summary_rect =
+                        draw_summary_bar(&painter, origin, viewport, task, &children, y, row_height, is_selected);
 
                     // Collapse/expand toggle button (small triangle to the left of bar)
                     let toggle_x = summary_rect.left() - 14.0;
@@ -280,15 +720,73 @@ pub fn show_gantt_chart(
                         *selected_task = Some(task_id);
                         consumed_click = true;
                     }
+
+                    // Dragging a summary bar moves its whole subtree; the app
+                    // applies the shift, so the children move next frame.
+                    if summary_resp.drag_started() && bar_drag {
+                        let ptr = summary_resp.interact_pointer_pos().unwrap_or(Pos2::ZERO);
+                        ui.ctx().data_mut(|data| {
+                            data.insert_persisted(
+                                drag_id(pane, task_id, "summary"),
+                                DragSnapshot {
+                                    start: task.start,
+                                    end: task.end,
+                                    start_pointer_x: timeline_x(ptr.x, origin, viewport),
+                                    start_pointer_y: ptr.y,
+                                },
+                            );
+                        });
+                        *selected_task = Some(task_id);
+                        consumed_click = true;
+                    }
+                    if summary_resp.dragged() && bar_drag {
+                        ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+                        let ptr = summary_resp.interact_pointer_pos().unwrap_or(Pos2::ZERO);
+                        let snapshot = ui
+                            .ctx()
+                            .data_mut(|data| data.get_persisted::<DragSnapshot>(drag_id(pane, task_id, "summary")));
+                        if let Some(snapshot) = snapshot {
+                            let delta_x = timeline_x(ptr.x, origin, viewport) - snapshot.start_pointer_x;
+                            let target = snap_date(snapshot.start + chrono::Duration::days(drag_days(delta_x, viewport)), week_snap);
+                            let (target, target_end) = drag_span((snapshot.start, snapshot.end), target, working_drag);
+                            let step = match working_drag {
+                                Some(calendar) => calendar.working_days_crossed(task.start, target),
+                                None => (target - task.start).num_days(),
+                            };
+                            if step != 0 {
+                                let moved = drag_id(pane, task_id, "summary-moved");
+                                let first = !ui.ctx().data_mut(|data| data.get_temp::<bool>(moved)).unwrap_or(false);
+                                ui.ctx().data_mut(|data| data.insert_temp(moved, true));
+                                interaction.shift_subtree = Some((task_id, step, first));
+                            }
+                            let delta = (target - snapshot.start).num_days();
+                            drag_pointer_x = Some(ptr.x);
+                            drag_ghost = Some(DragGhost {
+                                task: task_id,
+                                from: (snapshot.start, snapshot.end),
+                                to: (target, target_end),
+                                delta,
+                                y: summary_rect.y_range(),
+                                pointer: ptr,
+                                milestone: false,
+                            });
+                        }
+                    }
+                    if summary_resp.drag_stopped() {
+                        ui.ctx().data_mut(|data| {
+                            data.remove::<DragSnapshot>(drag_id(pane, task_id, "summary"));
+                            data.remove::<bool>(drag_id(pane, task_id, "summary-moved"));
+                        });
+                    }
                     if summary_resp.secondary_clicked() {
                         let open_pos = ui.input(|i| i.pointer.interact_pos().unwrap_or(summary_rect.center()));
-                        ui.ctx().data_mut(|d| d.insert_temp(Id::new(("ctx-menu", task_id)), open_pos));
+                        ui.ctx().data_mut(|d| d.insert_temp(pane.with(("ctx-menu", task_id)), open_pos));
                     }
                     // Context menu for parent tasks
-                    let ctx_pos: Option<Pos2> = ui.ctx().data_mut(|d| d.get_temp(Id::new(("ctx-menu", task_id))));
+                    let ctx_pos: Option<Pos2> = ui.ctx().data_mut(|d| d.get_temp(pane.with(("ctx-menu", task_id))));
                     if let Some(open_pos) = ctx_pos {
                         let mut close_menu = false;
-                        egui::Area::new(Id::new(("ctx-area", task_id)))
+                        egui::Area::new(pane.with(("ctx-area", task_id)))
                             .fixed_pos(open_pos)
                             .order(egui::Order::Foreground)
                             .show(ui.ctx(), |ui| {
@@ -297,6 +795,38 @@ pub fn show_gantt_chart(
                                         interaction.add_subtask = Some(task_id);
                                         close_menu = true;
                                     }
+                                    if ui
+                                        .button(egui_phosphor::regular::CROSSHAIR.to_string() + "  Focus on This Phase")
+                                        .on_hover_text("Show only this phase in the chart and task list")
+                                        .clicked()
+                                    {
+                                        interaction.focus_phase = Some(task_id);
+                                        close_menu = true;
+                                    }
+                                    if ui
+                                        .button(egui_phosphor::regular::FLAG_CHECKERED.to_string() + "  Add Completion Milestone")
+                                        .on_hover_text("A milestone after this phase that follows its finish date")
+                                        .clicked()
+                                    {
+                                        interaction.completion_milestone = Some(task_id);
+                                        close_menu = true;
+                                    }
+                                    if ui.button(egui_phosphor::regular::COPY.to_string() + "  Duplicate Phase…").clicked() {
+                                        interaction.duplicate_phase = Some(task_id);
+                                        close_menu = true;
+                                    }
+                                    if ui.button(egui_phosphor::regular::EXPORT.to_string() + "  Export Phase…").clicked() {
+                                        interaction.export_phase = Some(task_id);
+                                        close_menu = true;
+                                    }
+                                    if ui.button(egui_phosphor::regular::DOWNLOAD_SIMPLE.to_string() + "  Import into Phase…").clicked() {
+                                        interaction.import_into_phase = Some(task_id);
+                                        close_menu = true;
+                                    }
+                                    if ui.button(egui_phosphor::regular::PUSH_PIN.to_string() + "  Pin Details").clicked() {
+                                        interaction.pin_card = Some(task_id);
+                                        close_menu = true;
+                                    }
                                     if ui.button(egui_phosphor::regular::TRASH.to_string() + "  Delete Group").clicked() {
                                         interaction.delete_task = Some(task_id);
                                         close_menu = true;
@@ -304,32 +834,16 @@ pub fn show_gantt_chart(
                                 });
                             });
                         if close_menu || ui.input(|i| i.pointer.secondary_pressed()) {
-                            ui.ctx().data_mut(|d| d.remove::<Pos2>(Id::new(("ctx-menu", task_id))));
+                            ui.ctx().data_mut(|d| d.remove::<Pos2>(pane.with(("ctx-menu", task_id))));
                         }
                     }
 
                     if summary_resp.hovered() {
                         hovered_task = Some(task_id);
-                        egui::show_tooltip_at_pointer(
-                            ui.ctx(),
-                            ui.layer_id(),
-                            egui::Id::new(("summary-tip", task_id)),
-                            |ui| {
-                                let task = &tasks[task_i];
-                                ui.strong(&task.name);
-                                ui.label(format!(
-                                    "{} → {}",
-                                    task.start.format("%d/%m/%Y"),
-                                    task.end.format("%d/%m/%Y"),
-                                ));
-                                ui.label(format!("Progress: {}%", (task.progress * 100.0) as i32));
-                                ui.label(egui::RichText::new("Right-click for options").size(9.0).color(theme::text_dim()));
-                            },
-                        );
                     }
                 } else if tasks[task_i].is_milestone {
                     let task = &mut tasks[task_i];
-                    let task_rect = draw_milestone(&painter, origin, viewport, task, y, row_height, is_selected);
+                    let task_rect = draw_milestone(&painter, origin, viewport, task, overlays.fill(task), y, row_height, is_selected);
                     let response = ui.interact(
                         task_rect.expand(6.0),
                         ui.make_persistent_id(("milestone", task.id)),
@@ -343,14 +857,14 @@ pub fn show_gantt_chart(
                     // Right-click context menu for milestones
                     if response.secondary_clicked() {
                         let open_pos = ui.input(|i| i.pointer.interact_pos().unwrap_or(task_rect.center()));
-                        ui.ctx().data_mut(|d| d.insert_temp(Id::new(("ctx-menu", task.id)), open_pos));
+                        ui.ctx().data_mut(|d| d.insert_temp(pane.with(("ctx-menu", task.id)), open_pos));
                     }
-                    let ctx_pos: Option<Pos2> = ui.ctx().data_mut(|d| d.get_temp(Id::new(("ctx-menu", task.id))));
+                    let ctx_pos: Option<Pos2> = ui.ctx().data_mut(|d| d.get_temp(pane.with(("ctx-menu", task.id))));
                     if let Some(open_pos) = ctx_pos {
                         let mut close_menu = false;
                         let tid = task.id;
                         let is_child = task_parent_id.is_some();
-                        egui::Area::new(Id::new(("ctx-area", tid)))
+                        egui::Area::new(pane.with(("ctx-area", tid)))
                             .fixed_pos(open_pos)
                             .order(egui::Order::Foreground)
                             .show(ui.ctx(), |ui| {
@@ -361,6 +875,14 @@ pub fn show_gantt_chart(
                                             close_menu = true;
                                         }
                                     }
+                                    if ui.button(egui_phosphor::regular::ARROWS_LEFT_RIGHT.to_string() + "  Convert to Task…").clicked() {
+                                        interaction.convert_to_task = Some(tid);
+                                        close_menu = true;
+                                    }
+                                    if ui.button(egui_phosphor::regular::PUSH_PIN.to_string() + "  Pin Details").clicked() {
+                                        interaction.pin_card = Some(tid);
+                                        close_menu = true;
+                                    }
                                     if ui.button(egui_phosphor::regular::TRASH.to_string() + "  Delete Task").clicked() {
                                         interaction.delete_task = Some(tid);
                                         close_menu = true;
@@ -368,33 +890,33 @@ pub fn show_gantt_chart(
                                 });
                             });
                         if close_menu || ui.input(|i| i.pointer.secondary_pressed()) {
-                            ui.ctx().data_mut(|d| d.remove::<Pos2>(Id::new(("ctx-menu", tid))));
+                            ui.ctx().data_mut(|d| d.remove::<Pos2>(pane.with(("ctx-menu", tid))));
                         }
                     }
 
-                    if response.drag_started() && !shift_held {
+                    if response.drag_started() && bar_drag {
                         let ptr = response.interact_pointer_pos().unwrap_or(Pos2::ZERO);
                         ui.ctx().data_mut(|data| {
                             data.insert_persisted(
-                                drag_id(task.id, "milestone"),
+                                drag_id(pane, task.id, "milestone"),
                                 DragSnapshot {
                                     start: task.start,
                                     end: task.end,
-                                    start_pointer_x: ptr.x,
+                                    start_pointer_x: timeline_x(ptr.x, origin, viewport),
                                     start_pointer_y: ptr.y,
                                 },
                             );
                         });
                     }
 
-                    if response.dragged() && !shift_held {
+                    if response.dragged() && bar_drag {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
                         let ptr = response.interact_pointer_pos().unwrap_or(Pos2::ZERO);
                         let snapshot = ui.ctx().data_mut(|data| {
-                            data.get_persisted::<DragSnapshot>(drag_id(task.id, "milestone"))
+                            data.get_persisted::<DragSnapshot>(drag_id(pane, task.id, "milestone"))
                         });
                         if let Some(snapshot) = snapshot {
-                            let delta_x = ptr.x - snapshot.start_pointer_x;
+                            let delta_x = timeline_x(ptr.x, origin, viewport) - snapshot.start_pointer_x;
                             let delta_y = ptr.y - snapshot.start_pointer_y;
                             let is_reorder_drag =
                                 delta_y.abs() > row_height * 0.45 && delta_y.abs() > delta_x.abs();
@@ -413,38 +935,45 @@ pub fn show_gantt_chart(
                                         reorder_request = Some((task_i, target_task_i));
                                     }
                                 }
-                            } else {
+                            } else if !(task.auto_date && dependencies.iter().any(|d| d.to_task == task.id)) {
+                                // Auto-dated milestones follow their predecessors instead.
                                 let day_delta = drag_days(delta_x, viewport);
-                                task.start = snapshot.start + chrono::Duration::days(day_delta);
+                                let before = (task.start, task.end);
+                                let target = snap_date(snapshot.start + chrono::Duration::days(day_delta), week_snap);
+                                task.start = drag_span((snapshot.start, snapshot.start), target, working_drag).0;
                                 task.end = task.start;
+                                let day_delta = (task.start - snapshot.start).num_days();
                                 interaction.changed = true;
+                                if (task.start, task.end) != before {
+                                    interaction.moved_from = Some((task.id, before));
+                                }
                                 *selected_task = Some(task.id);
+                                drag_pointer_x = Some(ptr.x);
+                                drag_ghost = Some(DragGhost {
+                                    task: task.id,
+                                    from: (snapshot.start, snapshot.end),
+                                    to: (task.start, task.end),
+                                    delta: day_delta,
+                                    y: response.rect.y_range(),
+                                    pointer: ptr,
+                                    milestone: true,
+                                });
                             }
                         }
                     }
 
                     if response.drag_stopped() {
                         ui.ctx().data_mut(|data| {
-                            data.remove::<DragSnapshot>(drag_id(task.id, "milestone"));
+                            data.remove::<DragSnapshot>(drag_id(pane, task.id, "milestone"));
                         });
                     }
 
                     if response.hovered() {
                         hovered_task = Some(task.id);
-                        egui::show_tooltip_at_pointer(
-                            ui.ctx(),
-                            ui.layer_id(),
-                            egui::Id::new(("milestone-tip", task.id)),
-                            |ui| {
-                                ui.strong(&task.name);
-                                ui.label(task.start.format("%d/%m/%Y").to_string());
-                                ui.label(format!("Progress: {}%", (task.progress * 100.0) as i32));
-                            },
-                        );
                     }
                 } else {
                     let task = &mut tasks[task_i];
-                    let bar_rect = draw_task_bar(&painter, origin, viewport, task, y, row_height, is_selected);
+                    let bar_rect = draw_task_bar(&painter, &mut bar_shapes, origin, viewport, task, overlays.fill(task), y, row_height, is_selected);
 
                     let bar_response = ui.interact(
                         bar_rect,
@@ -479,14 +1008,14 @@ pub fn show_gantt_chart(
                     // Right-click context menu for regular tasks
                     if bar_response.secondary_clicked() {
                         let open_pos = ui.input(|i| i.pointer.interact_pos().unwrap_or(bar_rect.center()));
-                        ui.ctx().data_mut(|d| d.insert_temp(Id::new(("ctx-menu", task.id)), open_pos));
+                        ui.ctx().data_mut(|d| d.insert_temp(pane.with(("ctx-menu", task.id)), open_pos));
                     }
-                    let ctx_pos: Option<Pos2> = ui.ctx().data_mut(|d| d.get_temp(Id::new(("ctx-menu", task.id))));
+                    let ctx_pos: Option<Pos2> = ui.ctx().data_mut(|d| d.get_temp(pane.with(("ctx-menu", task.id))));
                     if let Some(open_pos) = ctx_pos {
                         let mut close_menu = false;
                         let tid = task.id;
                         let is_child = task_parent_id.is_some();
-                        egui::Area::new(Id::new(("ctx-area", tid)))
+                        egui::Area::new(pane.with(("ctx-area", tid)))
                             .fixed_pos(open_pos)
                             .order(egui::Order::Foreground)
                             .show(ui.ctx(), |ui| {
@@ -497,6 +1026,14 @@ pub fn show_gantt_chart(
                                             close_menu = true;
                                         }
                                     }
+                                    if ui.button(egui_phosphor::regular::DIAMOND.to_string() + "  Convert to Milestone").clicked() {
+                                        interaction.convert_to_milestone = Some(tid);
+                                        close_menu = true;
+                                    }
+                                    if ui.button(egui_phosphor::regular::PUSH_PIN.to_string() + "  Pin Details").clicked() {
+                                        interaction.pin_card = Some(tid);
+                                        close_menu = true;
+                                    }
                                     if ui.button(egui_phosphor::regular::TRASH.to_string() + "  Delete Task").clicked() {
                                         interaction.delete_task = Some(tid);
                                         close_menu = true;
@@ -504,47 +1041,47 @@ pub fn show_gantt_chart(
                                 });
                             });
                         if close_menu || ui.input(|i| i.pointer.secondary_pressed()) {
-                            ui.ctx().data_mut(|d| d.remove::<Pos2>(Id::new(("ctx-menu", tid))));
+                            ui.ctx().data_mut(|d| d.remove::<Pos2>(pane.with(("ctx-menu", tid))));
                         }
                     }
 
-                    if left_response.drag_started() && !shift_held {
+                    if left_response.drag_started() && bar_drag {
                         let ptr = left_response.interact_pointer_pos().unwrap_or(Pos2::ZERO);
                         ui.ctx().data_mut(|data| {
                             data.insert_persisted(
-                                drag_id(task.id, "left"),
+                                drag_id(pane, task.id, "left"),
                                 DragSnapshot {
                                     start: task.start,
                                     end: task.end,
-                                    start_pointer_x: ptr.x,
+                                    start_pointer_x: timeline_x(ptr.x, origin, viewport),
                                     start_pointer_y: ptr.y,
                                 },
                             );
                         });
                     }
-                    if right_response.drag_started() && !shift_held {
+                    if right_response.drag_started() && bar_drag {
                         let ptr = right_response.interact_pointer_pos().unwrap_or(Pos2::ZERO);
                         ui.ctx().data_mut(|data| {
                             data.insert_persisted(
-                                drag_id(task.id, "right"),
+                                drag_id(pane, task.id, "right"),
                                 DragSnapshot {
                                     start: task.start,
                                     end: task.end,
-                                    start_pointer_x: ptr.x,
+                                    start_pointer_x: timeline_x(ptr.x, origin, viewport),
                                     start_pointer_y: ptr.y,
                                 },
                             );
                         });
                     }
-                    if bar_response.drag_started() && !shift_held {
+                    if bar_response.drag_started() && bar_drag {
                         let ptr = bar_response.interact_pointer_pos().unwrap_or(Pos2::ZERO);
                         ui.ctx().data_mut(|data| {
                             data.insert_persisted(
-                                drag_id(task.id, "move"),
+                                drag_id(pane, task.id, "move"),
                                 DragSnapshot {
                                     start: task.start,
                                     end: task.end,
-                                    start_pointer_x: ptr.x,
+                                    start_pointer_x: timeline_x(ptr.x, origin, viewport),
                                     start_pointer_y: ptr.y,
                                 },
                             );
@@ -556,41 +1093,69 @@ pub fn show_gantt_chart(
                         consumed_click = true;
                     }
 
-                    if left_response.dragged() && !shift_held {
+                    if left_response.dragged() && bar_drag {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
                         let ptr_x = left_response.interact_pointer_pos().map(|p| p.x).unwrap_or(0.0);
                         let snapshot = ui
                             .ctx()
-                            .data_mut(|data| data.get_persisted::<DragSnapshot>(drag_id(task.id, "left")));
+                            .data_mut(|data| data.get_persisted::<DragSnapshot>(drag_id(pane, task.id, "left")));
                         if let Some(snapshot) = snapshot {
-                            let total_delta_x = ptr_x - snapshot.start_pointer_x;
+                            let total_delta_x = timeline_x(ptr_x, origin, viewport) - snapshot.start_pointer_x;
                             let day_delta = drag_days(total_delta_x, viewport);
-                            let new_start = snapshot.start + chrono::Duration::days(day_delta);
+                            let new_start = snap_date(snapshot.start + chrono::Duration::days(day_delta), week_snap);
+                            let before = (task.start, task.end);
                             task.start = new_start.min(snapshot.end);
                             task.end = snapshot.end.max(task.start);
                             interaction.changed = true;
+                            if (task.start, task.end) != before {
+                                interaction.moved_from = Some((task.id, before));
+                            }
+                            drag_pointer_x = Some(ptr_x);
+                            drag_ghost = Some(DragGhost {
+                                task: task.id,
+                                from: (snapshot.start, snapshot.end),
+                                to: (task.start, task.end),
+                                delta: (task.start - snapshot.start).num_days(),
+                                y: bar_rect.y_range(),
+                                pointer: Pos2::new(ptr_x, bar_rect.top()),
+                                milestone: false,
+                            });
                         }
-                    } else if right_response.dragged() && !shift_held {
+                    } else if right_response.dragged() && bar_drag {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
                         let ptr_x = right_response.interact_pointer_pos().map(|p| p.x).unwrap_or(0.0);
                         let snapshot = ui
                             .ctx()
-                            .data_mut(|data| data.get_persisted::<DragSnapshot>(drag_id(task.id, "right")));
+                            .data_mut(|data| data.get_persisted::<DragSnapshot>(drag_id(pane, task.id, "right")));
                         if let Some(snapshot) = snapshot {
-                            let total_delta_x = ptr_x - snapshot.start_pointer_x;
+                            let total_delta_x = timeline_x(ptr_x, origin, viewport) - snapshot.start_pointer_x;
                             let day_delta = drag_days(total_delta_x, viewport);
-                            let new_end = snapshot.end + chrono::Duration::days(day_delta);
+                            let new_end = snap_date(snapshot.end + chrono::Duration::days(day_delta), week_snap);
+                            let before = (task.start, task.end);
                             task.end = new_end.max(snapshot.start);
                             interaction.changed = true;
+                            if (task.start, task.end) != before {
+                                interaction.moved_from = Some((task.id, before));
+                            }
+                            drag_pointer_x = Some(ptr_x);
+                            drag_ghost = Some(DragGhost {
+                                task: task.id,
+                                from: (snapshot.start, snapshot.end),
+                                to: (task.start, task.end),
+                                delta: (task.end - snapshot.end).num_days(),
+                                y: bar_rect.y_range(),
+                                pointer: Pos2::new(ptr_x, bar_rect.top()),
+                                milestone: false,
+                            });
                         }
-                    } else if bar_response.dragged() && !shift_held {
+                    } else if bar_response.dragged() && bar_drag {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
                         let ptr = bar_response.interact_pointer_pos().unwrap_or(Pos2::ZERO);
                         let snapshot = ui
                             .ctx()
-                            .data_mut(|data| data.get_persisted::<DragSnapshot>(drag_id(task.id, "move")));
+                            .data_mut(|data| data.get_persisted::<DragSnapshot>(drag_id(pane, task.id, "move")));
                         if let Some(snapshot) = snapshot {
-                            let delta_x = ptr.x - snapshot.start_pointer_x;
+                            let delta_x = timeline_x(ptr.x, origin, viewport) - snapshot.start_pointer_x;
                             let delta_y = ptr.y - snapshot.start_pointer_y;
                             let is_reorder_drag =
                                 delta_y.abs() > row_height * 0.45 && delta_y.abs() > delta_x.abs();
@@ -611,26 +1176,43 @@ pub fn show_gantt_chart(
                                 }
                             } else {
                                 let day_delta = drag_days(delta_x, viewport);
-                                task.start = snapshot.start + chrono::Duration::days(day_delta);
-                                task.end = snapshot.end + chrono::Duration::days(day_delta);
+                                let target = snap_date(snapshot.start + chrono::Duration::days(day_delta), week_snap);
+                                let (start, end) = drag_span((snapshot.start, snapshot.end), target, working_drag);
+                                let day_delta = (start - snapshot.start).num_days();
+                                let before = (task.start, task.end);
+                                task.start = start;
+                                task.end = end;
                                 interaction.changed = true;
+                                if (task.start, task.end) != before {
+                                    interaction.moved_from = Some((task.id, before));
+                                }
+                                drag_pointer_x = Some(ptr.x);
+                                drag_ghost = Some(DragGhost {
+                                    task: task.id,
+                                    from: (snapshot.start, snapshot.end),
+                                    to: (task.start, task.end),
+                                    delta: day_delta,
+                                    y: bar_rect.y_range(),
+                                    pointer: ptr,
+                                    milestone: false,
+                                });
                             }
                         }
                     }
 
                     if left_response.drag_stopped() {
                         ui.ctx().data_mut(|data| {
-                            data.remove::<DragSnapshot>(drag_id(task.id, "left"));
+                            data.remove::<DragSnapshot>(drag_id(pane, task.id, "left"));
                         });
                     }
                     if right_response.drag_stopped() {
                         ui.ctx().data_mut(|data| {
-                            data.remove::<DragSnapshot>(drag_id(task.id, "right"));
+                            data.remove::<DragSnapshot>(drag_id(pane, task.id, "right"));
                         });
                     }
                     if bar_response.drag_stopped() {
                         ui.ctx().data_mut(|data| {
-                            data.remove::<DragSnapshot>(drag_id(task.id, "move"));
+                            data.remove::<DragSnapshot>(drag_id(pane, task.id, "move"));
                         });
                     }
 
@@ -656,24 +1238,85 @@ pub fn show_gantt_chart(
                         painter.rect_filled(rh, Rounding::same(2.0), theme::handle_color());
                     }
 
-                    // Tooltip on hover
                     if bar_response.hovered() || left_response.hovered() || right_response.hovered() {
                         hovered_task = Some(task.id);
-                        egui::show_tooltip_at_pointer(
-                            ui.ctx(),
-                            ui.layer_id(),
-                            egui::Id::new(("task-tip", task.id)),
-                            |ui| {
-                                ui.strong(&task.name);
-                                ui.label(format!(
-                                    "{} → {}",
-                                    task.start.format("%d/%m/%Y"),
-                                    task.end.format("%d/%m/%Y"),
-                                ));
-                                ui.label(format!("Progress: {}%", (task.progress * 100.0) as i32));
-                            },
+                    }
+                }
+            }
+
+            painter.set(bar_shapes_idx, egui::Shape::Vec(bar_shapes));
+
+            // Ghost outlines for previewed date changes.
+            for &(ghost_id, start, end) in overlays.ghost_bars {
+                if let Some(&(_, rect)) = task_positions.get(&ghost_id) {
+                    let x0 = origin.x + viewport.date_to_x(start);
+                    let x1 = (origin.x + viewport.date_to_x(end)).max(x0 + 6.0);
+                    let ghost = Rect::from_min_max(Pos2::new(x0, rect.top()), Pos2::new(x1, rect.bottom()));
+                    painter.rect_filled(ghost, Rounding::same(theme::bar_rounding()), with_alpha(theme::accent(), 30));
+                    let outline = [
+                        ghost.left_top(),
+                        ghost.right_top(),
+                        ghost.right_bottom(),
+                        ghost.left_bottom(),
+                        ghost.left_top(),
+                    ];
+                    painter.extend(egui::Shape::dashed_line(&outline, Stroke::new(1.2, theme::accent()), 4.0, 3.0));
+                }
+            }
+
+            // Conflicting date ranges, tinted over the bars.
+            for &(task_id, from, to) in overlays.conflicts {
+                if let Some(&(_, rect)) = task_positions.get(&task_id) {
+                    let x0 = (origin.x + viewport.date_to_x(from)).max(rect.left());
+                    let x1 = (origin.x + viewport.date_to_x(to)).min(rect.right());
+                    if x1 > x0 {
+                        let tint = Rect::from_x_y_ranges(x0..=x1, rect.y_range());
+                        painter.rect_filled(tint, Rounding::ZERO, with_alpha(theme::slip_major(), 90));
+                        painter.hline(x0..=x1, rect.bottom() - 1.0, Stroke::new(2.0, theme::slip_major()));
+                    }
+                }
+            }
+
+            // Baseline slippage whiskers on tasks that finish later than planned.
+            if let Some(baseline) = baseline {
+                for &task_i in &visible_rows {
+                    let task = &tasks[task_i];
+                    if let Some(&(_, rect)) = task_positions.get(&task.id) {
+                        draw_slippage_marker(&painter, origin, viewport, baseline, task, rect);
+                    }
+                }
+            }
+
+            // Dashed extensions to where the pace so far would finish a task.
+            for &(task_id, finish) in overlays.forecast_bars {
+                if let (Some(&(_, rect)), Some(task)) = (task_positions.get(&task_id), tasks.iter().find(|t| t.id == task_id)) {
+                    draw_forecast_extension(&painter, origin, viewport, finish, rect, overlays.fill(task));
+                }
+            }
+
+            // Estimated finishes beyond the planned end.
+            for &(task_id, finish) in overlays.late_finishes {
+                if let Some(&(_, rect)) = task_positions.get(&task_id) {
+                    draw_estimated_finish(&painter, origin, viewport, finish, rect);
+                }
+            }
+
+            draw_annotations(&painter, origin, viewport, overlays.annotations, &task_positions);
+
+            // Highlight pulse around a task that was just scrolled to.
+            if let Some((pulse_task, started)) = ui.ctx().data(|d| d.get_temp::<(Uuid, f64)>(pulse_id)) {
+                let t = ((ui.input(|i| i.time) - started) / PULSE_DURATION) as f32;
+                match task_positions.get(&pulse_task) {
+                    Some(&(_, rect)) if t < 1.0 => {
+                        let alpha = ((1.0 - t) * 220.0) as u8;
+                        painter.rect_stroke(
+                            rect.expand(3.0 + t * 10.0),
+                            Rounding::same(theme::bar_rounding() + 3.0 + t * 10.0),
+                            Stroke::new(2.5, with_alpha(theme::border_accent(), alpha)),
                         );
+                        ui.ctx().request_repaint();
                     }
+                    _ => ui.ctx().data_mut(|d| d.remove::<(Uuid, f64)>(pulse_id)),
                 }
             }
 
@@ -705,23 +1348,54 @@ pub fn show_gantt_chart(
                 interaction.changed = true;
             }
 
+            for &guide in overlays.guides {
+                let x = origin.x + viewport.date_to_x(guide);
+                painter.extend(egui::Shape::dashed_line(
+                    &[Pos2::new(x, origin.y + hh), Pos2::new(x, origin.y + chart_height)],
+                    Stroke::new(1.0, with_alpha(theme::accent(), 150)),
+                    6.0,
+                    4.0,
+                ));
+            }
+
             // Draw today marker in header (no full-height line through tasks)
             draw_today_line(&painter, origin, viewport);
+            draw_marker_labels(&painter, origin, viewport, overlays.markers);
 
 
 
-            // Sticky header overlay when vertically scrolled past the content header.
             let clip_rect = ui.clip_rect();
+            if overlays.name_gutter.show {
+                let row_ys: Vec<(Uuid, f32)> = visible_rows
+                    .iter()
+                    .enumerate()
+                    .map(|(vis_i, &task_i)| (tasks[task_i].id, origin.y + hh + vis_i as f32 * (row_height + row_padding)))
+                    .collect();
+                let (clicked, layout) =
+                    draw_name_gutter(ui, pane, tasks, &row_ys, row_height + row_padding, clip_rect, overlays.name_gutter);
+                if let Some(clicked) = clicked {
+                    *selected_task = Some(clicked);
+                    consumed_click = true;
+                }
+                interaction.name_gutter = layout;
+            }
+
+            // Sticky header overlay when vertically scrolled past the content header.
             if origin.y < clip_rect.top() {
                 let sticky_origin = Pos2::new(origin.x, clip_rect.top());
                 draw_timeline_header(
                     &painter,
                     sticky_origin,
                     viewport,
+                    &overlays.calendar,
                     chart_width,
                     sticky_origin.y + hh,
                 );
+                if let Some(range) = selected_range {
+                    draw_selection_range(&painter, sticky_origin, viewport, range, chart_width, sticky_origin.y + hh);
+                }
                 draw_today_line(&painter, sticky_origin, viewport);
+                draw_marker_labels(&painter, sticky_origin, viewport, overlays.markers);
 
                 // Soft shadow under pinned header for separation.
                 let r = theme::rendering();
@@ -736,15 +1410,75 @@ pub fn show_gantt_chart(
                 );
             }
 
+            // Ruler: hovering the header shows the exact date under the
+            // pointer; clicking it toggles a guide line at that date.
+            let header_rect = Rect::from_min_size(
+                Pos2::new(clip_rect.left(), origin.y.max(clip_rect.top())),
+                Vec2::new(clip_rect.width(), hh),
+            );
+            if let Some(pos) = response.hover_pos().filter(|p| header_rect.contains(*p)) {
+                let day = ((pos.x - origin.x) / viewport.pixels_per_day).floor() as i64;
+                let date = viewport.start + chrono::Duration::days(day);
+                egui::show_tooltip_at_pointer(ui.ctx(), ui.layer_id(), pane.with("ruler-tip"), |ui| {
+                    ui.strong(date.format("%A %d %B %Y").to_string());
+                    ui.label(format!(
+                        "Week {} · {}",
+                        overlays.calendar.week_number(date),
+                        overlays.calendar.quarter_label(date)
+                    ));
+                    let hint = if overlays.guides.contains(&date) { "Click to remove guide" } else { "Click to place a guide" };
+                    ui.label(egui::RichText::new(hint).small().weak());
+                });
+                if response.clicked() {
+                    interaction.toggle_guide = Some(date);
+                    consumed_click = true;
+                }
+            }
+
+            // Rich hover card for the bar under the pointer, after a delay.
+            let card_id = pane.with("hover-card");
+            match hovered_task {
+                Some(id) => {
+                    let now = ui.input(|i| i.time);
+                    let since = ui.ctx().data_mut(|d| match d.get_temp::<(Uuid, f64)>(card_id) {
+                        Some((hovered, since)) if hovered == id => since,
+                        _ => {
+                            d.insert_temp(card_id, (id, now));
+                            now
+                        }
+                    });
+                    let waited = (now - since) as f32;
+                    if waited < overlays.hover_delay {
+                        ui.ctx().request_repaint_after(std::time::Duration::from_secs_f32(overlays.hover_delay - waited));
+                    } else if let Some(task) = tasks.iter().find(|t| t.id == id) {
+                        let card = hover_card::CardContext {
+                            tasks,
+                            dependencies,
+                            resources: overlays.resources,
+                            calendar: overlays.calendar,
+                        };
+                        egui::show_tooltip_at_pointer(ui.ctx(), ui.layer_id(), card_id, |ui| {
+                            hover_card::show_task_card(ui, task, &card);
+                            show_slip_tooltip_line(ui, baseline, task);
+                            ui.label(egui::RichText::new("Right-click to pin or for more options").size(9.0).color(theme::text_dim()));
+                        });
+                    }
+                }
+                None => ui.ctx().data_mut(|d| d.remove::<(Uuid, f64)>(card_id)),
+            }
+
             // Add arrow interaction + focus mode for dependencies
             let focus_task = hovered_task.or(*selected_task);
             let pointer_pos = ui.input(|i| i.pointer.hover_pos());
+            let dep_menu_id = pane.with("dep-ctx-menu");
+            let mut under_pointer: Vec<(f32, &Dependency, Vec<Pos2>)> = Vec::new();
 
             for dep in dependencies {
                 if let (Some(&(_, from_rect)), Some(&(_, to_rect))) =
                     (task_positions.get(&dep.from_task), task_positions.get(&dep.to_task))
                 {
-                    let (start_pt, end_pt) = dependency_endpoints(from_rect, to_rect, dep.kind);
+                    let (start_pt, end_pt) =
+                        dependency_endpoints(from_rect, to_rect, dep.kind, anchors.offset(dep));
                     let route = dependency_route_points(start_pt, end_pt, dep.kind);
 
                     let is_related = focus_task
@@ -759,6 +1493,7 @@ pub fn show_gantt_chart(
                             theme::dep_arrow_hover(),
                             1.8,
                             3.0,
+                            dep.line_style,
                         );
                         if route.len() >= 2 {
                             let last_from = route[route.len() - 2];
@@ -767,60 +1502,137 @@ pub fn show_gantt_chart(
                         }
                     }
 
-                    let is_hovered_arrow = pointer_pos
-                        .map(|p| is_point_near_polyline(p, &route, 6.0))
-                        .unwrap_or(false);
-
-                    if is_hovered_arrow {
-                        draw_dependency_polyline(
-                            &painter,
-                            &route,
-                            theme::dep_arrow_hover(),
-                            2.2,
-                            3.0,
-                        );
-                        if route.len() >= 2 {
-                            let last_from = route[route.len() - 2];
-                            let last_to = route[route.len() - 1];
-                            draw_arrowhead(&painter, last_from, last_to, theme::dep_arrow_hover());
+                    if let Some(d) = pointer_pos.map(|p| distance_to_polyline(p, &route)) {
+                        if d <= ARROW_HIT_DISTANCE {
+                            under_pointer.push((d, dep, route));
                         }
+                    }
+                }
+            }
 
-                        let dep_hit = ui.interact(
-                            Rect::from_center_size(
-                                pointer_pos.unwrap_or(start_pt),
-                                Vec2::splat(16.0),
-                            ),
-                            ui.make_persistent_id(("dep-arrow", dep.from_task, dep.to_task)),
-                            Sense::click(),
-                        );
-                        if dep_hit.secondary_clicked() {
-                            interaction.remove_dependency = Some((dep.from_task, dep.to_task));
-                        }
+            // Only the nearest arrow under the pointer reacts; when several
+            // overlap, right-click lists them all.
+            under_pointer.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let dep_pick_id = pane.with("dep-pick");
+            let task_name = |id: Uuid| tasks.iter().find(|t| t.id == id).map_or("?", |t| t.name.as_str());
+            let picking = ui.ctx().data(|d| d.get_temp::<DepPickState>(dep_pick_id)).is_some();
+            if let (Some((_, dep, route)), false) = (under_pointer.first(), picking) {
+                let pos = pointer_pos.unwrap_or(route[0]);
+                draw_dependency_polyline(&painter, route, theme::dep_arrow_hover(), 2.2, 3.0, dep.line_style);
+                if route.len() >= 2 {
+                    draw_arrowhead(&painter, route[route.len() - 2], route[route.len() - 1], theme::dep_arrow_hover());
+                }
+
+                let dep_hit = ui.interact(
+                    Rect::from_center_size(pos, Vec2::splat(ARROW_HIT_DISTANCE * 2.0)),
+                    pane.with("dep-arrow-hit"),
+                    Sense::click(),
+                );
+                if dep_hit.secondary_clicked() {
+                    if under_pointer.len() > 1 {
+                        let candidates = under_pointer.iter().map(|(_, d, _)| (d.from_task, d.to_task)).collect();
+                        ui.ctx().data_mut(|d| d.insert_temp(dep_pick_id, DepPickState { candidates, pos }));
+                    } else {
+                        ui.ctx().data_mut(|d| {
+                            d.insert_temp(dep_menu_id, DepMenuState { from_task: dep.from_task, to_task: dep.to_task, pos })
+                        });
+                    }
+                }
 
-                        egui::show_tooltip_at_pointer(
-                            ui.ctx(),
-                            ui.layer_id(),
-                            egui::Id::new(("dep-tip", dep.from_task, dep.to_task)),
-                            |ui| {
-                                let from_name = tasks
-                                    .iter()
-                                    .find(|t| t.id == dep.from_task)
-                                    .map(|t| t.name.as_str())
-                                    .unwrap_or("?");
-                                let to_name = tasks
-                                    .iter()
-                                    .find(|t| t.id == dep.to_task)
-                                    .map(|t| t.name.as_str())
-                                    .unwrap_or("?");
-                                ui.label(format!("{} → {}", from_name, to_name));
-                                ui.label(
-                                    egui::RichText::new("Right-click to remove")
-                                        .size(10.0)
-                                        .color(theme::text_dim()),
-                                );
-                            },
+                let others = under_pointer.len() - 1;
+                egui::show_tooltip_at_pointer(ui.ctx(), ui.layer_id(), pane.with("dep-tip"), |ui| {
+                    ui.label(format!("{} → {}", task_name(dep.from_task), task_name(dep.to_task)));
+                    if let Some(note) = dep.note() {
+                        ui.label(egui::RichText::new(note).italics().color(theme::text_secondary()));
+                    }
+                    for (key, value) in dep.details() {
+                        ui.label(egui::RichText::new(format!("{}: {}", key, value)).size(10.0).color(theme::text_secondary()));
+                    }
+                    if others > 0 {
+                        ui.label(
+                            egui::RichText::new(format!("+{} more link{} here", others, if others == 1 { "" } else { "s" }))
+                                .size(10.0)
+                                .color(theme::text_dim()),
                         );
                     }
+                    let hint = if others > 0 { "Right-click to choose a link" } else { "Right-click for options" };
+                    ui.label(egui::RichText::new(hint).size(10.0).color(theme::text_dim()));
+                });
+            }
+
+            // Chooser for overlapping arrows: hovering an entry highlights it,
+            // clicking opens that link's menu.
+            let dep_pick: Option<DepPickState> = ui.ctx().data(|d| d.get_temp(dep_pick_id));
+            if let Some(pick) = dep_pick {
+                let mut chosen = None;
+                egui::Area::new(pane.with("dep-pick-area"))
+                    .fixed_pos(pick.pos)
+                    .order(egui::Order::Foreground)
+                    .show(ui.ctx(), |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.label(egui::RichText::new("Links here").size(10.0).color(theme::text_dim()));
+                            for &(from, to) in &pick.candidates {
+                                let Some(dep) = dependencies.iter().find(|d| d.from_task == from && d.to_task == to) else {
+                                    continue;
+                                };
+                                let text = format!("{} → {}  ({})", task_name(from), task_name(to), dep.kind.short_label());
+                                let button = ui.button(text);
+                                if button.hovered() {
+                                    if let (Some(&(_, from_rect)), Some(&(_, to_rect))) =
+                                        (task_positions.get(&from), task_positions.get(&to))
+                                    {
+                                        let (start_pt, end_pt) =
+                                            dependency_endpoints(from_rect, to_rect, dep.kind, anchors.offset(dep));
+                                        let route = dependency_route_points(start_pt, end_pt, dep.kind);
+                                        draw_dependency_polyline(&painter, &route, theme::dep_arrow_hover(), 2.6, 3.0, dep.line_style);
+                                    }
+                                }
+                                if button.clicked() {
+                                    chosen = Some((from, to));
+                                }
+                            }
+                        });
+                    });
+                if let Some((from_task, to_task)) = chosen {
+                    ui.ctx().data_mut(|d| {
+                        d.remove::<DepPickState>(dep_pick_id);
+                        d.insert_temp(dep_menu_id, DepMenuState { from_task, to_task, pos: pick.pos });
+                    });
+                } else if ui.input(|i| i.pointer.secondary_pressed() || i.key_pressed(egui::Key::Escape)) {
+                    ui.ctx().data_mut(|d| d.remove::<DepPickState>(dep_pick_id));
+                }
+            }
+
+            // Context menu for the right-clicked dependency arrow
+            let dep_menu: Option<DepMenuState> = ui.ctx().data_mut(|d| d.get_temp(dep_menu_id));
+            if let Some(menu) = dep_menu {
+                let current = dependencies
+                    .iter()
+                    .find(|d| d.from_task == menu.from_task && d.to_task == menu.to_task)
+                    .cloned();
+                let mut close_menu = current.is_none();
+                if let Some(dep) = current {
+                    egui::Area::new(pane.with("dep-ctx-area"))
+                        .fixed_pos(menu.pos)
+                        .order(egui::Order::Foreground)
+                        .show(ui.ctx(), |ui| {
+                            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                                if let Some(updated) = show_dependency_style_menu(ui, &dep) {
+                                    interaction.update_dependency = Some(updated);
+                                    close_menu = true;
+                                }
+                                ui.separator();
+                                if ui.button(egui_phosphor::regular::TRASH.to_string() + "  Remove Dependency").clicked() {
+                                    interaction.remove_dependency = Some((dep.from_task, dep.to_task));
+                                    close_menu = true;
+                                }
+                            });
+                        });
+                }
+                if close_menu
+                    || ui.input(|i| i.pointer.secondary_pressed() || i.key_pressed(egui::Key::Escape))
+                {
+                    ui.ctx().data_mut(|d| d.remove::<DepMenuState>(dep_menu_id));
                 }
             }
 
@@ -830,7 +1642,7 @@ pub fn show_gantt_chart(
             }
 
             // ── Shift+Drag link creation ─────────────────────────────
-            let link_id = Id::new("dep-link-drag");
+            let link_id = pane.with("dep-link-drag");
             let pointer_pos = ui.input(|i| i.pointer.interact_pos());
             let primary_pressed = ui.input(|i| i.pointer.primary_pressed());
             let primary_down = ui.input(|i| i.pointer.primary_down());
@@ -843,9 +1655,12 @@ pub fn show_gantt_chart(
                     for task in tasks.iter() {
                         if let Some(&(_, rect)) = task_positions.get(&task.id) {
                             if rect.contains(ptr) {
+                                let (from_point, from_finish) = nearest_anchor(rect, ptr);
                                 let state = LinkDragState {
                                     from_task: task.id,
-                                    from_point: Pos2::new(rect.left(), rect.center().y),
+                                    from_point,
+                                    from_start: !from_finish,
+                                    picking: false,
                                 };
                                 ui.ctx().data_mut(|d| d.insert_temp(link_id, state));
                                 break;
@@ -855,61 +1670,284 @@ pub fn show_gantt_chart(
                 }
             }
 
+            // ── M+drag measure ───────────────────────────────────────
+            let measure_id = pane.with("measure");
+            let date_at = |x: f32| {
+                viewport.start + chrono::Duration::days(((x - origin.x) / viewport.pixels_per_day).round() as i64)
+            };
+            if measuring && primary_pressed {
+                if let Some(ptr) = pointer_pos.filter(|p| response.rect.contains(*p)) {
+                    ui.ctx().data_mut(|d| d.insert_temp(measure_id, (date_at(ptr.x), ptr.y)));
+                }
+            }
+            let measure: Option<(NaiveDate, f32)> = ui.ctx().data_mut(|d| d.get_temp(measure_id));
+            if let Some((from, y)) = measure {
+                match pointer_pos.filter(|_| primary_down) {
+                    Some(ptr) => {
+                        draw_measure(&painter, origin, viewport, &overlays.calendar, (from, date_at(ptr.x)), y);
+                    }
+                    None => ui.ctx().data_mut(|d| d.remove::<(NaiveDate, f32)>(measure_id)),
+                }
+            }
+
             // Draw the in-progress link line
             let link_state: Option<LinkDragState> =
                 ui.ctx().data_mut(|d| d.get_temp(link_id));
+            // The bar under the pointer and the edge it is nearest to decide
+            // where the link snaps and which kind it becomes.
+            let link_target = link_state.as_ref().zip(pointer_pos).and_then(|(state, ptr)| {
+                tasks.iter().filter(|t| t.id != state.from_task).find_map(|t| {
+                    let &(_, rect) = task_positions.get(&t.id)?;
+                    rect.contains(ptr).then(|| {
+                        let (anchor, to_finish) = nearest_anchor(rect, ptr);
+                        (t.id, rect, anchor, link_kind(state.from_start, to_finish))
+                    })
+                })
+            });
             if let Some(ref state) = link_state {
                 if let Some(ptr) = pointer_pos {
-                    if primary_down {
+                    if primary_down || state.picking {
+                        if let Some(&(_, from_rect)) = task_positions.get(&state.from_task) {
+                            draw_link_anchor(&painter, from_rect, state.from_point);
+                        }
+                        let (end, kind) = match link_target {
+                            Some((_, rect, anchor, kind)) => {
+                                draw_link_anchor(&painter, rect, anchor);
+                                (anchor, kind)
+                            }
+                            None => (ptr, link_kind(state.from_start, false)),
+                        };
                         draw_dependency_arrow(
                             &painter,
                             state.from_point,
-                            ptr,
-                            DependencyKind::FinishToStart,
+                            end,
+                            kind,
                             theme::dep_creating(),
                             1.5,
+                            LineStyle::Solid,
                         );
+                        if link_target.is_some() {
+                            painter.text(
+                                ptr + Vec2::new(10.0, -12.0),
+                                egui::Align2::LEFT_BOTTOM,
+                                kind.short_label(),
+                                theme::font_sub(),
+                                theme::dep_creating(),
+                            );
+                        }
                     }
                 }
             }
 
-            // On release, check if we landed on a target bar
-            if primary_released {
-                if let Some(state) = link_state {
-                    if let Some(ptr) = pointer_pos {
-                        for task in tasks.iter() {
-                            if task.id != state.from_task {
-                                if let Some(&(_, rect)) = task_positions.get(&task.id) {
-                                    if rect.contains(ptr) {
-                                        interaction.new_dependency = Some(Dependency {
-                                            from_task: state.from_task,
-                                            to_task: task.id,
-                                            kind: DependencyKind::FinishToStart,
-                                        });
-                                        break;
-                                    }
-                                }
-                            }
-                        }
+            // On release, link to the target bar using the edge it was dropped on
+            if primary_released && link_state.is_some() {
+                if let (Some(state), Some((to_task, _, _, kind))) = (&link_state, link_target) {
+                    interaction.new_dependency = Some(Dependency::new(state.from_task, to_task, kind));
+                }
+                ui.ctx().data_mut(|d| d.remove::<LinkDragState>(link_id));
+            }
+
+            if let Some(ghost) = &drag_ghost {
+                draw_slip_impact(&painter, tasks, dependencies, &task_positions, ghost);
+                draw_drag_ghost(ui, &painter, pane, origin, viewport, ghost);
+            }
+
+            // Dragging a bar near the visible edge scrolls the chart; the app
+            // grows the viewport when the scroll runs past its range.
+            if let Some(ptr_x) = drag_pointer_x {
+                let visible = ui.clip_rect();
+                let step = if ptr_x > visible.right() - AUTOSCROLL_EDGE {
+                    (ptr_x - (visible.right() - AUTOSCROLL_EDGE)) / AUTOSCROLL_EDGE
+                } else if ptr_x < visible.left() + AUTOSCROLL_EDGE {
+                    (ptr_x - (visible.left() + AUTOSCROLL_EDGE)) / AUTOSCROLL_EDGE
+                } else {
+                    0.0
+                };
+                if step != 0.0 {
+                    interaction.drag_autoscroll = Some(step.clamp(-1.0, 1.0) * AUTOSCROLL_SPEED);
+                }
+            }
+
+            // Action toolbar over the selected bar, hidden while it is dragged.
+            let bar_busy = drag_pointer_x.is_some() || reorder_preview_target.is_some() || link_state.is_some();
+            let selected_bar = selected_task.and_then(|id| Some((id, task_positions.get(&id)?.1)));
+            if let Some((id, rect)) = selected_bar.filter(|(_, r)| overlays.bar_toolbar && !bar_busy && ui.clip_rect().intersects(*r)) {
+                let done = tasks.iter().find(|t| t.id == id).is_some_and(|t| t.progress >= 1.0);
+                match draw_bar_toolbar(ui, pane, rect, done) {
+                    Some(BarAction::Edit) => interaction.edit_task = Some(id),
+                    Some(BarAction::Link) => {
+                        let state = LinkDragState {
+                            from_task: id,
+                            from_point: rect.right_center(),
+                            from_start: false,
+                            picking: true,
+                        };
+                        ui.ctx().data_mut(|d| d.insert_temp(link_id, state));
                     }
-                    ui.ctx().data_mut(|d| d.remove::<LinkDragState>(link_id));
+                    Some(BarAction::AddSubtask) => interaction.add_subtask = Some(id),
+                    Some(BarAction::MarkDone) => interaction.mark_done = Some(id),
+                    Some(BarAction::Delete) => interaction.delete_task = Some(id),
+                    None => {}
                 }
             }
+            if link_state.as_ref().is_some_and(|s| s.picking) && ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                ui.ctx().data_mut(|d| d.remove::<LinkDragState>(link_id));
+            }
+
+            interaction.shapes_painted = paint_list_len(ui).saturating_sub(first_shape);
         });
+    interaction.scroll_offset = output.state.offset;
 
     interaction
 }
 
-fn drag_id(task_id: Uuid, mode: &'static str) -> Id {
-    Id::new(("drag", task_id, mode))
-}
-
-fn drag_days(delta_x: f32, viewport: &TimelineViewport) -> i64 {
-    (delta_x / viewport.pixels_per_day).round() as i64
-}
+/// Number of shapes queued so far on the layer `ui` paints into.
+/// Bounds for the name gutter width, auto-fitted or dragged.
+const GUTTER_MIN_WIDTH: f32 = 80.0;
+const GUTTER_MAX_WIDTH: f32 = 260.0;
+const GUTTER_MAX_DRAG_WIDTH: f32 = 480.0;
+
+/// Frozen column of task names pinned to the visible left edge of the chart,
+/// so rows stay identifiable when scrolled far right. Without a fixed width
+/// it fits the longest visible name; dragging its divider sets one and
+/// double-clicking the divider returns to auto-fit. Returns the task whose
+/// name was clicked and the changed layout, if any.
+fn draw_name_gutter(
+    ui: &mut Ui,
+    pane: Id,
+    tasks: &[Task],
+    row_ys: &[(Uuid, f32)],
+    row_span: f32,
+    clip_rect: Rect,
+    layout: NameGutter,
+) -> (Option<Uuid>, Option<NameGutter>) {
+    let Some(&(_, body_top)) = row_ys.first() else {
+        return (None, None);
+    };
+    let font = egui::FontId::proportional(11.0);
+    let indent = 12.0;
+    let depth = |task: &Task| {
+        let mut depth = 0;
+        let mut parent = task.parent_id;
+        while let Some(pid) = parent {
+            depth += 1;
+            parent = tasks.iter().find(|t| t.id == pid).and_then(|t| t.parent_id);
+        }
+        depth
+    };
+    let rows: Vec<(&Task, f32, usize)> = row_ys
+        .iter()
+        .filter(|(_, y)| *y + row_span >= clip_rect.top() && *y <= clip_rect.bottom())
+        .filter_map(|(id, y)| tasks.iter().find(|t| t.id == *id).map(|t| (t, *y, depth(t))))
+        .collect();
+    // While the divider is dragged the live width is kept in temp data and
+    // only reported (and persisted) when the drag ends.
+    let drag_id = pane.with("name-gutter-drag");
+    let drag_width: Option<f32> = ui.ctx().data(|d| d.get_temp(drag_id));
+    let width = drag_width.or(layout.width).unwrap_or_else(|| {
+        let widest = ui.fonts(|f| {
+            rows.iter()
+                .map(|(t, _, d)| f.layout_no_wrap(t.name.clone(), font.clone(), Color32::WHITE).size().x + *d as f32 * indent)
+                .fold(0.0, f32::max)
+        });
+        (widest + 16.0).clamp(GUTTER_MIN_WIDTH, GUTTER_MAX_WIDTH)
+    });
+    let top = body_top.max(clip_rect.top());
+    let gutter = Rect::from_min_max(Pos2::new(clip_rect.left(), top), Pos2::new(clip_rect.left() + width, clip_rect.bottom()));
+    if gutter.height() <= 0.0 {
+        return (None, None);
+    }
 
-fn row_index_from_pointer_y(
-    pointer_y: f32,
+    let response = ui.interact(gutter, pane.with("name-gutter"), Sense::click());
+    let painter = ui.painter().with_clip_rect(gutter);
+    painter.rect_filled(gutter, 0.0, theme::bg_panel());
+    let line_height = ui.fonts(|f| f.row_height(&font));
+    let mut clicked = None;
+    for (task, y, depth) in &rows {
+        let row = Rect::from_min_size(Pos2::new(gutter.left(), *y), Vec2::new(width, row_span));
+        if response.hover_pos().is_some_and(|p| row.contains(p)) {
+            painter.rect_filled(row, 0.0, with_alpha(theme::accent(), 24));
+            if response.clicked() {
+                clicked = Some(task.id);
+            }
+        }
+        let summary = task.has_children(tasks);
+        let text_x = row.left() + 6.0 + *depth as f32 * indent;
+        let color = if summary { theme::text_primary() } else { theme::text_secondary() };
+        let mut job = egui::text::LayoutJob::simple(task.name.clone(), font.clone(), color, (row.right() - 6.0 - text_x).max(8.0));
+        job.wrap.max_rows = if layout.wrap { ((row_span - 2.0) / line_height).floor().max(1.0) as usize } else { 1 };
+        job.wrap.break_anywhere = !layout.wrap;
+        job.wrap.overflow_character = Some('…');
+        let galley = ui.fonts(|f| f.layout_job(job));
+        painter
+            .with_clip_rect(row.intersect(gutter))
+            .galley(Pos2::new(text_x, row.center().y - galley.size().y / 2.0), galley, color);
+        painter.line_segment(
+            [row.left_bottom(), row.right_bottom()],
+            Stroke::new(0.5, theme::border_subtle()),
+        );
+    }
+
+    // Divider: drag to resize, double-click to auto-fit again.
+    let divider = Rect::from_center_size(gutter.right_center(), Vec2::new(6.0, gutter.height()));
+    let divider_response = ui
+        .interact(divider, pane.with("name-gutter-divider"), Sense::click_and_drag())
+        .on_hover_cursor(egui::CursorIcon::ResizeHorizontal);
+    let active = divider_response.hovered() || divider_response.dragged();
+    painter.line_segment(
+        [gutter.right_top(), gutter.right_bottom()],
+        Stroke::new(if active { 2.0 } else { 1.0 }, if active { theme::accent() } else { theme::border_subtle() }),
+    );
+    let mut changed = None;
+    if divider_response.double_clicked() {
+        changed = Some(NameGutter { width: None, ..layout });
+    } else if divider_response.dragged() {
+        let new_width = (width + divider_response.drag_delta().x).clamp(GUTTER_MIN_WIDTH, GUTTER_MAX_DRAG_WIDTH);
+        ui.ctx().data_mut(|d| d.insert_temp(drag_id, new_width));
+    } else if divider_response.drag_stopped() {
+        ui.ctx().data_mut(|d| d.remove::<f32>(drag_id));
+        changed = Some(NameGutter { width: Some(width), ..layout });
+    }
+    (clicked, changed)
+}
+
+fn paint_list_len(ui: &Ui) -> usize {
+    let layer_id = ui.layer_id();
+    ui.ctx().graphics(|g| g.get(layer_id).map_or(0, |list| list.next_idx().0))
+}
+
+fn drag_id(pane: Id, task_id: Uuid, mode: &'static str) -> Id {
+    pane.with(("drag", task_id, mode))
+}
+
+/// Pointer x measured from a fixed date instead of the canvas edge, so a drag
+/// keeps its anchor when the chart scrolls or the viewport grows under it.
+fn timeline_x(ptr_x: f32, origin: Pos2, viewport: &TimelineViewport) -> f32 {
+    let epoch = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default();
+    ptr_x - origin.x - viewport.date_to_x(epoch)
+}
+
+fn drag_days(delta_x: f32, viewport: &TimelineViewport) -> i64 {
+    (delta_x / viewport.pixels_per_day).round() as i64
+}
+
+/// Where a bar dragged from `from` with its start let go at `target` ends
+/// up: shifted by the calendar days between them, or with `working`, by the
+/// working days crossed, keeping its working duration.
+fn drag_span(from: (NaiveDate, NaiveDate), target: NaiveDate, working: Option<&ProjectCalendar>) -> (NaiveDate, NaiveDate) {
+    match working {
+        Some(calendar) => calendar.move_by_working_days(from, target),
+        None => (target, from.1 + (target - from.0)),
+    }
+}
+
+/// `date`, moved to the nearest week start when snapping is on.
+fn snap_date(date: NaiveDate, week_snap: Option<&ProjectCalendar>) -> NaiveDate {
+    week_snap.map_or(date, |calendar| calendar.nearest_week_start(date))
+}
+
+fn row_index_from_pointer_y(
+    pointer_y: f32,
     origin: Pos2,
     row_height: f32,
     row_padding: f32,
@@ -967,6 +2005,7 @@ fn draw_timeline_header(
     painter: &egui::Painter,
     origin: Pos2,
     viewport: &TimelineViewport,
+    calendar: &ProjectCalendar,
     width: f32,
     grid_bottom_y: f32,
 ) {
@@ -988,26 +2027,32 @@ fn draw_timeline_header(
     );
 
     // Subtle weekend tint in header (especially useful in Weeks view).
-    draw_weekend_header_bands(painter, origin, viewport, width);
+    draw_weekend_header_bands(painter, origin, viewport, calendar, width);
 
     let mut date = viewport.start;
     let end = viewport.end;
 
+    // Vertical grid lines are gathered into a single shape list, painted
+    // through a placeholder so they stay underneath the header labels.
+    let grid_idx = painter.add(egui::Shape::Noop);
+    let mut grid_lines: Vec<egui::Shape> = Vec::new();
+    let grid_stroke = Stroke::new(0.5, theme::grid_line());
+
     match viewport.scale {
         TimelineScale::Days => {
             while date <= end {
                 let x = origin.x + viewport.date_to_x(date);
 
-                painter.line_segment(
+                grid_lines.push(egui::Shape::line_segment(
                     [
                         Pos2::new(x, origin.y + hh),
                         Pos2::new(x, grid_bottom_y),
                     ],
-                    Stroke::new(0.5, theme::grid_line()),
-                );
+                    grid_stroke,
+                ));
 
                 if viewport.pixels_per_day >= 20.0 {
-                    let is_weekend = date.weekday().num_days_from_monday() >= 5;
+                    let is_weekend = !calendar.is_working_day(date);
                     let day_color = if is_weekend {
                         theme::text_dim()
                     } else {
@@ -1036,34 +2081,32 @@ fn draw_timeline_header(
             }
         }
         TimelineScale::Weeks => {
-            let weekday = date.weekday().num_days_from_monday();
-            date -= chrono::Duration::days(weekday as i64);
+            date = calendar.week_start(date);
             let show_weekdays = viewport.pixels_per_day >= 18.0 * 1.4;
-            let weekday_labels = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
 
             while date <= end {
                 let x = origin.x + viewport.date_to_x(date);
                 let week_band_top = origin.y + 22.0;
 
-                painter.line_segment(
+                grid_lines.push(egui::Shape::line_segment(
                     [
                         Pos2::new(x, week_band_top),
                         Pos2::new(x, grid_bottom_y),
                     ],
-                    Stroke::new(0.5, theme::grid_line()),
-                );
+                    grid_stroke,
+                ));
 
                 painter.text(
                     Pos2::new(x + 3.0, origin.y + 26.0),
                     egui::Align2::LEFT_CENTER,
-                    date.format("W%V").to_string(),
+                    format!("W{}", calendar.week_number(date)),
                     theme::font_sub(),
                     theme::text_secondary(),
                 );
 
                 if show_weekdays {
-                    for (day_offset, label) in weekday_labels.iter().enumerate() {
-                        let day_date = date + chrono::Duration::days(day_offset as i64);
+                    for day_offset in 0..7 {
+                        let day_date = date + chrono::Duration::days(day_offset);
                         if day_date > end {
                             break;
                         }
@@ -1071,7 +2114,7 @@ fn draw_timeline_header(
                         painter.text(
                             Pos2::new(day_x, origin.y + 39.0),
                             egui::Align2::CENTER_CENTER,
-                            *label,
+                            day_date.format("%a").to_string(),
                             theme::font_small(),
                             theme::text_dim(),
                         );
@@ -1097,20 +2140,29 @@ fn draw_timeline_header(
             while date <= end {
                 let x = origin.x + viewport.date_to_x(date);
 
-                painter.line_segment(
+                grid_lines.push(egui::Shape::line_segment(
                     [
                         Pos2::new(x, origin.y + hh),
                         Pos2::new(x, grid_bottom_y),
                     ],
-                    Stroke::new(0.5, theme::grid_line()),
-                );
+                    grid_stroke,
+                ));
 
+                if calendar.is_quarter_start(date) {
+                    painter.text(
+                        Pos2::new(x + 5.0, origin.y + 12.0),
+                        egui::Align2::LEFT_CENTER,
+                        calendar.quarter_label(date),
+                        theme::font_header(),
+                        theme::text_primary(),
+                    );
+                }
                 painter.text(
-                    Pos2::new(x + 5.0, origin.y + 18.0),
+                    Pos2::new(x + 5.0, origin.y + 30.0),
                     egui::Align2::LEFT_CENTER,
                     date.format("%b %Y").to_string(),
-                    theme::font_header(),
-                    theme::text_primary(),
+                    theme::font_sub(),
+                    theme::text_secondary(),
                 );
 
                 let (y, m) = if date.month() == 12 {
@@ -1123,12 +2175,15 @@ fn draw_timeline_header(
             }
         }
     }
+
+    painter.set(grid_idx, egui::Shape::Vec(grid_lines));
 }
 
 fn draw_weekend_bands(
     painter: &egui::Painter,
     origin: Pos2,
     viewport: &TimelineViewport,
+    calendar: &ProjectCalendar,
     width: f32,
     bottom_y: f32,
 ) {
@@ -1140,42 +2195,42 @@ fn draw_weekend_bands(
     let mut date = viewport.start;
     let end = viewport.end;
     let right = origin.x + width;
+    let top = origin.y + header_height();
+    let shade = theme::weekend_shade();
+    let sep_stroke = Stroke::new(1.0, with_alpha(theme::border_subtle(), theme::rendering().weekend_sep_alpha));
+    let mut mesh = egui::Mesh::default();
+    let mut separators: Vec<egui::Shape> = Vec::new();
     while date <= end {
-        let weekday = date.weekday().num_days_from_monday();
-        if weekday >= 5 {
+        if !calendar.is_working_day(date) {
             let x0 = origin.x + viewport.date_to_x(date);
             let next_day = date + chrono::Duration::days(1);
             let x1 = (origin.x + viewport.date_to_x(next_day)).min(right);
             if x1 > x0 {
-                painter.rect_filled(
-                    Rect::from_min_max(
-                        Pos2::new(x0, origin.y + header_height()),
-                        Pos2::new(x1, bottom_y),
-                    ),
-                    0.0,
-                    theme::weekend_shade(),
+                mesh.add_colored_rect(
+                    Rect::from_min_max(Pos2::new(x0, top), Pos2::new(x1, bottom_y)),
+                    shade,
                 );
 
-                // Crisp separator at weekend start (Saturday) for quick scanning.
-                if weekday == 5 {
-                    painter.line_segment(
-                        [
-                            Pos2::new(x0, origin.y + header_height()),
-                            Pos2::new(x0, bottom_y),
-                        ],
-                        Stroke::new(1.0, with_alpha(theme::border_subtle(), theme::rendering().weekend_sep_alpha)),
-                    );
+                // Crisp separator where the weekend starts for quick scanning.
+                if calendar.is_working_day(date - chrono::Duration::days(1)) {
+                    separators.push(egui::Shape::line_segment(
+                        [Pos2::new(x0, top), Pos2::new(x0, bottom_y)],
+                        sep_stroke,
+                    ));
                 }
             }
         }
         date += chrono::Duration::days(1);
     }
+    painter.add(egui::Shape::mesh(mesh));
+    painter.extend(separators);
 }
 
 fn draw_weekend_header_bands(
     painter: &egui::Painter,
     origin: Pos2,
     viewport: &TimelineViewport,
+    calendar: &ProjectCalendar,
     width: f32,
 ) {
     if viewport.pixels_per_day < 7.0 || viewport.scale == TimelineScale::Months {
@@ -1188,22 +2243,97 @@ fn draw_weekend_header_bands(
     let y_min = origin.y + 22.0;
     let y_max = origin.y + header_height();
 
+    let shade = theme::weekend_header_shade();
+    let mut mesh = egui::Mesh::default();
     while date <= end {
-        let weekday = date.weekday().num_days_from_monday();
-        if weekday >= 5 {
+        if !calendar.is_working_day(date) {
             let x0 = origin.x + viewport.date_to_x(date);
             let next_day = date + chrono::Duration::days(1);
             let x1 = (origin.x + viewport.date_to_x(next_day)).min(right);
             if x1 > x0 {
-                painter.rect_filled(
+                mesh.add_colored_rect(
                     Rect::from_min_max(Pos2::new(x0, y_min), Pos2::new(x1, y_max)),
-                    0.0,
-                    theme::weekend_header_shade(),
+                    shade,
                 );
             }
         }
         date += chrono::Duration::days(1);
     }
+    painter.add(egui::Shape::mesh(mesh));
+}
+
+/// Span between two dates with its length in calendar and working days,
+/// drawn at height `y` while measuring.
+fn draw_measure(
+    painter: &egui::Painter,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    calendar: &ProjectCalendar,
+    (a, b): (NaiveDate, NaiveDate),
+    y: f32,
+) {
+    let (start, end) = (a.min(b), a.max(b));
+    let x0 = origin.x + viewport.date_to_x(start);
+    let x1 = origin.x + viewport.date_to_x(end);
+    let top = origin.y + header_height();
+    let color = theme::accent();
+    let stroke = Stroke::new(1.5, color);
+    for x in [x0, x1] {
+        painter.line_segment([Pos2::new(x, top), Pos2::new(x, y + 40.0)], Stroke::new(1.0, color.gamma_multiply(0.6)));
+    }
+    painter.line_segment([Pos2::new(x0, y), Pos2::new(x1, y)], stroke);
+    for (x, dir) in [(x0, 1.0), (x1, -1.0)] {
+        painter.line_segment([Pos2::new(x, y), Pos2::new(x + 5.0 * dir, y - 4.0)], stroke);
+        painter.line_segment([Pos2::new(x, y), Pos2::new(x + 5.0 * dir, y + 4.0)], stroke);
+    }
+    let days = (end - start).num_days();
+    let label = format!(
+        "{} day{} · {} working  ({} – {})",
+        days,
+        if days == 1 { "" } else { "s" },
+        calendar.working_days_between(start, end),
+        start.format("%d %b"),
+        end.format("%d %b")
+    );
+    let galley = painter.layout_no_wrap(label, theme::font_sub(), theme::text_primary());
+    let pos = Pos2::new((x0 + x1) * 0.5 - galley.size().x * 0.5, y - galley.size().y - 8.0);
+    let bg = Rect::from_min_size(pos, galley.size()).expand2(Vec2::new(6.0, 3.0));
+    painter.rect(bg, 4.0, theme::bg_panel(), Stroke::new(1.0, color));
+    painter.galley(pos, galley, theme::text_primary());
+}
+
+/// Shade the selected task's start–end range in the header and draw faint
+/// guides at its boundaries down to `bottom_y`, for reading alignment
+/// against other bars.
+fn draw_selection_range(
+    painter: &egui::Painter,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    (start, end): (NaiveDate, NaiveDate),
+    width: f32,
+    bottom_y: f32,
+) {
+    let hh = header_height();
+    let (left, right) = (origin.x, origin.x + width);
+    let x0 = origin.x + viewport.date_to_x(start);
+    let x1 = (origin.x + viewport.date_to_x(end)).max(x0 + 2.0);
+    if x1 < left || x0 > right {
+        return;
+    }
+    let accent = theme::accent();
+    painter.rect_filled(
+        Rect::from_min_max(
+            Pos2::new(x0.max(left), origin.y + 22.0),
+            Pos2::new(x1.min(right), origin.y + hh),
+        ),
+        0.0,
+        accent.gamma_multiply(0.18),
+    );
+    let guide = Stroke::new(1.0, accent.gamma_multiply(0.35));
+    let edges = [Some(x0), (end > start).then_some(x1)];
+    for x in edges.into_iter().flatten().filter(|x| (left..=right).contains(x)) {
+        painter.line_segment([Pos2::new(x, origin.y + 22.0), Pos2::new(x, bottom_y)], guide);
+    }
 }
 
 fn draw_today_line(
@@ -1231,13 +2361,119 @@ fn draw_today_line(
 
 }
 
+/// Shaded date ranges behind the bars, each labelled just below the header.
+fn draw_date_bands(
+    painter: &egui::Painter,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    bands: &[DateBand],
+    bottom_y: f32,
+) {
+    let hh = header_height();
+    for (i, band) in bands.iter().enumerate() {
+        let x0 = origin.x + viewport.date_to_x(band.start);
+        let x1 = origin.x + viewport.date_to_x(band.end + chrono::Duration::days(1));
+        let rect = Rect::from_min_max(Pos2::new(x0, origin.y + hh), Pos2::new(x1, bottom_y));
+        // Alternate two tints so adjacent bands stay distinguishable.
+        let tint = if i % 2 == 0 { theme::accent() } else { theme::slip_moderate() };
+        painter.rect_filled(rect, 0.0, with_alpha(tint, 18));
+        painter.with_clip_rect(rect).text(
+            Pos2::new(x0 + 4.0, origin.y + hh + 3.0),
+            egui::Align2::LEFT_TOP,
+            &band.name,
+            theme::font_sub(),
+            with_alpha(tint, 200),
+        );
+    }
+}
+
+/// Full-height lines for the project's date markers, under the bars.
+fn draw_marker_lines(
+    painter: &egui::Painter,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    markers: &[DateMarker],
+    bottom_y: f32,
+) {
+    let stroke = Stroke::new(1.0, with_alpha(theme::slip_moderate(), 170));
+    for marker in markers {
+        let x = origin.x + viewport.date_to_x(marker.date);
+        painter.line_segment([Pos2::new(x, origin.y + header_height()), Pos2::new(x, bottom_y)], stroke);
+    }
+}
+
+/// Marker names as tags along the bottom of the timeline header.
+fn draw_marker_labels(
+    painter: &egui::Painter,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    markers: &[DateMarker],
+) {
+    let hh = header_height();
+    for marker in markers {
+        let x = origin.x + viewport.date_to_x(marker.date);
+        let galley = painter.layout_no_wrap(marker.name.clone(), theme::font_sub(), theme::bg_dark());
+        let tag = Rect::from_min_size(
+            Pos2::new(x, origin.y + hh - galley.size().y - 3.0),
+            galley.size() + Vec2::new(6.0, 2.0),
+        );
+        painter.rect_filled(tag, Rounding { nw: 0.0, ne: 3.0, sw: 0.0, se: 3.0 }, theme::slip_moderate());
+        painter.galley(tag.min + Vec2::new(3.0, 1.0), galley, theme::bg_dark());
+    }
+}
+
+/// Notes as small boxes over the chart, on their task's row or stacked
+/// below the header, each with its arrow. Notes on hidden rows are skipped.
+fn draw_annotations(
+    painter: &egui::Painter,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    annotations: &[Annotation],
+    task_positions: &std::collections::HashMap<Uuid, (usize, Rect)>,
+) {
+    let color = theme::slip_moderate();
+    let row_y = |task: Option<Uuid>, floating: usize| match task {
+        Some(id) => task_positions.get(&id).map(|(_, rect)| rect.center().y),
+        None => Some(origin.y + header_height() + 14.0 + floating as f32 * 20.0),
+    };
+    let mut floating = 0;
+    for note in annotations {
+        let Some(y) = row_y(note.task, floating) else { continue };
+        if note.task.is_none() {
+            floating += 1;
+        }
+        let x = origin.x + viewport.date_to_x(note.date);
+        let galley = painter.layout_no_wrap(note.text.clone(), theme::font_sub(), theme::text_primary());
+        let rect = Rect::from_min_size(Pos2::new(x, y - galley.size().y / 2.0 - 2.0), galley.size() + Vec2::new(8.0, 4.0));
+        if let (Some(to), Some(to_y)) = (note.arrow_to, row_y(note.arrow_task.or(note.task), 0)) {
+            let target = Pos2::new(origin.x + viewport.date_to_x(to), if note.arrow_task.is_some() { to_y } else { y });
+            let from = if target.x < rect.left() {
+                rect.left_center()
+            } else if target.x > rect.right() {
+                rect.right_center()
+            } else if target.y > rect.bottom() {
+                rect.center_bottom()
+            } else {
+                rect.center_top()
+            };
+            painter.line_segment([from, target], Stroke::new(1.2, color));
+            draw_arrowhead(painter, from, target, color);
+        }
+        painter.rect(rect, Rounding::same(3.0), theme::bg_panel(), Stroke::new(1.0, color));
+        painter.galley(rect.min + Vec2::new(4.0, 2.0), galley, theme::text_primary());
+    }
+}
+
 /// Draw a summary / parent task bar (bracket style, spans all children).
+/// `children` are the counted direct children, for segmented progress.
 /// Returns the interaction rect for click handling.
+#[allow(clippy::too_many_arguments)]
 fn draw_summary_bar(
     painter: &egui::Painter,
     origin: Pos2,
     viewport: &TimelineViewport,
     task: &Task,
+    children: &[&Task],
     y: f32,
     row_height: f32,
     is_selected: bool,
@@ -1246,11 +2482,8 @@ fn draw_summary_bar(
     let x_end   = origin.x + viewport.date_to_x(task.end);
     let width   = (x_end - x_start).max(8.0);
 
-    // Draw a thin horizontal bar in the middle of the row (bracket body)
-    let bar_h    = (row_height * 0.35).max(5.0);
-    let bar_y    = y + (row_height - bar_h) * 0.5;
-    let bar_rect = Rect::from_min_size(Pos2::new(x_start, bar_y), Vec2::new(width, bar_h));
-
+    let style = theme::rendering().summary_bar_style;
+    let tick_color = with_alpha(task.color, 220);
     // Muted color based on task color
     let body_color = Color32::from_rgba_premultiplied(
         task.color.r() / 2,
@@ -1258,31 +2491,76 @@ fn draw_summary_bar(
         task.color.b() / 2,
         200,
     );
-    let tick_color = with_alpha(task.color, 220);
+
+    let bar_rect = match style {
+        SummaryBarStyle::Bracket => {
+            // Draw a thin horizontal bar in the middle of the row (bracket body)
+            let bar_h = (row_height * 0.35).max(5.0);
+            let bar_y = y + (row_height - bar_h) * 0.5;
+            Rect::from_min_size(Pos2::new(x_start, bar_y), Vec2::new(width, bar_h))
+        }
+        SummaryBarStyle::Solid | SummaryBarStyle::Outline => {
+            let inset = theme::bar_inset() + 1.0;
+            Rect::from_min_size(Pos2::new(x_start, y + inset), Vec2::new(width, row_height - inset * 2.0))
+        }
+    };
 
     // Body
-    painter.rect_filled(bar_rect, Rounding::same(2.0), body_color);
+    match style {
+        SummaryBarStyle::Bracket | SummaryBarStyle::Solid => {
+            painter.rect_filled(bar_rect, Rounding::same(2.0), body_color);
+        }
+        SummaryBarStyle::Outline => {}
+    }
 
-    // Progress fill
-    if task.progress > 0.0 {
-        let prog_rect = Rect::from_min_size(
-            bar_rect.min,
-            Vec2::new(width * task.progress, bar_h),
-        );
-        painter.rect_filled(prog_rect, Rounding::same(2.0), with_alpha(task.color, 180));
+    // Progress fill: flat, or one segment per child sized like the rollup.
+    let alpha = if style == SummaryBarStyle::Outline { 70 } else { 180 };
+    let fill = with_alpha(task.color, alpha);
+    let tick = Color32::from_white_alpha(theme::rendering().progress_tick_alpha);
+    let mut shapes = Vec::new();
+    let weights: Vec<f32> = children.iter().map(|c| c.rollup_weight()).collect();
+    let total: f32 = weights.iter().sum();
+    let segmented = theme::rendering().summary_progress == SummaryProgress::Segments
+        && children.len() > 1
+        && task.progress_override.is_none();
+    if segmented {
+        // A phase of only milestones splits evenly, as in the rollup.
+        let share = |i: usize| if total > 0.0 { weights[i] / total } else { 1.0 / children.len() as f32 };
+        let mut x = bar_rect.left();
+        for (i, child) in children.iter().enumerate() {
+            let w = bar_rect.width() * share(i);
+            if w <= 0.0 {
+                continue;
+            }
+            let segment = Rect::from_min_size(Pos2::new(x, bar_rect.top()), Vec2::new(w, bar_rect.height()));
+            push_progress(&mut shapes, segment, child.progress, Rounding::ZERO, fill, tick);
+            if x > bar_rect.left() {
+                let divider = Stroke::new(1.0, with_alpha(theme::bg_dark(), 160));
+                shapes.push(egui::Shape::vline(x, bar_rect.y_range(), divider));
+            }
+            x += w;
+        }
+    } else {
+        push_progress(&mut shapes, bar_rect, task.progress, Rounding::same(2.0), fill, tick);
     }
+    painter.extend(shapes);
 
-    // Left downward tick
-    let tick_h = row_height * 0.5;
-    painter.line_segment(
-        [Pos2::new(x_start, bar_y), Pos2::new(x_start, bar_y + tick_h)],
-        Stroke::new(3.0, tick_color),
-    );
-    // Right downward tick
-    painter.line_segment(
-        [Pos2::new(x_start + width, bar_y), Pos2::new(x_start + width, bar_y + tick_h)],
-        Stroke::new(3.0, tick_color),
-    );
+    match style {
+        SummaryBarStyle::Bracket => {
+            // Left and right downward ticks
+            let tick_h = row_height * 0.5;
+            for x in [x_start, x_start + width] {
+                painter.line_segment(
+                    [Pos2::new(x, bar_rect.top()), Pos2::new(x, bar_rect.top() + tick_h)],
+                    Stroke::new(3.0, tick_color),
+                );
+            }
+        }
+        SummaryBarStyle::Solid => {}
+        SummaryBarStyle::Outline => {
+            painter.rect_stroke(bar_rect, Rounding::same(2.0), Stroke::new(1.5, tick_color));
+        }
+    }
 
     // Selection highlight
     if is_selected {
@@ -1299,7 +2577,11 @@ fn draw_summary_bar(
     painter.text(
         Pos2::new(label_x, label_y),
         egui::Align2::LEFT_CENTER,
-        format!("{} ({:.0}%)", task.name, task.progress * 100.0),
+        if task.progress_override.is_some() {
+            format!("{} ({:.0}% manual)", task.name, task.progress * 100.0)
+        } else {
+            format!("{} ({:.0}%)", task.name, task.progress * 100.0)
+        },
         egui::FontId::proportional(11.0),
         with_alpha(Color32::WHITE, 180),
     );
@@ -1308,11 +2590,41 @@ fn draw_summary_bar(
     bar_rect.expand(4.0)
 }
 
+/// Progress fill shared by task and summary bars: the first `progress` of
+/// `rect` in `fill`, with a thin divider where the fill stops.
+fn push_progress(
+    shapes: &mut Vec<egui::Shape>,
+    rect: Rect,
+    progress: f32,
+    rounding: Rounding,
+    fill: Color32,
+    tick: Color32,
+) {
+    if progress <= 0.0 {
+        return;
+    }
+    let width = rect.width() * progress.clamp(0.0, 1.0);
+    let done = Rect::from_min_size(rect.min, Vec2::new(width, rect.height()));
+    shapes.push(egui::Shape::rect_filled(done, rounding, fill));
+    if progress < 0.98 {
+        let x = rect.left() + width;
+        shapes.push(egui::Shape::line_segment(
+            [Pos2::new(x, rect.top() + 2.0), Pos2::new(x, rect.bottom() - 2.0)],
+            Stroke::new(1.0, tick),
+        ));
+    }
+}
+
+/// Draw a regular task bar. Bar shapes are appended to `shapes` for batched
+/// painting; only the clipped name label goes straight to `painter`.
+#[allow(clippy::too_many_arguments)]
 fn draw_task_bar(
     painter: &egui::Painter,
+    shapes: &mut Vec<egui::Shape>,
     origin: Pos2,
     viewport: &TimelineViewport,
     task: &Task,
+    fill: Color32,
     y: f32,
     row_height: f32,
     is_selected: bool,
@@ -1328,21 +2640,34 @@ fn draw_task_bar(
     );
     let br = theme::bar_rounding();
     let rounding = Rounding::same(br);
-    let r = theme::rendering();
+    let mut r = theme::rendering();
+
+    // Tentative work is drawn faded, flat and hatched so it doesn't look committed.
+    let (fill, pattern) = if task.tentative {
+        r.bar_shadow_alpha_1 = 0;
+        r.bar_shadow_alpha_2 = 0;
+        r.bar_glaze_alpha = 0;
+        let pattern = if task.pattern == BarPattern::None { BarPattern::Hatch } else { task.pattern };
+        (fill.gamma_multiply(0.45), pattern)
+    } else {
+        (fill, task.pattern)
+    };
+    // External work is greyed out so it reads as context, not ownership.
+    let fill = if task.external { greyed(fill) } else { fill };
 
     // Layered shadow (skipped when alpha is 0 for flat themes)
     if r.bar_shadow_alpha_1 > 0 {
         let shadow_rect_1 = bar_rect.translate(Vec2::new(0.0, r.bar_shadow_offset_y_1));
-        painter.rect_filled(shadow_rect_1, rounding, Color32::from_black_alpha(r.bar_shadow_alpha_1));
+        shapes.push(egui::Shape::rect_filled(shadow_rect_1, rounding, Color32::from_black_alpha(r.bar_shadow_alpha_1)));
     }
     if r.bar_shadow_alpha_2 > 0 {
         let shadow_rect_2 = bar_rect.translate(Vec2::new(r.bar_shadow_offset_x_2, r.bar_shadow_offset_y_2));
-        painter.rect_filled(shadow_rect_2, rounding, Color32::from_black_alpha(r.bar_shadow_alpha_2));
+        shapes.push(egui::Shape::rect_filled(shadow_rect_2, rounding, Color32::from_black_alpha(r.bar_shadow_alpha_2)));
     }
 
     // Main bar — flat fill when darken_factor is 1.0
-    let base_color = darken_color(task.color, r.bar_darken_factor);
-    painter.rect_filled(bar_rect, rounding, base_color);
+    let base_color = darken_color(fill, r.bar_darken_factor);
+    shapes.push(egui::Shape::rect_filled(bar_rect, rounding, base_color));
 
     // Mid-body glaze (skipped for flat themes)
     if r.bar_glaze_alpha > 0 {
@@ -1350,11 +2675,11 @@ fn draw_task_bar(
             Pos2::new(bar_rect.left(), bar_rect.top() + bar_rect.height() * r.bar_glaze_top_frac),
             Vec2::new(bar_width, bar_rect.height() * r.bar_glaze_height_frac),
         );
-        painter.rect_filled(
+        shapes.push(egui::Shape::rect_filled(
             body_glaze,
             Rounding::same((br - 1.0).max(1.0)),
-            with_alpha(task.color, r.bar_glaze_alpha),
-        );
+            with_alpha(fill, r.bar_glaze_alpha),
+        ));
     }
 
     // Top specular highlight (skipped for flat themes)
@@ -1363,7 +2688,7 @@ fn draw_task_bar(
             bar_rect.min,
             Vec2::new(bar_width, (bar_rect.height() * r.bar_highlight_height_frac).max(3.0)),
         );
-        painter.rect_filled(
+        shapes.push(egui::Shape::rect_filled(
             highlight_rect,
             Rounding {
                 nw: br,
@@ -1372,90 +2697,245 @@ fn draw_task_bar(
                 se: 0.0,
             },
             Color32::from_white_alpha(r.bar_highlight_alpha),
-        );
+        ));
     }
 
     // Bottom contrast edge (skipped for flat themes)
     if r.bar_bottom_edge_alpha > 0 {
-        painter.line_segment(
+        shapes.push(egui::Shape::line_segment(
             [
                 Pos2::new(bar_rect.left() + 1.0, bar_rect.bottom() - 1.0),
                 Pos2::new(bar_rect.right() - 1.0, bar_rect.bottom() - 1.0),
             ],
             Stroke::new(1.0, Color32::from_black_alpha(r.bar_bottom_edge_alpha)),
-        );
+        ));
     }
 
+    push_bar_pattern(shapes, bar_rect.shrink(1.0), pattern);
+
     // Progress fill (darkened overlay)
-    if task.progress > 0.0 {
-        let progress_width = bar_width * task.progress.clamp(0.0, 1.0);
-        let progress_rect = Rect::from_min_size(
-            bar_rect.min,
-            Vec2::new(progress_width, bar_rect.height()),
-        );
-        painter.rect_filled(progress_rect, rounding, theme::progress_overlay());
-
-        // Progress divider tick
-        if task.progress < 0.98 {
-            let tick_x = bar_rect.left() + progress_width;
-            painter.line_segment(
-                [
-                    Pos2::new(tick_x, bar_rect.top() + 2.0),
-                    Pos2::new(tick_x, bar_rect.bottom() - 2.0),
-                ],
-                Stroke::new(1.0, Color32::from_white_alpha(r.progress_tick_alpha)),
-            );
-        }
-    }
+    let tick = Color32::from_white_alpha(r.progress_tick_alpha);
+    push_progress(shapes, bar_rect, task.progress, rounding, theme::progress_overlay(), tick);
 
     // Selection glow
     if is_selected {
-        painter.rect_stroke(
+        shapes.push(egui::Shape::rect_stroke(
             bar_rect.expand(r.selection_glow_outer_expand),
             Rounding::same(br + r.selection_glow_outer_expand),
             Stroke::new(2.0, with_alpha(theme::border_accent(), r.selection_glow_outer_alpha)),
-        );
-        painter.rect_stroke(
+        ));
+        shapes.push(egui::Shape::rect_stroke(
             bar_rect.expand(r.selection_glow_inner_expand),
             Rounding::same(br + r.selection_glow_inner_expand),
             Stroke::new(2.0, theme::border_accent()),
-        );
+        ));
+    }
+
+    // External tasks get a dashed outline.
+    if task.external {
+        let outline = bar_rect.expand(1.5);
+        let corners = [
+            outline.left_top(),
+            outline.right_top(),
+            outline.right_bottom(),
+            outline.left_bottom(),
+            outline.left_top(),
+        ];
+        shapes.extend(egui::Shape::dashed_line(
+            &corners,
+            Stroke::new(1.2, theme::text_secondary()),
+            4.0,
+            3.0,
+        ));
     }
 
     // Overdue indicator — red border when past due and not complete
     let today = chrono::Local::now().date_naive();
     if !task.is_milestone && task.end < today && task.progress < 1.0 {
-        painter.rect_stroke(
+        shapes.push(egui::Shape::rect_stroke(
             bar_rect.expand(1.0),
             Rounding::same(br + 1.0),
             Stroke::new(2.0, Color32::from_rgb(220, 60, 60)),
-        );
+        ));
     }
 
-    // Task name on bar (single line, clipped to bar bounds)
-    if bar_width > 30.0 {
-        let galley = painter.layout_no_wrap(
-            task.name.clone(),
-            theme::font_bar(),
-            theme::text_on_bar(),
-        );
-        let clipped = painter.with_clip_rect(bar_rect);
-        let text_y = y + inset + (bar_rect.height() - galley.size().y) / 2.0;
-        clipped.galley(
-            Pos2::new(bar_rect.left() + 6.0, text_y),
-            galley,
-            Color32::TRANSPARENT,
-        );
+    // Task name on the bar, or beside it like a milestone's when it doesn't fit
+    let galley = painter.layout_no_wrap(task.name.clone(), theme::font_bar(), theme::text_on(base_color));
+    let text_y = y + inset + (bar_rect.height() - galley.size().y) / 2.0;
+    if galley.size().x + 12.0 <= bar_width {
+        painter.galley(Pos2::new(bar_rect.left() + 6.0, text_y), galley, Color32::TRANSPARENT);
+    } else {
+        let galley = painter.layout_no_wrap(task.name.clone(), theme::font_bar(), theme::text_secondary());
+        painter.galley(Pos2::new(bar_rect.right() + 6.0, text_y), galley, Color32::TRANSPARENT);
     }
 
     bar_rect
 }
 
+/// Tessellate `pattern` over `rect` into `shapes`. Hatch lines are clipped
+/// to the rectangle analytically so they can share the batched bar shapes.
+fn push_bar_pattern(shapes: &mut Vec<egui::Shape>, rect: Rect, pattern: BarPattern) {
+    let p = theme::patterns();
+    match pattern {
+        BarPattern::None => {}
+        BarPattern::Hatch => {
+            let stroke = Stroke::new(p.hatch_width, with_alpha(theme::text_on_bar(), p.hatch_alpha));
+            let spacing = p.hatch_spacing.max(2.0);
+            // Lines rising at 45°: x - (bottom - y) = c, for c across the bar.
+            let mut c = rect.left() - rect.height();
+            while c < rect.right() {
+                // Parameter t runs up the line from the bottom edge.
+                let t0 = (rect.left() - c).max(0.0);
+                let t1 = (rect.right() - c).min(rect.height());
+                if t1 > t0 {
+                    shapes.push(egui::Shape::line_segment(
+                        [
+                            Pos2::new(c + t0, rect.bottom() - t0),
+                            Pos2::new(c + t1, rect.bottom() - t1),
+                        ],
+                        stroke,
+                    ));
+                }
+                c += spacing;
+            }
+        }
+        BarPattern::Dots => {
+            let color = with_alpha(theme::text_on_bar(), p.dot_alpha);
+            let spacing = p.dot_spacing.max(2.0);
+            let mut row = 0;
+            let mut y = rect.top() + spacing * 0.5;
+            while y < rect.bottom() {
+                // Offset every other row for a staggered grid.
+                let mut x = rect.left() + spacing * if row % 2 == 0 { 0.5 } else { 1.0 };
+                while x < rect.right() {
+                    shapes.push(egui::Shape::circle_filled(Pos2::new(x, y), p.dot_radius, color));
+                    x += spacing;
+                }
+                y += spacing;
+                row += 1;
+            }
+        }
+    }
+}
+
+/// Color for a finish slip of `days` (assumed positive).
+fn slip_color(days: i64) -> Color32 {
+    if days <= SLIP_MINOR_DAYS {
+        theme::slip_minor()
+    } else if days <= SLIP_MODERATE_DAYS {
+        theme::slip_moderate()
+    } else {
+        theme::slip_major()
+    }
+}
+
+/// Draw a whisker from the baseline finish to the current finish when the
+/// task has slipped. `rect` is the task's bar (or milestone diamond) rect.
+fn draw_slippage_marker(
+    painter: &egui::Painter,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    baseline: &Baseline,
+    task: &Task,
+    rect: Rect,
+) {
+    let Some(planned) = baseline.dates(task.id) else {
+        return;
+    };
+    let days = (task.end - planned.end).num_days();
+    if days <= 0 {
+        return;
+    }
+    let color = slip_color(days);
+    let stroke = Stroke::new(1.5, color);
+    let x_planned = origin.x + viewport.date_to_x(planned.end);
+    let x_current = if task.is_milestone { rect.center().x } else { rect.right() };
+    let y = if task.is_milestone { rect.center().y } else { rect.bottom() - 1.5 };
+
+    // Tick at the planned finish, then a line with an arrowhead at the current finish.
+    painter.line_segment([Pos2::new(x_planned, y - 4.0), Pos2::new(x_planned, y + 2.0)], stroke);
+    painter.line_segment([Pos2::new(x_planned, y), Pos2::new(x_current, y)], stroke);
+    let head = (x_current - x_planned).min(5.0);
+    painter.add(egui::Shape::convex_polygon(
+        vec![
+            Pos2::new(x_current, y),
+            Pos2::new(x_current - head, y - 3.0),
+            Pos2::new(x_current - head, y + 3.0),
+        ],
+        color,
+        Stroke::NONE,
+    ));
+}
+
+/// Draw a dashed outline continuing the bar from its end to the forecast
+/// finish.
+fn draw_forecast_extension(
+    painter: &egui::Painter,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    finish: NaiveDate,
+    rect: Rect,
+    color: Color32,
+) {
+    let x = origin.x + viewport.date_to_x(finish);
+    if x <= rect.right() {
+        return;
+    }
+    let stroke = Stroke::new(1.2, color);
+    let (top, bottom) = (rect.top() + 1.0, rect.bottom() - 1.0);
+    let outline = [
+        Pos2::new(rect.right(), top),
+        Pos2::new(x, top),
+        Pos2::new(x, bottom),
+        Pos2::new(rect.right(), bottom),
+    ];
+    painter.rect_filled(Rect::from_min_max(outline[0], outline[2]), 0.0, color.gamma_multiply(0.12));
+    painter.extend(egui::Shape::dashed_line(&outline, stroke, 4.0, 3.0));
+}
+
+/// Draw a thin line from the end of the bar to a small flag at the date the
+/// remaining work is estimated to finish.
+fn draw_estimated_finish(painter: &egui::Painter, origin: Pos2, viewport: &TimelineViewport, finish: NaiveDate, rect: Rect) {
+    let color = theme::slip_moderate();
+    let x = origin.x + viewport.date_to_x(finish);
+    let y = rect.center().y;
+    painter.line_segment([Pos2::new(rect.right(), y), Pos2::new(x, y)], Stroke::new(1.0, color.gamma_multiply(0.6)));
+    painter.line_segment([Pos2::new(x, rect.top()), Pos2::new(x, rect.bottom())], Stroke::new(1.5, color));
+    painter.add(egui::Shape::convex_polygon(
+        vec![Pos2::new(x, rect.top()), Pos2::new(x + 5.0, rect.top() + 3.0), Pos2::new(x, rect.top() + 6.0)],
+        color,
+        Stroke::NONE,
+    ));
+}
+
+/// Tooltip line describing slip against the baseline, if any.
+fn show_slip_tooltip_line(ui: &mut Ui, baseline: Option<&Baseline>, task: &Task) {
+    let Some(days) = baseline.and_then(|b| b.finish_slip_days(task)) else {
+        return;
+    };
+    let (text, color) = match days {
+        0 => ("On baseline".to_string(), theme::text_dim()),
+        d if d > 0 => (format!("Slipped {} day{} vs baseline", d, if d == 1 { "" } else { "s" }), slip_color(d)),
+        d => (format!("{} day{} ahead of baseline", -d, if d == -1 { "" } else { "s" }), theme::text_secondary()),
+    };
+    ui.label(egui::RichText::new(text).color(color));
+    if let Some(interim) = baseline.and_then(|b| b.interim(task.id)) {
+        let label = if interim.label.is_empty() { String::new() } else { format!(": {}", interim.label) };
+        ui.label(
+            egui::RichText::new(format!("Re-baselined {}{}", interim.captured.format("%d %b %Y"), label))
+                .size(11.0)
+                .color(theme::text_dim()),
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn draw_milestone(
     painter: &egui::Painter,
     origin: Pos2,
     viewport: &TimelineViewport,
     task: &Task,
+    fill: Color32,
     y: f32,
     row_height: f32,
     is_selected: bool,
@@ -1463,6 +2943,8 @@ fn draw_milestone(
     let x = origin.x + viewport.date_to_x(task.start);
     let center = Pos2::new(x, y + row_height / 2.0);
     let size = (row_height / 2.0 - 3.0).max(6.0);
+    let fill = if task.tentative { fill.gamma_multiply(0.45) } else { fill };
+    let fill = if task.external { greyed(fill) } else { fill };
 
     // Shadow diamond
     let shadow_offset = Vec2::new(1.0, 1.5);
@@ -1485,11 +2967,21 @@ fn draw_milestone(
         Pos2::new(center.x, center.y + size),
         Pos2::new(center.x - size, center.y),
     ];
-    painter.add(egui::Shape::convex_polygon(
-        points.clone(),
-        task.color,
-        Stroke::NONE,
-    ));
+    // Achieved milestones are filled; pending ones are drawn hollow, filled
+    // from the bottom by any partial progress.
+    if task.is_achieved() {
+        painter.add(egui::Shape::convex_polygon(points.clone(), fill, Stroke::NONE));
+    } else {
+        painter.add(egui::Shape::convex_polygon(points.clone(), theme::bg_dark(), Stroke::NONE));
+        if theme::rendering().milestone_progress && task.progress > 0.0 {
+            let level = center.y + size - 2.0 * size * task.progress.min(1.0);
+            let filled = clip_below(&points, level);
+            if filled.len() >= 3 {
+                painter.add(egui::Shape::convex_polygon(filled, with_alpha(fill, 180), Stroke::NONE));
+            }
+        }
+        painter.add(egui::Shape::closed_line(points.clone(), Stroke::new(2.0, fill)));
+    }
 
     if is_selected {
         painter.add(egui::Shape::convex_polygon(
@@ -1511,7 +3003,107 @@ fn draw_milestone(
     Rect::from_center_size(center, Vec2::splat(size * 2.0 + 2.0))
 }
 
-fn dependency_endpoints(from_rect: Rect, to_rect: Rect, kind: DependencyKind) -> (Pos2, Pos2) {
+/// Anchor layout for dependency arrows: per-link vertical offsets on the source
+/// and target bar edges, plus the edges whose links are drawn as a bundle.
+struct DependencyAnchors {
+    offsets: std::collections::HashMap<(Uuid, Uuid), (f32, f32)>,
+    /// (edge point at bar center height, outward direction, link count)
+    bundles: Vec<(Pos2, f32, usize)>,
+}
+
+impl DependencyAnchors {
+    fn offset(&self, dep: &Dependency) -> (f32, f32) {
+        self.offsets
+            .get(&(dep.from_task, dep.to_task))
+            .copied()
+            .unwrap_or((0.0, 0.0))
+    }
+}
+
+/// Spread the connection points of links that share a bar edge so they don't
+/// stack on the same pixel. Links are ordered by the vertical position of the
+/// task at their other end to avoid crossings; edges with more than
+/// [`MAX_STAGGERED_LINKS`] links keep a single centered anchor and are bundled.
+fn dependency_anchors(
+    dependencies: &[Dependency],
+    task_positions: &std::collections::HashMap<Uuid, (usize, Rect)>,
+) -> DependencyAnchors {
+    type EdgeLink = ((Uuid, Uuid), bool, f32);
+    let mut edges: std::collections::HashMap<(Uuid, bool), Vec<EdgeLink>> =
+        std::collections::HashMap::new();
+
+    for dep in dependencies {
+        let (Some(&(_, from_rect)), Some(&(_, to_rect))) =
+            (task_positions.get(&dep.from_task), task_positions.get(&dep.to_task))
+        else {
+            continue;
+        };
+        let (from_right, to_right) = match dep.kind {
+            DependencyKind::FinishToStart  => (true, false),
+            DependencyKind::StartToStart   => (false, false),
+            DependencyKind::FinishToFinish => (true, true),
+            DependencyKind::StartToFinish  => (false, true),
+        };
+        let key = (dep.from_task, dep.to_task);
+        edges
+            .entry((dep.from_task, from_right))
+            .or_default()
+            .push((key, true, to_rect.center().y));
+        edges
+            .entry((dep.to_task, to_right))
+            .or_default()
+            .push((key, false, from_rect.center().y));
+    }
+
+    let mut anchors = DependencyAnchors {
+        offsets: std::collections::HashMap::new(),
+        bundles: Vec::new(),
+    };
+    for ((task_id, right_edge), mut links) in edges {
+        let n = links.len();
+        if n < 2 {
+            continue;
+        }
+        let Some(&(_, rect)) = task_positions.get(&task_id) else { continue };
+        if n > MAX_STAGGERED_LINKS {
+            let x = if right_edge { rect.right() } else { rect.left() };
+            let dir = if right_edge { 1.0 } else { -1.0 };
+            anchors.bundles.push((Pos2::new(x, rect.center().y), dir, n));
+            continue;
+        }
+        links.sort_by(|a, b| a.2.total_cmp(&b.2));
+        let spacing = (rect.height() * 0.7 / (n - 1) as f32).min(6.0);
+        for (i, (key, is_source, _)) in links.iter().enumerate() {
+            let dy = (i as f32 - (n - 1) as f32 / 2.0) * spacing;
+            let entry = anchors.offsets.entry(*key).or_insert((0.0, 0.0));
+            if *is_source {
+                entry.0 = dy;
+            } else {
+                entry.1 = dy;
+            }
+        }
+    }
+    anchors
+}
+
+/// Draw the shared trunk of bundled links: a thicker stub whose width grows
+/// with the number of links, ending in a junction dot where they fan out.
+fn draw_dependency_bundles(painter: &egui::Painter, anchors: &DependencyAnchors) {
+    let color = with_alpha(theme::dep_arrow(), 200);
+    for &(edge, dir, count) in &anchors.bundles {
+        let junction = Pos2::new(edge.x + dir * DEP_STUB, edge.y);
+        let width = 1.4 + (count as f32).sqrt() * 0.6;
+        painter.line_segment([edge, junction], Stroke::new(width, color));
+        painter.circle_filled(junction, width * 0.9, color);
+    }
+}
+
+fn dependency_endpoints(
+    from_rect: Rect,
+    to_rect: Rect,
+    kind: DependencyKind,
+    (from_dy, to_dy): (f32, f32),
+) -> (Pos2, Pos2) {
     // Route endpoints based on dependency type:
     // FS (Finish→Start):  exit from right of from, enter left of to
     // SS (Start→Start):   exit from left of from, enter left of to
@@ -1523,20 +3115,17 @@ fn dependency_endpoints(from_rect: Rect, to_rect: Rect, kind: DependencyKind) ->
         DependencyKind::FinishToFinish => (from_rect.right(), to_rect.right()),
         DependencyKind::StartToFinish  => (from_rect.left(),  to_rect.right()),
     };
-    let from = Pos2::new(start_x, from_rect.center().y);
-    let to   = Pos2::new(end_x,   to_rect.center().y);
+    let from = Pos2::new(start_x, from_rect.center().y + from_dy);
+    let to   = Pos2::new(end_x,   to_rect.center().y + to_dy);
     (from, to)
 }
 
 fn dependency_route_points(from: Pos2, to: Pos2, kind: DependencyKind) -> Vec<Pos2> {
-    // Short fixed stub length — exits/enters each bar by this amount before turning.
-    const STUB: f32 = 10.0;
-
     match kind {
         DependencyKind::FinishToStart => {
             // Exit right side, enter left side.
-            let exit_x = from.x + STUB;
-            let enter_x = to.x - STUB;
+            let exit_x = from.x + DEP_STUB;
+            let enter_x = to.x - DEP_STUB;
 
             if exit_x <= enter_x {
                 // Normal forward case: exit stub → drop straight → enter stub.
@@ -1582,7 +3171,7 @@ fn dependency_route_points(from: Pos2, to: Pos2, kind: DependencyKind) -> Vec<Po
 
         DependencyKind::StartToStart => {
             // Both exit/enter the LEFT side — lane to the left of both bars.
-            let lane_x = (from.x.min(to.x) - STUB).max(2.0);
+            let lane_x = (from.x.min(to.x) - DEP_STUB).max(2.0);
             if (from.y - to.y).abs() < 1.0 {
                 vec![from, Pos2::new(lane_x, from.y), to]
             } else {
@@ -1597,7 +3186,7 @@ fn dependency_route_points(from: Pos2, to: Pos2, kind: DependencyKind) -> Vec<Po
 
         DependencyKind::FinishToFinish => {
             // Both exit/enter the RIGHT side — lane to the right of both bars.
-            let lane_x = from.x.max(to.x) + STUB;
+            let lane_x = from.x.max(to.x) + DEP_STUB;
             if (from.y - to.y).abs() < 1.0 {
                 vec![from, Pos2::new(lane_x, from.y), to]
             } else {
@@ -1612,8 +3201,8 @@ fn dependency_route_points(from: Pos2, to: Pos2, kind: DependencyKind) -> Vec<Po
 
         DependencyKind::StartToFinish => {
             // Exit left, enter right.
-            let exit_x = from.x - STUB;
-            let enter_x = to.x + STUB;
+            let exit_x = from.x - DEP_STUB;
+            let enter_x = to.x + DEP_STUB;
             if exit_x >= enter_x {
                 // Normal: from bar starts after to bar ends.
                 let mid_x = to.x + (from.x - to.x) / 2.0;
@@ -1655,6 +3244,58 @@ fn dependency_route_points(from: Pos2, to: Pos2, kind: DependencyKind) -> Vec<Po
     }
 }
 
+/// Style controls shown in a dependency's context menu.
+/// Returns the edited dependency when the user changed something.
+fn show_dependency_style_menu(ui: &mut Ui, dep: &Dependency) -> Option<Dependency> {
+    let mut updated = dep.clone();
+    let mut changed = false;
+
+    ui.label(egui::RichText::new("Line").size(10.0).color(theme::text_dim()).strong());
+    ui.horizontal(|ui| {
+        for style in LineStyle::all() {
+            if ui.selectable_label(updated.line_style == *style, style.label()).clicked() {
+                updated.line_style = *style;
+                changed = true;
+            }
+        }
+    });
+
+    ui.label(egui::RichText::new("Color").size(10.0).color(theme::text_dim()).strong());
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing = egui::vec2(4.0, 4.0);
+        if ui.selectable_label(updated.color.is_none(), "Default").clicked() {
+            updated.color = None;
+            changed = true;
+        }
+        for color in theme::task_palette() {
+            let (rect, resp) = ui.allocate_exact_size(Vec2::splat(14.0), Sense::click());
+            ui.painter().rect_filled(rect, Rounding::same(3.0), color);
+            if updated.color == Some(color) {
+                ui.painter().rect_stroke(rect.expand(1.0), Rounding::same(4.0), Stroke::new(2.0, Color32::WHITE));
+            }
+            if resp.clicked() {
+                updated.color = Some(color);
+                changed = true;
+            }
+        }
+    });
+
+    if ui.checkbox(&mut updated.critical, "Critical").changed() {
+        changed = true;
+    }
+
+    changed.then_some(updated)
+}
+
+/// Stroke color and width for a dependency arrow, honouring its per-link style.
+fn dependency_style(dep: &Dependency, allow_critical: bool) -> (Color32, f32) {
+    if dep.critical && allow_critical {
+        (theme::dep_critical(), 2.2)
+    } else {
+        (dep.color.unwrap_or_else(|| with_alpha(theme::dep_arrow(), 180)), 1.4)
+    }
+}
+
 fn draw_dependency_arrow(
     painter: &egui::Painter,
     from: Pos2,
@@ -1662,9 +3303,10 @@ fn draw_dependency_arrow(
     kind: DependencyKind,
     color: Color32,
     width: f32,
+    line_style: LineStyle,
 ) {
     let route = dependency_route_points(from, to, kind);
-    draw_dependency_polyline(painter, &route, color, width, 3.0);
+    draw_dependency_polyline(painter, &route, color, width, 3.0, line_style);
     if route.len() >= 2 {
         let last_from = route[route.len() - 2];
         let last_to = route[route.len() - 1];
@@ -1678,6 +3320,7 @@ fn draw_dependency_polyline(
     color: Color32,
     width: f32,
     corner_radius: f32,
+    line_style: LineStyle,
 ) {
     if points.len() < 2 {
         return;
@@ -1725,18 +3368,104 @@ fn draw_dependency_polyline(
 
     draw_points.push(*points.last().unwrap_or(&points[0]));
 
-    for seg in draw_points.windows(2) {
-        painter.line_segment([seg[0], seg[1]], stroke);
+    match line_style {
+        LineStyle::Solid => {
+            painter.add(egui::Shape::line(draw_points, stroke));
+        }
+        LineStyle::Dashed => {
+            painter.extend(egui::Shape::dashed_line(&draw_points, stroke, 5.0, 3.0));
+        }
     }
 }
 
-fn is_point_near_polyline(point: Pos2, points: &[Pos2], threshold: f32) -> bool {
-    if points.len() < 2 {
-        return false;
+/// Outline of a dragged bar at its original dates, plus a badge by the
+/// pointer with the new dates and how far they moved.
+/// Outline in amber the tasks down the dragged task's links that would have
+/// to move for the links to hold, with how many days each would slip.
+fn draw_slip_impact(
+    painter: &egui::Painter,
+    tasks: &[Task],
+    dependencies: &[Dependency],
+    task_positions: &std::collections::HashMap<Uuid, (usize, Rect)>,
+    ghost: &DragGhost,
+) {
+    let impact = crate::model::impact::slip_impact(tasks, dependencies, ghost.task, ghost.from, ghost.to);
+    let amber = theme::slip_minor();
+    for (id, days) in impact {
+        let Some(&(_, rect)) = task_positions.get(&id) else { continue };
+        let rect = rect.expand(2.0);
+        painter.rect_filled(rect, Rounding::same(theme::bar_rounding() + 2.0), with_alpha(amber, 40));
+        painter.rect_stroke(rect, Rounding::same(theme::bar_rounding() + 2.0), Stroke::new(2.0, amber));
+        painter.text(
+            Pos2::new(rect.right() + 4.0, rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            format!("+{}d", days),
+            theme::font_small(),
+            amber,
+        );
+    }
+}
+
+fn draw_drag_ghost(
+    ui: &Ui,
+    painter: &egui::Painter,
+    pane: Id,
+    origin: Pos2,
+    viewport: &TimelineViewport,
+    ghost: &DragGhost,
+) {
+    let stroke = Stroke::new(1.2, theme::text_dim());
+    let x0 = origin.x + viewport.date_to_x(ghost.from.0);
+    if ghost.milestone {
+        let c = Pos2::new(x0, ghost.y.center());
+        let r = ghost.y.span() / 2.0;
+        let points = vec![c + Vec2::new(0.0, -r), c + Vec2::new(r, 0.0), c + Vec2::new(0.0, r), c + Vec2::new(-r, 0.0)];
+        painter.add(egui::Shape::closed_line(points, stroke));
+    } else {
+        let x1 = origin.x + viewport.date_to_x(ghost.from.1);
+        let rect = Rect::from_x_y_ranges(x0..=x1.max(x0 + 6.0), ghost.y);
+        let corners = [rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom(), rect.left_top()];
+        for pair in corners.windows(2) {
+            painter.extend(egui::Shape::dashed_line(pair, stroke, 4.0, 3.0));
+        }
     }
+
+    let dates = if ghost.milestone {
+        ghost.to.0.format("%a %d %b").to_string()
+    } else {
+        format!("{} – {}", ghost.to.0.format("%a %d %b"), ghost.to.1.format("%a %d %b"))
+    };
+    let text = format!("{}   {:+}d", dates, ghost.delta);
+    let badge = ui.ctx().layer_painter(egui::LayerId::new(egui::Order::Tooltip, pane.with("drag-badge")));
+    let galley = badge.layout_no_wrap(text, egui::FontId::proportional(11.5), theme::text_primary());
+    let rect = Rect::from_min_size(ghost.pointer + Vec2::new(12.0, -galley.size().y - 14.0), galley.size())
+        .expand2(Vec2::new(6.0, 3.0));
+    badge.rect(rect, Rounding::same(4.0), theme::bg_panel(), Stroke::new(1.0, theme::border_subtle()));
+    badge.galley(rect.min + Vec2::new(6.0, 3.0), galley, theme::text_primary());
+}
+
+/// The part of convex polygon `points` below the horizontal line at `y`.
+fn clip_below(points: &[Pos2], y: f32) -> Vec<Pos2> {
+    let mut out = Vec::with_capacity(points.len() + 2);
+    for (i, &a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        if a.y >= y {
+            out.push(a);
+        }
+        if (a.y < y) != (b.y < y) {
+            let t = (y - a.y) / (b.y - a.y);
+            out.push(Pos2::new(a.x + (b.x - a.x) * t, y));
+        }
+    }
+    out
+}
+
+/// Shortest distance from `point` to the polyline through `points`.
+fn distance_to_polyline(point: Pos2, points: &[Pos2]) -> f32 {
     points
         .windows(2)
-        .any(|seg| distance_to_segment(point, seg[0], seg[1]) <= threshold)
+        .map(|seg| distance_to_segment(point, seg[0], seg[1]))
+        .fold(f32::INFINITY, f32::min)
 }
 
 fn distance_to_segment(p: Pos2, a: Pos2, b: Pos2) -> f32 {
@@ -1755,6 +3484,13 @@ fn with_alpha(color: Color32, alpha: u8) -> Color32 {
     Color32::from_rgba_premultiplied(color.r(), color.g(), color.b(), alpha)
 }
 
+/// Mostly-desaturated version of `color`, used for external tasks.
+fn greyed(color: Color32) -> Color32 {
+    let luma = (0.3 * color.r() as f32 + 0.59 * color.g() as f32 + 0.11 * color.b() as f32) as u8;
+    let grey = Color32::from_rgba_unmultiplied(luma, luma, luma, color.a());
+    color.lerp_to_gamma(grey, 0.75)
+}
+
 fn darken_color(color: Color32, factor: f32) -> Color32 {
     let f = factor.clamp(0.0, 1.0);
     Color32::from_rgb(
@@ -0,0 +1,130 @@
+use crate::model::task::Dependency;
+use crate::model::{ProjectCalendar, Resource, Task};
+use crate::ui::theme;
+use egui::{RichText, Ui};
+
+/// Longest notes excerpt shown on the card, in characters.
+const NOTES_EXCERPT: usize = 160;
+/// Links listed per direction before collapsing into "+N more".
+const MAX_LINKS: usize = 5;
+
+/// What a hover card needs besides the task itself.
+pub struct CardContext<'a> {
+    pub tasks: &'a [Task],
+    pub dependencies: &'a [Dependency],
+    pub resources: &'a [Resource],
+    /// For the working-day duration.
+    pub calendar: ProjectCalendar,
+}
+
+fn section(ui: &mut Ui, title: &str) {
+    ui.add_space(4.0);
+    ui.label(RichText::new(title).size(10.0).color(theme::text_dim()).strong());
+}
+
+fn link_lines(ui: &mut Ui, ctx: &CardContext, links: Vec<(&Dependency, uuid::Uuid)>) {
+    for (dep, other) in links.iter().take(MAX_LINKS) {
+        let name = ctx.tasks.iter().find(|t| t.id == *other).map(|t| t.name.as_str()).unwrap_or("?");
+        let lag = match dep.lag_days {
+            0 => String::new(),
+            d => format!(" {:+}d", d),
+        };
+        let note = dep.note().map(|n| format!(" — {}", n)).unwrap_or_default();
+        ui.label(
+            RichText::new(format!("{}  {}{}{}", dep.kind.short_label(), name, lag, note))
+                .size(11.0)
+                .color(theme::text_secondary()),
+        );
+    }
+    if links.len() > MAX_LINKS {
+        ui.label(RichText::new(format!("+{} more", links.len() - MAX_LINKS)).size(10.0).color(theme::text_dim()));
+    }
+}
+
+/// Render the full details of `task`: dates, progress, people, links and a
+/// notes excerpt. Used for the chart's hover card and for pinned cards.
+pub fn show_task_card(ui: &mut Ui, task: &Task, ctx: &CardContext) {
+    ui.set_max_width(280.0);
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(task.priority.icon()).size(11.0));
+        ui.strong(&task.name);
+    });
+
+    if task.is_milestone {
+        ui.label(task.start.format("%a %d/%m/%Y").to_string());
+        ui.label(if task.is_achieved() { "Achieved" } else { "Not achieved" });
+    } else {
+        let days = (task.end - task.start).num_days();
+        let working = ctx.calendar.working_days_between(task.start, task.end);
+        ui.label(format!(
+            "{} → {}  ·  {} day{} ({} working)",
+            task.start.format("%d/%m/%Y"),
+            task.end.format("%d/%m/%Y"),
+            days,
+            if days == 1 { "" } else { "s" },
+            working
+        ));
+        let manual = if task.progress_override.is_some() { " (manual)" } else { "" };
+        ui.label(format!("Progress: {}%{}", (task.progress * 100.0) as i32, manual));
+    }
+
+    let mut flags = vec![format!("{} priority", task.priority.label())];
+    if task.tentative {
+        flags.push("Tentative".to_string());
+    }
+    if task.external {
+        flags.push("External".to_string());
+    }
+    if let Some(status) = &task.link_status {
+        flags.push(format!("Linked: {}", status));
+    }
+    ui.label(RichText::new(flags.join("  ·  ")).size(10.0).color(theme::text_dim()));
+
+    let assignees: Vec<String> = task
+        .assignments
+        .iter()
+        .filter_map(|a| {
+            let r = ctx.resources.iter().find(|r| r.id == a.resource_id)?;
+            Some(if (a.units - 1.0).abs() < f32::EPSILON {
+                r.name.clone()
+            } else {
+                format!("{} ({:.0}%)", r.name, a.units * 100.0)
+            })
+        })
+        .collect();
+    if !assignees.is_empty() {
+        section(ui, "Assigned to");
+        ui.label(RichText::new(assignees.join(", ")).size(11.0).color(theme::text_secondary()));
+    }
+
+    let predecessors: Vec<_> = ctx
+        .dependencies
+        .iter()
+        .filter(|d| d.to_task == task.id)
+        .map(|d| (d, d.from_task))
+        .collect();
+    let successors: Vec<_> = ctx
+        .dependencies
+        .iter()
+        .filter(|d| d.from_task == task.id)
+        .map(|d| (d, d.to_task))
+        .collect();
+    if !predecessors.is_empty() {
+        section(ui, "Predecessors");
+        link_lines(ui, ctx, predecessors);
+    }
+    if !successors.is_empty() {
+        section(ui, "Successors");
+        link_lines(ui, ctx, successors);
+    }
+
+    let notes = task.description.trim();
+    if !notes.is_empty() {
+        section(ui, "Notes");
+        let mut excerpt: String = notes.chars().take(NOTES_EXCERPT).collect();
+        if notes.chars().count() > NOTES_EXCERPT {
+            excerpt.push('…');
+        }
+        ui.label(RichText::new(excerpt).size(11.0).color(theme::text_secondary()));
+    }
+}
@@ -0,0 +1,252 @@
+//! Command registry and rebindable keyboard shortcuts.
+//!
+//! Every keyboard-triggered action is a [`Command`]. The [`Keymap`] maps
+//! commands to shortcuts and only stores the user's overrides, so commands
+//! added in later versions pick up their default bindings automatically.
+
+use egui::{Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// An action that can be bound to a keyboard shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Command {
+    NewProject,
+    OpenProject,
+    Save,
+    SaveAs,
+    Undo,
+    Redo,
+    AddTask,
+    DeleteTask,
+    LinkTasks,
+    ZoomIn,
+    ZoomOut,
+    ZoomDay,
+    ZoomWeek,
+    ZoomMonth,
+    ZoomFit,
+    GoToToday,
+    NavigateBack,
+    NavigateForward,
+    SelectPrevious,
+    SelectNext,
+    ToggleSplitView,
+    TogglePerfOverlay,
+    OpenBenchmark,
+    ShowShortcuts,
+    CheckSchedule,
+    CheckProject,
+}
+
+impl Command {
+    pub fn all() -> &'static [Command] {
+        &[
+            Command::NewProject,
+            Command::OpenProject,
+            Command::Save,
+            Command::SaveAs,
+            Command::Undo,
+            Command::Redo,
+            Command::AddTask,
+            Command::DeleteTask,
+            Command::LinkTasks,
+            Command::ZoomIn,
+            Command::ZoomOut,
+            Command::ZoomDay,
+            Command::ZoomWeek,
+            Command::ZoomMonth,
+            Command::ZoomFit,
+            Command::GoToToday,
+            Command::NavigateBack,
+            Command::NavigateForward,
+            Command::SelectPrevious,
+            Command::SelectNext,
+            Command::ToggleSplitView,
+            Command::TogglePerfOverlay,
+            Command::OpenBenchmark,
+            Command::ShowShortcuts,
+            Command::CheckSchedule,
+            Command::CheckProject,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Command::NewProject => "New Project",
+            Command::OpenProject => "Open Project",
+            Command::Save => "Save",
+            Command::SaveAs => "Save As",
+            Command::Undo => "Undo",
+            Command::Redo => "Redo",
+            Command::AddTask => "Add Task",
+            Command::DeleteTask => "Delete Selected Task",
+            Command::LinkTasks => "Link Selected Task",
+            Command::ZoomIn => "Zoom In",
+            Command::ZoomOut => "Zoom Out",
+            Command::ZoomDay => "Zoom to Days",
+            Command::ZoomWeek => "Zoom to Weeks",
+            Command::ZoomMonth => "Zoom to Months",
+            Command::ZoomFit => "Fit Timeline",
+            Command::GoToToday => "Go to Today",
+            Command::NavigateBack => "Back (previous view position)",
+            Command::NavigateForward => "Forward",
+            Command::SelectPrevious => "Previously Selected Task",
+            Command::SelectNext => "Next Selected Task",
+            Command::ToggleSplitView => "Toggle Split Timeline",
+            Command::TogglePerfOverlay => "Toggle Performance Overlay",
+            Command::OpenBenchmark => "Generate Benchmark Project",
+            Command::ShowShortcuts => "Keyboard Shortcuts",
+            Command::CheckSchedule => "Check Schedule",
+            Command::CheckProject => "Check Project",
+        }
+    }
+
+    fn default_bindings(self) -> Vec<KeyboardShortcut> {
+        let ctrl = Modifiers::COMMAND;
+        let ctrl_shift = Modifiers::COMMAND | Modifiers::SHIFT;
+        let sc = KeyboardShortcut::new;
+        match self {
+            Command::NewProject => vec![sc(ctrl, Key::N)],
+            Command::OpenProject => vec![sc(ctrl, Key::O)],
+            Command::Save => vec![sc(ctrl, Key::S)],
+            Command::SaveAs => vec![sc(ctrl_shift, Key::S)],
+            Command::Undo => vec![sc(ctrl, Key::Z)],
+            Command::Redo => vec![sc(ctrl, Key::Y), sc(ctrl_shift, Key::Z)],
+            Command::AddTask => vec![sc(ctrl, Key::T)],
+            Command::DeleteTask => vec![sc(Modifiers::NONE, Key::Delete)],
+            Command::LinkTasks => vec![sc(Modifiers::NONE, Key::L)],
+            Command::ZoomIn => vec![sc(ctrl, Key::Plus), sc(ctrl, Key::Equals)],
+            Command::ZoomOut => vec![sc(ctrl, Key::Minus)],
+            Command::ZoomDay => vec![sc(ctrl, Key::Num1)],
+            Command::ZoomWeek => vec![sc(ctrl, Key::Num2)],
+            Command::ZoomMonth => vec![sc(ctrl, Key::Num3)],
+            Command::ZoomFit => vec![sc(ctrl, Key::Num4)],
+            Command::GoToToday => vec![sc(Modifiers::ALT, Key::Home)],
+            Command::NavigateBack => vec![sc(Modifiers::ALT | Modifiers::SHIFT, Key::ArrowLeft)],
+            Command::NavigateForward => vec![sc(Modifiers::ALT | Modifiers::SHIFT, Key::ArrowRight)],
+            Command::SelectPrevious => vec![sc(Modifiers::ALT, Key::ArrowLeft)],
+            Command::SelectNext => vec![sc(Modifiers::ALT, Key::ArrowRight)],
+            Command::ToggleSplitView => vec![],
+            Command::TogglePerfOverlay => vec![sc(Modifiers::NONE, Key::F12)],
+            Command::OpenBenchmark => vec![sc(ctrl_shift, Key::F12)],
+            Command::ShowShortcuts => vec![sc(ctrl, Key::Slash)],
+            Command::CheckSchedule => vec![sc(Modifiers::NONE, Key::F7)],
+            Command::CheckProject => vec![sc(Modifiers::SHIFT, Key::F7)],
+        }
+    }
+}
+
+/// Format a shortcut as e.g. `Ctrl+Shift+S`. This is also the form stored in
+/// the settings file.
+pub fn format_shortcut(shortcut: &KeyboardShortcut) -> String {
+    let m = shortcut.modifiers;
+    let mut out = String::new();
+    if m.command || m.ctrl || m.mac_cmd {
+        out.push_str("Ctrl+");
+    }
+    if m.alt {
+        out.push_str("Alt+");
+    }
+    if m.shift {
+        out.push_str("Shift+");
+    }
+    out.push_str(shortcut.logical_key.name());
+    out
+}
+
+/// Parse a shortcut written by [`format_shortcut`].
+pub fn parse_shortcut(text: &str) -> Option<KeyboardShortcut> {
+    let mut modifiers = Modifiers::NONE;
+    let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+    let key_name = parts.pop()?;
+    for part in parts {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "cmd" => modifiers = modifiers | Modifiers::COMMAND,
+            "shift" => modifiers = modifiers | Modifiers::SHIFT,
+            "alt" => modifiers = modifiers | Modifiers::ALT,
+            _ => return None,
+        }
+    }
+    Key::from_name(key_name).map(|key| KeyboardShortcut::new(modifiers, key))
+}
+
+/// User-customisable mapping from commands to shortcuts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keymap {
+    /// Bindings that differ from the defaults. An empty list unbinds.
+    overrides: BTreeMap<Command, Vec<String>>,
+}
+
+impl Keymap {
+    pub fn bindings(&self, command: Command) -> Vec<KeyboardShortcut> {
+        match self.overrides.get(&command) {
+            Some(list) => list.iter().filter_map(|s| parse_shortcut(s)).collect(),
+            None => command.default_bindings(),
+        }
+    }
+
+    /// Display text for menus, e.g. `Ctrl+S`; empty when unbound.
+    pub fn label(&self, command: Command) -> String {
+        self.bindings(command).first().map(format_shortcut).unwrap_or_default()
+    }
+
+    /// Replace all bindings of `command`. Passing `None` unbinds it.
+    /// Any other command using the same shortcut loses that binding.
+    pub fn set(&mut self, command: Command, shortcut: Option<KeyboardShortcut>) {
+        if let Some(sc) = shortcut {
+            for &other in Command::all() {
+                if other != command && self.bindings(other).contains(&sc) {
+                    let remaining = self
+                        .bindings(other)
+                        .iter()
+                        .filter(|b| **b != sc)
+                        .map(format_shortcut)
+                        .collect();
+                    self.overrides.insert(other, remaining);
+                }
+            }
+        }
+        self.overrides.insert(command, shortcut.iter().map(format_shortcut).collect());
+    }
+
+    pub fn reset(&mut self, command: Command) {
+        self.overrides.remove(&command);
+    }
+
+    pub fn reset_all(&mut self) {
+        self.overrides.clear();
+    }
+
+    pub fn is_default(&self, command: Command) -> bool {
+        !self.overrides.contains_key(&command)
+    }
+
+    /// Consume this frame's key presses and return the commands they trigger.
+    ///
+    /// Shortcuts with more modifiers are checked first so `Ctrl+Shift+Z` is not
+    /// swallowed by `Ctrl+Z`. Shortcuts without Ctrl/Alt are ignored while a
+    /// text field has keyboard focus.
+    pub fn triggered(&self, ctx: &egui::Context) -> Vec<Command> {
+        let text_focused = ctx.memory(|m| m.focused().is_some());
+        let mut bindings: Vec<(Command, KeyboardShortcut)> = Command::all()
+            .iter()
+            .flat_map(|&cmd| self.bindings(cmd).into_iter().map(move |sc| (cmd, sc)))
+            .filter(|(_, sc)| !text_focused || sc.modifiers.command || sc.modifiers.alt)
+            .collect();
+        bindings.sort_by_key(|(_, sc)| {
+            std::cmp::Reverse(sc.modifiers.command as u8 + sc.modifiers.shift as u8 + sc.modifiers.alt as u8)
+        });
+
+        let mut commands = Vec::new();
+        ctx.input_mut(|i| {
+            for (cmd, sc) in &bindings {
+                if i.consume_shortcut(sc) && !commands.contains(cmd) {
+                    commands.push(*cmd);
+                }
+            }
+        });
+        commands
+    }
+}
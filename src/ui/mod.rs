@@ -1,9 +1,20 @@
+pub mod capture;
+pub mod color_by;
 pub mod dialogs;
 pub mod filter_bar;
 pub mod gantt_chart;
+pub mod hover_card;
+pub mod keymap;
+pub mod perf_overlay;
+pub mod reminders;
+pub mod resource_histogram;
+pub mod roadmap;
+pub mod swimlanes;
 pub mod task_editor;
 pub mod task_table;
 pub mod theme_def;
 pub mod theme_manager;
 pub mod theme;
 pub mod toolbar;
+pub mod tour;
+pub mod watch_strip;
@@ -0,0 +1,66 @@
+use crate::ui::theme;
+use egui::{Context, RichText};
+use std::collections::VecDeque;
+
+/// Number of recent frames averaged by the overlay.
+const FRAME_WINDOW: usize = 120;
+
+/// Rolling performance counters shown by the debug overlay (F12).
+#[derive(Debug, Clone, Default)]
+pub struct PerfStats {
+    frame_times_ms: VecDeque<f32>,
+    pub visible_rows: usize,
+    pub shapes_painted: usize,
+    pub task_count: usize,
+    pub dependency_count: usize,
+}
+
+impl PerfStats {
+    /// Record the CPU time of the previous frame, in seconds.
+    pub fn record_frame(&mut self, seconds: f32) {
+        if self.frame_times_ms.len() == FRAME_WINDOW {
+            self.frame_times_ms.pop_front();
+        }
+        self.frame_times_ms.push_back(seconds * 1000.0);
+    }
+
+    pub fn average_ms(&self) -> f32 {
+        if self.frame_times_ms.is_empty() {
+            return 0.0;
+        }
+        self.frame_times_ms.iter().sum::<f32>() / self.frame_times_ms.len() as f32
+    }
+
+    pub fn max_ms(&self) -> f32 {
+        self.frame_times_ms.iter().copied().fold(0.0, f32::max)
+    }
+}
+
+/// Draw the performance overlay in the top-right corner of the window.
+pub fn show_perf_overlay(ctx: &Context, stats: &PerfStats) {
+    let rect = ctx.screen_rect();
+    egui::Area::new(egui::Id::new("perf-overlay"))
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .fixed_pos(egui::pos2(rect.right() - 190.0, rect.top() + 40.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.set_width(170.0);
+                let line = |ui: &mut egui::Ui, label: &str, value: String| {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(label).size(10.5).color(theme::text_dim()));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.label(RichText::new(value).size(10.5).monospace().color(theme::text_primary()));
+                        });
+                    });
+                };
+                ui.label(RichText::new("Performance").strong().size(11.0));
+                line(ui, "Frame (avg)", format!("{:.2} ms", stats.average_ms()));
+                line(ui, "Frame (max)", format!("{:.2} ms", stats.max_ms()));
+                line(ui, "Shapes painted", stats.shapes_painted.to_string());
+                line(ui, "Visible rows", stats.visible_rows.to_string());
+                line(ui, "Tasks", stats.task_count.to_string());
+                line(ui, "Dependencies", stats.dependency_count.to_string());
+            });
+        });
+}
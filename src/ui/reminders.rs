@@ -0,0 +1,77 @@
+use crate::model::task::ReminderAnchor;
+use crate::model::Task;
+use crate::ui::theme;
+use chrono::NaiveDate;
+use egui::{RichText, Ui};
+use uuid::Uuid;
+
+/// Actions from the reminder banner.
+pub enum ReminderAction {
+    None,
+    /// Select the task and scroll the chart to it.
+    Select(Uuid),
+    /// Hide this reminder until tomorrow.
+    Dismiss(Uuid),
+}
+
+/// Tasks whose reminder is active today and has not been dismissed.
+pub fn due_reminders<'a>(tasks: &'a [Task], dismissed: &[Uuid], today: NaiveDate) -> Vec<&'a Task> {
+    tasks
+        .iter()
+        .filter(|t| t.reminder_due(today) && !dismissed.contains(&t.id))
+        .collect()
+}
+
+/// "starts in 3 days", "finishes today", …
+fn reminder_text(task: &Task, today: NaiveDate) -> String {
+    let (Some(reminder), Some(target)) = (task.reminder, task.reminder_target()) else {
+        return String::new();
+    };
+    let verb = match reminder.anchor {
+        ReminderAnchor::Start => "starts",
+        ReminderAnchor::Finish => "finishes",
+    };
+    match (target - today).num_days() {
+        0 => format!("{} today", verb),
+        1 => format!("{} tomorrow", verb),
+        d => format!("{} in {} days ({})", verb, d, target.format("%d/%m")),
+    }
+}
+
+/// Render the reminder banner for the given due tasks.
+pub fn show_reminder_banner(due: &[&Task], today: NaiveDate, ui: &mut Ui) -> ReminderAction {
+    let mut action = ReminderAction::None;
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing = egui::vec2(6.0, 4.0);
+        ui.label(
+            RichText::new(format!("{}  Reminders", egui_phosphor::regular::BELL_RINGING))
+                .size(11.0)
+                .color(theme::slip_moderate())
+                .strong(),
+        );
+        for task in due {
+            let name = ui
+                .add(
+                    egui::Label::new(RichText::new(&task.name).size(11.0).color(theme::text_primary()).strong())
+                        .sense(egui::Sense::click()),
+                )
+                .on_hover_text("Select and scroll to this task");
+            if name.clicked() {
+                action = ReminderAction::Select(task.id);
+            }
+            ui.label(RichText::new(reminder_text(task, today)).size(11.0).color(theme::text_secondary()));
+            if ui
+                .add(
+                    egui::Button::new(RichText::new(egui_phosphor::regular::X).size(9.0).color(theme::text_dim()))
+                        .frame(false),
+                )
+                .on_hover_text("Dismiss until tomorrow")
+                .clicked()
+            {
+                action = ReminderAction::Dismiss(task.id);
+            }
+            ui.label(RichText::new("·").color(theme::text_dim()));
+        }
+    });
+    action
+}
@@ -0,0 +1,131 @@
+use crate::model::resource::daily_load;
+use crate::model::Project;
+use crate::ui::theme;
+use egui::{Context, Pos2, Rect, RichText, Sense, Stroke, Vec2};
+
+/// Height of one resource's histogram strip.
+const STRIP_HEIGHT: f32 = 44.0;
+/// Load drawn at the top of a strip; higher loads are clipped.
+const MAX_SCALE: f32 = 2.0;
+
+/// Render the "Resource Load" window: one strip per resource with a bar per
+/// day showing summed allocation units, the resource's capacity (FTE on the
+/// days it works) as a stepped line, and bars red above capacity.
+pub fn show_resource_histogram(project: &Project, open: &mut bool, ctx: &Context) {
+    egui::Window::new(RichText::new("Resource Load").strong().size(14.0))
+        .open(open)
+        .resizable(true)
+        .collapsible(true)
+        .default_size([620.0, 260.0])
+        .show(ctx, |ui| {
+            let (Some(from), Some(to)) = (
+                project.tasks.iter().map(|t| t.start).min(),
+                project.tasks.iter().map(|t| t.end).max(),
+            ) else {
+                ui.label(RichText::new("No tasks").color(theme::text_dim()));
+                return;
+            };
+            if project.resources.is_empty() {
+                ui.label(
+                    RichText::new("No resources yet — add them via Edit → Resources…")
+                        .color(theme::text_dim()),
+                );
+                return;
+            }
+            let days = ((to - from).num_days().max(1)) as usize;
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for resource in &project.resources {
+                    let load = daily_load(&project.tasks, resource.id, from, days);
+                    let capacity: Vec<f32> = (0..days)
+                        .map(|i| resource.capacity(&project.calendar, from + chrono::Duration::days(i as i64)))
+                        .collect();
+                    let over = |i: usize| capacity[i] > 0.0 && load[i] > capacity[i] + f32::EPSILON;
+                    let peak = load
+                        .iter()
+                        .zip(&capacity)
+                        .filter(|(_, &cap)| cap > 0.0)
+                        .map(|(&units, _)| units)
+                        .fold(0.0, f32::max);
+                    let over_days = (0..days).filter(|&i| over(i)).count();
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(&resource.name).strong().size(11.5));
+                        ui.label(
+                            RichText::new(format!("{:.2} FTE", resource.fte))
+                                .size(10.0)
+                                .color(theme::text_dim()),
+                        );
+                        let peak_color = if over_days > 0 { theme::slip_major() } else { theme::text_dim() };
+                        let summary = if over_days > 0 {
+                            format!("peak {:.0}% · over capacity on {} day(s)", peak * 100.0, over_days)
+                        } else {
+                            format!("peak {:.0}%", peak * 100.0)
+                        };
+                        ui.label(RichText::new(summary).size(10.0).color(peak_color));
+                    });
+
+                    let (rect, response) = ui.allocate_exact_size(
+                        Vec2::new(ui.available_width(), STRIP_HEIGHT),
+                        Sense::hover(),
+                    );
+                    let painter = ui.painter_at(rect);
+                    painter.rect_filled(rect, 2.0, theme::bg_dark());
+                    let day_w = rect.width() / days as f32;
+                    let y_for = |units: f32| rect.bottom() - (units / MAX_SCALE).min(1.0) * rect.height();
+
+                    let mut mesh = egui::Mesh::default();
+                    for (i, &units) in load.iter().enumerate() {
+                        let x0 = rect.left() + i as f32 * day_w;
+                        if capacity[i] <= 0.0 {
+                            mesh.add_colored_rect(
+                                Rect::from_min_max(Pos2::new(x0, rect.top()), Pos2::new(x0 + day_w, rect.bottom())),
+                                theme::weekend_shade(),
+                            );
+                        }
+                        if units <= 0.0 {
+                            continue;
+                        }
+                        let color = if over(i) {
+                            theme::slip_major()
+                        } else if capacity[i] <= 0.0 {
+                            theme::text_dim().gamma_multiply(0.5)
+                        } else {
+                            theme::accent()
+                        };
+                        mesh.add_colored_rect(
+                            Rect::from_min_max(
+                                Pos2::new(x0, y_for(units)),
+                                Pos2::new(x0 + (day_w - 1.0).max(1.0), rect.bottom()),
+                            ),
+                            color,
+                        );
+                    }
+                    painter.add(egui::Shape::mesh(mesh));
+
+                    // Capacity line, stepping down on non-working days
+                    let mut points = Vec::with_capacity(days * 2);
+                    for (i, &cap) in capacity.iter().enumerate() {
+                        let x0 = rect.left() + i as f32 * day_w;
+                        points.push(Pos2::new(x0, y_for(cap)));
+                        points.push(Pos2::new(x0 + day_w, y_for(cap)));
+                    }
+                    painter.add(egui::Shape::line(
+                        points,
+                        Stroke::new(1.5, theme::text_secondary().gamma_multiply(0.8)),
+                    ));
+
+                    if let Some(pos) = response.hover_pos() {
+                        let i = (((pos.x - rect.left()) / day_w) as usize).min(days - 1);
+                        let date = from + chrono::Duration::days(i as i64);
+                        response.on_hover_text(format!(
+                            "{}: {:.0}% of {:.0}% capacity",
+                            date.format("%a %d/%m/%Y"),
+                            load[i] * 100.0,
+                            capacity[i] * 100.0
+                        ));
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+        });
+}
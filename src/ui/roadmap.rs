@@ -0,0 +1,166 @@
+use crate::app::GanttApp;
+use crate::model::lanes::{bucket_span, swimlanes};
+use crate::model::view::{LaneGroup, RoadmapBucket};
+use crate::ui::theme;
+use chrono::NaiveDate;
+use egui::{Align2, Context, FontId, Pos2, Rect, RichText, Rounding, Sense, Stroke, Vec2};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Height of one row of chips.
+const CHIP_ROW: f32 = 24.0;
+/// Width of the lane-title column.
+const TITLE_WIDTH: f32 = 140.0;
+/// Narrowest bucket column before the view scrolls sideways instead.
+const MIN_COLUMN: f32 = 90.0;
+const HEADER_HEIGHT: f32 = 22.0;
+
+/// Render the "Roadmap" window: the same lanes as the swimlane view, but
+/// with tasks drawn as rounded chips snapped to whole quarters or months
+/// instead of a day scale, for high-level stakeholder communication.
+pub fn show_roadmap(app: &mut GanttApp, ctx: &Context) {
+    let settings = app.theme_manager.settings();
+    let (mut group, mut bucket) = (settings.lane_group, settings.roadmap_bucket);
+    let filter = app.filter_state();
+    let lanes = swimlanes(&app.project, group, true, |t| bucket_span(t, bucket), |t| filter.matches(t));
+    let mut open = true;
+    let mut jump: Option<Uuid> = None;
+    egui::Window::new(RichText::new("Roadmap").strong().size(14.0))
+        .open(&mut open)
+        .resizable(true)
+        .collapsible(true)
+        .default_size([760.0, 380.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("roadmap-group")
+                    .selected_text(format!("Lanes by {}", group.label()))
+                    .show_ui(ui, |ui| {
+                        for &g in LaneGroup::all() {
+                            ui.selectable_value(&mut group, g, g.label());
+                        }
+                    });
+                for &b in RoadmapBucket::all() {
+                    ui.selectable_value(&mut bucket, b, b.label());
+                }
+            });
+            let tasks: HashMap<Uuid, &crate::model::Task> = app.project.tasks.iter().map(|t| (t.id, t)).collect();
+            let spans: Vec<(NaiveDate, NaiveDate)> = lanes
+                .iter()
+                .flat_map(|l| l.rows.iter().flatten())
+                .filter_map(|id| tasks.get(id))
+                .map(|t| bucket_span(t, bucket))
+                .collect();
+            let (Some(from), Some(to)) = (spans.iter().map(|s| s.0).min(), spans.iter().map(|s| s.1).max()) else {
+                ui.label(RichText::new("No tasks").color(theme::text_dim()));
+                return;
+            };
+            let mut columns = vec![from];
+            while let Some(&last) = columns.last().filter(|&&d| d < to) {
+                columns.push(bucket.next(last));
+            }
+            columns.pop();
+            let column_w = ((ui.available_width() - TITLE_WIDTH) / columns.len() as f32).max(MIN_COLUMN);
+            let width = TITLE_WIDTH + column_w * columns.len() as f32;
+            let column_of = |date: NaiveDate| columns.iter().position(|&c| c >= date).unwrap_or(columns.len());
+            let font = FontId::proportional(11.0);
+
+            egui::ScrollArea::both().auto_shrink([false, true]).show(ui, |ui| {
+                let body_h: f32 = lanes.iter().map(|l| l.rows.len().max(1) as f32 * CHIP_ROW + 8.0).sum();
+                let (rect, _) = ui.allocate_exact_size(Vec2::new(width, HEADER_HEIGHT + body_h), Sense::hover());
+                let painter = ui.painter_at(rect);
+                let left = rect.left() + TITLE_WIDTH;
+                let x_of = |col: usize| left + col as f32 * column_w;
+
+                painter.rect_filled(
+                    Rect::from_min_size(rect.min, Vec2::new(width, HEADER_HEIGHT)),
+                    0.0,
+                    theme::bg_header(),
+                );
+                for (i, &start) in columns.iter().enumerate() {
+                    if i % 2 == 1 {
+                        painter.rect_filled(
+                            Rect::from_min_max(
+                                Pos2::new(x_of(i), rect.top() + HEADER_HEIGHT),
+                                Pos2::new(x_of(i + 1), rect.bottom()),
+                            ),
+                            0.0,
+                            theme::bg_row_even(),
+                        );
+                    }
+                    painter.line_segment(
+                        [Pos2::new(x_of(i), rect.top()), Pos2::new(x_of(i), rect.bottom())],
+                        Stroke::new(1.0, theme::grid_line()),
+                    );
+                    painter.text(
+                        Pos2::new(x_of(i) + column_w / 2.0, rect.top() + HEADER_HEIGHT / 2.0),
+                        Align2::CENTER_CENTER,
+                        bucket.title(start),
+                        font.clone(),
+                        theme::text_secondary(),
+                    );
+                }
+
+                let mut y = rect.top() + HEADER_HEIGHT;
+                for lane in &lanes {
+                    let lane_h = lane.rows.len().max(1) as f32 * CHIP_ROW + 8.0;
+                    let title_rect = Rect::from_min_size(Pos2::new(rect.left(), y), Vec2::new(TITLE_WIDTH - 6.0, lane_h));
+                    painter.with_clip_rect(title_rect).text(
+                        Pos2::new(rect.left() + 4.0, y + 4.0 + CHIP_ROW / 2.0),
+                        Align2::LEFT_CENTER,
+                        &lane.title,
+                        FontId::proportional(12.0),
+                        theme::text_primary(),
+                    );
+                    for (r, row) in lane.rows.iter().enumerate() {
+                        let row_y = y + 4.0 + r as f32 * CHIP_ROW;
+                        for task in row.iter().filter_map(|id| tasks.get(id)) {
+                            let (start, end) = bucket_span(task, bucket);
+                            let chip = Rect::from_min_max(
+                                Pos2::new(x_of(column_of(start)) + 4.0, row_y + 2.0),
+                                Pos2::new(x_of(column_of(end)) - 4.0, row_y + CHIP_ROW - 2.0),
+                            );
+                            let response = ui
+                                .interact(chip, ui.id().with(("roadmap-chip", &lane.title, task.id)), Sense::click())
+                                .on_hover_text(format!(
+                                    "{}\n{} – {}",
+                                    task.name,
+                                    task.start.format("%d/%m/%Y"),
+                                    task.end.format("%d/%m/%Y")
+                                ));
+                            let rounding = Rounding::same(chip.height() / 2.0);
+                            painter.rect_filled(chip, rounding, task.color);
+                            if app.selected_task == Some(task.id) || response.hovered() {
+                                painter.rect_stroke(chip, rounding, Stroke::new(1.5, theme::accent()));
+                            }
+                            painter.with_clip_rect(chip.shrink(4.0)).text(
+                                Pos2::new(chip.left() + chip.height() / 2.0, chip.center().y),
+                                Align2::LEFT_CENTER,
+                                &task.name,
+                                font.clone(),
+                                theme::text_on(task.color),
+                            );
+                            if response.clicked() {
+                                jump = Some(task.id);
+                            }
+                        }
+                    }
+                    y += lane_h;
+                    painter.line_segment(
+                        [Pos2::new(rect.left(), y), Pos2::new(rect.right(), y)],
+                        Stroke::new(1.0, theme::border_subtle()),
+                    );
+                }
+            });
+        });
+    if (group, bucket) != (settings.lane_group, settings.roadmap_bucket) {
+        app.theme_manager.update_settings(|s| {
+            s.lane_group = group;
+            s.roadmap_bucket = bucket;
+        });
+    }
+    if let Some(id) = jump {
+        app.selected_task = Some(id);
+        app.scroll_to_task(id);
+    }
+    app.show_roadmap = open;
+}
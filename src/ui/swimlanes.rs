@@ -0,0 +1,157 @@
+use crate::app::GanttApp;
+use crate::model::lanes::{span, swimlanes};
+use crate::model::view::LaneGroup;
+use crate::ui::theme;
+use chrono::{Datelike, NaiveDate};
+use egui::{Align2, Color32, Context, FontId, Pos2, Rect, RichText, Sense, Stroke, Vec2};
+use uuid::Uuid;
+
+/// Height of one sub-row in a lane.
+const ROW_HEIGHT: f32 = 20.0;
+/// Height of the month strip above the lanes.
+const SCALE_HEIGHT: f32 = 18.0;
+/// Narrowest day width before the view scrolls sideways instead.
+const MIN_DAY_WIDTH: f32 = 2.0;
+
+fn first_of_next_month(date: NaiveDate) -> NaiveDate {
+    let (y, m) = if date.month() == 12 { (date.year() + 1, 1) } else { (date.year(), date.month() + 1) };
+    NaiveDate::from_ymd_opt(y, m, 1).unwrap_or(date)
+}
+
+/// Render the "Swimlanes" window: leaf tasks grouped into lanes by phase,
+/// assignee or priority, with tasks that don't overlap sharing a sub-row so
+/// the whole plan fits on a compact roadmap. Clicking a bar selects the task.
+pub fn show_swimlanes(app: &mut GanttApp, ctx: &Context) {
+    let settings = app.theme_manager.settings();
+    let (mut group, mut stack) = (settings.lane_group, settings.stack_lanes);
+    let filter = app.filter_state();
+    let lanes = swimlanes(&app.project, group, stack, span, |t| filter.matches(t));
+    let mut open = true;
+    let mut jump: Option<Uuid> = None;
+    egui::Window::new(RichText::new("Swimlanes").strong().size(14.0))
+        .open(&mut open)
+        .resizable(true)
+        .collapsible(true)
+        .default_size([720.0, 360.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("swimlane-group")
+                    .selected_text(format!("Group by {}", group.label()))
+                    .show_ui(ui, |ui| {
+                        for &g in LaneGroup::all() {
+                            ui.selectable_value(&mut group, g, g.label());
+                        }
+                    });
+                ui.checkbox(&mut stack, "Stack overlapping tasks")
+                    .on_hover_text("Tasks in a lane that don't overlap share a row");
+                let rows: usize = lanes.iter().map(|l| l.rows.len()).sum();
+                ui.label(
+                    RichText::new(format!("{} lane(s) · {} row(s)", lanes.len(), rows))
+                        .size(10.0)
+                        .color(theme::text_dim()),
+                );
+            });
+            let tasks: std::collections::HashMap<Uuid, &crate::model::Task> =
+                app.project.tasks.iter().map(|t| (t.id, t)).collect();
+            let (Some(from), Some(to)) = (
+                lanes.iter().flat_map(|l| l.rows.iter().flatten()).filter_map(|id| tasks.get(id)).map(|t| span(t).0).min(),
+                lanes.iter().flat_map(|l| l.rows.iter().flatten()).filter_map(|id| tasks.get(id)).map(|t| span(t).1).max(),
+            ) else {
+                ui.label(RichText::new("No tasks").color(theme::text_dim()));
+                return;
+            };
+            let days = (to - from).num_days().max(1) as f32;
+            let day_w = (ui.available_width() / days).max(MIN_DAY_WIDTH);
+            let width = day_w * days;
+            let font = FontId::proportional(10.5);
+
+            egui::ScrollArea::both().auto_shrink([false, true]).show(ui, |ui| {
+                let (scale, _) = ui.allocate_exact_size(Vec2::new(width, SCALE_HEIGHT), Sense::hover());
+                let x_of = |date: NaiveDate| scale.left() + (date - from).num_days() as f32 * day_w;
+                let painter = ui.painter();
+                let mut month = from.with_day(1).unwrap_or(from);
+                while month < to {
+                    let x = x_of(month.max(from));
+                    painter.line_segment(
+                        [Pos2::new(x, scale.top()), Pos2::new(x, scale.bottom())],
+                        Stroke::new(1.0, theme::grid_line()),
+                    );
+                    if day_w * 28.0 > 40.0 {
+                        painter.text(
+                            Pos2::new(x + 3.0, scale.center().y),
+                            Align2::LEFT_CENTER,
+                            month.format("%b %Y").to_string(),
+                            font.clone(),
+                            theme::text_secondary(),
+                        );
+                    }
+                    month = first_of_next_month(month);
+                }
+
+                for lane in &lanes {
+                    ui.add_space(4.0);
+                    ui.label(RichText::new(&lane.title).strong().size(11.5));
+                    let (rect, _) = ui.allocate_exact_size(
+                        Vec2::new(width, lane.rows.len() as f32 * ROW_HEIGHT),
+                        Sense::hover(),
+                    );
+                    ui.painter().rect_filled(rect, 2.0, theme::bg_dark());
+                    for (r, row) in lane.rows.iter().enumerate() {
+                        let y = rect.top() + r as f32 * ROW_HEIGHT;
+                        let starts: Vec<f32> = row.iter().filter_map(|id| tasks.get(id)).map(|t| x_of(span(t).0)).collect();
+                        for task in row.iter().filter_map(|id| tasks.get(id)) {
+                            let (start, end) = span(task);
+                            let bar = Rect::from_min_max(
+                                Pos2::new(x_of(start), y + 2.0),
+                                Pos2::new(x_of(end).max(x_of(start) + 4.0), y + ROW_HEIGHT - 2.0),
+                            );
+                            let response = ui
+                                .interact(bar, ui.id().with(("swimlane", &lane.title, task.id)), Sense::click())
+                                .on_hover_text(format!(
+                                    "{}\n{} – {}",
+                                    task.name,
+                                    task.start.format("%d/%m/%Y"),
+                                    task.end.format("%d/%m/%Y")
+                                ));
+                            let painter = ui.painter_at(rect);
+                            painter.rect_filled(bar, theme::bar_rounding(), task.color);
+                            if app.selected_task == Some(task.id) || response.hovered() {
+                                painter.rect_stroke(bar, theme::bar_rounding(), Stroke::new(1.5, theme::accent()));
+                            }
+                            let galley = painter.layout_no_wrap(task.name.clone(), font.clone(), theme::text_on(task.color));
+                            if galley.size().x + 8.0 <= bar.width() {
+                                painter.galley(Pos2::new(bar.left() + 4.0, bar.center().y - galley.size().y / 2.0), galley, Color32::TRANSPARENT);
+                            } else {
+                                // Beside the bar, cut short before the next bar in the row.
+                                let next = starts.iter().copied().filter(|x| *x >= bar.right()).fold(rect.right(), f32::min);
+                                let room = Rect::from_min_max(Pos2::new(bar.right() + 4.0, bar.top()), Pos2::new(next - 4.0, bar.bottom()));
+                                if room.width() > 12.0 {
+                                    painter.with_clip_rect(room).text(
+                                        Pos2::new(room.left(), bar.center().y),
+                                        Align2::LEFT_CENTER,
+                                        &task.name,
+                                        font.clone(),
+                                        theme::text_secondary(),
+                                    );
+                                }
+                            }
+                            if response.clicked() {
+                                jump = Some(task.id);
+                            }
+                        }
+                    }
+                }
+            });
+        });
+    if (group, stack) != (settings.lane_group, settings.stack_lanes) {
+        app.theme_manager.update_settings(|s| {
+            s.lane_group = group;
+            s.stack_lanes = stack;
+        });
+    }
+    if let Some(id) = jump {
+        app.selected_task = Some(id);
+        app.scroll_to_task(id);
+    }
+    app.show_swimlanes = open;
+}
@@ -1,5 +1,6 @@
-use crate::model::Task;
-use crate::model::task::{Dependency, DependencyKind, TaskPriority};
+use crate::model::audit::{slips, AuditEntry};
+use crate::model::{Assignment, Project, Resource, Task};
+use crate::model::task::{BarPattern, Dependency, DependencyKind, Reminder, ReminderAnchor, TaskPriority, NOTE_KEY};
 use crate::ui::theme;
 use egui::{Color32, Id, RichText, Ui};
 use uuid::Uuid;
@@ -9,8 +10,22 @@ pub enum EditorAction {
     None,
     Changed,
     RemoveDependency(Uuid, Uuid),
+    /// Replace the link between the same two tasks with this one (new kind or lag).
+    UpdateDependency(Dependency),
     AddSubtask(Uuid),
     AddDependency(Dependency),
+    ConvertToMilestone(Uuid),
+    ConvertToTask(Uuid),
+    /// Create a resource with this name and assign it to the edited task.
+    AssignNewResource(String),
+    /// Pull the status of the task's linked issue.
+    RefreshLink(Uuid),
+    /// Pin or unpin the task on the watch strip.
+    ToggleWatch(Uuid),
+    /// Select another task (e.g. a predecessor) and scroll to it.
+    Navigate(Uuid),
+    /// Lock the editor to this task, or let it follow the selection again.
+    TogglePin(Uuid),
 }
 
 /// Persistent state for the "add dependency" picker.
@@ -20,24 +35,144 @@ struct DepPickerState {
     kind: DependencyKind,
 }
 
-/// Short label for a dependency from this task's perspective.
-fn dep_kind_label(kind: DependencyKind, is_outgoing: bool) -> String {
-    let arrow = if is_outgoing {
-        egui_phosphor::regular::ARROW_RIGHT
-    } else {
-        egui_phosphor::regular::ARROW_LEFT
-    };
-    format!("[{}] {}", kind.short_label(), arrow)
+/// One row per link: the other task (click to go there), then inline kind
+/// and lag controls and a remove button. `incoming` lists predecessors.
+fn dependency_rows(
+    ui: &mut Ui,
+    links: &[&Dependency],
+    incoming: bool,
+    all_tasks: &[Task],
+    action: &mut EditorAction,
+) {
+    for dep in links {
+        let other_id = if incoming { dep.from_task } else { dep.to_task };
+        let other_name = all_tasks
+            .iter()
+            .find(|t| t.id == other_id)
+            .map(|t| t.name.clone())
+            .unwrap_or_else(|| "?".to_string());
+
+        ui.horizontal(|ui| {
+            let name = ui
+                .add(
+                    egui::Label::new(RichText::new(&other_name).size(11.0).color(theme::text_secondary()))
+                        .truncate()
+                        .sense(egui::Sense::click()),
+                )
+                .on_hover_text("Go to this task");
+            if name.clicked() {
+                *action = EditorAction::Navigate(other_id);
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let del = ui.add(
+                    egui::Button::new(
+                        RichText::new(egui_phosphor::regular::X).size(9.0).color(theme::text_dim()),
+                    )
+                    .frame(false),
+                );
+                if del.on_hover_text("Remove dependency").clicked() {
+                    *action = EditorAction::RemoveDependency(dep.from_task, dep.to_task);
+                }
+
+                // Inline lag and kind; edits replace the link in place.
+                let mut edited = (*dep).clone();
+                let details_changed = dependency_details(ui, &mut edited);
+                let lag = ui
+                    .add(
+                        egui::DragValue::new(&mut edited.lag_days)
+                            .range(-365..=365)
+                            .speed(0.1)
+                            .suffix("d"),
+                    )
+                    .on_hover_text("Lag in days (negative for lead time)");
+                egui::ComboBox::from_id_salt(("dep-kind", dep.from_task, dep.to_task))
+                    .selected_text(RichText::new(edited.kind.short_label()).size(11.0))
+                    .width(40.0)
+                    .show_ui(ui, |ui| {
+                        for k in DependencyKind::all() {
+                            ui.selectable_value(&mut edited.kind, *k, k.short_label())
+                                .on_hover_text(k.description());
+                        }
+                    });
+                if lag.changed() || edited.kind != dep.kind || details_changed {
+                    *action = EditorAction::UpdateDependency(edited);
+                }
+            });
+        });
+    }
+}
+
+/// Note button for a link, opening its note and key/value details.
+/// Returns whether anything changed.
+fn dependency_details(ui: &mut Ui, dep: &mut Dependency) -> bool {
+    let mut changed = false;
+    let icon = if dep.metadata.is_empty() { egui_phosphor::regular::NOTE_BLANK } else { egui_phosphor::regular::NOTE };
+    let hover = dep.note().map(str::to_string).unwrap_or_else(|| "Add a note or details".to_string());
+    let new_id = Id::new(("dep-new-detail", dep.from_task, dep.to_task));
+    ui.menu_button(RichText::new(icon).size(11.0).color(theme::text_dim()), |ui| {
+        ui.set_min_width(220.0);
+        ui.label(RichText::new("Note").size(10.0).color(theme::text_dim()).strong());
+        let mut note = dep.metadata.get(NOTE_KEY).cloned().unwrap_or_default();
+        let resp = ui.add(
+            egui::TextEdit::singleline(&mut note)
+                .hint_text("e.g. waiting on vendor PO")
+                .desired_width(f32::INFINITY),
+        );
+        if resp.changed() {
+            if note.trim().is_empty() {
+                dep.metadata.remove(NOTE_KEY);
+            } else {
+                dep.metadata.insert(NOTE_KEY.to_string(), note);
+            }
+            changed = true;
+        }
+        ui.add_space(4.0);
+        ui.label(RichText::new("Details").size(10.0).color(theme::text_dim()).strong());
+        let keys: Vec<String> = dep.details().map(|(k, _)| k.clone()).collect();
+        for key in keys {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&key).size(11.0).color(theme::text_secondary()));
+                if let Some(value) = dep.metadata.get_mut(&key) {
+                    changed |= ui.add(egui::TextEdit::singleline(value).desired_width(110.0)).changed();
+                }
+                if ui.small_button(egui_phosphor::regular::X).on_hover_text("Remove").clicked() {
+                    dep.metadata.remove(&key);
+                    changed = true;
+                }
+            });
+        }
+        let (mut key, mut value): (String, String) = ui.data(|d| d.get_temp(new_id)).unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut key).hint_text("key").desired_width(70.0));
+            ui.add(egui::TextEdit::singleline(&mut value).hint_text("value").desired_width(90.0));
+            let key_ok = !key.trim().is_empty() && key.trim() != NOTE_KEY;
+            if ui.add_enabled(key_ok, egui::Button::new("Add").small()).clicked() {
+                dep.metadata.insert(key.trim().to_string(), value.trim().to_string());
+                changed = true;
+                key.clear();
+                value.clear();
+            }
+        });
+        ui.data_mut(|d| d.insert_temp(new_id, (key, value)));
+    })
+    .response
+    .on_hover_text(hover);
+    changed
+}
+
+/// Temp-data key asking the editor to focus its name field.
+const FOCUS_NAME: &str = "task-editor-focus-name";
+
+/// Focus the editor's name field the next time it is drawn.
+pub fn request_name_focus(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(Id::new(FOCUS_NAME), true));
 }
 
 /// Render an inline task editor for the selected task.
 /// Also shows dependencies involving this task.
-pub fn show_task_editor(
-    task: &mut Task,
-    all_tasks: &[Task],
-    dependencies: &[Dependency],
-    ui: &mut Ui,
-) -> EditorAction {
+pub fn show_task_editor(task: &mut Task, project: &Project, watched: bool, pinned: bool, ui: &mut Ui) -> EditorAction {
+    let (all_tasks, dependencies) = (&project.tasks[..], &project.dependencies[..]);
+    let (calendar, resources) = (&project.calendar, &project.resources[..]);
     let mut action = EditorAction::None;
     let task_id = task.id;
 
@@ -45,11 +180,39 @@ pub fn show_task_editor(
     ui.add_space(6.0);
     ui.horizontal(|ui| {
         ui.label(
-            RichText::new("Edit Task")
+            RichText::new(if pinned { "Edit Task (locked)" } else { "Edit Task" })
                 .strong()
                 .size(13.0)
                 .color(theme::text_primary()),
         );
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let (icon, color, hint) = if pinned {
+                (egui_phosphor::regular::LOCK_SIMPLE, theme::accent(), "Unlock: follow the selection again")
+            } else {
+                (
+                    egui_phosphor::regular::LOCK_SIMPLE_OPEN,
+                    theme::text_dim(),
+                    "Lock the editor to this task while you select others",
+                )
+            };
+            let resp = ui
+                .add(egui::Button::new(RichText::new(icon).color(color)).frame(false))
+                .on_hover_text(hint);
+            if resp.clicked() {
+                action = EditorAction::TogglePin(task_id);
+            }
+            let (icon, color, hint) = if watched {
+                (egui_phosphor::regular::PUSH_PIN, theme::accent(), "Remove from the watch strip")
+            } else {
+                (egui_phosphor::regular::PUSH_PIN, theme::text_dim(), "Pin to the watch strip above the chart")
+            };
+            let resp = ui
+                .add(egui::Button::new(RichText::new(icon).color(color)).frame(false))
+                .on_hover_text(hint);
+            if resp.clicked() {
+                action = EditorAction::ToggleWatch(task_id);
+            }
+        });
     });
     ui.add_space(4.0);
 
@@ -80,6 +243,9 @@ pub fn show_task_editor(
                 .font(egui::FontId::proportional(12.0))
                 .text_color(theme::text_primary()),
         );
+        if ui.ctx().data_mut(|d| d.remove_temp::<bool>(Id::new(FOCUS_NAME))).unwrap_or(false) {
+            name_edit.request_focus();
+        }
         if name_edit.changed() {
             action = EditorAction::Changed;
         }
@@ -169,9 +335,53 @@ pub fn show_task_editor(
                 ui.label(RichText::new("(auto)").size(9.0).color(theme::text_dim()));
             });
             ui.add_space(2.0);
-            // Progress: read-only for parent
-            ui.label(RichText::new("Progress").size(10.0).color(theme::text_dim()).strong());
-            ui.label(RichText::new(format!("{:.0}%  (auto-calculated)", task.progress * 100.0)).size(11.0).color(theme::text_secondary()));
+            // Progress: rolled up from children unless manually overridden
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Progress").size(10.0).color(theme::text_dim()).strong());
+                if task.progress_override.is_some() {
+                    ui.label(
+                        RichText::new(" manual ")
+                            .size(9.0)
+                            .color(Color32::WHITE)
+                            .background_color(theme::slip_moderate()),
+                    );
+                }
+            });
+            if let Some(mut value) = task.progress_override {
+                ui.horizontal(|ui| {
+                    let slider = egui::Slider::new(&mut value, 0.0..=1.0)
+                        .custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
+                        .custom_parser(|s| {
+                            let s = s.trim().trim_end_matches('%');
+                            s.parse::<f64>().ok().map(|v| v / 100.0)
+                        });
+                    let resp = ui.add_sized([ui.available_width() - 28.0, 20.0], slider);
+                    if resp.changed() {
+                        task.progress_override = Some(value);
+                        action = EditorAction::Changed;
+                    }
+                    if ui
+                        .small_button(egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE)
+                        .on_hover_text("Revert to the progress rolled up from subtasks")
+                        .clicked()
+                    {
+                        task.progress_override = None;
+                        action = EditorAction::Changed;
+                    }
+                });
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("{:.0}%  (auto-calculated)", task.progress * 100.0)).size(11.0).color(theme::text_secondary()));
+                    if ui
+                        .small_button(egui_phosphor::regular::PENCIL_SIMPLE)
+                        .on_hover_text("Override with a judged value")
+                        .clicked()
+                    {
+                        task.progress_override = Some(task.progress);
+                        action = EditorAction::Changed;
+                    }
+                });
+            }
             ui.add_space(4.0);
             // Add subtask button
             let btn = egui::Button::new(RichText::new(egui_phosphor::regular::PLUS.to_string() + "  Add Subtask").color(Color32::WHITE).size(12.0))
@@ -198,6 +408,7 @@ pub fn show_task_editor(
                         if task.start > task.end {
                             task.end = task.start;
                         }
+                        task.apply_effort(resources);
                         action = EditorAction::Changed;
                     }
                 });
@@ -219,10 +430,41 @@ pub fn show_task_editor(
                         if task.end < task.start {
                             task.start = task.end;
                         }
+                        // Changing the finish of an effort-driven task re-derives its work.
+                        let units = task.delivered_units(resources);
+                        if task.work_days.is_some() && units > 0.0 {
+                            task.work_days = Some((task.end - task.start).num_days() as f32 * units);
+                        }
                         action = EditorAction::Changed;
                     }
                 });
             });
+            if let Some(earliest) = crate::model::impact::earliest_start(all_tasks, dependencies, calendar, task) {
+                ui.horizontal(|ui| {
+                    let (color, hint) = if task.start < earliest {
+                        (theme::slip_minor(), "Starts before its links allow")
+                    } else {
+                        (theme::text_dim(), "The earliest start its predecessors and lags allow")
+                    };
+                    ui.label(
+                        RichText::new(format!("Earliest start {}", earliest.format("%a %d %b %Y")))
+                            .size(10.0)
+                            .color(color),
+                    )
+                    .on_hover_text(hint);
+                    if task.start != earliest
+                        && ui
+                            .small_button("Snap to Earliest")
+                            .on_hover_text("Move the task to its earliest start, keeping its duration")
+                            .clicked()
+                    {
+                        let duration = task.end - task.start;
+                        task.start = earliest;
+                        task.end = earliest + duration;
+                        action = EditorAction::Changed;
+                    }
+                });
+            }
         } else {
             // Milestone: single date
             ui.label(
@@ -231,7 +473,10 @@ pub fn show_task_editor(
                     .color(theme::text_dim())
                     .strong(),
             );
-            let resp = ui.add(
+            let has_predecessors = dependencies.iter().any(|d| d.to_task == task_id);
+            let auto = task.auto_date && has_predecessors;
+            let resp = ui.add_enabled(
+                !auto,
                 egui_extras::DatePickerButton::new(&mut task.start)
                     .id_salt("dp_milestone"),
             );
@@ -239,13 +484,36 @@ pub fn show_task_editor(
                 task.end = task.start;
                 action = EditorAction::Changed;
             }
+            let resp = ui
+                .checkbox(&mut task.auto_date, RichText::new("Track predecessors").size(11.0))
+                .on_hover_text("Keep this milestone on the latest finish of its predecessors");
+            if resp.changed() {
+                action = EditorAction::Changed;
+            }
+            if task.auto_date && !has_predecessors {
+                ui.label(
+                    RichText::new("Add a predecessor link to track its finish.")
+                        .size(10.0)
+                        .color(theme::text_dim()),
+                );
+            }
         }
 
         ui.add_space(2.0);
 
         // ── Progress ──────────────────────────────────────────────────
         // Only show editable slider for non-parent tasks (parents auto-calculate from children)
-        if !is_parent_task {
+        if task.is_milestone {
+            let mut achieved = task.is_achieved();
+            if ui
+                .checkbox(&mut achieved, RichText::new("Achieved").size(11.0))
+                .on_hover_text("Milestones are either reached or not; there is no partial progress")
+                .changed()
+            {
+                task.set_achieved(achieved);
+                action = EditorAction::Changed;
+            }
+        } else if !is_parent_task {
             ui.label(
                 RichText::new("Progress")
                     .size(10.0)
@@ -264,6 +532,31 @@ pub fn show_task_editor(
                     action = EditorAction::Changed;
                 }
             });
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Remaining").size(10.0).color(theme::text_dim()));
+                let mut clear = false;
+                if let Some(hours) = task.remaining_hours.as_mut() {
+                    let resp = ui
+                        .add(egui::DragValue::new(hours).range(0.0..=100_000.0).speed(0.5).suffix(" h"))
+                        .on_hover_text("Hours of work left; re-estimated from the progress when it changes");
+                    if resp.changed() {
+                        action = EditorAction::Changed;
+                    }
+                    clear = ui.small_button("Clear").on_hover_text("Stop forecasting this task's finish").clicked();
+                } else if ui
+                    .small_button("Estimate")
+                    .on_hover_text("Estimate the hours left from the progress, to forecast the finish")
+                    .clicked()
+                {
+                    // The app fills in the estimate from the plan.
+                    task.remaining_hours = Some(0.0);
+                    action = EditorAction::Changed;
+                }
+                if clear {
+                    task.remaining_hours = None;
+                    action = EditorAction::Changed;
+                }
+            });
         }
 
         ui.add_space(2.0);
@@ -288,6 +581,93 @@ pub fn show_task_editor(
 
         ui.add_space(2.0);
 
+        // ── Reminder ──────────────────────────────────────────────────
+        ui.horizontal(|ui| {
+            let mut enabled = task.reminder.is_some();
+            if ui
+                .checkbox(&mut enabled, RichText::new("Remind me").size(11.0))
+                .on_hover_text("Show a banner ahead of the task's start or finish")
+                .changed()
+            {
+                task.reminder = enabled.then(Reminder::default);
+                action = EditorAction::Changed;
+            }
+            if let Some(reminder) = &mut task.reminder {
+                if ui
+                    .add(egui::DragValue::new(&mut reminder.days_before).range(0..=365).suffix(" d"))
+                    .changed()
+                {
+                    action = EditorAction::Changed;
+                }
+                ui.label(RichText::new("before").size(11.0).color(theme::text_dim()));
+                egui::ComboBox::from_id_salt("reminder_anchor")
+                    .width(60.0)
+                    .selected_text(RichText::new(reminder.anchor.label()).size(11.0))
+                    .show_ui(ui, |ui| {
+                        for anchor in ReminderAnchor::all() {
+                            if ui.selectable_value(&mut reminder.anchor, *anchor, anchor.label()).changed() {
+                                action = EditorAction::Changed;
+                            }
+                        }
+                    });
+            }
+        });
+
+        ui.add_space(2.0);
+
+        // ── Linked issue ──────────────────────────────────────────────
+        ui.label(
+            RichText::new("Link")
+                .size(10.0)
+                .color(theme::text_dim())
+                .strong(),
+        );
+        ui.horizontal(|ui| {
+            let mut link = task.link.clone().unwrap_or_default();
+            let has_link = !link.trim().is_empty();
+            let button_space = if has_link { 56.0 } else { 0.0 };
+            let resp = ui.add_sized(
+                [ui.available_width() - button_space, 20.0],
+                egui::TextEdit::singleline(&mut link)
+                    .font(egui::FontId::proportional(11.0))
+                    .hint_text("GitHub issue or Jira ticket URL"),
+            );
+            if resp.changed() {
+                task.link = if link.trim().is_empty() { None } else { Some(link.trim().to_string()) };
+                action = EditorAction::Changed;
+            }
+            if has_link {
+                if ui
+                    .small_button(egui_phosphor::regular::ARROW_SQUARE_OUT)
+                    .on_hover_text("Open in browser")
+                    .clicked()
+                {
+                    let _ = open::that(link.trim());
+                }
+                if ui
+                    .small_button(egui_phosphor::regular::ARROWS_CLOCKWISE)
+                    .on_hover_text("Refresh: pull the linked item's status into progress")
+                    .clicked()
+                {
+                    action = EditorAction::RefreshLink(task_id);
+                }
+            }
+        });
+        if let Some(synced) = task.last_synced {
+            let status = task.link_status.as_deref().unwrap_or("unknown");
+            ui.label(
+                RichText::new(format!(
+                    "Status: {} · last synced {}",
+                    status,
+                    synced.with_timezone(&chrono::Local).format("%d/%m/%Y %H:%M")
+                ))
+                .size(10.0)
+                .color(theme::text_dim()),
+            );
+        }
+
+        ui.add_space(2.0);
+
         // ── Color ─────────────────────────────────────────────────────
         ui.label(
             RichText::new("Color")
@@ -322,69 +702,83 @@ pub fn show_task_editor(
             }
         });
 
-        ui.add_space(2.0);
-
-        // ── Milestone toggle ──────────────────────────────────────────
-        ui.horizontal(|ui| {
-            let mut is_milestone = task.is_milestone;
-            let resp = ui.checkbox(&mut is_milestone, "");
-            ui.label(
-                RichText::new("Milestone")
-                    .size(11.0)
-                    .color(theme::text_secondary()),
-            );
+        if !is_parent_task {
+            let resp = ui
+                .checkbox(&mut task.tentative, RichText::new("Tentative").size(11.0))
+                .on_hover_text("Pencilled-in work: drawn faded and hatched, and left out of rollups, costs and exports");
             if resp.changed() {
-                task.is_milestone = is_milestone;
-                if is_milestone {
-                    task.end = task.start;
-                }
                 action = EditorAction::Changed;
             }
-        });
+            let resp = ui
+                .checkbox(&mut task.external, RichText::new("External").size(11.0))
+                .on_hover_text("Owned by another team or vendor: greyed out and left out of resource load");
+            if resp.changed() {
+                action = EditorAction::Changed;
+            }
+        }
+        if !task.is_milestone {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Pattern").size(10.0).color(theme::text_dim()));
+                egui::ComboBox::from_id_salt("pattern_combo")
+                    .selected_text(RichText::new(task.pattern.label()).size(11.0))
+                    .show_ui(ui, |ui| {
+                        for p in BarPattern::all() {
+                            if ui.selectable_value(&mut task.pattern, *p, p.label()).changed() {
+                                action = EditorAction::Changed;
+                            }
+                        }
+                    });
+            });
+        }
 
-        ui.add_space(4.0);
+        ui.add_space(2.0);
 
-        // ── Dependencies ─────────────────────────────────────────────
-        let task_deps: Vec<&Dependency> = dependencies
-            .iter()
-            .filter(|d| d.from_task == task_id || d.to_task == task_id)
-            .collect();
+        // ── Task / milestone conversion ───────────────────────────────
+        if !is_parent_task {
+            let (icon, label) = if task.is_milestone {
+                (egui_phosphor::regular::ARROWS_LEFT_RIGHT, "Convert to Task…")
+            } else {
+                (egui_phosphor::regular::DIAMOND, "Convert to Milestone")
+            };
+            let resp = ui
+                .add(egui::Button::new(RichText::new(format!("{}  {}", icon, label)).size(11.0)))
+                .on_hover_text(if task.is_milestone {
+                    "Give the milestone a duration, keeping its date as the finish"
+                } else {
+                    "Collapse the task onto its finish date, keeping its links"
+                });
+            if resp.clicked() {
+                action = if task.is_milestone {
+                    EditorAction::ConvertToTask(task_id)
+                } else {
+                    EditorAction::ConvertToMilestone(task_id)
+                };
+            }
+        }
 
-        ui.separator();
-        ui.add_space(2.0);
-        ui.label(
-            RichText::new("Dependencies")
-                .size(10.0)
-                .color(theme::text_dim())
-                .strong(),
-        );
-        ui.add_space(2.0);
+        ui.add_space(4.0);
 
-        if task_deps.is_empty() {
+        // ── Assignments ──────────────────────────────────────────────
+        if !is_parent_task && !task.is_milestone {
+            ui.separator();
+            ui.add_space(2.0);
             ui.label(
-                RichText::new("No dependencies yet")
-                    .size(9.5)
-                    .color(theme::text_dim()),
+                RichText::new("Assigned to")
+                    .size(10.0)
+                    .color(theme::text_dim())
+                    .strong(),
             );
-        } else {
-            for dep in &task_deps {
-                let is_outgoing = dep.from_task == task_id;
-                let other_id = if is_outgoing { dep.to_task } else { dep.from_task };
-                let other_name = all_tasks
+            ui.add_space(2.0);
+            let mut unassign: Option<Uuid> = None;
+            let mut units_changed = false;
+            for assignment in &mut task.assignments {
+                let name = resources
                     .iter()
-                    .find(|t| t.id == other_id)
-                    .map(|t| t.name.clone())
-                    .unwrap_or_else(|| "?".to_string());
-
-                let kind_lbl = dep_kind_label(dep.kind, is_outgoing);
-                let label = format!("{} {}", kind_lbl, other_name);
-
+                    .find(|r| r.id == assignment.resource_id)
+                    .map(|r| r.name.as_str())
+                    .unwrap_or("?");
                 ui.horizontal(|ui| {
-                    ui.label(
-                        RichText::new(&label)
-                            .size(11.0)
-                            .color(theme::text_secondary()),
-                    );
+                    ui.label(RichText::new(name).size(11.0).color(theme::text_secondary()));
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         let del = ui.add(
                             egui::Button::new(
@@ -392,20 +786,144 @@ pub fn show_task_editor(
                             )
                             .frame(false),
                         );
-                        if del.on_hover_text("Remove dependency").clicked() {
-                            action = EditorAction::RemoveDependency(dep.from_task, dep.to_task);
+                        if del.on_hover_text("Remove assignment").clicked() {
+                            unassign = Some(assignment.resource_id);
+                        }
+                        let mut percent = (assignment.units * 100.0).round();
+                        let resp = ui
+                            .add(
+                                egui::DragValue::new(&mut percent)
+                                    .range(5.0..=200.0)
+                                    .speed(5.0)
+                                    .suffix("%"),
+                            )
+                            .on_hover_text("Allocation units");
+                        if resp.changed() {
+                            assignment.units = percent / 100.0;
+                            units_changed = true;
                         }
                     });
                 });
             }
+            if let Some(rid) = unassign {
+                task.assignments.retain(|a| a.resource_id != rid);
+                units_changed = true;
+            }
+            if units_changed {
+                task.apply_effort(resources);
+                action = EditorAction::Changed;
+            }
+
+            // Effort-driven scheduling: duration = work / assigned units.
+            ui.horizontal(|ui| {
+                let mut effort_driven = task.work_days.is_some();
+                let resp = ui
+                    .checkbox(&mut effort_driven, RichText::new("Effort-driven").size(11.0))
+                    .on_hover_text("Derive the duration from total work and allocation units");
+                if resp.changed() {
+                    task.work_days = effort_driven.then(|| {
+                        let units = task.delivered_units(resources);
+                        let units = if units > 0.0 { units } else { 1.0 };
+                        (task.end - task.start).num_days() as f32 * units
+                    });
+                    action = EditorAction::Changed;
+                }
+                if let Some(work) = task.work_days.as_mut() {
+                    let resp = ui.add(
+                        egui::DragValue::new(work)
+                            .range(0.5..=10_000.0)
+                            .speed(0.5)
+                            .suffix(" person-days"),
+                    );
+                    if resp.changed() {
+                        task.apply_effort(resources);
+                        action = EditorAction::Changed;
+                    }
+                }
+            });
+
+            let available: Vec<&Resource> = resources
+                .iter()
+                .filter(|r| !task.assignments.iter().any(|a| a.resource_id == r.id))
+                .collect();
+            let new_name_id = Id::new(("new-resource-name", task_id));
+            let mut new_name: String = ui.ctx().data_mut(|d| d.get_temp(new_name_id).unwrap_or_default());
+            ui.horizontal(|ui| {
+                if !available.is_empty() {
+                    egui::ComboBox::from_id_salt("assign-resource")
+                        .selected_text(RichText::new("Assign…").size(11.0))
+                        .width(100.0)
+                        .show_ui(ui, |ui| {
+                            for r in &available {
+                                if ui.selectable_label(false, r.name.as_str()).clicked() {
+                                    task.assignments.push(Assignment::new(r.id));
+                                    task.apply_effort(resources);
+                                    action = EditorAction::Changed;
+                                }
+                            }
+                        });
+                }
+                let field_w = (ui.available_width() - 30.0).clamp(60.0, 160.0);
+                let resp = ui.add(
+                    egui::TextEdit::singleline(&mut new_name)
+                        .hint_text("New resource…")
+                        .desired_width(field_w),
+                );
+                let can_add = !new_name.trim().is_empty();
+                let btn = egui::Button::new(RichText::new("＋").size(13.0).color(Color32::WHITE))
+                    .fill(if can_add { theme::accent() } else { theme::bg_field() })
+                    .rounding(egui::Rounding::same(4.0));
+                let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if (ui.add_enabled(can_add, btn).clicked() || enter) && can_add {
+                    action = EditorAction::AssignNewResource(new_name.trim().to_string());
+                    new_name.clear();
+                }
+            });
+            ui.ctx().data_mut(|d| d.insert_temp(new_name_id, new_name));
+            ui.add_space(4.0);
+        }
+
+        // ── Dependencies ─────────────────────────────────────────────
+        let predecessors: Vec<&Dependency> = dependencies.iter().filter(|d| d.to_task == task_id).collect();
+        let successors: Vec<&Dependency> = dependencies.iter().filter(|d| d.from_task == task_id).collect();
+
+        ui.separator();
+        ui.add_space(2.0);
+        if predecessors.is_empty() && successors.is_empty() {
+            ui.label(
+                RichText::new("Dependencies")
+                    .size(10.0)
+                    .color(theme::text_dim())
+                    .strong(),
+            );
+            ui.add_space(2.0);
+            ui.label(
+                RichText::new("No dependencies yet")
+                    .size(9.5)
+                    .color(theme::text_dim()),
+            );
+        }
+        for (title, links, incoming) in [("Predecessors", &predecessors, true), ("Successors", &successors, false)] {
+            if links.is_empty() {
+                continue;
+            }
+            ui.label(
+                RichText::new(format!("{} ({})", title, links.len()))
+                    .size(10.0)
+                    .color(theme::text_dim())
+                    .strong(),
+            );
+            ui.add_space(2.0);
+            dependency_rows(ui, links, incoming, all_tasks, &mut action);
+            ui.add_space(2.0);
         }
 
         ui.add_space(4.0);
 
         // ── Add dependency picker ─────────────────────────────────────
-        // Candidates: if this task is a child, only siblings (same parent);
-        // otherwise all tasks except self, parent tasks (summary bars), and
-        // tasks already linked to this one.
+        // Candidates: any task in any phase except parent tasks (summary
+        // bars), tasks already linked to this one and links
+        // `Project::check_link` rejects.
         let already_linked: std::collections::HashSet<Uuid> = dependencies
             .iter()
             .filter(|d| d.from_task == task_id || d.to_task == task_id)
@@ -415,17 +933,11 @@ pub fn show_task_editor(
         let candidates: Vec<(Uuid, String)> = all_tasks
             .iter()
             .filter(|t| {
-                if t.id == task_id { return false; }
                 if already_linked.contains(&t.id) { return false; }
                 // Don't link to/from summary (parent) tasks
                 let t_is_parent = t.has_children(all_tasks);
                 if t_is_parent { return false; }
-                // If this task is a child, restrict to siblings (same parent)
-                if let Some(my_parent) = task.parent_id {
-                    return t.parent_id == Some(my_parent);
-                }
-                // Top-level task: only link to other top-level tasks
-                t.parent_id.is_none()
+                project.check_link(task_id, t.id).is_ok()
             })
             .map(|t| (t.id, t.name.clone()))
             .collect();
@@ -493,11 +1005,11 @@ pub fn show_task_editor(
                 let add_resp = ui.add_enabled(can_add, btn);
                 if add_resp.clicked() {
                     if let Some(target) = state.target_id {
-                        action = EditorAction::AddDependency(Dependency {
-                            from_task: task_id,
-                            to_task: target,
-                            kind: state.kind,
-                        });
+                        action = EditorAction::AddDependency(Dependency::new(
+                            task_id,
+                            target,
+                            state.kind,
+                        ));
                         state.target_id = None; // reset after adding
                     }
                 }
@@ -522,3 +1034,208 @@ pub fn show_task_editor(
 
     action
 }
+
+/// A change the bulk editor applies to every selected task.
+pub enum BulkEdit {
+    Priority(TaskPriority),
+    Color(Color32),
+    Assign(Uuid),
+    Unassign(Uuid),
+    /// Move by this many calendar days.
+    Shift(i64),
+    /// Leave multi-selection.
+    ClearSelection,
+}
+
+fn bulk_label(ui: &mut Ui, text: &str) {
+    ui.label(RichText::new(text).size(10.0).color(theme::text_dim()).strong());
+}
+
+/// Reduced editor shown when several tasks are selected: each control
+/// applies its value to all of them at once.
+pub fn show_bulk_editor(count: usize, resources: &[Resource], ui: &mut Ui) -> Option<BulkEdit> {
+    let mut edit = None;
+
+    ui.add_space(6.0);
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(format!("Edit {} Tasks", count))
+                .strong()
+                .size(13.0)
+                .color(theme::text_primary()),
+        );
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui
+                .add(egui::Button::new(RichText::new(egui_phosphor::regular::X).color(theme::text_dim())).frame(false))
+                .on_hover_text("Clear the selection")
+                .clicked()
+            {
+                edit = Some(BulkEdit::ClearSelection);
+            }
+        });
+    });
+    ui.add_space(4.0);
+
+    let frame = egui::Frame {
+        fill: theme::bg_dark(),
+        rounding: egui::Rounding::same(theme::widget_rounding_val()),
+        inner_margin: egui::Margin::same(theme::layout().editor_inner_margin),
+        outer_margin: egui::Margin::ZERO,
+        stroke: egui::Stroke::new(1.0, theme::border_subtle()),
+        shadow: egui::epaint::Shadow::NONE,
+    };
+
+    frame.show(ui, |ui| {
+        ui.spacing_mut().item_spacing.y = 6.0;
+        ui.visuals_mut().extreme_bg_color = theme::bg_field();
+
+        // ── Priority ──────────────────────────────────────────────────
+        bulk_label(ui, "Priority");
+        egui::ComboBox::from_id_salt("bulk_priority_combo")
+            .selected_text(RichText::new("Set priority…").size(11.0))
+            .width(ui.available_width())
+            .show_ui(ui, |ui| {
+                for p in TaskPriority::all() {
+                    if ui.selectable_label(false, format!("{} {}", p.icon(), p.label())).clicked() {
+                        edit = Some(BulkEdit::Priority(*p));
+                    }
+                }
+            });
+
+        // ── Color ─────────────────────────────────────────────────────
+        bulk_label(ui, "Color");
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing = egui::vec2(4.0, 4.0);
+            for color in theme::task_palette() {
+                let (rect, resp) = ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::click());
+                ui.painter().rect_filled(rect, egui::Rounding::same(3.0), color);
+                if resp.on_hover_text("Set all to this color").clicked() {
+                    edit = Some(BulkEdit::Color(color));
+                }
+            }
+        });
+
+        // ── Assignees ─────────────────────────────────────────────────
+        if !resources.is_empty() {
+            bulk_label(ui, "Assigned to");
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("bulk_assign_combo")
+                    .selected_text(RichText::new("Add…").size(11.0))
+                    .show_ui(ui, |ui| {
+                        for r in resources {
+                            if ui.selectable_label(false, &r.name).clicked() {
+                                edit = Some(BulkEdit::Assign(r.id));
+                            }
+                        }
+                    });
+                egui::ComboBox::from_id_salt("bulk_unassign_combo")
+                    .selected_text(RichText::new("Remove…").size(11.0))
+                    .show_ui(ui, |ui| {
+                        for r in resources {
+                            if ui.selectable_label(false, &r.name).clicked() {
+                                edit = Some(BulkEdit::Unassign(r.id));
+                            }
+                        }
+                    });
+            });
+        }
+
+        // ── Shift dates ───────────────────────────────────────────────
+        bulk_label(ui, "Shift dates");
+        let days_id = Id::new("bulk_shift_days");
+        let mut days: i64 = ui.ctx().data(|d| d.get_temp(days_id)).unwrap_or(7);
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut days).range(-365..=365).suffix(" days"));
+            if ui.add_enabled(days != 0, egui::Button::new("Shift")).clicked() {
+                edit = Some(BulkEdit::Shift(days));
+            }
+        });
+        ui.ctx().data_mut(|d| d.insert_temp(days_id, days));
+
+        ui.label(
+            RichText::new("Ctrl+click rows in the task list to add or remove tasks")
+                .size(9.5)
+                .color(theme::text_dim()),
+        );
+    });
+
+    edit
+}
+
+/// Render the task's history from the project's audit log as a mini
+/// timeline: one bar per saved change of dates or progress, oldest first,
+/// with unsaved edits as a final "now" row.
+pub fn show_task_history(history: &[&AuditEntry], task: &Task, ui: &mut Ui) {
+    const ROW: f32 = 14.0;
+    const LABEL_W: f32 = 64.0;
+    const PROGRESS_W: f32 = 34.0;
+
+    let (slipped, days) = slips(history);
+    let unsaved = history
+        .last()
+        .is_none_or(|e| e.start != task.start || e.end != task.end || (e.progress - task.progress).abs() >= 0.005);
+    let mut rows: Vec<(String, chrono::NaiveDate, chrono::NaiveDate, f32)> = history
+        .iter()
+        .map(|e| (e.at.with_timezone(&chrono::Local).format("%d/%m/%y").to_string(), e.start, e.end, e.progress))
+        .collect();
+    if unsaved {
+        rows.push(("now".to_string(), task.start, task.end, task.progress));
+    }
+    egui::CollapsingHeader::new(RichText::new(format!("History ({})", history.len())).size(11.0).strong())
+        .id_salt("task-history")
+        .show(ui, |ui| {
+            if history.is_empty() {
+                ui.label(RichText::new("Recorded each time the project is saved").size(10.0).color(theme::text_dim()));
+                return;
+            }
+            let summary = match slipped {
+                0 => "Finish never slipped".to_string(),
+                n => format!("Finish slipped {} time{} (+{} days in all)", n, if n == 1 { "" } else { "s" }, days),
+            };
+            ui.label(
+                RichText::new(summary)
+                    .size(10.5)
+                    .color(if slipped > 0 { theme::slip_moderate() } else { theme::text_secondary() }),
+            );
+            let (Some(from), Some(to)) = (rows.iter().map(|r| r.1).min(), rows.iter().map(|r| r.2).max()) else {
+                return;
+            };
+            let span = (to - from).num_days().max(1) as f32;
+            let (rect, _) =
+                ui.allocate_exact_size(egui::vec2(ui.available_width(), rows.len() as f32 * ROW), egui::Sense::hover());
+            let painter = ui.painter_at(rect);
+            let track = egui::Rangef::new(rect.left() + LABEL_W, rect.right() - PROGRESS_W);
+            let x_of = |d: chrono::NaiveDate| track.min + (d - from).num_days() as f32 / span * track.span();
+            let font = egui::FontId::proportional(9.5);
+            let mut previous_end: Option<chrono::NaiveDate> = None;
+            for (i, (label, start, end, progress)) in rows.iter().enumerate() {
+                let y = rect.top() + i as f32 * ROW;
+                painter.text(
+                    egui::pos2(rect.left(), y + ROW / 2.0),
+                    egui::Align2::LEFT_CENTER,
+                    label,
+                    font.clone(),
+                    theme::text_dim(),
+                );
+                let color = if previous_end.is_some_and(|p| *end > p) { theme::slip_moderate() } else { task.color };
+                let bar = egui::Rect::from_min_max(
+                    egui::pos2(x_of(*start), y + 3.0),
+                    egui::pos2(x_of(*end).max(x_of(*start) + 3.0), y + ROW - 3.0),
+                );
+                painter.rect_filled(bar, 2.0, color.gamma_multiply(0.45));
+                painter.rect_filled(
+                    egui::Rect::from_min_max(bar.min, egui::pos2(bar.left() + bar.width() * progress, bar.bottom())),
+                    2.0,
+                    color,
+                );
+                painter.text(
+                    egui::pos2(rect.right(), y + ROW / 2.0),
+                    egui::Align2::RIGHT_CENTER,
+                    format!("{:.0}%", progress * 100.0),
+                    font.clone(),
+                    theme::text_secondary(),
+                );
+                previous_end = Some(*end);
+            }
+        });
+}
@@ -1,6 +1,9 @@
-use crate::model::Task;
+use crate::model::project::DropPlace;
 use crate::model::task::TaskPriority;
-use crate::ui::{filter_bar, theme};
+use crate::model::{ProjectCalendar, Resource, Task};
+use crate::ui::filter_bar::{self, ColumnFilters};
+use crate::ui::theme;
+use chrono::NaiveDate;
 use egui::{Color32, RichText, Ui};
 use uuid::Uuid;
 
@@ -8,49 +11,143 @@ use uuid::Uuid;
 pub enum TaskTableAction {
     None,
     Select(Uuid),
+    /// Ctrl+click: add the task to, or remove it from, the multi-selection.
+    ToggleSelect(Uuid),
     Delete(Uuid),
     Add,
     ToggleCollapse(Uuid),
+    /// Link (from, to) finish → start, from a Shift+drag between rows.
+    Link(Uuid, Uuid),
+    /// Move a task (with its subtasks) next to or into another, from the
+    /// row drag handle.
+    Move(Uuid, Uuid, DropPlace),
+    /// Progress typed or stepped in the DONE column. `begin` marks the
+    /// start of an edit, so a whole drag or run of key steps undoes at once.
+    SetProgress { id: Uuid, progress: f32, begin: bool },
+}
+
+/// Percent step for the arrow keys in the DONE column; Shift doubles it.
+const PROGRESS_STEP: f32 = 5.0;
+
+/// Editable percent-complete field for a leaf task. Up/Down step by 5%,
+/// Shift+Up/Down by 10%, and the value can also be typed or dragged.
+fn progress_field(ui: &mut Ui, task: &Task) -> Option<(f32, bool)> {
+    let id = ui.next_auto_id();
+    let mut percent = (task.progress * 100.0).round();
+    let mut stepped = false;
+    if ui.memory(|m| m.has_focus(id)) {
+        let steps = ui.input_mut(|i| {
+            let mut steps = 0.0;
+            for (mods, size) in [(egui::Modifiers::NONE, 1.0), (egui::Modifiers::SHIFT, 2.0)] {
+                steps += size * i.count_and_consume_key(mods, egui::Key::ArrowUp) as f32;
+                steps -= size * i.count_and_consume_key(mods, egui::Key::ArrowDown) as f32;
+            }
+            steps
+        });
+        if steps != 0.0 {
+            percent = ((percent / PROGRESS_STEP).round() + steps).clamp(0.0, 100.0 / PROGRESS_STEP) * PROGRESS_STEP;
+            // Drop the half-typed text so the field shows the stepped value.
+            ui.data_mut(|d| d.remove::<String>(id));
+            stepped = true;
+        }
+    }
+    let resp = ui
+        .add_sized(
+            [48.0, 16.0],
+            egui::DragValue::new(&mut percent)
+                .range(0.0..=100.0)
+                .speed(0.5)
+                .max_decimals(0)
+                .suffix("%"),
+        )
+        .on_hover_text("Percent complete — type a value, drag, or use ↑/↓ (Shift for 10%)");
+    let begin = resp.gained_focus() || resp.drag_started();
+    (begin || stepped || resp.changed()).then_some((percent / 100.0, begin))
+}
+
+/// Where a row drag dropped at `y` lands on the row `rect`: the top and
+/// bottom quarters insert before/after it, the middle makes it the parent
+/// (milestones split in half, as they cannot have subtasks).
+fn drop_place(rect: egui::Rect, y: f32, target: &Task) -> DropPlace {
+    let t = (y - rect.top()) / rect.height().max(1.0);
+    match (target.is_milestone, t) {
+        (true, t) if t < 0.5 => DropPlace::Before,
+        (true, _) => DropPlace::After,
+        (false, t) if t < 0.25 => DropPlace::Before,
+        (false, t) if t > 0.75 => DropPlace::After,
+        _ => DropPlace::Into,
+    }
+}
+
+/// A column header that opens a filter popup when clicked, marked with a
+/// funnel while its filter is set. The popup stays open while a date
+/// picker in it is used; a second click on the header or Escape closes it.
+fn filter_header(ui: &mut Ui, text: &str, width: f32, active: bool, add_contents: impl FnOnce(&mut Ui)) {
+    ui.allocate_ui(egui::vec2(width, 16.0), |ui| {
+        let (label, color) = if active {
+            (format!("{} {}", text, egui_phosphor::regular::FUNNEL), theme::accent())
+        } else {
+            (text.to_string(), theme::text_dim())
+        };
+        let resp = ui
+            .add(egui::Button::new(RichText::new(label).size(9.0).color(color).strong()).frame(false))
+            .on_hover_text("Filter this column");
+        let popup_id = ui.make_persistent_id(("column_filter", text));
+        if resp.clicked() {
+            ui.memory_mut(|m| m.toggle_popup(popup_id));
+        }
+        egui::popup_below_widget(ui, popup_id, &resp, egui::PopupCloseBehavior::IgnoreClicks, |ui| {
+            ui.set_min_width(160.0);
+            add_contents(ui);
+        });
+    });
+}
+
+/// Popup contents for a date column: a picker, and a button to drop the
+/// filter. `fallback` is what the picker shows while no date is set.
+fn date_filter(ui: &mut Ui, label: &str, id: &str, date: &mut Option<NaiveDate>, fallback: NaiveDate) {
+    ui.label(RichText::new(label).size(11.0).color(theme::text_secondary()));
+    let mut picked = date.unwrap_or(fallback);
+    if ui.add(egui_extras::DatePickerButton::new(&mut picked).id_salt(id)).changed() {
+        *date = Some(picked);
+    }
+    if ui.add_enabled(date.is_some(), egui::Button::new("Any date")).clicked() {
+        *date = None;
+    }
 }
 
 /// Render the left-side task table panel.
-/// `search_query` and `filter_priority` are used to hide non-matching tasks.
+/// `filter` is used to hide non-matching tasks; the column headers edit
+/// `priority` and `columns`, which feed the next frame's filter.
+#[allow(clippy::too_many_arguments)]
 pub fn show_task_table(
     tasks: &[Task],
+    calendar: &ProjectCalendar,
+    resources: &[Resource],
     selected_task: Option<Uuid>,
-    search_query: &str,
-    filter_priority: Option<TaskPriority>,
+    multi_selection: &[Uuid],
+    filter: &filter_bar::FilterState,
+    priority: &mut Option<TaskPriority>,
+    columns: &mut ColumnFilters,
     ui: &mut Ui,
 ) -> TaskTableAction {
     let mut action = TaskTableAction::None;
 
-    // Determine which tasks are visible after filtering
-    // A parent task is shown if it or any of its children pass the filter.
-    let passes_filter = |t: &Task| -> bool {
-        if !filter_bar::task_matches(
-            &t.name,
-            &t.description,
-            t.priority,
-            search_query,
-            filter_priority,
-        ) {
-            // Check if any child passes the filter
-            let child_passes = tasks.iter().any(|child| {
-                child.parent_id == Some(t.id)
-                    && filter_bar::task_matches(
-                        &child.name,
-                        &child.description,
-                        child.priority,
-                        search_query,
-                        filter_priority,
-                    )
-            });
-            if !child_passes {
-                return false;
+    // Determine which tasks are visible after filtering: a task is shown if
+    // it or any of its descendants passes the filter.
+    let filtering = filter.is_active();
+    let mut hits: std::collections::HashSet<Uuid> = std::collections::HashSet::new();
+    for task in tasks.iter().filter(|t| filter.matches(t)) {
+        hits.insert(task.id);
+        let mut parent = task.parent_id;
+        while let Some(pid) = parent.filter(|pid| filter.in_focus(*pid)) {
+            if !hits.insert(pid) {
+                break;
             }
+            parent = tasks.iter().find(|t| t.id == pid).and_then(|t| t.parent_id);
         }
-        true
-    };
+    }
+    let passes_filter = |t: &Task| hits.contains(&t.id);
 
     let visible_count = tasks.iter().filter(|t| passes_filter(t)).count();
 
@@ -105,12 +202,44 @@ pub fn show_task_table(
                         ui.label(RichText::new(text).size(9.0).color(theme::text_dim()).strong());
                     });
                 };
+                let today = chrono::Local::now().date_naive();
+                hdr(ui, "", 8.0);    // drag handle
                 hdr(ui, "", 14.0);   // color dot
-                hdr(ui, "!", 10.0);  // priority icon column
-                hdr(ui, "TASK", 100.0);
-                hdr(ui, "START", 50.0);
-                hdr(ui, "END", 50.0);
-                hdr(ui, "DONE", 55.0);
+                filter_header(ui, "!", 10.0, priority.is_some(), |ui| {
+                    ui.selectable_value(priority, None, "— All —");
+                    for p in TaskPriority::all() {
+                        ui.selectable_value(priority, Some(*p), format!("{} {}", p.icon(), p.label()));
+                    }
+                });
+                filter_header(ui, "TASK", 100.0, columns.assignee.is_some(), |ui| {
+                    ui.label(RichText::new("Assigned to").size(11.0).color(theme::text_secondary()));
+                    ui.selectable_value(&mut columns.assignee, None, "— Anyone —");
+                    for r in resources {
+                        ui.selectable_value(&mut columns.assignee, Some(r.id), &r.name);
+                    }
+                });
+                filter_header(ui, "START", 50.0, columns.start_from.is_some(), |ui| {
+                    date_filter(ui, "Starting on or after", "col_filter_start", &mut columns.start_from, today);
+                });
+                filter_header(ui, "END", 50.0, columns.end_by.is_some(), |ui| {
+                    date_filter(ui, "Ending on or before", "col_filter_end", &mut columns.end_by, today);
+                });
+                hdr(ui, "DAYS", 44.0);
+                filter_header(ui, "DONE", 55.0, columns.progress.is_some(), |ui| {
+                    ui.label(RichText::new("Percent complete").size(11.0).color(theme::text_secondary()));
+                    let (mut lo, mut hi) = columns.progress.unwrap_or((0.0, 100.0));
+                    let from = ui.add(egui::Slider::new(&mut lo, 0.0..=100.0).step_by(5.0).suffix("%").text("from"));
+                    let to = ui.add(egui::Slider::new(&mut hi, 0.0..=100.0).step_by(5.0).suffix("%").text("to"));
+                    if from.changed() {
+                        hi = hi.max(lo);
+                    }
+                    if to.changed() {
+                        lo = lo.min(hi);
+                    }
+                    if from.changed() || to.changed() {
+                        columns.progress = Some((lo, hi)).filter(|&r| r != (0.0, 100.0));
+                    }
+                });
             },
         );
     });
@@ -122,6 +251,9 @@ pub fn show_task_table(
         .auto_shrink([false, false])
         .show(ui, |ui| {
             let today = chrono::Local::now().date_naive();
+            let icons = theme::icons();
+            let mut row_rects: Vec<(Uuid, egui::Rect)> = Vec::new();
+            let mut moving: Option<(Uuid, bool)> = None;
 
             for (i, task) in tasks.iter().enumerate() {
                 // Skip if filtered out
@@ -129,8 +261,9 @@ pub fn show_task_table(
                     continue;
                 }
 
-                // Skip children of collapsed parents
-                if let Some(pid) = task.parent_id {
+                // Skip children of collapsed parents. While filtering, matches
+                // are shown even under collapsed parents so hits never hide.
+                if let Some(pid) = task.parent_id.filter(|_| !filtering) {
                     if let Some(parent) = tasks.iter().find(|t| t.id == pid) {
                         if parent.collapsed {
                             continue;
@@ -138,7 +271,7 @@ pub fn show_task_table(
                     }
                 }
 
-                let is_selected = selected_task == Some(task.id);
+                let is_selected = selected_task == Some(task.id) || multi_selection.contains(&task.id);
                 let is_parent = task.has_children(tasks);
                 let is_child = task.parent_id.is_some();
                 let is_overdue =
@@ -177,18 +310,48 @@ pub fn show_task_table(
                     ui.horizontal(|ui| {
                         ui.spacing_mut().item_spacing.x = 4.0;
 
+                        // Drag handle: reorder, or drop onto a row to reparent
+                        let handle = ui
+                            .add(
+                                egui::Label::new(
+                                    RichText::new(egui_phosphor::regular::DOTS_SIX_VERTICAL)
+                                        .size(10.0)
+                                        .color(theme::text_dim()),
+                                )
+                                .sense(egui::Sense::drag()),
+                            )
+                            .on_hover_cursor(egui::CursorIcon::Grab)
+                            .on_hover_text("Drag to move; drop on a row's middle to make it a subtask");
+                        if handle.dragged() {
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+                            moving = Some((task.id, false));
+                        } else if handle.drag_stopped() {
+                            moving = Some((task.id, true));
+                        }
+
                         // Expand/collapse for parent tasks
                         if is_parent {
-                            let tri = if task.collapsed { egui_phosphor::regular::CARET_RIGHT } else { egui_phosphor::regular::CARET_DOWN };
-                            if ui
-                                .add(
-                                    egui::Button::new(
-                                        RichText::new(tri).size(9.0).color(theme::text_dim()),
-                                    )
-                                    .frame(false),
-                                )
-                                .clicked()
-                            {
+                            // Collapsed parents opened up by the filter get an
+                            // accent caret so it's clear they are collapsed.
+                            let opened_by_filter = task.collapsed
+                                && filtering
+                                && tasks.iter().any(|c| c.parent_id == Some(task.id) && hits.contains(&c.id));
+                            let (tri, tri_color) = if opened_by_filter {
+                                (egui_phosphor::regular::CARET_DOWN, theme::accent())
+                            } else if task.collapsed {
+                                (egui_phosphor::regular::CARET_RIGHT, theme::text_dim())
+                            } else {
+                                (egui_phosphor::regular::CARET_DOWN, theme::text_dim())
+                            };
+                            let caret = ui.add(
+                                egui::Button::new(RichText::new(tri).size(9.0).color(tri_color)).frame(false),
+                            );
+                            let caret = if opened_by_filter {
+                                caret.on_hover_text("Collapsed — expanded while the filter shows matching subtasks")
+                            } else {
+                                caret
+                            };
+                            if caret.clicked() {
                                 action = TaskTableAction::ToggleCollapse(task.id);
                             }
                         } else if is_child {
@@ -205,22 +368,16 @@ pub fn show_task_table(
 
                         // Priority icon
                         let pri_icon = task.priority.icon();
-                        let pri_color = match task.priority {
-                            TaskPriority::Critical => egui::Color32::from_rgb(220, 60, 60),
-                            TaskPriority::High => egui::Color32::from_rgb(220, 140, 40),
-                            TaskPriority::Medium => egui::Color32::from_rgb(200, 180, 40),
-                            TaskPriority::Low => egui::Color32::from_rgb(80, 160, 80),
-                            TaskPriority::None => theme::text_dim(),
-                        };
+                        let pri_color = theme::readable_on(crate::ui::color_by::priority_color(task.priority), row_bg);
                         ui.label(
                             RichText::new(pri_icon).size(9.0).color(pri_color).strong(),
                         );
 
                         // Task name
                         let name = if task.is_milestone {
-                            format!("◆ {}", task.name)
+                            format!("{} {}", icons.milestone, task.name)
                         } else if is_overdue {
-                            format!("⚠ {}", task.name)
+                            format!("{} {}", icons.overdue, task.name)
                         } else {
                             task.name.clone()
                         };
@@ -253,12 +410,44 @@ pub fn show_task_table(
                                     action = TaskTableAction::Delete(task.id);
                                 }
 
-                                let pbar = egui::ProgressBar::new(task.progress)
-                                    .desired_width(48.0)
-                                    .fill(task.color)
-                                    .rounding(egui::Rounding::same(3.0));
-                                ui.add(pbar);
+                                if task.is_milestone {
+                                    let (icon, color, hint) = if task.is_achieved() {
+                                        (egui_phosphor::regular::CHECK_CIRCLE, task.color, "Achieved")
+                                    } else {
+                                        (egui_phosphor::regular::CIRCLE, theme::text_dim(), "Not achieved")
+                                    };
+                                    ui.add_sized([48.0, 14.0], egui::Label::new(RichText::new(icon).size(12.0).color(color)))
+                                        .on_hover_text(hint);
+                                } else if !is_parent {
+                                    if let Some((progress, begin)) = progress_field(ui, task) {
+                                        action = TaskTableAction::SetProgress { id: task.id, progress, begin };
+                                    }
+                                } else {
+                                    let pbar = egui::ProgressBar::new(task.progress)
+                                        .desired_width(48.0)
+                                        .fill(task.color)
+                                        .rounding(egui::Rounding::same(3.0));
+                                    let resp = ui.add(pbar);
+                                    if task.progress_override.is_some() {
+                                        resp.on_hover_text("Manual progress (overrides the rollup)");
+                                    }
+                                }
 
+                                if !task.is_milestone {
+                                    let days = (task.end - task.start).num_days();
+                                    let working = calendar.working_days_between(task.start, task.end);
+                                    ui.add_sized(
+                                        [44.0, 14.0],
+                                        egui::Label::new(
+                                            RichText::new(format!("{}d·{}w", days, working))
+                                                .size(9.5)
+                                                .color(theme::text_dim()),
+                                        ),
+                                    )
+                                    .on_hover_text(format!("{} calendar days, {} working days", days, working));
+                                } else {
+                                    ui.add_space(44.0);
+                                }
                                 ui.label(
                                     RichText::new(task.end.format("%m/%d").to_string())
                                         .size(10.0)
@@ -287,11 +476,88 @@ pub fn show_task_table(
                     egui::Sense::click(),
                 );
                 if row_click.clicked() && matches!(action, TaskTableAction::None) {
-                    action = TaskTableAction::Select(task.id);
+                    action = if ui.input(|i| i.modifiers.command) {
+                        TaskTableAction::ToggleSelect(task.id)
+                    } else {
+                        TaskTableAction::Select(task.id)
+                    };
                 }
+                row_rects.push((task.id, row_rect));
 
                 ui.add_space(theme::row_gap());
             }
+
+            // Row drag handle: show where the task will land, move it on release.
+            if let Some((id, dropped)) = moving {
+                let pointer = ui.input(|i| i.pointer.latest_pos());
+                let target = pointer.and_then(|ptr| {
+                    let &(target_id, rect) = row_rects.iter().find(|(_, r)| r.y_range().contains(ptr.y))?;
+                    let target = tasks.iter().find(|t| t.id == target_id)?;
+                    (target_id != id).then(|| (target_id, rect, drop_place(rect, ptr.y, target)))
+                });
+                if dropped {
+                    if let Some((target_id, _, place)) = target {
+                        action = TaskTableAction::Move(id, target_id, place);
+                    }
+                } else if let Some((_, rect, place)) = target {
+                    let painter = ui.ctx().layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("table-move-drag")));
+                    let stroke = egui::Stroke::new(2.0, theme::accent());
+                    match place {
+                        DropPlace::Before => painter.hline(rect.x_range(), rect.top() - 1.0, stroke),
+                        DropPlace::After => painter.hline(rect.x_range(), rect.bottom() + 1.0, stroke),
+                        DropPlace::Into => painter.rect_stroke(rect, egui::Rounding::same(4.0), stroke),
+                    };
+                }
+            }
+
+            // Shift+drag from one row onto another links them finish → start,
+            // like Shift+drag between bars in the chart.
+            let link_id = egui::Id::new("table-link-drag");
+            let (pointer, shift, pressed, released) = ui.input(|i| {
+                (
+                    i.pointer.interact_pos(),
+                    i.modifiers.shift,
+                    i.pointer.primary_pressed(),
+                    i.pointer.primary_released(),
+                )
+            });
+            let row_at = |pos: egui::Pos2| row_rects.iter().find(|(_, r)| r.contains(pos)).copied();
+            if shift && pressed && ui.ui_contains_pointer() {
+                if let Some((id, _)) = pointer.and_then(row_at) {
+                    ui.ctx().data_mut(|d| d.insert_temp(link_id, id));
+                }
+            }
+            let link_from: Option<Uuid> = ui.ctx().data(|d| d.get_temp(link_id));
+            if let Some(from) = link_from {
+                let target = pointer.and_then(row_at).filter(|(id, _)| *id != from);
+                if released {
+                    ui.ctx().data_mut(|d| d.remove::<Uuid>(link_id));
+                    if let Some((to, _)) = target {
+                        action = TaskTableAction::Link(from, to);
+                    }
+                } else if let Some(ptr) = pointer {
+                    let painter = ui.ctx().layer_painter(egui::LayerId::new(egui::Order::Foreground, link_id));
+                    let stroke = egui::Stroke::new(1.5, theme::dep_creating());
+                    let start = match row_rects.iter().find(|(id, _)| *id == from) {
+                        Some((_, rect)) => {
+                            painter.rect_stroke(*rect, egui::Rounding::same(4.0), stroke);
+                            rect.right_center()
+                        }
+                        None => ptr,
+                    };
+                    if let Some((_, rect)) = target {
+                        painter.rect_stroke(rect, egui::Rounding::same(4.0), stroke);
+                        painter.text(
+                            ptr + egui::vec2(10.0, -12.0),
+                            egui::Align2::LEFT_BOTTOM,
+                            "FS",
+                            theme::font_sub(),
+                            theme::dep_creating(),
+                        );
+                    }
+                    painter.arrow(start, ptr - start, stroke);
+                }
+            }
         });
 
     action
@@ -26,6 +26,12 @@ pub fn set_active(def: &ThemeDefinition) {
     });
 }
 
+/// Apply a density preset to the installed theme for this frame.
+/// Call after [`set_active`] and before [`apply_theme`].
+pub fn set_density(density: crate::ui::theme_def::Density) {
+    ACTIVE.with(|cell| density.apply(&mut cell.borrow_mut()));
+}
+
 /// Read the full definition (rarely needed; prefer the named accessors below).
 pub fn with_active<R>(f: impl FnOnce(&ThemeDefinition) -> R) -> R {
     ACTIVE.with(|cell| f(&cell.borrow()))
@@ -67,6 +73,10 @@ color_accessor!(progress_overlay, progress_overlay);
 color_accessor!(dep_arrow, dep_arrow);
 color_accessor!(dep_arrow_hover, dep_arrow_hover);
 color_accessor!(dep_creating, dep_creating);
+color_accessor!(dep_critical, dep_critical);
+color_accessor!(slip_minor, slip_minor);
+color_accessor!(slip_moderate, slip_moderate);
+color_accessor!(slip_major, slip_major);
 
 color_accessor!(widget_bg_inactive, widget_bg_inactive);
 color_accessor!(widget_bg_hovered, widget_bg_hovered);
@@ -98,6 +108,103 @@ pub fn task_color(index: usize) -> Color32 {
     })
 }
 
+/// The colour of `palette` closest to `color`, or `color` itself when the
+/// palette is empty.
+pub fn nearest_palette_color(color: Color32, palette: &[Color32]) -> Color32 {
+    let distance = |c: &Color32| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(c.r(), color.r()) + d(c.g(), color.g()) + d(c.b(), color.b())
+    };
+    palette.iter().copied().min_by_key(distance).unwrap_or(color)
+}
+
+/// Task colours to swap for a newly chosen theme's palette, shown for
+/// confirmation before they are applied.
+#[derive(Debug, Clone)]
+pub struct PaletteRemap {
+    pub theme: String,
+    /// Old colour, its nearest palette colour and the number of tasks using it.
+    pub swaps: Vec<(Color32, Color32, usize)>,
+}
+
+impl PaletteRemap {
+    /// Swaps for every task colour not already in `palette`; `None` when
+    /// all tasks already use it.
+    pub fn plan(tasks: &[crate::model::Task], palette: &[Color32], theme: &str) -> Option<Self> {
+        let mut swaps: Vec<(Color32, Color32, usize)> = Vec::new();
+        for task in tasks.iter().filter(|t| !palette.contains(&t.color)) {
+            match swaps.iter_mut().find(|(old, _, _)| *old == task.color) {
+                Some((_, _, count)) => *count += 1,
+                None => swaps.push((task.color, nearest_palette_color(task.color, palette), 1)),
+            }
+        }
+        (!palette.is_empty() && !swaps.is_empty()).then(|| Self { theme: theme.to_string(), swaps })
+    }
+
+    pub fn task_count(&self) -> usize {
+        self.swaps.iter().map(|(_, _, count)| count).sum()
+    }
+}
+
+// ─── Contrast helpers ──────────────────────────────────────────────────────
+
+/// Minimum contrast ratio for text drawn on a colored fill.
+const MIN_TEXT_CONTRAST: f32 = 4.5;
+
+/// Relative luminance (WCAG 2) of an opaque colour, from 0 (black) to 1 (white).
+pub fn luminance(color: Color32) -> f32 {
+    let channel = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * channel(color.r()) + 0.7152 * channel(color.g()) + 0.0722 * channel(color.b())
+}
+
+/// Contrast ratio between two colours, from 1 (same) to 21 (black on white).
+pub fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// A translucent (premultiplied) colour as it appears over the chart background.
+fn over_background(color: Color32) -> Color32 {
+    if color.a() == 255 {
+        return color;
+    }
+    let bg = bg_dark();
+    let keep = 1.0 - color.a() as f32 / 255.0;
+    let mix = |c: u8, b: u8| (c as f32 + b as f32 * keep).min(255.0) as u8;
+    Color32::from_rgb(mix(color.r(), bg.r()), mix(color.g(), bg.g()), mix(color.b(), bg.b()))
+}
+
+/// Text colour for labels drawn on `fill`: the theme's `text_on_bar` when it
+/// is readable there, otherwise near-black or white, whichever contrasts more.
+pub fn text_on(fill: Color32) -> Color32 {
+    let fill = over_background(fill);
+    let preferred = text_on_bar();
+    if contrast_ratio(preferred, fill) >= MIN_TEXT_CONTRAST {
+        return preferred;
+    }
+    let (dark, light) = (Color32::from_rgb(20, 20, 24), Color32::WHITE);
+    if contrast_ratio(dark, fill) >= contrast_ratio(light, fill) { dark } else { light }
+}
+
+/// `color` darkened or lightened just enough to reach a 3:1 contrast against
+/// `background`, for small coloured glyphs such as priority badges.
+pub fn readable_on(color: Color32, background: Color32) -> Color32 {
+    const MIN_GLYPH_CONTRAST: f32 = 3.0;
+    let background = over_background(background);
+    let target = if luminance(background) > 0.4 { Color32::BLACK } else { Color32::WHITE };
+    let mut adjusted = color;
+    for step in 1..=10 {
+        if contrast_ratio(adjusted, background) >= MIN_GLYPH_CONTRAST {
+            break;
+        }
+        adjusted = color.lerp_to_gamma(target, step as f32 / 10.0);
+    }
+    adjusted
+}
+
 // ─── Typography accessors ──────────────────────────────────────────────────
 
 pub fn font_header() -> FontId {
@@ -180,12 +287,26 @@ pub fn reorder_anim_duration() -> f32 {
     ACTIVE.with(|c| c.borrow().motion.reorder_anim_duration)
 }
 
+pub fn viewport_anim_duration() -> f32 {
+    ACTIVE.with(|c| c.borrow().motion.viewport_anim_duration)
+}
+
 // ─── Zoom accessors ────────────────────────────────────────────────────────
 
 pub fn zoom() -> crate::ui::theme_def::ThemeZoom {
     ACTIVE.with(|c| c.borrow().zoom.clone())
 }
 
+// ─── Pattern / icon accessors ──────────────────────────────────────────────
+
+pub fn patterns() -> crate::ui::theme_def::ThemePatterns {
+    ACTIVE.with(|c| c.borrow().patterns.clone())
+}
+
+pub fn icons() -> crate::ui::theme_def::ThemeIcons {
+    ACTIVE.with(|c| c.borrow().icons.clone())
+}
+
 pub fn is_light() -> bool {
     ACTIVE.with(|c| c.borrow().meta.variant == "light")
 }
@@ -107,6 +107,8 @@ pub struct ThemeDefinition {
     pub layout: ThemeLayout,
     pub motion: ThemeMotion,
     pub zoom: ThemeZoom,
+    pub patterns: ThemePatterns,
+    pub icons: ThemeIcons,
 }
 
 impl Default for ThemeDefinition {
@@ -121,6 +123,8 @@ impl Default for ThemeDefinition {
             layout: ThemeLayout::default(),
             motion: ThemeMotion::default(),
             zoom: ThemeZoom::default(),
+            patterns: ThemePatterns::default(),
+            icons: ThemeIcons::default(),
         }
     }
 }
@@ -206,6 +210,16 @@ pub struct ThemeColors {
     pub dep_arrow_hover: Color32,
     #[serde(with = "hex_color")]
     pub dep_creating: Color32,
+    #[serde(with = "hex_color")]
+    pub dep_critical: Color32,
+
+    // Baseline slippage markers (by slip size)
+    #[serde(with = "hex_color")]
+    pub slip_minor: Color32,
+    #[serde(with = "hex_color")]
+    pub slip_moderate: Color32,
+    #[serde(with = "hex_color")]
+    pub slip_major: Color32,
 
     // Widget colors (egui Visuals overrides)
     #[serde(with = "hex_color")]
@@ -265,6 +279,11 @@ impl Default for ThemeColors {
             dep_arrow: Color32::from_rgba_unmultiplied(219, 174, 94, 110),
             dep_arrow_hover: Color32::from_rgba_unmultiplied(242, 202, 134, 180),
             dep_creating: Color32::from_rgba_unmultiplied(120, 200, 255, 180),
+            dep_critical: Color32::from_rgb(235, 80, 80),
+
+            slip_minor: Color32::from_rgb(230, 200, 80),
+            slip_moderate: Color32::from_rgb(240, 140, 60),
+            slip_major: Color32::from_rgb(235, 70, 70),
 
             widget_bg_inactive: Color32::from_rgb(38, 42, 54),
             widget_bg_hovered: Color32::from_rgb(48, 53, 67),
@@ -439,6 +458,36 @@ pub struct ThemeRendering {
 
     // Today marker
     pub today_diamond_size: f32,
+
+    /// How parent (summary) task bars are drawn.
+    pub summary_bar_style: SummaryBarStyle,
+    /// How progress is shown on summary bars.
+    pub summary_progress: SummaryProgress,
+    /// Fill pending milestones from the bottom up by their progress.
+    pub milestone_progress: bool,
+}
+
+/// Rendering style for parent/summary task bars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SummaryBarStyle {
+    /// Thin bar with downward ticks at both ends.
+    #[default]
+    Bracket,
+    /// Full-height bar in a muted shade of the task color.
+    Solid,
+    /// Full-height outline with no fill apart from progress.
+    Outline,
+}
+
+/// How progress is drawn on parent (summary) bars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SummaryProgress {
+    /// One fill across the bar for the rolled-up progress.
+    Flat,
+    /// One segment per child, as wide as its share of the rollup and filled
+    /// by its own progress, so a lagging child shows where it sits.
+    #[default]
+    Segments,
 }
 
 impl Default for ThemeRendering {
@@ -481,6 +530,10 @@ impl Default for ThemeRendering {
             weekend_sep_alpha: 140,
 
             today_diamond_size: 5.5,
+
+            summary_bar_style: SummaryBarStyle::Bracket,
+            summary_progress: SummaryProgress::Segments,
+            milestone_progress: true,
         }
     }
 }
@@ -516,12 +569,15 @@ impl Default for ThemeLayout {
 pub struct ThemeMotion {
     /// Duration (s) for row reorder animation.
     pub reorder_anim_duration: f32,
+    /// Duration (s) of animated zoom and scroll jumps; 0 jumps instantly.
+    pub viewport_anim_duration: f32,
 }
 
 impl Default for ThemeMotion {
     fn default() -> Self {
         Self {
             reorder_anim_duration: 0.14,
+            viewport_anim_duration: 0.35,
         }
     }
 }
@@ -551,3 +607,120 @@ impl Default for ThemeZoom {
         }
     }
 }
+
+// ─── Bar patterns ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemePatterns {
+    /// Distance between diagonal hatch lines.
+    pub hatch_spacing: f32,
+    pub hatch_width: f32,
+    pub hatch_alpha: u8,
+    /// Distance between dot centers.
+    pub dot_spacing: f32,
+    pub dot_radius: f32,
+    pub dot_alpha: u8,
+}
+
+impl Default for ThemePatterns {
+    fn default() -> Self {
+        Self {
+            hatch_spacing: 7.0,
+            hatch_width: 1.5,
+            hatch_alpha: 80,
+            dot_spacing: 6.0,
+            dot_radius: 1.2,
+            dot_alpha: 110,
+        }
+    }
+}
+
+// ─── Icons ──────────────────────────────────────────────────────────────────
+
+/// Glyphs used as inline markers in the task table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeIcons {
+    pub milestone: String,
+    pub overdue: String,
+}
+
+impl Default for ThemeIcons {
+    fn default() -> Self {
+        Self {
+            milestone: "◆".into(),
+            overdue: "⚠".into(),
+        }
+    }
+}
+
+// ─── Density ────────────────────────────────────────────────────────────────
+
+/// User-selected density preset, applied on top of whichever theme is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Density {
+    Compact,
+    #[default]
+    Normal,
+    Comfortable,
+}
+
+impl Density {
+    pub fn label(self) -> &'static str {
+        match self {
+            Density::Compact => "Compact",
+            Density::Normal => "Normal",
+            Density::Comfortable => "Comfortable",
+        }
+    }
+
+    pub fn all() -> [Density; 3] {
+        [Density::Compact, Density::Normal, Density::Comfortable]
+    }
+
+    /// (row size, padding, font) multipliers relative to the theme's values.
+    fn factors(self) -> (f32, f32, f32) {
+        match self {
+            Density::Compact => (0.72, 0.5, 0.9),
+            Density::Normal => (1.0, 1.0, 1.0),
+            Density::Comfortable => (1.25, 1.5, 1.08),
+        }
+    }
+
+    /// Scale row heights, paddings and font sizes of `def` for this preset.
+    pub fn apply(self, def: &mut ThemeDefinition) {
+        if self == Density::Normal {
+            return;
+        }
+        let (rows, pad, font) = self.factors();
+
+        let s = &mut def.sizing;
+        s.row_height *= rows;
+        s.row_gap *= pad;
+        s.bar_inset *= pad;
+        s.header_height *= rows.max(0.85);
+        s.status_bar_height *= rows.max(0.85);
+
+        let sp = &mut def.spacing;
+        sp.item_spacing_x *= pad.max(0.75);
+        sp.item_spacing_y *= pad;
+        sp.button_padding_x *= pad.max(0.75);
+        sp.button_padding_y *= pad;
+
+        let t = &mut def.typography;
+        for size in [
+            &mut t.font_header_size,
+            &mut t.font_sub_size,
+            &mut t.font_bar_size,
+            &mut t.font_small_size,
+            &mut t.font_body_size,
+            &mut t.font_menu_size,
+            &mut t.font_label_size,
+            &mut t.font_status_size,
+        ] {
+            // Keep sizes on half-point steps so text stays crisp.
+            *size = (*size * font * 2.0).round() / 2.0;
+        }
+    }
+}
@@ -9,16 +9,276 @@ use std::path::PathBuf;
 #[serde(default)]
 pub struct AppSettings {
     pub active_theme: String,
+    /// Scroll the chart to a task (and pulse it) when it is picked in the table.
+    pub scroll_to_selection: bool,
+    /// Row height, padding and font size preset applied on top of the theme.
+    pub density: crate::ui::theme_def::Density,
+    /// User overrides of the default keyboard shortcuts.
+    pub keymap: crate::ui::keymap::Keymap,
+    /// Switch between `system_dark_theme` and `system_light_theme` to match
+    /// the OS color scheme.
+    pub follow_system_theme: bool,
+    pub system_dark_theme: String,
+    pub system_light_theme: String,
+    /// Where the chart starts when a project is opened.
+    pub open_scroll: OpenScroll,
+    /// How long a bar must be hovered before its detail card appears.
+    pub hover_card_delay_ms: u32,
+    /// Days deleted tasks stay in the trash before they are purged.
+    pub trash_retention_days: u32,
+    /// Frozen column of task names at the chart's left edge.
+    pub name_gutter: crate::model::view::NameGutter,
+    /// Folder whose new CSV files are imported into the open project.
+    pub watch_folder: Option<std::path::PathBuf>,
+    /// Watched CSVs update same-named tasks instead of always appending.
+    pub watch_merge_by_name: bool,
+    /// Earlier versions kept as `<file>.bak1…` on every save (0 = none).
+    pub backup_count: u32,
+    /// Values given to tasks created from the Add Task dialog or as subtasks.
+    pub new_task: NewTaskDefaults,
+    /// Save which parents are collapsed in the project file instead of a
+    /// per-user view-state file in the config directory.
+    pub collapse_in_file: bool,
+    /// Where the selected task's editor is shown.
+    pub editor_dock: EditorDock,
+    /// Flag siblings that share a resource over the same dates.
+    pub check_sibling_overlaps: bool,
+    /// Your resource name in shared plans, for the "Only my tasks" filter.
+    pub user_name: String,
+    /// Faint chart row tint by priority or health.
+    pub row_tint: crate::model::view::RowTint,
+    /// How the swimlane view groups tasks.
+    pub lane_group: crate::model::view::LaneGroup,
+    /// Stack non-overlapping tasks onto shared rows in the swimlane view.
+    pub stack_lanes: bool,
+    /// Column width of the roadmap view.
+    pub roadmap_bucket: crate::model::view::RoadmapBucket,
+    /// Journal edits next to the project file and merge other planners'.
+    pub shared_sync: bool,
+    /// Extend bars with a dashed forecast when they are running late.
+    pub forecast_bars: bool,
+    /// Undo steps kept; each holds a copy of the tasks and links.
+    pub undo_depth: usize,
+    /// Room around the tasks when the timeline range is recalculated.
+    pub viewport_padding: crate::model::timeline::ViewportPadding,
+    /// Progress given to each value of an imported CSV's Status column.
+    pub status_mapping: crate::io::csv_import::StatusMapping,
+    /// Dragging a bar moves its successors along, keeping each link's gap
+    /// instead of leaving them where they are.
+    pub keep_link_gaps: bool,
+    /// Dragging a bar moves it by working days, skipping weekends, so its
+    /// working duration stays the same.
+    pub working_day_drag: bool,
+    /// Zooming switches between the Days, Weeks and Months scales.
+    pub auto_scale: bool,
+    /// Saving keeps a snapshot of the task dates per week in the project,
+    /// for replaying the plan's history.
+    pub weekly_snapshots: bool,
+    /// A milestone is marked achieved once all its predecessors are done.
+    pub auto_achieve_milestones: bool,
+    /// The first-run tour was finished or skipped; opening the sample from
+    /// the welcome window no longer starts it.
+    pub tour_seen: bool,
+    /// Projects last opened or saved, newest first.
+    pub recent_files: Vec<std::path::PathBuf>,
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
             active_theme: "Default Dark".into(),
+            scroll_to_selection: true,
+            density: Default::default(),
+            keymap: Default::default(),
+            follow_system_theme: false,
+            system_dark_theme: "Default Dark".into(),
+            system_light_theme: "Clean Light".into(),
+            open_scroll: OpenScroll::default(),
+            hover_card_delay_ms: 400,
+            trash_retention_days: 30,
+            name_gutter: Default::default(),
+            watch_folder: None,
+            watch_merge_by_name: true,
+            backup_count: 3,
+            new_task: NewTaskDefaults::default(),
+            collapse_in_file: false,
+            editor_dock: EditorDock::default(),
+            check_sibling_overlaps: false,
+            user_name: String::new(),
+            row_tint: Default::default(),
+            lane_group: Default::default(),
+            stack_lanes: true,
+            roadmap_bucket: Default::default(),
+            shared_sync: false,
+            forecast_bars: false,
+            undo_depth: crate::model::history::DEFAULT_DEPTH,
+            viewport_padding: Default::default(),
+            status_mapping: Default::default(),
+            keep_link_gaps: false,
+            working_day_drag: false,
+            auto_scale: true,
+            weekly_snapshots: false,
+            auto_achieve_milestones: false,
+            tour_seen: false,
+            recent_files: Vec::new(),
         }
     }
 }
 
+/// Placement of the task editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum EditorDock {
+    /// Above the task table in the left panel.
+    #[default]
+    Left,
+    /// In its own panel on the right, leaving the table the full left panel.
+    Right,
+    /// In a movable window.
+    Floating,
+}
+
+impl EditorDock {
+    pub fn label(self) -> &'static str {
+        match self {
+            EditorDock::Left => "Above Task List",
+            EditorDock::Right => "Right Panel",
+            EditorDock::Floating => "Floating Window",
+        }
+    }
+
+    pub fn all() -> [EditorDock; 3] {
+        [EditorDock::Left, EditorDock::Right, EditorDock::Floating]
+    }
+}
+
+/// How a new task's bar color is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum NewTaskColor {
+    /// The next color of the theme's task palette.
+    #[default]
+    Palette,
+    /// The parent's color (the palette for top-level tasks).
+    Parent,
+    /// Always `NewTaskDefaults::fixed_color`.
+    Fixed,
+}
+
+impl NewTaskColor {
+    pub fn label(self) -> &'static str {
+        match self {
+            NewTaskColor::Palette => "Next palette color",
+            NewTaskColor::Parent => "Same as parent",
+            NewTaskColor::Fixed => "Always",
+        }
+    }
+
+    pub fn all() -> [NewTaskColor; 3] {
+        [NewTaskColor::Palette, NewTaskColor::Parent, NewTaskColor::Fixed]
+    }
+}
+
+/// Defaults for newly created tasks.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct NewTaskDefaults {
+    /// Length of a new task in calendar days.
+    pub duration_days: u32,
+    pub priority: crate::model::task::TaskPriority,
+    pub color: NewTaskColor,
+    pub fixed_color: [u8; 3],
+    /// Name of an unnamed milestone; `{n}` becomes its number among the
+    /// project's milestones and `{date}` its date.
+    pub milestone_name: String,
+    /// Length of a new subtask; `None` uses `duration_days`.
+    pub subtask_duration_days: Option<u32>,
+    /// Name of a new subtask; `{parent}` becomes the parent's name and `{n}`
+    /// the subtask's number under it.
+    pub subtask_name: String,
+    /// Give new subtasks their parent's color, whatever `color` says.
+    pub subtask_parent_color: bool,
+    /// Assign new subtasks to the parent's resources.
+    pub subtask_parent_assignees: bool,
+    /// Waterfall children: start each new subtask when the parent's last
+    /// subtask ends and link it from that one, finish → start.
+    pub chain_subtasks: bool,
+}
+
+impl Default for NewTaskDefaults {
+    fn default() -> Self {
+        Self {
+            duration_days: 7,
+            priority: Default::default(),
+            color: NewTaskColor::Palette,
+            fixed_color: [70, 130, 180],
+            milestone_name: "Milestone {n}".into(),
+            subtask_duration_days: None,
+            subtask_name: "New Subtask".into(),
+            subtask_parent_color: false,
+            subtask_parent_assignees: false,
+            chain_subtasks: false,
+        }
+    }
+}
+
+impl NewTaskDefaults {
+    pub fn duration(&self) -> chrono::Duration {
+        chrono::Duration::days(self.duration_days.max(1) as i64)
+    }
+
+    pub fn subtask_duration(&self) -> chrono::Duration {
+        chrono::Duration::days(self.subtask_duration_days.unwrap_or(self.duration_days).max(1) as i64)
+    }
+
+    /// Name for the `number`th subtask of `parent`, falling back to "New
+    /// Subtask" when the template is empty.
+    pub fn subtask_name(&self, parent: &str, number: usize) -> String {
+        let name = self.subtask_name.replace("{parent}", parent).replace("{n}", &number.to_string());
+        match name.trim() {
+            "" => "New Subtask".to_string(),
+            trimmed => trimmed.to_string(),
+        }
+    }
+
+    /// Name for the `number`th milestone, falling back to "New Milestone"
+    /// when the template is empty.
+    pub fn milestone_name(&self, number: usize, date: chrono::NaiveDate) -> String {
+        let name = self
+            .milestone_name
+            .replace("{n}", &number.to_string())
+            .replace("{date}", &date.format("%d/%m/%Y").to_string());
+        match name.trim() {
+            "" => "New Milestone".to_string(),
+            trimmed => trimmed.to_string(),
+        }
+    }
+}
+
+/// Horizontal position of the chart right after opening a project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum OpenScroll {
+    /// The project's first date.
+    #[default]
+    ProjectStart,
+    /// Today, when it falls inside the project.
+    Today,
+    /// The start of the earliest task that is not yet complete.
+    FirstIncomplete,
+}
+
+impl OpenScroll {
+    pub fn label(self) -> &'static str {
+        match self {
+            OpenScroll::ProjectStart => "Project Start",
+            OpenScroll::Today => "Today",
+            OpenScroll::FirstIncomplete => "First Incomplete Task",
+        }
+    }
+
+    pub fn all() -> [OpenScroll; 3] {
+        [OpenScroll::ProjectStart, OpenScroll::Today, OpenScroll::FirstIncomplete]
+    }
+}
+
 /// Manages all available themes and the active selection.
 pub struct ThemeManager {
     /// All loaded themes, keyed by `meta.name`.
@@ -29,6 +289,8 @@ pub struct ThemeManager {
     themes_dir: PathBuf,
     /// Path to the settings file.
     settings_path: PathBuf,
+    /// Persisted settings; `active_theme` is refreshed on every save.
+    settings: AppSettings,
 }
 
 impl ThemeManager {
@@ -88,6 +350,7 @@ impl ThemeManager {
             active_index,
             themes_dir,
             settings_path,
+            settings,
         }
     }
 
@@ -115,16 +378,43 @@ impl ThemeManager {
         &self.themes_dir
     }
 
+    pub fn settings(&self) -> &AppSettings {
+        &self.settings
+    }
+
+    /// Change a setting and persist it immediately.
+    pub fn update_settings(&mut self, f: impl FnOnce(&mut AppSettings)) {
+        f(&mut self.settings);
+        self.save_settings();
+    }
+
     // ── Switching ───────────────────────────────────────────────
 
-    /// Switch to a theme by index.
+    /// Switch to a theme by index. This turns off following the system theme.
     pub fn set_active(&mut self, index: usize) {
         if index < self.themes.len() {
             self.active_index = index;
+            self.settings.follow_system_theme = false;
             self.save_settings();
         }
     }
 
+    /// When following the system theme, activate the dark or light theme of
+    /// the configured pair to match `system`. Cheap to call every frame.
+    pub fn sync_with_system(&mut self, system: Option<egui::Theme>) {
+        if !self.settings.follow_system_theme {
+            return;
+        }
+        let name = match system {
+            Some(egui::Theme::Light) => &self.settings.system_light_theme,
+            Some(egui::Theme::Dark) => &self.settings.system_dark_theme,
+            None => return,
+        };
+        if let Some(idx) = self.themes.iter().position(|t| &t.meta.name == name) {
+            self.active_index = idx;
+        }
+    }
+
     /// Switch to a theme by name.
     pub fn set_active_by_name(&mut self, name: &str) {
         if let Some(idx) = self.themes.iter().position(|t| t.meta.name == name) {
@@ -188,17 +478,74 @@ impl ThemeManager {
             .unwrap_or_default()
     }
 
-    fn save_settings(&self) {
-        let settings = AppSettings {
-            active_theme: self.themes[self.active_index].meta.name.clone(),
-        };
-        if let Ok(json) = serde_json::to_string_pretty(&settings) {
+    fn save_settings(&mut self) {
+        // While following the system, the active theme is chosen automatically
+        // and the manual choice is kept for when following is turned off.
+        if !self.settings.follow_system_theme {
+            self.settings.active_theme = self.themes[self.active_index].meta.name.clone();
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&self.settings) {
             let _ = std::fs::create_dir_all(self.settings_path.parent().unwrap_or(&self.settings_path));
             let _ = std::fs::write(&self.settings_path, json);
         }
     }
 }
 
+/// Settings, keymap and user themes packed into one file, for moving a
+/// setup to another machine.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SettingsBundle {
+    version: u32,
+    settings: AppSettings,
+    themes: Vec<ThemeDefinition>,
+}
+
+impl ThemeManager {
+    /// Write the settings (including the keymap) and every user theme to
+    /// `path`. Returns how many themes were included.
+    pub fn export_bundle(&self, path: &std::path::Path) -> Result<usize, String> {
+        let builtins: Vec<String> = builtin_themes().into_iter().map(|t| t.meta.name).collect();
+        let themes: Vec<ThemeDefinition> =
+            self.themes.iter().filter(|t| !builtins.contains(&t.meta.name)).cloned().collect();
+        let count = themes.len();
+        let bundle = SettingsBundle { version: 1, settings: self.settings.clone(), themes };
+        let json = serde_json::to_string_pretty(&bundle).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(count)
+    }
+
+    /// Replace the settings with those in a bundle from [`Self::export_bundle`]
+    /// and add its themes to the themes folder, overwriting same-named files.
+    /// Returns how many themes were imported.
+    pub fn import_bundle(&mut self, path: &std::path::Path) -> Result<usize, String> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let bundle: SettingsBundle =
+            serde_json::from_str(&contents).map_err(|e| format!("Not a settings bundle: {}", e))?;
+        std::fs::create_dir_all(&self.themes_dir).map_err(|e| format!("Failed to create themes folder: {}", e))?;
+        for theme in &bundle.themes {
+            let file: String = theme
+                .meta
+                .name
+                .chars()
+                .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+                .collect();
+            let json = serde_json::to_string_pretty(theme).map_err(|e| format!("Failed to serialize theme: {}", e))?;
+            std::fs::write(self.themes_dir.join(format!("{}.json", file)), json)
+                .map_err(|e| format!("Failed to write theme '{}': {}", theme.meta.name, e))?;
+        }
+        self.settings = bundle.settings;
+        self.reload_user_themes();
+        self.active_index = self
+            .themes
+            .iter()
+            .position(|t| t.meta.name == self.settings.active_theme)
+            .unwrap_or(0);
+        self.save_settings();
+        Ok(bundle.themes.len())
+    }
+}
+
 // ─── Built-in preset themes ────────────────────────────────────────────────
 
 fn builtin_themes() -> Vec<ThemeDefinition> {
@@ -460,5 +807,7 @@ fn clean_light_theme() -> ThemeDefinition {
     t.rendering.bar_highlight_alpha = 50;
     t.rendering.milestone_shadow_alpha = 20;
     t.rendering.sticky_shadow_alpha = 18;
+    // The thin bracket is hard to spot on a bright background.
+    t.rendering.summary_bar_style = crate::ui::theme_def::SummaryBarStyle::Solid;
     t
 }
@@ -1,37 +1,186 @@
 use crate::app::GanttApp;
+use crate::io::table_text::TableFormat;
+use crate::ui::keymap::Command;
 use crate::ui::theme;
 use egui::{menu, RichText, Ui};
 
 /// Render the top toolbar / menu bar.
 pub fn show_toolbar(app: &mut GanttApp, ui: &mut Ui) {
+    let keys = app.theme_manager.settings().keymap.clone();
     menu::bar(ui, |ui| {
         ui.menu_button(RichText::new("  File  ").font(theme::font_menu()), |ui| {
-            if ui.button("  New Project").clicked() {
+            if ui.add(egui::Button::new("  New Project").shortcut_text(keys.label(Command::NewProject))).clicked() {
                 app.new_project();
                 ui.close_menu();
             }
-            if ui.button("  Open...").clicked() {
+            if ui.add(egui::Button::new("  Open...").shortcut_text(keys.label(Command::OpenProject))).clicked() {
                 app.open_project();
                 ui.close_menu();
             }
+            if ui
+                .button("  Open Read-Only...")
+                .on_hover_text("Review a plan without any risk of changing it; Save As makes an editable copy")
+                .clicked()
+            {
+                app.open_project_read_only();
+                ui.close_menu();
+            }
             ui.separator();
-            if ui.button("  Save          Ctrl+S").clicked() {
+            if ui
+                .add_enabled(!app.read_only, egui::Button::new("  Save").shortcut_text(keys.label(Command::Save)))
+                .on_disabled_hover_text("Opened read-only; use Save As… to keep an editable copy")
+                .clicked()
+            {
                 app.save_project();
                 ui.close_menu();
             }
-            if ui.button("  Save As...").clicked() {
+            if ui.add(egui::Button::new("  Save As...").shortcut_text(keys.label(Command::SaveAs))).clicked() {
                 app.save_project_as();
                 ui.close_menu();
             }
+            if ui
+                .button("  Save As Template...")
+                .on_hover_text("Save a copy without progress, starting in week zero, to start new projects from")
+                .clicked()
+            {
+                app.template_name = Some(app.project.name.clone());
+                ui.close_menu();
+            }
+            ui.horizontal(|ui| {
+                ui.label("  Keep Backups");
+                let mut backups = app.theme_manager.settings().backup_count;
+                if ui
+                    .add(egui::DragValue::new(&mut backups).range(0..=20))
+                    .on_hover_text("Earlier versions saved next to the project as .bak1 (newest), .bak2, …")
+                    .changed()
+                {
+                    app.theme_manager.update_settings(|s| s.backup_count = backups);
+                }
+            });
+            let mut weekly = app.theme_manager.settings().weekly_snapshots;
+            if ui
+                .checkbox(&mut weekly, "Keep Weekly Snapshots")
+                .on_hover_text("Each save stores that week's task dates in the project, for View → Plan History")
+                .changed()
+            {
+                app.theme_manager.update_settings(|s| s.weekly_snapshots = weekly);
+            }
+            if ui
+                .button("  Compare With...")
+                .on_hover_text("Show what another version of this plan adds, removes or changes")
+                .clicked()
+            {
+                app.compare_with_file();
+                ui.close_menu();
+            }
             ui.separator();
             if ui.button("  Import CSV...").clicked() {
                 app.import_csv();
                 ui.close_menu();
             }
+            if ui
+                .button("  CSV Status Mapping...")
+                .on_hover_text("Which progress each value of an imported Status column stands for")
+                .clicked()
+            {
+                app.show_status_mapping = true;
+                ui.close_menu();
+            }
             if ui.button("  Export CSV...").clicked() {
                 app.export_csv();
                 ui.close_menu();
             }
+            if ui
+                .button("  Export CSV per Assignee...")
+                .on_hover_text("One CSV per resource with only their tasks, written to a folder")
+                .clicked()
+            {
+                app.export_csv_per_assignee();
+                ui.close_menu();
+            }
+            if ui.button("  Import Dependencies CSV...").clicked() {
+                app.import_dependencies_csv();
+                ui.close_menu();
+            }
+            if ui.button("  Export Dependencies CSV...").clicked() {
+                app.export_dependencies_csv();
+                ui.close_menu();
+            }
+            if ui
+                .add_enabled(app.selected_task.is_some(), egui::Button::new("  Import into Selected Task..."))
+                .on_hover_text("Add a .gantt.json or CSV file's tasks as children of the selected task")
+                .clicked()
+            {
+                if let Some(id) = app.selected_task {
+                    app.request_import_phase(id);
+                }
+                ui.close_menu();
+            }
+            let watch_label = if app.theme_manager.settings().watch_folder.is_some() {
+                "  Watch Folder (on)..."
+            } else {
+                "  Watch Folder..."
+            };
+            if ui
+                .button(watch_label)
+                .on_hover_text("Import CSV files dropped into a folder automatically")
+                .clicked()
+            {
+                app.show_watch_folder = true;
+                ui.close_menu();
+            }
+            let mut shared_sync = app.theme_manager.settings().shared_sync;
+            if ui
+                .checkbox(&mut shared_sync, "Sync via Shared Folder")
+                .on_hover_text(
+                    "Journal your edits next to the project file and merge other planners' on open, on save \
+                     and every few seconds",
+                )
+                .changed()
+            {
+                app.theme_manager.update_settings(|s| s.shared_sync = shared_sync);
+                if shared_sync {
+                    app.start_sync();
+                    if app.file_path.is_none() {
+                        app.status_message = "Sync starts once the project is saved".to_string();
+                    }
+                } else {
+                    app.stop_sync();
+                }
+            }
+            let live_label = if app.live.is_some() {
+                "  Live Session (on)..."
+            } else {
+                "  Live Session (Experimental)..."
+            };
+            if ui
+                .add_enabled(!app.read_only, egui::Button::new(live_label))
+                .on_hover_text("Host the plan on the network, or join one, to co-edit it in real time")
+                .clicked()
+            {
+                app.show_live_session = true;
+                ui.close_menu();
+            }
+            if ui.button("  Status Report...").clicked() {
+                app.open_status_report();
+                ui.close_menu();
+            }
+            if ui.button("  Print Schedule...").clicked() {
+                app.show_print_export = true;
+                ui.close_menu();
+            }
+            if ui.button("  Export PowerPoint...").clicked() {
+                app.show_pptx_export = true;
+                ui.close_menu();
+            }
+            if ui
+                .button("  Capture Region...")
+                .on_hover_text("Drag a rectangle over the chart to copy or save just that part as an image")
+                .clicked()
+            {
+                app.capture = Some(crate::ui::capture::Capture::new());
+                ui.close_menu();
+            }
         });
 
         ui.menu_button(RichText::new("  Edit  ").font(theme::font_menu()), |ui| {
@@ -39,30 +188,183 @@ pub fn show_toolbar(app: &mut GanttApp, ui: &mut Ui) {
             let can_redo = app.undo_history.can_redo();
 
             if ui
-                .add_enabled(can_undo, egui::Button::new("  Undo         Ctrl+Z"))
+                .add_enabled(can_undo, egui::Button::new("  Undo").shortcut_text(keys.label(Command::Undo)))
                 .clicked()
             {
                 app.undo();
                 ui.close_menu();
             }
             if ui
-                .add_enabled(can_redo, egui::Button::new("  Redo         Ctrl+Y"))
+                .add_enabled(can_redo, egui::Button::new("  Redo").shortcut_text(keys.label(Command::Redo)))
                 .clicked()
             {
                 app.redo();
                 ui.close_menu();
             }
+            ui.horizontal(|ui| {
+                ui.label("  Undo Steps");
+                let mut depth = app.undo_history.depth();
+                let held = format!(
+                    "{} step(s) held, about {:.1} MB. Each step keeps a copy of every task and link.",
+                    app.undo_history.len(),
+                    app.undo_history.estimated_bytes() as f64 / 1_048_576.0
+                );
+                if ui.add(egui::DragValue::new(&mut depth).range(1..=500)).on_hover_text(held).changed() {
+                    app.undo_history.set_depth(depth);
+                    app.theme_manager.update_settings(|s| s.undo_depth = depth);
+                }
+            });
+            if ui
+                .add_enabled(app.undo_history.len() > 0, egui::Button::new("  Clear Undo History"))
+                .clicked()
+            {
+                app.clear_undo_history();
+                ui.close_menu();
+            }
+            ui.separator();
+            ui.menu_button("  Copy as Table", |ui| {
+                for format in [TableFormat::Markdown, TableFormat::Tsv] {
+                    if ui
+                        .button(format.label())
+                        .on_hover_text("Copy the filtered tasks, or the selected task and its subtasks")
+                        .clicked()
+                    {
+                        app.copy_as_table(ui.ctx(), format);
+                        ui.close_menu();
+                    }
+                }
+            });
+            let can_link = app.file_path.is_some() && app.selected_task.is_some();
+            if ui
+                .add_enabled(can_link, egui::Button::new("  Copy Link to Task"))
+                .on_hover_text("Copy a ganttapp:// link that opens this file at the selected task")
+                .clicked()
+            {
+                app.copy_task_link(ui.ctx());
+                ui.close_menu();
+            }
+            // Everything that edits the project; a read-only one cannot be.
+            ui.add_enabled_ui(!app.read_only, |ui| {
+                if ui
+                    .add_enabled(
+                        app.selected_task.is_some(),
+                        egui::Button::new("  Link Selected Task…").shortcut_text(keys.label(Command::LinkTasks)),
+                    )
+                    .on_hover_text("Link the selected task to another found by name, without dragging")
+                    .clicked()
+                {
+                    app.request_link();
+                    ui.close_menu();
+                }
+                if ui.button("  Batch Rename…").clicked() {
+                    app.show_batch_rename = true;
+                    ui.close_menu();
+                }
+                if ui.button("  Adjust Dates…").clicked() {
+                    app.show_batch_dates = true;
+                    ui.close_menu();
+                }
+                ui.separator();
+                if ui.button("  Resources…").clicked() {
+                    app.show_resources = true;
+                    ui.close_menu();
+                }
+                if ui.button("  Level Resources…").clicked() {
+                    app.level_resources();
+                    ui.close_menu();
+                }
+                ui.separator();
+                if ui.button("  Set Baseline").clicked() {
+                    app.set_baseline();
+                    ui.close_menu();
+                }
+                if ui
+                    .add_enabled(
+                        app.project.baseline.is_some() && app.selected_task.is_some(),
+                        egui::Button::new("  Re-baseline Selection…"),
+                    )
+                    .on_hover_text("Make the selected tasks' current dates their baseline, e.g. after an approved change")
+                    .on_disabled_hover_text("Set a baseline and select the tasks or phase to re-baseline")
+                    .clicked()
+                {
+                    app.rebaseline_label = Some(String::new());
+                    ui.close_menu();
+                }
+                if ui
+                    .add_enabled(app.project.baseline.is_some(), egui::Button::new("  Clear Baseline"))
+                    .clicked()
+                {
+                    app.clear_baseline();
+                    ui.close_menu();
+                }
+                if ui
+                    .add_enabled(app.link_sync.is_none(), egui::Button::new("  Refresh Linked Issues"))
+                    .on_hover_text("Pull status from every task's GitHub or Jira link")
+                    .clicked()
+                {
+                    app.refresh_links(&[]);
+                    ui.close_menu();
+                }
+                ui.separator();
+                if ui.button("  Project Properties…").clicked() {
+                    app.edit_project_name = app.project.name.clone();
+                    app.show_project_properties = true;
+                    ui.close_menu();
+                }
+                if ui.button("  Markers, Bands & Notes…").clicked() {
+                    app.show_markers = true;
+                    ui.close_menu();
+                }
+                let trash_label = match app.project.trash.len() {
+                    0 => "  Trash…".to_string(),
+                    n => format!("  Trash ({})…", n),
+                };
+                if ui.button(trash_label).clicked() {
+                    app.show_trash = true;
+                    ui.close_menu();
+                }
+                if ui.button("  Project Settings…").clicked() {
+                    app.show_project_settings = true;
+                    ui.close_menu();
+                }
+            });
+            if ui.button("  New Task Defaults…").clicked() {
+                app.show_new_task_defaults = true;
+                ui.close_menu();
+            }
+            if ui
+                .add(egui::Button::new("  Keyboard Shortcuts…").shortcut_text(keys.label(Command::ShowShortcuts)))
+                .clicked()
+            {
+                app.show_shortcuts = true;
+                ui.close_menu();
+            }
+            if ui
+                .button("  Register ganttapp:// Links")
+                .on_hover_text("Open task links from wikis and emails in this app")
+                .clicked()
+            {
+                app.register_url_scheme();
+                ui.close_menu();
+            }
         });
 
         ui.menu_button(RichText::new("  View  ").font(theme::font_menu()), |ui| {
-            if ui.button("  Zoom In        Ctrl+Scroll ↑").clicked() {
+            if ui.add(egui::Button::new("  Zoom In").shortcut_text(keys.label(Command::ZoomIn))).clicked() {
                 app.viewport.zoom_in();
                 ui.close_menu();
             }
-            if ui.button("  Zoom Out      Ctrl+Scroll ↓").clicked() {
+            if ui.add(egui::Button::new("  Zoom Out").shortcut_text(keys.label(Command::ZoomOut))).clicked() {
                 app.viewport.zoom_out();
                 ui.close_menu();
             }
+            let jumps = [Command::ZoomDay, Command::ZoomWeek, Command::ZoomMonth, Command::ZoomFit, Command::GoToToday];
+            for command in jumps {
+                if ui.add(egui::Button::new(format!("  {}", command.label())).shortcut_text(keys.label(command))).clicked() {
+                    app.run_command(command);
+                    ui.close_menu();
+                }
+            }
             ui.separator();
             ui.label(RichText::new("Timeline Scale").small().weak());
             if ui
@@ -95,14 +397,285 @@ pub fn show_toolbar(app: &mut GanttApp, ui: &mut Ui) {
             {
                 ui.close_menu();
             }
+            let mut auto_scale = app.theme_manager.settings().auto_scale;
+            if ui
+                .checkbox(&mut auto_scale, "Switch Scale When Zooming")
+                .on_hover_text("Zooming past the point where the header gets cramped or sparse moves to the next scale")
+                .changed()
+            {
+                app.theme_manager.update_settings(|s| s.auto_scale = auto_scale);
+            }
+            if ui
+                .button("  Open Project at This Zoom")
+                .on_hover_text("Save the current scale and zoom in the project file")
+                .clicked()
+                && !app.refuse_read_only()
+            {
+                app.project.default_zoom = Some(app.viewport.zoom());
+                app.project.touch();
+                app.status_message = "The project will open at this scale and zoom".to_string();
+                ui.close_menu();
+            }
+            if app.project.default_zoom.is_some() && ui.button("  Forget Project Zoom").clicked() && !app.refuse_read_only() {
+                app.project.default_zoom = None;
+                app.project.touch();
+                app.status_message = "The project will open at the default zoom".to_string();
+                ui.close_menu();
+            }
+            ui.separator();
+            ui.label(RichText::new("Color Bars By").small().weak());
+            for &mode in crate::model::view::ColorMode::all() {
+                if ui.radio_value(&mut app.color_mode, mode, mode.label()).clicked() {
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
+            ui.label(RichText::new("Tint Rows By").small().weak());
+            let mut row_tint = app.theme_manager.settings().row_tint;
+            for &tint in crate::model::view::RowTint::all() {
+                if ui.radio_value(&mut row_tint, tint, tint.label()).clicked() {
+                    app.theme_manager.update_settings(|s| s.row_tint = row_tint);
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
+            let mut split = app.split_view;
+            if ui.checkbox(&mut split, "Split Timeline").changed() {
+                app.toggle_split_view();
+                ui.close_menu();
+            }
+            let mut detached = app.detached_viewport.is_some();
+            if ui
+                .checkbox(&mut detached, "Chart in Separate Window")
+                .on_hover_text("A read-only chart window for a second monitor or projector")
+                .changed()
+            {
+                app.toggle_detached_chart();
+                ui.close_menu();
+            }
+            ui.checkbox(&mut app.show_resource_histogram, "Resource Load");
+            ui.checkbox(&mut app.show_swimlanes, "Swimlanes");
+            ui.checkbox(&mut app.show_roadmap, "Roadmap");
+            if ui
+                .checkbox(&mut app.show_plan_history, "Plan History")
+                .on_hover_text("Scrub through the weekly snapshots to see how the plan evolved")
+                .changed()
+            {
+                app.plan_history_week = app.project.snapshots.len().saturating_sub(1);
+            }
+            if ui
+                .add_enabled(!app.guide_lines.is_empty(), egui::Button::new("  Clear Guide Lines"))
+                .on_hover_text("Click a date in the timeline header to place a guide line")
+                .clicked()
+            {
+                app.guide_lines.clear();
+                ui.close_menu();
+            }
+            if ui
+                .add(egui::Button::new("  Check Schedule…").shortcut_text(keys.label(Command::CheckSchedule)))
+                .clicked()
+            {
+                app.check_schedule();
+                ui.close_menu();
+            }
+            if ui
+                .add(egui::Button::new("  Check Project…").shortcut_text(keys.label(Command::CheckProject)))
+                .on_hover_text("Look for broken links, orphaned subtasks and other inconsistencies in the file")
+                .clicked()
+            {
+                app.check_project();
+                ui.close_menu();
+            }
+            if ui.button("  Project Statistics…").clicked() {
+                app.show_statistics = true;
+                ui.close_menu();
+            }
+            let mut scroll_to_selection = app.theme_manager.settings().scroll_to_selection;
+            if ui.checkbox(&mut scroll_to_selection, "Scroll to Selected Task").changed() {
+                app.theme_manager.update_settings(|s| s.scroll_to_selection = scroll_to_selection);
+            }
+            let mut name_gutter = app.theme_manager.settings().name_gutter;
+            ui.menu_button("Task Names in Chart", |ui| {
+                ui.checkbox(&mut name_gutter.show, "Show");
+                ui.radio_value(&mut name_gutter.wrap, false, "Truncate Long Names");
+                ui.radio_value(&mut name_gutter.wrap, true, "Wrap Long Names");
+                if ui
+                    .add_enabled(name_gutter.width.is_some(), egui::Button::new("Auto-fit Width"))
+                    .on_hover_text("Or double-click the column's divider")
+                    .clicked()
+                {
+                    name_gutter.width = None;
+                    ui.close_menu();
+                }
+            });
+            if name_gutter != app.theme_manager.settings().name_gutter {
+                app.theme_manager.update_settings(|s| s.name_gutter = name_gutter);
+            }
+            let mut collapse_in_file = app.theme_manager.settings().collapse_in_file;
+            if ui
+                .checkbox(&mut collapse_in_file, "Save Collapsed Rows in File")
+                .on_hover_text("Off: collapsed parents are remembered per user, so people sharing a file keep their own outline")
+                .changed()
+            {
+                app.theme_manager.update_settings(|s| s.collapse_in_file = collapse_in_file);
+            }
+            let mut keep_link_gaps = app.theme_manager.settings().keep_link_gaps;
+            if ui
+                .checkbox(&mut keep_link_gaps, "Drag Successors Along")
+                .on_hover_text("Moving or resizing a bar moves the tasks linked after it by the same amount, keeping each gap")
+                .changed()
+            {
+                app.theme_manager.update_settings(|s| s.keep_link_gaps = keep_link_gaps);
+            }
+            let mut working_day_drag = app.theme_manager.settings().working_day_drag;
+            if ui
+                .checkbox(&mut working_day_drag, "Drag by Working Days")
+                .on_hover_text("Moving a bar skips weekends, so a 5-working-day task dragged across one stays 5 working days long")
+                .changed()
+            {
+                app.theme_manager.update_settings(|s| s.working_day_drag = working_day_drag);
+            }
+            let mut auto_achieve = app.theme_manager.settings().auto_achieve_milestones;
+            if ui
+                .checkbox(&mut auto_achieve, "Achieve Milestones Automatically")
+                .on_hover_text("Mark a milestone achieved as soon as every task linked into it is 100% done")
+                .changed()
+            {
+                app.theme_manager.update_settings(|s| s.auto_achieve_milestones = auto_achieve);
+            }
+            let mut forecast_bars = app.theme_manager.settings().forecast_bars;
+            if ui
+                .checkbox(&mut forecast_bars, "Show Forecast Finish")
+                .on_hover_text("Extend tasks under way with a dashed bar to where their pace so far would finish them")
+                .changed()
+            {
+                app.theme_manager.update_settings(|s| s.forecast_bars = forecast_bars);
+            }
+            let mut siblings = app.theme_manager.settings().check_sibling_overlaps;
+            if ui
+                .checkbox(&mut siblings, "Highlight Sibling Conflicts")
+                .on_hover_text("Tint where tasks under the same parent share a resource over the same dates")
+                .changed()
+            {
+                app.theme_manager.update_settings(|s| s.check_sibling_overlaps = siblings);
+                if app.schedule_check.is_some() {
+                    app.check_schedule();
+                }
+            }
+            ui.horizontal(|ui| {
+                ui.label("  My Name");
+                let mut name = app.theme_manager.settings().user_name.clone();
+                let edit = ui
+                    .add(egui::TextEdit::singleline(&mut name).desired_width(120.0).hint_text("Resource name"))
+                    .on_hover_text("The resource that is you, for the \"Only my tasks\" filter");
+                if edit.changed() {
+                    app.theme_manager.update_settings(|s| s.user_name = name);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("  Hover Card Delay");
+                let mut delay = app.theme_manager.settings().hover_card_delay_ms;
+                if ui
+                    .add(egui::DragValue::new(&mut delay).range(0..=3000).speed(10).suffix(" ms"))
+                    .changed()
+                {
+                    app.theme_manager.update_settings(|s| s.hover_card_delay_ms = delay);
+                }
+            });
+            let editor_dock = app.theme_manager.settings().editor_dock;
+            ui.menu_button("Task Editor", |ui| {
+                for dock in crate::ui::theme_manager::EditorDock::all() {
+                    if ui.radio(editor_dock == dock, dock.label()).clicked() {
+                        app.theme_manager.update_settings(|s| s.editor_dock = dock);
+                        ui.close_menu();
+                    }
+                }
+            });
+            let mut padding = app.theme_manager.settings().viewport_padding;
+            ui.menu_button("Timeline Padding", |ui| {
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    ui.label("Before first task");
+                    changed |= ui.add(egui::DragValue::new(&mut padding.before_days).range(0..=365).suffix(" d")).changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label("After last task");
+                    changed |= ui.add(egui::DragValue::new(&mut padding.after_days).range(0..=365).suffix(" d")).changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label("At least");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut padding.percent).range(0.0..=50.0).speed(0.5).suffix(" %"))
+                        .on_hover_text("Share of the project's span, for long projects")
+                        .changed();
+                });
+                if changed {
+                    app.theme_manager.update_settings(|s| s.viewport_padding = padding);
+                    app.recalculate_viewport();
+                }
+            });
+            let open_scroll = app.theme_manager.settings().open_scroll;
+            ui.menu_button("On Open, Scroll To", |ui| {
+                for option in crate::ui::theme_manager::OpenScroll::all() {
+                    if ui.radio(open_scroll == option, option.label()).clicked() {
+                        app.theme_manager.update_settings(|s| s.open_scroll = option);
+                        ui.close_menu();
+                    }
+                }
+            });
+            ui.separator();
+            ui.label(RichText::new("Density").small().weak());
+            let current = app.theme_manager.settings().density;
+            for density in crate::ui::theme_def::Density::all() {
+                if ui.radio(current == density, density.label()).clicked() {
+                    app.theme_manager.update_settings(|s| s.density = density);
+                    ui.close_menu();
+                }
+            }
             ui.separator();
             ui.label(RichText::new("Theme").small().weak());
             let themes = app.theme_manager.list();
             let active_idx = app.theme_manager.active_index();
+            let following = app.theme_manager.settings().follow_system_theme;
+            if ui.radio(following, "Auto (follow system)").clicked() {
+                app.theme_manager.update_settings(|s| s.follow_system_theme = true);
+                ui.close_menu();
+            }
+            if following {
+                ui.indent("system_theme_pair", |ui| {
+                    let settings = app.theme_manager.settings().clone();
+                    let pair = [
+                        ("Dark", settings.system_dark_theme),
+                        ("Light", settings.system_light_theme),
+                    ];
+                    for (variant, current) in pair {
+                        ui.menu_button(format!("{}: {}", variant, current), |ui| {
+                            for (_, name) in &themes {
+                                if ui.radio(*name == current, name).clicked() {
+                                    let name = name.clone();
+                                    app.theme_manager.update_settings(|s| {
+                                        if variant == "Dark" {
+                                            s.system_dark_theme = name;
+                                        } else {
+                                            s.system_light_theme = name;
+                                        }
+                                    });
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    }
+                });
+            }
             for (idx, name) in &themes {
-                let selected = *idx == active_idx;
+                let selected = !following && *idx == active_idx;
                 if ui.radio(selected, name).clicked() {
                     app.theme_manager.set_active(*idx);
+                    let palette = &app.theme_manager.active().colors.task_palette;
+                    // A read-only plan keeps its colors; only the theme changes.
+                    if !app.read_only {
+                        app.palette_remap = theme::PaletteRemap::plan(&app.project.tasks, palette, name);
+                    }
                     ui.close_menu();
                 }
             }
@@ -116,18 +689,125 @@ pub fn show_toolbar(app: &mut GanttApp, ui: &mut Ui) {
                 let _ = open::that(&dir);
                 ui.close_menu();
             }
+            if ui
+                .button("  Export Settings Bundle...")
+                .on_hover_text("Settings, shortcuts and your themes in one file, to take to another machine")
+                .clicked()
+            {
+                app.export_settings_bundle();
+                ui.close_menu();
+            }
+            if ui.button("  Import Settings Bundle...").clicked() {
+                app.import_settings_bundle();
+                ui.close_menu();
+            }
+        });
+
+        ui.menu_button(RichText::new("  Views  ").font(theme::font_menu()), |ui| {
+            let mut apply = None;
+            let mut delete = None;
+            for (idx, view) in app.project.views.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.button(format!("  {}", view.name)).clicked() {
+                        apply = Some(idx);
+                        ui.close_menu();
+                    }
+                    if ui
+                        .small_button(egui_phosphor::regular::X)
+                        .on_hover_text("Delete view")
+                        .clicked()
+                    {
+                        delete = Some(idx);
+                    }
+                });
+            }
+            if app.project.views.is_empty() {
+                ui.label(RichText::new("No saved views").small().weak());
+            }
+            ui.separator();
+            if ui.button("  Save Current View…").clicked() {
+                app.new_view_name.clear();
+                app.show_save_view = true;
+                ui.close_menu();
+            }
+            if let Some(idx) = apply {
+                app.apply_view(idx);
+            }
+            if let Some(idx) = delete {
+                app.delete_view(idx);
+            }
         });
 
         ui.menu_button(RichText::new("  Help  ").font(theme::font_menu()), |ui| {
+            if ui.button("Take the Tour").clicked() {
+                app.tour = Some(crate::ui::tour::Tour::default());
+                ui.close_menu();
+            }
+            if ui
+                .button("Load Sample Project")
+                .on_hover_text("Replace the open project with a small example plan")
+                .clicked()
+            {
+                app.load_sample_project();
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.button("Export Diagnostics…").on_hover_text("Logs, settings and an anonymized project summary for a bug report").clicked() {
+                app.export_diagnostics();
+                ui.close_menu();
+            }
+            if ui.button("Open Log Folder").clicked() {
+                app.open_log_folder();
+                ui.close_menu();
+            }
+            ui.separator();
             if ui.button("About").clicked() {
                 app.show_about = true;
                 ui.close_menu();
             }
         });
 
+        ui.separator();
+        let back = ui
+            .add_enabled(app.navigation.can_go_back(), egui::Button::new(egui_phosphor::regular::ARROW_LEFT).frame(false))
+            .on_hover_text(format!("Back to the previous view position ({})", keys.label(Command::NavigateBack)));
+        if back.clicked() {
+            app.navigate_back();
+        }
+        let forward = ui
+            .add_enabled(app.navigation.can_go_forward(), egui::Button::new(egui_phosphor::regular::ARROW_RIGHT).frame(false))
+            .on_hover_text(format!("Forward ({})", keys.label(Command::NavigateForward)));
+        if forward.clicked() {
+            app.navigate_forward();
+        }
+        let previous = ui
+            .add_enabled(
+                app.selection_history.can_go_back(app.selected_task),
+                egui::Button::new(egui_phosphor::regular::ARROW_U_UP_LEFT).frame(false),
+            )
+            .on_hover_text(format!("Previously selected task ({})", keys.label(Command::SelectPrevious)));
+        if previous.clicked() {
+            app.select_previous();
+        }
+        let next = ui
+            .add_enabled(
+                app.selection_history.can_go_forward(),
+                egui::Button::new(egui_phosphor::regular::ARROW_U_UP_RIGHT).frame(false),
+            )
+            .on_hover_text(format!("Next selected task ({})", keys.label(Command::SelectNext)));
+        if next.clicked() {
+            app.select_next();
+        }
+
         // Right-aligned project name
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-            let modified = if app.file_path.is_some() { "" } else { " (unsaved)" };
+            let modified = if app.file_path.is_none() {
+                " (unsaved)"
+            } else if app.is_dirty() {
+                " (modified)"
+            } else {
+                ""
+            };
             ui.label(
                 RichText::new(format!("{}{}", app.project.name, modified))
                     .size(11.0)
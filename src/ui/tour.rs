@@ -0,0 +1,155 @@
+//! First-run tour: a card that walks through the chart, the task list, the
+//! editor and linking, dimming the rest of the window around each one.
+
+use crate::ui::theme;
+use egui::{Color32, Context, Rect, RichText};
+
+/// Parts of the window the tour points at, as laid out this frame.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TourTargets {
+    pub chart: Option<Rect>,
+    pub table: Option<Rect>,
+    pub editor: Option<Rect>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourStep {
+    Chart,
+    Table,
+    Editor,
+    Linking,
+}
+
+impl TourStep {
+    const ALL: [TourStep; 4] = [TourStep::Chart, TourStep::Table, TourStep::Editor, TourStep::Linking];
+
+    fn title(self) -> &'static str {
+        match self {
+            TourStep::Chart => "The chart",
+            TourStep::Table => "The task list",
+            TourStep::Editor => "The editor",
+            TourStep::Linking => "Linking tasks",
+        }
+    }
+
+    fn body(self) -> &'static str {
+        match self {
+            TourStep::Chart => {
+                "Every task is a bar on the timeline. Drag a bar to move it, drag its ends to change \
+                 its dates, and scroll with Ctrl held to zoom."
+            }
+            TourStep::Table => {
+                "Tasks in plan order. Click one to select it, drag rows to reorder or nest them, and \
+                 use the filter above to find tasks by name or priority."
+            }
+            TourStep::Editor => {
+                "The selected task's details: dates, progress, priority, resources and links. \
+                 Changes apply as you type and can be undone."
+            }
+            TourStep::Linking => {
+                "Hold Shift and drag from one bar to another to make the second wait for the first. \
+                 The link button above a selected bar does the same."
+            }
+        }
+    }
+
+    fn target(self, targets: &TourTargets) -> Option<Rect> {
+        match self {
+            TourStep::Chart | TourStep::Linking => targets.chart,
+            TourStep::Table => targets.table,
+            TourStep::Editor => targets.editor,
+        }
+    }
+}
+
+/// Progress through the tour.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tour {
+    index: usize,
+}
+
+impl Tour {
+    pub fn step(&self) -> TourStep {
+        TourStep::ALL[self.index]
+    }
+}
+
+pub enum TourAction {
+    None,
+    /// The tour was finished or skipped.
+    Close,
+}
+
+/// Dim the window around the current step's target and show its card.
+pub fn show_tour(ctx: &Context, tour: &mut Tour, targets: &TourTargets) -> TourAction {
+    let step = tour.step();
+    let screen = ctx.screen_rect();
+    let target = step.target(targets);
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("tour-shade")));
+    let shade = Color32::from_black_alpha(140);
+    match target {
+        Some(rect) => {
+            let rect = rect.intersect(screen);
+            painter.rect_filled(Rect::from_min_max(screen.min, egui::pos2(screen.max.x, rect.top())), 0.0, shade);
+            painter.rect_filled(Rect::from_min_max(egui::pos2(screen.min.x, rect.bottom()), screen.max), 0.0, shade);
+            painter.rect_filled(
+                Rect::from_min_max(egui::pos2(screen.min.x, rect.top()), egui::pos2(rect.left(), rect.bottom())),
+                0.0,
+                shade,
+            );
+            painter.rect_filled(
+                Rect::from_min_max(egui::pos2(rect.right(), rect.top()), egui::pos2(screen.max.x, rect.bottom())),
+                0.0,
+                shade,
+            );
+            painter.rect_stroke(rect.shrink(1.0), 4.0, egui::Stroke::new(2.0, theme::accent()));
+        }
+        None => {
+            painter.rect_filled(screen, 0.0, shade);
+        }
+    }
+
+    let mut action = TourAction::None;
+    let card_pos = target.map_or(screen.center(), |r| r.center());
+    egui::Area::new(egui::Id::new("tour-card"))
+        .order(egui::Order::Tooltip)
+        .pivot(egui::Align2::CENTER_CENTER)
+        .fixed_pos(card_pos)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).inner_margin(12.0).show(ui, |ui| {
+                ui.set_max_width(300.0);
+                ui.label(
+                    RichText::new(format!("{} of {}", tour.index + 1, TourStep::ALL.len()))
+                        .size(10.0)
+                        .color(theme::text_dim()),
+                );
+                ui.label(RichText::new(step.title()).strong().size(14.0));
+                ui.add_space(4.0);
+                ui.label(step.body());
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let last = tour.index + 1 == TourStep::ALL.len();
+                    let next = egui::Button::new(RichText::new(if last { "Done" } else { "Next" }).color(Color32::WHITE))
+                        .fill(theme::accent())
+                        .rounding(egui::Rounding::same(4.0));
+                    if ui.add_sized([70.0, 24.0], next).clicked() {
+                        if last {
+                            action = TourAction::Close;
+                        } else {
+                            tour.index += 1;
+                        }
+                    }
+                    if tour.index > 0 && ui.add_sized([70.0, 24.0], egui::Button::new("Back")).clicked() {
+                        tour.index -= 1;
+                    }
+                    if !last && ui.button("Skip Tour").clicked() {
+                        action = TourAction::Close;
+                    }
+                });
+            });
+        });
+    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        action = TourAction::Close;
+    }
+    action
+}
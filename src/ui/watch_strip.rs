@@ -0,0 +1,91 @@
+use crate::model::Task;
+use crate::ui::theme;
+use chrono::NaiveDate;
+use egui::{RichText, Ui};
+use uuid::Uuid;
+
+/// Actions from the watch strip.
+pub enum WatchAction {
+    None,
+    /// Select the task and scroll the chart to it.
+    Select(Uuid),
+    /// Remove the task from the watch list.
+    Unwatch(Uuid),
+}
+
+/// "3d left", "due today" or "2d overdue"; done tasks just say "done".
+fn remaining_label(task: &Task, today: NaiveDate) -> (String, egui::Color32) {
+    if task.progress >= 1.0 {
+        return ("done".to_string(), theme::text_dim());
+    }
+    let days = (task.end - today).num_days();
+    match days {
+        d if d < 0 => (format!("{}d overdue", -d), theme::slip_major()),
+        0 => ("due today".to_string(), theme::slip_moderate()),
+        d => (format!("{}d left", d), theme::text_secondary()),
+    }
+}
+
+/// Render the watched tasks as a row of chips, in the order they were pinned.
+/// Ids that no longer match a task are skipped.
+pub fn show_watch_strip(tasks: &[Task], watch_list: &[Uuid], today: NaiveDate, ui: &mut Ui) -> WatchAction {
+    let mut action = WatchAction::None;
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing = egui::vec2(6.0, 4.0);
+        ui.label(
+            RichText::new(format!("{}  Watching", egui_phosphor::regular::PUSH_PIN))
+                .size(10.0)
+                .color(theme::text_dim())
+                .strong(),
+        );
+        for id in watch_list {
+            let Some(task) = tasks.iter().find(|t| t.id == *id) else {
+                continue;
+            };
+            let (remaining, remaining_color) = remaining_label(task, today);
+            egui::Frame::default()
+                .fill(theme::bg_field())
+                .stroke(egui::Stroke::new(1.0, theme::border_subtle()))
+                .rounding(egui::Rounding::same(4.0))
+                .inner_margin(egui::Margin::symmetric(6.0, 2.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing.x = 4.0;
+                        let (rect, _) = ui.allocate_exact_size(egui::vec2(8.0, 8.0), egui::Sense::hover());
+                        ui.painter().rect_filled(rect, egui::Rounding::same(2.0), task.color);
+                        let name = ui
+                            .add(
+                                egui::Label::new(RichText::new(&task.name).size(11.0).color(theme::text_primary()))
+                                    .sense(egui::Sense::click()),
+                            )
+                            .on_hover_text("Select and scroll to this task");
+                        if name.clicked() {
+                            action = WatchAction::Select(task.id);
+                        }
+                        let dates = if task.is_milestone {
+                            task.start.format("%d/%m").to_string()
+                        } else {
+                            format!("{} → {}", task.start.format("%d/%m"), task.end.format("%d/%m"))
+                        };
+                        ui.label(RichText::new(dates).size(10.0).color(theme::text_dim()));
+                        ui.label(RichText::new(remaining).size(10.0).color(remaining_color));
+                        if ui
+                            .add(
+                                egui::Button::new(
+                                    RichText::new(egui_phosphor::regular::X)
+                                        .size(9.0)
+                                        .color(theme::text_dim()),
+                                )
+                                .frame(false),
+                            )
+                            .on_hover_text("Stop watching")
+                            .clicked()
+                        {
+                            action = WatchAction::Unwatch(task.id);
+                        }
+                    });
+                });
+        }
+    });
+    action
+}